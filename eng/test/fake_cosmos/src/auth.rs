@@ -0,0 +1,95 @@
+use crate::reply::Reply;
+use azure_core::auth::Secret;
+use azure_core::headers::{AUTHORIZATION, MS_DATE};
+use azure_core::hmac::hmac_sha256;
+use azure_core::{Request, StatusCode};
+use url::form_urlencoded;
+
+/// Checks the request is signed with the master key, as the service does.
+///
+/// Resource tokens and AAD tokens are rejected since the fake has no users
+/// nor identities.
+pub(crate) fn validate(request: &Request, primary_key: &str) -> Result<(), Reply> {
+    let unauthorized = |message: &str| Reply::error(StatusCode::Unauthorized, message);
+
+    let authorization = request
+        .headers()
+        .get_optional_str(&AUTHORIZATION)
+        .ok_or_else(|| unauthorized("the authorization header is missing"))?;
+    let date = request
+        .headers()
+        .get_optional_str(&MS_DATE)
+        .ok_or_else(|| unauthorized("the x-ms-date header is missing"))?;
+
+    // the whole token is url encoded, `type=master&ver=1.0&sig=...` once decoded
+    let token = form_urlencoded::parse(format!("token={authorization}").as_bytes())
+        .next()
+        .map(|(_, token)| token.into_owned())
+        .unwrap_or_default();
+    let mut kind = None;
+    let mut signature = None;
+    for part in token.split('&') {
+        match part.split_once('=') {
+            Some(("type", value)) => kind = Some(value),
+            Some(("sig", value)) => signature = Some(value),
+            _ => {}
+        }
+    }
+    if kind != Some("master") {
+        return Err(unauthorized("only master key signatures are supported"));
+    }
+
+    let (resource_type, resource_link) = resource_of(request.url().path());
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}\n\n",
+        request.method().as_ref().to_lowercase(),
+        resource_type,
+        resource_link,
+        date.to_lowercase()
+    );
+    let expected = hmac_sha256(&string_to_sign, &Secret::new(primary_key.to_owned()))
+        .map_err(|_| unauthorized("the primary key of the gateway is not base64 encoded"))?;
+    if signature != Some(expected.as_str()) {
+        return Err(unauthorized(&format!(
+            "the input authorization token can't serve the request, the payload to sign was {string_to_sign:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// The resource type and resource link signed by a request of the path.
+///
+/// Feeds, such as `dbs/db/colls`, are signed with the type of their items
+/// and the link of their parent, and items with their type and own link.
+fn resource_of(path: &str) -> (&str, String) {
+    let segments: Vec<&str> = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    match segments.len() {
+        0 => ("", String::new()),
+        len if len % 2 == 1 => (segments[len - 1], segments[..len - 1].join("/")),
+        len => (segments[len - 2], segments.join("/")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_of_paths() {
+        assert_eq!(resource_of("/"), ("", String::new()));
+        assert_eq!(resource_of("/dbs"), ("dbs", String::new()));
+        assert_eq!(resource_of("/dbs/db"), ("dbs", String::from("dbs/db")));
+        assert_eq!(
+            resource_of("/dbs/db/colls/coll/docs"),
+            ("docs", String::from("dbs/db/colls/coll"))
+        );
+        assert_eq!(
+            resource_of("/dbs/db/colls/coll/docs/doc"),
+            ("docs", String::from("dbs/db/colls/coll/docs/doc"))
+        );
+    }
+}
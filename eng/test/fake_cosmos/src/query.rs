@@ -0,0 +1,673 @@
+//! The subset of the Cosmos DB SQL dialect the fake gateway runs:
+//!
+//! ```sql
+//! SELECT [TOP n] * | VALUE expression | expression [AS name], ...
+//! FROM alias
+//! [WHERE expression]
+//! [ORDER BY expression [ASC | DESC], ...]
+//! ```
+//!
+//! Expressions are made of properties such as `c.address.city` or
+//! `c["name"]`, literals, `@parameters`, comparisons, `AND`, `OR`, `NOT`
+//! and the `IS_DEFINED`, `IS_NULL`, `CONTAINS`, `STARTSWITH`, `ENDSWITH`,
+//! `ARRAY_CONTAINS`, `LOWER` and `UPPER` functions.
+//!
+//! As in the service, comparing values of different types yields
+//! `undefined`, and documents are only selected when the filter is `true`.
+
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// A parsed query.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Query {
+    top: Option<usize>,
+    projection: Projection,
+    alias: String,
+    filter: Option<Expr>,
+    order_by: Vec<(Expr, bool)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Projection {
+    All,
+    Value(Expr),
+    Fields(Vec<(String, Expr)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Parameter(String),
+    /// The alias followed by the property names or array indexes.
+    Path(String, Vec<Value>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, Comparison, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Query {
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+        };
+        let query = parser.query()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(token) => Err(format!("unexpected {token:?} at the end of the query")),
+        }
+    }
+
+    /// The results of the query over the documents, in order.
+    pub(crate) fn execute<'a>(
+        &self,
+        documents: impl Iterator<Item = &'a Value>,
+        parameters: &BTreeMap<&str, &Value>,
+    ) -> Result<Vec<Value>, String> {
+        let scope = Scope {
+            alias: &self.alias,
+            parameters,
+        };
+
+        let mut selected = Vec::new();
+        for document in documents {
+            let keep = match &self.filter {
+                Some(filter) => scope.eval(filter, document)? == Some(Value::Bool(true)),
+                None => true,
+            };
+            if keep {
+                let keys = self
+                    .order_by
+                    .iter()
+                    .map(|(expr, _)| scope.eval(expr, document))
+                    .collect::<Result<Vec<_>, _>>()?;
+                selected.push((keys, document));
+            }
+        }
+
+        selected.sort_by(|(a, _), (b, _)| {
+            self.order_by
+                .iter()
+                .zip(a.iter().zip(b))
+                .map(|((_, descending), (a, b))| {
+                    let ordering = compare_for_order_by(a.as_ref(), b.as_ref());
+                    if *descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut results = Vec::new();
+        for (_, document) in selected.into_iter().take(self.top.unwrap_or(usize::MAX)) {
+            match &self.projection {
+                Projection::All => results.push(document.clone()),
+                Projection::Value(expr) => {
+                    if let Some(value) = scope.eval(expr, document)? {
+                        results.push(value);
+                    }
+                }
+                Projection::Fields(fields) => {
+                    let mut object = Map::new();
+                    for (name, expr) in fields {
+                        if let Some(value) = scope.eval(expr, document)? {
+                            object.insert(name.clone(), value);
+                        }
+                    }
+                    results.push(Value::Object(object));
+                }
+            }
+        }
+        Ok(results)
+    }
+}
+
+struct Scope<'a> {
+    alias: &'a str,
+    parameters: &'a BTreeMap<&'a str, &'a Value>,
+}
+
+impl Scope<'_> {
+    /// The value of the expression for the document, `None` when undefined.
+    fn eval(&self, expr: &Expr, document: &Value) -> Result<Option<Value>, String> {
+        Ok(match expr {
+            Expr::Literal(value) => Some(value.clone()),
+            Expr::Parameter(name) => Some(
+                self.parameters
+                    .get(name.as_str())
+                    .map(|value| (*value).clone())
+                    .ok_or_else(|| format!("the parameter {name} is not defined"))?,
+            ),
+            Expr::Path(root, path) => {
+                if root != self.alias {
+                    return Err(format!("the identifier {root} could not be resolved"));
+                }
+                let mut value = Some(document);
+                for segment in path {
+                    value = value.and_then(|value| match segment {
+                        Value::String(key) => value.get(key),
+                        Value::Number(index) => value.get(index.as_u64()? as usize),
+                        _ => None,
+                    });
+                }
+                value.cloned()
+            }
+            Expr::Not(expr) => match self.eval(expr, document)? {
+                Some(Value::Bool(value)) => Some(Value::Bool(!value)),
+                _ => None,
+            },
+            Expr::And(a, b) => {
+                let a = self.eval(a, document)?;
+                let b = self.eval(b, document)?;
+                match (a, b) {
+                    (Some(Value::Bool(false)), _) | (_, Some(Value::Bool(false))) => {
+                        Some(Value::Bool(false))
+                    }
+                    (Some(Value::Bool(true)), Some(Value::Bool(true))) => Some(Value::Bool(true)),
+                    _ => None,
+                }
+            }
+            Expr::Or(a, b) => {
+                let a = self.eval(a, document)?;
+                let b = self.eval(b, document)?;
+                match (a, b) {
+                    (Some(Value::Bool(true)), _) | (_, Some(Value::Bool(true))) => {
+                        Some(Value::Bool(true))
+                    }
+                    (Some(Value::Bool(false)), Some(Value::Bool(false))) => {
+                        Some(Value::Bool(false))
+                    }
+                    _ => None,
+                }
+            }
+            Expr::Compare(a, comparison, b) => {
+                let a = self.eval(a, document)?;
+                let b = self.eval(b, document)?;
+                let (a, b) = (a.as_ref(), b.as_ref());
+                match comparison {
+                    Comparison::Equal => equals(a, b),
+                    Comparison::NotEqual => equals(a, b).map(|equal| !equal),
+                    Comparison::Less => compare(a, b).map(Ordering::is_lt),
+                    Comparison::LessOrEqual => compare(a, b).map(Ordering::is_le),
+                    Comparison::Greater => compare(a, b).map(Ordering::is_gt),
+                    Comparison::GreaterOrEqual => compare(a, b).map(Ordering::is_ge),
+                }
+                .map(Value::Bool)
+            }
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg, document))
+                    .collect::<Result<Vec<_>, _>>()?;
+                call(name, args)?
+            }
+        })
+    }
+}
+
+fn call(name: &str, args: Vec<Option<Value>>) -> Result<Option<Value>, String> {
+    let arity = |count: usize| {
+        if args.len() == count {
+            Ok(())
+        } else {
+            Err(format!("{name} takes {count} arguments"))
+        }
+    };
+    let string = |index: usize| match &args[index] {
+        Some(Value::String(value)) => Some(value.as_str()),
+        _ => None,
+    };
+    let boolean = |value: Option<bool>| value.map(Value::Bool);
+
+    Ok(match name.to_uppercase().as_str() {
+        "IS_DEFINED" => {
+            arity(1)?;
+            Some(Value::Bool(args[0].is_some()))
+        }
+        "IS_NULL" => {
+            arity(1)?;
+            Some(Value::Bool(args[0] == Some(Value::Null)))
+        }
+        "CONTAINS" => {
+            arity(2)?;
+            boolean(string(0).zip(string(1)).map(|(a, b)| a.contains(b)))
+        }
+        "STARTSWITH" => {
+            arity(2)?;
+            boolean(string(0).zip(string(1)).map(|(a, b)| a.starts_with(b)))
+        }
+        "ENDSWITH" => {
+            arity(2)?;
+            boolean(string(0).zip(string(1)).map(|(a, b)| a.ends_with(b)))
+        }
+        "ARRAY_CONTAINS" => {
+            arity(2)?;
+            match (&args[0], &args[1]) {
+                (Some(Value::Array(values)), Some(value)) => {
+                    Some(Value::Bool(values.contains(value)))
+                }
+                _ => None,
+            }
+        }
+        "LOWER" => {
+            arity(1)?;
+            string(0).map(|value| Value::String(value.to_lowercase()))
+        }
+        "UPPER" => {
+            arity(1)?;
+            string(0).map(|value| Value::String(value.to_uppercase()))
+        }
+        _ => return Err(format!("the function {name} is not supported")),
+    })
+}
+
+/// Whether values of the same type are equal, `None` for the others.
+fn equals(a: Option<&Value>, b: Option<&Value>) -> Option<bool> {
+    match (a?, b?) {
+        (Value::Array(a), Value::Array(b)) => Some(a == b),
+        (Value::Object(a), Value::Object(b)) => Some(a == b),
+        (a, b) => compare(Some(a), Some(b)).map(Ordering::is_eq),
+    }
+}
+
+/// Orders scalar values of the same type, `None` for the others.
+fn compare(a: Option<&Value>, b: Option<&Value>) -> Option<Ordering> {
+    match (a?, b?) {
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Orders values of any type: undefined, null, booleans, numbers, then
+/// strings.
+fn compare_for_order_by(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    fn rank(value: Option<&Value>) -> u8 {
+        match value {
+            None => 0,
+            Some(Value::Null) => 1,
+            Some(Value::Bool(_)) => 2,
+            Some(Value::Number(_)) => 3,
+            Some(Value::String(_)) => 4,
+            Some(Value::Array(_)) => 5,
+            Some(Value::Object(_)) => 6,
+        }
+    }
+    rank(a)
+        .cmp(&rank(b))
+        .then_with(|| compare(a, b).unwrap_or(Ordering::Equal))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Number(f64),
+    String(String),
+    Parameter(String),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: &[&str] = &[
+    "!=", "<>", "<=", ">=", "*", ",", ".", "(", ")", "[", "]", "=", "<", ">",
+];
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start();
+        let Some(first) = rest.chars().next() else {
+            return Ok(tokens);
+        };
+
+        let length = if first == '\'' || first == '"' {
+            let end = rest[1..]
+                .find(first)
+                .ok_or_else(|| String::from("a string of the query is not terminated"))?;
+            tokens.push(Token::String(rest[1..=end].to_owned()));
+            end + 2
+        } else if first.is_ascii_digit()
+            || (first == '-' && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let length = rest[1..]
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .map_or(rest.len(), |end| end + 1);
+            let number = rest[..length]
+                .parse()
+                .map_err(|_| format!("{} is not a number", &rest[..length]))?;
+            tokens.push(Token::Number(number));
+            length
+        } else if first.is_alphabetic() || first == '_' || first == '@' {
+            let length = rest[1..]
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .map_or(rest.len(), |end| end + 1);
+            tokens.push(match rest[..length].strip_prefix('@') {
+                Some(_) => Token::Parameter(rest[..length].to_owned()),
+                None => Token::Identifier(rest[..length].to_owned()),
+            });
+            length
+        } else {
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(*symbol))
+                .ok_or_else(|| format!("unexpected character {first:?} in the query"))?;
+            tokens.push(Token::Symbol(symbol));
+            symbol.len()
+        };
+        rest = &rest[length..];
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| String::from("the query ends unexpectedly"))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(
+            self.peek(),
+            Some(Token::Identifier(identifier)) if identifier.eq_ignore_ascii_case(keyword)
+        );
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(format!("{keyword} expected, found {:?}", self.peek()))
+        }
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(found)) if *found == symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), String> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(format!("{symbol} expected, found {:?}", self.peek()))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, String> {
+        match self.next()? {
+            Token::Identifier(identifier) => Ok(identifier),
+            token => Err(format!("identifier expected, found {token:?}")),
+        }
+    }
+
+    fn query(&mut self) -> Result<Query, String> {
+        self.expect_keyword("SELECT")?;
+        let top = if self.eat_keyword("TOP") {
+            match self.next()? {
+                Token::Number(top) if top >= 0.0 && top.fract() == 0.0 => Some(top as usize),
+                token => return Err(format!("the count of TOP is invalid: {token:?}")),
+            }
+        } else {
+            None
+        };
+
+        let projection = if self.eat_symbol("*") {
+            Projection::All
+        } else if self.eat_keyword("VALUE") {
+            Projection::Value(self.expr()?)
+        } else {
+            let mut fields = Vec::new();
+            loop {
+                let expr = self.expr()?;
+                let name = if self.eat_keyword("AS") {
+                    self.identifier()?
+                } else {
+                    match &expr {
+                        Expr::Path(_, path) => match path.last() {
+                            Some(Value::String(name)) => name.clone(),
+                            _ => format!("${}", fields.len() + 1),
+                        },
+                        _ => format!("${}", fields.len() + 1),
+                    }
+                };
+                fields.push((name, expr));
+                if !self.eat_symbol(",") {
+                    break;
+                }
+            }
+            Projection::Fields(fields)
+        };
+
+        self.expect_keyword("FROM")?;
+        let alias = self.identifier()?;
+
+        let filter = if self.eat_keyword("WHERE") {
+            Some(self.expr()?)
+        } else {
+            None
+        };
+
+        let mut order_by = Vec::new();
+        if self.eat_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let expr = self.expr()?;
+                let descending = if self.eat_keyword("DESC") {
+                    true
+                } else {
+                    self.eat_keyword("ASC");
+                    false
+                };
+                order_by.push((expr, descending));
+                if !self.eat_symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        Ok(Query {
+            top,
+            projection,
+            alias,
+            filter,
+            order_by,
+        })
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("OR") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.not()?;
+        while self.eat_keyword("AND") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.eat_keyword("NOT") {
+            Ok(Expr::Not(Box::new(self.not()?)))
+        } else {
+            self.comparison()
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let expr = self.primary()?;
+        let comparison = match self.peek() {
+            Some(Token::Symbol("=")) => Comparison::Equal,
+            Some(Token::Symbol("!=" | "<>")) => Comparison::NotEqual,
+            Some(Token::Symbol("<")) => Comparison::Less,
+            Some(Token::Symbol("<=")) => Comparison::LessOrEqual,
+            Some(Token::Symbol(">")) => Comparison::Greater,
+            Some(Token::Symbol(">=")) => Comparison::GreaterOrEqual,
+            _ => return Ok(expr),
+        };
+        self.position += 1;
+        Ok(Expr::Compare(
+            Box::new(expr),
+            comparison,
+            Box::new(self.primary()?),
+        ))
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next()? {
+            Token::Symbol("(") => {
+                let expr = self.expr()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Token::Number(number) => Ok(Expr::Literal(number_value(number))),
+            Token::String(string) => Ok(Expr::Literal(Value::String(string))),
+            Token::Parameter(name) => Ok(Expr::Parameter(name)),
+            Token::Identifier(identifier) => {
+                match identifier.to_lowercase().as_str() {
+                    "true" => return Ok(Expr::Literal(Value::Bool(true))),
+                    "false" => return Ok(Expr::Literal(Value::Bool(false))),
+                    "null" => return Ok(Expr::Literal(Value::Null)),
+                    _ => {}
+                }
+                if self.eat_symbol("(") {
+                    let mut args = Vec::new();
+                    if !self.eat_symbol(")") {
+                        loop {
+                            args.push(self.expr()?);
+                            if self.eat_symbol(")") {
+                                break;
+                            }
+                            self.expect_symbol(",")?;
+                        }
+                    }
+                    return Ok(Expr::Call(identifier, args));
+                }
+
+                let mut path = Vec::new();
+                loop {
+                    if self.eat_symbol(".") {
+                        path.push(Value::String(self.identifier()?));
+                    } else if self.eat_symbol("[") {
+                        path.push(match self.next()? {
+                            Token::String(key) => Value::String(key),
+                            Token::Number(index) if index >= 0.0 && index.fract() == 0.0 => {
+                                Value::from(index as u64)
+                            }
+                            token => return Err(format!("invalid property {token:?}")),
+                        });
+                        self.expect_symbol("]")?;
+                    } else {
+                        return Ok(Expr::Path(identifier, path));
+                    }
+                }
+            }
+            token => Err(format!("unexpected {token:?} in the query")),
+        }
+    }
+}
+
+/// Integers stay integers, so that projected values are returned as sent.
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        Value::from(number as i64)
+    } else {
+        Value::from(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(query: &str, documents: &[Value]) -> Vec<Value> {
+        let parameters = json!(2);
+        Query::parse(query)
+            .unwrap()
+            .execute(documents.iter(), &BTreeMap::from([("@two", &parameters)]))
+            .unwrap()
+    }
+
+    #[test]
+    fn queries() {
+        let documents = [
+            json!({ "id": "a", "n": 3, "tags": ["x"], "address": { "city": "Rome" } }),
+            json!({ "id": "b", "n": 1, "tags": [] }),
+            json!({ "id": "c", "n": 2, "tags": ["x", "y"], "address": { "city": "Oslo" } }),
+            json!({ "id": "d", "n": "2" }),
+        ];
+
+        assert_eq!(run("SELECT * FROM c", &documents).len(), 4);
+        assert_eq!(
+            run(
+                "SELECT VALUE c.id FROM c WHERE c.n >= @two ORDER BY c.n DESC",
+                &documents
+            ),
+            [json!("a"), json!("c")]
+        );
+        assert_eq!(
+            run("select top 1 c.id, c.address.city as city from c where is_defined(c.address) order by c.address.city", &documents),
+            [json!({ "id": "c", "city": "Oslo" })]
+        );
+        assert_eq!(
+            run(
+                "SELECT VALUE c.id FROM c WHERE NOT (c.n = 1 OR ARRAY_CONTAINS(c.tags, 'y')) AND c[\"id\"] != 'd'",
+                &documents
+            ),
+            [json!("a")]
+        );
+        assert!(Query::parse("SELECT * FROM c WHERE").is_err());
+        assert!(Query::parse("SELECT * FROM c")
+            .unwrap()
+            .execute(documents.iter(), &BTreeMap::new())
+            .is_ok());
+        assert!(Query::parse("SELECT * FROM c WHERE c.n = @missing")
+            .unwrap()
+            .execute(documents.iter(), &BTreeMap::new())
+            .is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use azure_core::headers::{self, HeaderName, HeaderValue, Headers};
+use azure_core::{date, BytesStream, Response, StatusCode};
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+
+const RESOURCE_QUOTA: &str =
+    "documentSize=10240;documentsSize=10485760;documentsCount=-1;collectionSize=10485760;";
+const RESOURCE_USAGE: &str = "documentSize=0;documentsSize=0;documentsCount=0;collectionSize=0;";
+
+/// The answer of the gateway to a request, turned into a response carrying
+/// every header the client reads.
+#[derive(Debug, Clone)]
+pub(crate) struct Reply {
+    status: StatusCode,
+    body: Option<Value>,
+    item_count: usize,
+    lsn: u64,
+    charge: f64,
+    /// The headers specific to the reply, such as its ETag.
+    headers: Vec<(HeaderName, String)>,
+}
+
+impl Reply {
+    pub(crate) fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            body: None,
+            item_count: 0,
+            lsn: 0,
+            charge: 1.0,
+            headers: Vec::new(),
+        }
+    }
+
+    /// A reply holding a single resource, with its ETag.
+    pub(crate) fn resource(status: StatusCode, resource: &Value) -> Self {
+        let mut reply = Self::new(status);
+        if let Some(etag) = resource["_etag"].as_str() {
+            reply = reply.header(headers::ETAG, etag);
+        }
+        reply.body = Some(resource.clone());
+        reply.item_count = 1;
+        reply
+    }
+
+    /// A reply holding a page of a feed, such as `Documents` or `Databases`.
+    pub(crate) fn feed(
+        rid: &str,
+        kind: &str,
+        items: Vec<Value>,
+        continuation: Option<String>,
+    ) -> Self {
+        let mut reply = Self::new(StatusCode::Ok);
+        reply.item_count = items.len();
+        let mut body = serde_json::Map::new();
+        body.insert(String::from("_rid"), json!(rid));
+        body.insert(String::from("_count"), json!(items.len()));
+        body.insert(kind.to_owned(), Value::Array(items));
+        reply.body = Some(Value::Object(body));
+        match continuation {
+            Some(continuation) => reply.header(headers::CONTINUATION, continuation),
+            None => reply,
+        }
+    }
+
+    /// A reply to a failed request, with the body the service returns.
+    pub(crate) fn error(status: StatusCode, message: &str) -> Self {
+        let mut reply = Self::new(status);
+        reply.charge = 0.0;
+        reply.body = Some(json!({
+            "code": status.canonical_reason().replace(' ', ""),
+            "message": message,
+        }));
+        reply
+    }
+
+    /// The reply to a throttled request, to be retried after a millisecond.
+    pub(crate) fn throttled() -> Self {
+        Self::error(
+            StatusCode::TooManyRequests,
+            "request rate is large, more request units may be needed",
+        )
+        .header(headers::X_MS_RETRY_AFTER_MS, "1")
+        .header(HeaderName::from_static("x-ms-substatus"), "3200")
+    }
+
+    pub(crate) fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub(crate) fn lsn(mut self, lsn: u64) -> Self {
+        self.lsn = lsn;
+        self
+    }
+
+    pub(crate) fn charge(mut self, charge: f64) -> Self {
+        self.charge = charge;
+        self
+    }
+
+    pub(crate) fn alt_content_path(self, alt_content_path: String) -> Self {
+        self.header(
+            HeaderName::from_static("x-ms-alt-content-path"),
+            alt_content_path,
+        )
+    }
+
+    pub(crate) fn header(mut self, name: HeaderName, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+impl From<Reply> for Response {
+    fn from(reply: Reply) -> Self {
+        let now = OffsetDateTime::now_utc();
+        let lsn = reply.lsn.to_string();
+        let mut headers = Headers::new();
+        let mut insert = |name: &'static str, value: String| {
+            headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+        };
+
+        insert("content-type", String::from("application/json"));
+        insert("date", date::to_rfc1123(&now));
+        insert("server", String::from("fake_cosmos"));
+        insert("x-ms-activity-id", uuid::Uuid::new_v4().to_string());
+        insert("x-ms-request-charge", reply.charge.to_string());
+        insert("x-ms-request-duration-ms", String::from("0.1"));
+        insert("x-ms-session-token", format!("0:-1#{lsn}"));
+        for name in [
+            "lsn",
+            "x-ms-item-lsn",
+            "x-ms-cosmos-llsn",
+            "x-ms-cosmos-item-llsn",
+            "x-ms-global-committed-lsn",
+            "x-ms-quorum-acked-lsn",
+            "x-ms-cosmos-quorum-acked-llsn",
+        ] {
+            insert(name, lsn.clone());
+        }
+        insert("x-ms-current-write-quorum", String::from("1"));
+        insert("x-ms-current-replica-set-size", String::from("1"));
+        insert("x-ms-number-of-read-regions", String::from("0"));
+        insert("x-ms-xp-role", String::from("1"));
+        insert("x-ms-transport-request-id", String::from("1"));
+        insert("x-ms-schemaversion", String::from("1.13"));
+        insert("x-ms-serviceversion", String::from("version=2.14.0.0"));
+        insert("x-ms-gatewayversion", String::from("version=2.14.0"));
+        insert(
+            "x-ms-last-state-change-utc",
+            date::to_rfc1123(&now).replace(" GMT", ".000 GMT"),
+        );
+        insert("x-ms-resource-quota", String::from(RESOURCE_QUOTA));
+        insert("x-ms-resource-usage", String::from(RESOURCE_USAGE));
+        insert("x-ms-alt-content-path", String::new());
+        insert("x-ms-content-path", String::new());
+        insert("x-ms-item-count", reply.item_count.to_string());
+        insert("collection-partition-index", String::from("0"));
+        insert("collection-service-index", String::from("0"));
+        insert("x-ms-max-media-storage-usage-mb", String::from("10240"));
+        insert("x-ms-media-storage-usage-mb", String::from("0"));
+        for (name, value) in reply.headers {
+            headers.insert(name, HeaderValue::from(value));
+        }
+
+        let body = reply
+            .body
+            .map(|body| bytes::Bytes::from(body.to_string()))
+            .unwrap_or_default();
+        Response::new(reply.status, headers, Box::pin(BytesStream::new(body)))
+    }
+}
@@ -0,0 +1,658 @@
+use crate::query::Query;
+use crate::reply::Reply;
+use crate::UNSUPPORTED;
+use azure_core::headers::{HeaderName, CONTINUATION, IF_MATCH, IF_NONE_MATCH, MAX_ITEM_COUNT};
+use azure_core::{Body, Method, Request, StatusCode};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
+
+const PARTITION_KEY: HeaderName = HeaderName::from_static("x-ms-documentdb-partitionkey");
+const IS_UPSERT: HeaderName = HeaderName::from_static("x-ms-documentdb-is-upsert");
+const IS_QUERY: HeaderName = HeaderName::from_static("x-ms-documentdb-isquery");
+const POPULATE_QUERY_METRICS: HeaderName =
+    HeaderName::from_static("x-ms-documentdb-populatequerymetrics");
+const QUERY_METRICS: HeaderName = HeaderName::from_static("x-ms-documentdb-query-metrics");
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// The databases of the gateway and everything they contain.
+#[derive(Debug, Default)]
+pub(crate) struct Store {
+    databases: BTreeMap<String, Database>,
+    /// Increased by every write, it is the session token of the responses.
+    lsn: u64,
+    rid_count: u64,
+}
+
+#[derive(Debug)]
+struct Database {
+    resource: Value,
+    collections: BTreeMap<String, Collection>,
+}
+
+#[derive(Debug)]
+struct Collection {
+    resource: Value,
+    /// The documents by serialized partition key and id.
+    documents: BTreeMap<(String, String), Value>,
+}
+
+impl Collection {
+    fn partition_key_path(&self) -> &str {
+        self.resource["partitionKey"]["paths"][0]
+            .as_str()
+            .unwrap_or_default()
+    }
+}
+
+impl Store {
+    pub(crate) fn handle(&mut self, request: &Request) -> Reply {
+        let segments: Vec<String> = request
+            .url()
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                percent_encoding::percent_decode_str(segment)
+                    .decode_utf8_lossy()
+                    .into_owned()
+            })
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+        let result = match (request.method(), segments.as_slice()) {
+            (Method::Get, []) => Ok(account(request)),
+            (Method::Post, ["dbs"]) => self.create_database(request),
+            (Method::Get, ["dbs"]) => self.list_databases(request),
+            (Method::Get, ["dbs", db]) => self
+                .database(db)
+                .map(|database| Reply::resource(StatusCode::Ok, &database.resource)),
+            (Method::Delete, ["dbs", db]) => self.delete_database(db),
+            (Method::Post, ["dbs", db, "colls"]) => self.create_collection(db, request),
+            (Method::Get, ["dbs", db, "colls"]) => self.list_collections(db, request),
+            (Method::Get, ["dbs", db, "colls", coll]) => self
+                .collection(db, coll)
+                .map(|collection| Reply::resource(StatusCode::Ok, &collection.resource)),
+            (Method::Put, ["dbs", db, "colls", coll]) => self.replace_collection(db, coll, request),
+            (Method::Delete, ["dbs", db, "colls", coll]) => self.delete_collection(db, coll),
+            (Method::Get, ["dbs", db, "colls", coll, "pkranges"]) => {
+                self.partition_key_ranges(db, coll)
+            }
+            (Method::Post, ["dbs", db, "colls", coll, "docs"])
+                if request.headers().get_optional_str(&IS_QUERY) == Some("true") =>
+            {
+                self.query_documents(db, coll, request)
+            }
+            (Method::Post, ["dbs", db, "colls", coll, "docs"]) => {
+                self.create_document(db, coll, request)
+            }
+            (Method::Get, ["dbs", db, "colls", coll, "docs"]) => {
+                self.list_documents(db, coll, request)
+            }
+            (Method::Get, ["dbs", db, "colls", coll, "docs", id]) => {
+                self.get_document(db, coll, id, request)
+            }
+            (Method::Put, ["dbs", db, "colls", coll, "docs", id]) => {
+                self.replace_document(db, coll, id, request)
+            }
+            (Method::Delete, ["dbs", db, "colls", coll, "docs", id]) => {
+                self.delete_document(db, coll, id, request)
+            }
+            (method, _) => Err(Reply::error(
+                UNSUPPORTED,
+                &format!(
+                    "{method} {} is not supported by the fake gateway",
+                    request.url().path()
+                ),
+            )),
+        };
+
+        // the link of the parent of the resource or the feed
+        let parent = &segments[..segments.len().saturating_sub(2 - segments.len() % 2)];
+        result
+            .unwrap_or_else(|reply| reply)
+            .lsn(self.lsn)
+            .alt_content_path(parent.join("/"))
+    }
+
+    fn create_database(&mut self, request: &Request) -> Result<Reply, Reply> {
+        let mut resource = json_body(request)?;
+        let id = id_of(&resource)?;
+        if self.databases.contains_key(&id) {
+            return Err(conflict(&id));
+        }
+
+        let rid = self.next_rid();
+        set_system_properties(
+            &mut resource,
+            &rid,
+            format!("dbs/{rid}/"),
+            &[("_colls", "colls/"), ("_users", "users/")],
+        );
+        self.databases.insert(
+            id,
+            Database {
+                resource: resource.clone(),
+                collections: BTreeMap::new(),
+            },
+        );
+        self.lsn += 1;
+        Ok(Reply::resource(StatusCode::Created, &resource))
+    }
+
+    fn list_databases(&self, request: &Request) -> Result<Reply, Reply> {
+        let databases = self
+            .databases
+            .values()
+            .map(|database| database.resource.clone())
+            .collect();
+        let (databases, continuation) = page(request, databases)?;
+        Ok(Reply::feed("", "Databases", databases, continuation))
+    }
+
+    fn delete_database(&mut self, db: &str) -> Result<Reply, Reply> {
+        self.databases
+            .remove(db)
+            .ok_or_else(|| not_found("database", db))?;
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::NoContent))
+    }
+
+    fn create_collection(&mut self, db: &str, request: &Request) -> Result<Reply, Reply> {
+        let mut resource = json_body(request)?;
+        let id = id_of(&resource)?;
+        if resource["partitionKey"]["paths"][0].as_str().is_none() {
+            return Err(bad_request(
+                "the partition key of the collection is missing",
+            ));
+        }
+        if resource.get("indexingPolicy").is_none() {
+            resource["indexingPolicy"] = json!({
+                "automatic": true,
+                "indexingMode": "consistent",
+                "includedPaths": [{ "path": "/*" }],
+                "excludedPaths": [{ "path": "/\"_etag\"/?" }],
+            });
+        }
+
+        let rid = self.next_rid();
+        let database = self.database_mut(db)?;
+        if database.collections.contains_key(&id) {
+            return Err(conflict(&id));
+        }
+        let database_self = database.resource["_self"].as_str().unwrap_or_default();
+        set_system_properties(
+            &mut resource,
+            &rid,
+            format!("{database_self}colls/{rid}/"),
+            &[
+                ("_docs", "docs/"),
+                ("_sprocs", "sprocs/"),
+                ("_triggers", "triggers/"),
+                ("_udfs", "udfs/"),
+                ("_conflicts", "conflicts/"),
+            ],
+        );
+        database.collections.insert(
+            id,
+            Collection {
+                resource: resource.clone(),
+                documents: BTreeMap::new(),
+            },
+        );
+        self.lsn += 1;
+        Ok(Reply::resource(StatusCode::Created, &resource))
+    }
+
+    fn list_collections(&self, db: &str, request: &Request) -> Result<Reply, Reply> {
+        let database = self.database(db)?;
+        let collections = database
+            .collections
+            .values()
+            .map(|collection| collection.resource.clone())
+            .collect();
+        let (collections, continuation) = page(request, collections)?;
+        Ok(Reply::feed(
+            database.resource["_rid"].as_str().unwrap_or_default(),
+            "DocumentCollections",
+            collections,
+            continuation,
+        ))
+    }
+
+    fn replace_collection(
+        &mut self,
+        db: &str,
+        coll: &str,
+        request: &Request,
+    ) -> Result<Reply, Reply> {
+        let mut resource = json_body(request)?;
+        if id_of(&resource)? != coll {
+            return Err(bad_request("the id of the collection cannot be changed"));
+        }
+        let collection = self.collection_mut(db, coll)?;
+        check_if_match(request, &collection.resource)?;
+        if resource["partitionKey"] != collection.resource["partitionKey"] {
+            return Err(bad_request(
+                "the partition key of the collection cannot be changed",
+            ));
+        }
+
+        let rid = collection.resource["_rid"]
+            .as_str()
+            .unwrap_or_default()
+            .to_owned();
+        let resource_self = collection.resource["_self"]
+            .as_str()
+            .unwrap_or_default()
+            .to_owned();
+        set_system_properties(
+            &mut resource,
+            &rid,
+            resource_self,
+            &[
+                ("_docs", "docs/"),
+                ("_sprocs", "sprocs/"),
+                ("_triggers", "triggers/"),
+                ("_udfs", "udfs/"),
+                ("_conflicts", "conflicts/"),
+            ],
+        );
+        collection.resource = resource.clone();
+        self.lsn += 1;
+        Ok(Reply::resource(StatusCode::Ok, &resource))
+    }
+
+    fn delete_collection(&mut self, db: &str, coll: &str) -> Result<Reply, Reply> {
+        self.database_mut(db)?
+            .collections
+            .remove(coll)
+            .ok_or_else(|| not_found("collection", coll))?;
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::NoContent))
+    }
+
+    /// The whole collection is a single range.
+    fn partition_key_ranges(&self, db: &str, coll: &str) -> Result<Reply, Reply> {
+        let collection = self.collection(db, coll)?;
+        let rid = collection.resource["_rid"].as_str().unwrap_or_default();
+        let range = json!({
+            "_rid": rid,
+            "id": "0",
+            "_etag": "\"00000000-0000-0000-0000-000000000000\"",
+            "minInclusive": "",
+            "maxExclusive": "FF",
+            "ridPrefix": 0,
+            "_self": format!("{}pkranges/0/", collection.resource["_self"].as_str().unwrap_or_default()),
+            "throughputFraction": 1.0,
+            "status": "online",
+            "parents": [],
+            "_ts": collection.resource["_ts"],
+        });
+        Ok(Reply::feed(rid, "PartitionKeyRanges", vec![range], None))
+    }
+
+    fn create_document(&mut self, db: &str, coll: &str, request: &Request) -> Result<Reply, Reply> {
+        let mut document = json_body(request)?;
+        let id = id_of(&document)?;
+        let partition_key = partition_key(request)?;
+        let upsert = request.headers().get_optional_str(&IS_UPSERT) == Some("true");
+
+        let rid = self.next_rid();
+        let collection = self.collection_mut(db, coll)?;
+        check_partition_key(collection, &document, &partition_key)?;
+        let key = (partition_key, id);
+        let status = match collection.documents.get(&key) {
+            Some(_) if !upsert => return Err(conflict(&key.1)),
+            Some(existing) => {
+                check_if_match(request, existing)?;
+                StatusCode::Ok
+            }
+            None => StatusCode::Created,
+        };
+
+        let collection_self = collection.resource["_self"].as_str().unwrap_or_default();
+        let resource_self = format!("{collection_self}docs/{rid}/");
+        set_system_properties(
+            &mut document,
+            &rid,
+            resource_self,
+            &[("_attachments", "attachments/")],
+        );
+        collection.documents.insert(key, document.clone());
+        self.lsn += 1;
+        Ok(Reply::resource(status, &document).charge(5.0))
+    }
+
+    fn list_documents(&self, db: &str, coll: &str, request: &Request) -> Result<Reply, Reply> {
+        let collection = self.collection(db, coll)?;
+        let partition_key = optional_partition_key(request)?;
+        let documents = collection
+            .documents
+            .iter()
+            .filter(|((key, _), _)| in_partition(&partition_key, key))
+            .map(|(_, document)| document.clone())
+            .collect();
+        let (documents, continuation) = page(request, documents)?;
+        Ok(Reply::feed(
+            collection.resource["_rid"].as_str().unwrap_or_default(),
+            "Documents",
+            documents,
+            continuation,
+        ))
+    }
+
+    fn query_documents(&self, db: &str, coll: &str, request: &Request) -> Result<Reply, Reply> {
+        let collection = self.collection(db, coll)?;
+        let partition_key = optional_partition_key(request)?;
+        let body = json_body(request)?;
+        let text = body["query"]
+            .as_str()
+            .ok_or_else(|| bad_request("the query is missing"))?;
+        let parameters: BTreeMap<&str, &Value> = body["parameters"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|parameter| Some((parameter["name"].as_str()?, &parameter["value"])))
+            .collect();
+
+        let query = Query::parse(text).map_err(|message| bad_request(&message))?;
+        let documents = collection
+            .documents
+            .iter()
+            .filter(|((key, _), _)| in_partition(&partition_key, key))
+            .map(|(_, document)| document);
+        let results = query
+            .execute(documents, &parameters)
+            .map_err(|message| bad_request(&message))?;
+        let (results, continuation) = page(request, results)?;
+        let output_document_count = results.len();
+        let reply = Reply::feed(
+            collection.resource["_rid"].as_str().unwrap_or_default(),
+            "Documents",
+            results,
+            continuation,
+        )
+        .charge(2.5);
+
+        // the metrics only count the documents of the page
+        match request.headers().get_optional_str(&POPULATE_QUERY_METRICS) {
+            Some("true") => Ok(reply.header(
+                QUERY_METRICS,
+                format!(
+                    "retrievedDocumentCount={output_document_count};\
+                     outputDocumentCount={output_document_count};totalExecutionTimeInMs=0.10"
+                ),
+            )),
+            _ => Ok(reply),
+        }
+    }
+
+    fn get_document(
+        &self,
+        db: &str,
+        coll: &str,
+        id: &str,
+        request: &Request,
+    ) -> Result<Reply, Reply> {
+        let key = (partition_key(request)?, id.to_owned());
+        let document = self
+            .collection(db, coll)?
+            .documents
+            .get(&key)
+            .ok_or_else(|| not_found("document", id))?;
+        if request.headers().get_optional_str(&IF_NONE_MATCH) == document["_etag"].as_str() {
+            return Ok(Reply::new(StatusCode::NotModified));
+        }
+        Ok(Reply::resource(StatusCode::Ok, document))
+    }
+
+    fn replace_document(
+        &mut self,
+        db: &str,
+        coll: &str,
+        id: &str,
+        request: &Request,
+    ) -> Result<Reply, Reply> {
+        let mut document = json_body(request)?;
+        if id_of(&document)? != id {
+            return Err(bad_request("the id of the document cannot be changed"));
+        }
+        let key = (partition_key(request)?, id.to_owned());
+        let collection = self.collection_mut(db, coll)?;
+        check_partition_key(collection, &document, &key.0)?;
+        let existing = collection
+            .documents
+            .get_mut(&key)
+            .ok_or_else(|| not_found("document", id))?;
+        check_if_match(request, existing)?;
+
+        let rid = existing["_rid"].as_str().unwrap_or_default().to_owned();
+        let resource_self = existing["_self"].as_str().unwrap_or_default().to_owned();
+        set_system_properties(
+            &mut document,
+            &rid,
+            resource_self,
+            &[("_attachments", "attachments/")],
+        );
+        *existing = document.clone();
+        self.lsn += 1;
+        Ok(Reply::resource(StatusCode::Ok, &document).charge(5.0))
+    }
+
+    fn delete_document(
+        &mut self,
+        db: &str,
+        coll: &str,
+        id: &str,
+        request: &Request,
+    ) -> Result<Reply, Reply> {
+        let key = (partition_key(request)?, id.to_owned());
+        let collection = self.collection_mut(db, coll)?;
+        let existing = collection
+            .documents
+            .get(&key)
+            .ok_or_else(|| not_found("document", id))?;
+        check_if_match(request, existing)?;
+        collection.documents.remove(&key);
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::NoContent).charge(5.0))
+    }
+
+    fn database(&self, db: &str) -> Result<&Database, Reply> {
+        self.databases
+            .get(db)
+            .ok_or_else(|| not_found("database", db))
+    }
+
+    fn database_mut(&mut self, db: &str) -> Result<&mut Database, Reply> {
+        self.databases
+            .get_mut(db)
+            .ok_or_else(|| not_found("database", db))
+    }
+
+    fn collection(&self, db: &str, coll: &str) -> Result<&Collection, Reply> {
+        self.database(db)?
+            .collections
+            .get(coll)
+            .ok_or_else(|| not_found("collection", coll))
+    }
+
+    fn collection_mut(&mut self, db: &str, coll: &str) -> Result<&mut Collection, Reply> {
+        self.database_mut(db)?
+            .collections
+            .get_mut(coll)
+            .ok_or_else(|| not_found("collection", coll))
+    }
+
+    fn next_rid(&mut self) -> String {
+        self.rid_count += 1;
+        format!("{:08x}", self.rid_count)
+    }
+}
+
+/// The account has a single region, served by the gateway itself.
+fn account(request: &Request) -> Reply {
+    let endpoint = format!("{}/", request.url().origin().ascii_serialization());
+    let region = json!({ "name": "Fake Region", "databaseAccountEndpoint": endpoint });
+    let account = json!({
+        "id": request.url().host_str().unwrap_or_default(),
+        "_rid": request.url().host_str().unwrap_or_default(),
+        "writableLocations": [region],
+        "readableLocations": [region],
+        "enableMultipleWriteLocations": false,
+    });
+    Reply::resource(StatusCode::Ok, &account)
+}
+
+/// Sets the properties the service adds to the resources it stores.
+fn set_system_properties(
+    resource: &mut Value,
+    rid: &str,
+    resource_self: String,
+    links: &[(&str, &str)],
+) {
+    let Value::Object(properties) = resource else {
+        return;
+    };
+    properties.insert(String::from("_rid"), json!(rid));
+    properties.insert(String::from("_self"), json!(resource_self));
+    properties.insert(
+        String::from("_etag"),
+        json!(format!("\"{}\"", uuid::Uuid::new_v4())),
+    );
+    properties.insert(
+        String::from("_ts"),
+        json!(OffsetDateTime::now_utc().unix_timestamp()),
+    );
+    for (name, link) in links {
+        properties.insert((*name).to_owned(), json!(link));
+    }
+}
+
+/// Splits the items into the page asked for by the request and the
+/// continuation token of the next page, which is the offset of its first
+/// item.
+fn page(request: &Request, items: Vec<Value>) -> Result<(Vec<Value>, Option<String>), Reply> {
+    let page_size = request
+        .headers()
+        .get_optional_str(&MAX_ITEM_COUNT)
+        .and_then(|count| count.parse::<i64>().ok())
+        .filter(|count| *count > 0)
+        .map_or(DEFAULT_PAGE_SIZE, |count| count as usize);
+    let start = match request.headers().get_optional_str(&CONTINUATION) {
+        Some(continuation) => continuation
+            .parse::<usize>()
+            .map_err(|_| bad_request("the continuation token is invalid"))?,
+        None => 0,
+    };
+
+    let end = items.len().min(start.saturating_add(page_size));
+    let continuation = (end < items.len()).then(|| end.to_string());
+    let page = items.into_iter().take(end).skip(start).collect();
+    Ok((page, continuation))
+}
+
+fn json_body(request: &Request) -> Result<Value, Reply> {
+    let body = match request.body() {
+        Body::Bytes(bytes) => bytes as &[u8],
+        #[cfg(not(target_arch = "wasm32"))]
+        Body::SeekableStream(_) => return Err(bad_request("streamed bodies are not supported")),
+    };
+    let value: Value = serde_json::from_slice(body)
+        .map_err(|error| bad_request(&format!("the body is not valid json: {error}")))?;
+    if value.is_object() {
+        Ok(value)
+    } else {
+        Err(bad_request("the body is not a json object"))
+    }
+}
+
+fn id_of(resource: &Value) -> Result<String, Reply> {
+    match resource["id"].as_str() {
+        Some(id) if !id.is_empty() => Ok(id.to_owned()),
+        _ => Err(bad_request("the id of the resource is missing")),
+    }
+}
+
+/// The partition key of the request, serialized as the client does.
+fn partition_key(request: &Request) -> Result<String, Reply> {
+    optional_partition_key(request)?.ok_or_else(|| {
+        bad_request("the partition key must be provided in the x-ms-documentdb-partitionkey header")
+    })
+}
+
+fn optional_partition_key(request: &Request) -> Result<Option<String>, Reply> {
+    request
+        .headers()
+        .get_optional_str(&PARTITION_KEY)
+        .map(|partition_key| {
+            let value: Value = serde_json::from_str(partition_key)
+                .map_err(|_| bad_request("the partition key header is not valid json"))?;
+            match value {
+                Value::Array(ref values) if values.len() == 1 => Ok(value.to_string()),
+                _ => Err(bad_request(
+                    "the partition key header must hold a single component",
+                )),
+            }
+        })
+        .transpose()
+}
+
+/// Whether a document of the partition is in the scope of a request, which
+/// is the whole collection without a partition key.
+fn in_partition(scope: &Option<String>, partition_key: &str) -> bool {
+    match scope {
+        Some(scope) => scope == partition_key,
+        None => true,
+    }
+}
+
+/// Checks the partition key of the request is the one of the document.
+fn check_partition_key(
+    collection: &Collection,
+    document: &Value,
+    partition_key: &str,
+) -> Result<(), Reply> {
+    let value = document
+        .pointer(collection.partition_key_path())
+        .cloned()
+        .unwrap_or_else(|| Value::Object(Map::new()));
+    let expected = Value::Array(vec![value]).to_string();
+    if expected == partition_key {
+        Ok(())
+    } else {
+        Err(bad_request(
+            "the partition key extracted from the document doesn't match the one specified in the header",
+        ))
+    }
+}
+
+fn check_if_match(request: &Request, resource: &Value) -> Result<(), Reply> {
+    match request.headers().get_optional_str(&IF_MATCH) {
+        Some(etag) if etag != "*" && Some(etag) != resource["_etag"].as_str() => Err(Reply::error(
+            StatusCode::PreconditionFailed,
+            "one of the specified pre-condition is not met",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn bad_request(message: &str) -> Reply {
+    Reply::error(StatusCode::BadRequest, message)
+}
+
+fn not_found(kind: &str, id: &str) -> Reply {
+    Reply::error(
+        StatusCode::NotFound,
+        &format!("the {kind} {id} does not exist"),
+    )
+}
+
+fn conflict(id: &str) -> Reply {
+    Reply::error(
+        StatusCode::Conflict,
+        &format!("a resource with the id {id} already exists"),
+    )
+}
@@ -51,6 +51,16 @@ impl HttpError {
     pub fn error_message(&self) -> Option<&str> {
         self.details.message.as_deref()
     }
+
+    /// Get the body of the unsuccessful response.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Get the value of a header of the unsuccessful response.
+    pub fn header(&self, name: &headers::HeaderName) -> Option<&str> {
+        self.headers.get(name.as_str()).map(String::as_str)
+    }
 }
 
 impl std::fmt::Display for HttpError {
@@ -110,9 +120,24 @@ struct NestedError {
     code: Option<String>,
 }
 
+/// The error format of OData services, such as Azure Table storage and the
+/// Azure Cosmos DB Table API.
+#[derive(Deserialize)]
+struct ODataError {
+    code: Option<String>,
+    message: Option<ODataErrorMessage>,
+}
+
+#[derive(Deserialize)]
+struct ODataErrorMessage {
+    value: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct ErrorBody {
     error: Option<NestedError>,
+    #[serde(rename = "odata.error")]
+    odata_error: Option<ODataError>,
     message: Option<String>,
     code: Option<String>,
 }
@@ -122,7 +147,11 @@ struct ErrorBody {
 /// For more info, see [here](https://github.com/microsoft/api-guidelines/blob/vNext/azure/Guidelines.md#handling-errors)
 pub(crate) fn get_error_code_from_body(body: &[u8]) -> Option<String> {
     let decoded: ErrorBody = from_json(body).ok()?;
-    decoded.error.and_then(|e| e.code).or(decoded.code)
+    decoded
+        .error
+        .and_then(|e| e.code)
+        .or_else(|| decoded.odata_error.and_then(|e| e.code))
+        .or(decoded.code)
 }
 
 /// Gets the error message if it's present in the body
@@ -130,5 +159,32 @@ pub(crate) fn get_error_code_from_body(body: &[u8]) -> Option<String> {
 /// For more info, see [here](https://github.com/microsoft/api-guidelines/blob/vNext/azure/Guidelines.md#handling-errors)
 pub(crate) fn get_error_message_from_body(body: &[u8]) -> Option<String> {
     let decoded: ErrorBody = from_json(body).ok()?;
-    decoded.error.and_then(|e| e.message).or(decoded.message)
+    decoded
+        .error
+        .and_then(|e| e.message)
+        .or_else(|| {
+            decoded
+                .odata_error
+                .and_then(|e| e.message)
+                .and_then(|m| m.value)
+        })
+        .or(decoded.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odata_error_body() {
+        let body = br#"{"odata.error":{"code":"EntityAlreadyExists","message":{"lang":"en-us","value":"The specified entity already exists."}}}"#;
+        assert_eq!(
+            get_error_code_from_body(body).as_deref(),
+            Some("EntityAlreadyExists")
+        );
+        assert_eq!(
+            get_error_message_from_body(body).as_deref(),
+            Some("The specified entity already exists.")
+        );
+    }
 }
@@ -120,6 +120,29 @@ macro_rules! setters {
 ///
 /// Additionally, `#[stream]` can be used before the operation name to generate code appropriate for list operations
 /// and `#[skip]` can be used at the end of the list of options for options where we should not generate a setter.
+///
+/// `#[stream]` operations may also be generic over their client:
+///
+/// ```
+/// # use azure_core::prelude::MaxResults;
+/// # use std::num::NonZeroU32;
+/// pub trait PathClient: Clone + std::fmt::Debug {}
+///
+/// #[derive(Clone, Debug)]
+/// pub struct FileClient;
+/// impl PathClient for FileClient {}
+///
+/// azure_core::operation! {
+///     #[stream]
+///     ListHandles<C: PathClient>,
+///     client: C,
+///     ?max_results: MaxResults
+/// }
+///
+/// let builder = ListHandlesBuilder::new(FileClient)
+///     .max_results(NonZeroU32::new(10).unwrap());
+/// assert!(builder.max_results.is_some());
+/// ```
 #[macro_export]
 macro_rules! operation {
     // Construct the builder.
@@ -209,6 +232,24 @@ macro_rules! operation {
                 $($nosetter: $nstype),*
             }
     };
+    // `operation! { #[stream] ListHandles<C: PathClient>, client: C, ?max_results: MaxResults }`
+    (#[stream] $(#[$outer:meta])*
+        $name:ident<$($generic:ident: $first_constraint:ident $(+ $constraint:ident)*),* $(+ $lt:lifetime)?>,
+        client: $client:ty,
+        $($required:ident: $rtype:ty,)*
+        $(?$optional:ident: $otype:ty),*) => {
+            $crate::operation!{
+                @builder
+                $(#[$outer])*
+                $name<$($generic: $first_constraint $(+ $constraint)*),* $(+ $lt)*>,
+                client: $client,
+                @required
+                $($required: $rtype,)*
+                @optional
+                $($optional: $otype,)*
+                @nosetter
+            }
+    };
     // Construct a builder and the `Future` related code
     ($(#[$outer:meta])* $name:ident<$($generic:ident: $first_constraint:ident $(+ $constraint:ident)* ),* $(+ $lt:lifetime)?>,
         client: $client:ty,
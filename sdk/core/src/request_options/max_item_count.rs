@@ -9,6 +9,11 @@ impl MaxItemCount {
     pub fn new(count: i32) -> Self {
         Self(count)
     }
+
+    /// The max number of items, or `None` to let the service decide
+    pub fn count(&self) -> Option<u32> {
+        u32::try_from(self.0).ok().filter(|count| *count > 0)
+    }
 }
 
 impl Header for MaxItemCount {
@@ -94,6 +94,9 @@ impl Policy for AuthorizationPolicy {
 /// 3. Find if the uri **is** the ending string (without the leading slash). If so return an empty
 ///    string. This covers the exception of the rule above.
 /// 4. Return the received uri unchanged.
+///
+/// Offers are the exception: they are addressed by resource id, and their resource link is the
+/// lowercase resource id alone.
 fn generate_resource_link(request: &Request) -> String {
     static ENDING_STRINGS: &[&str] = &[
         "/dbs",
@@ -106,6 +109,8 @@ fn generate_resource_link(request: &Request) -> String {
         "/pkranges",
         "/udfs",
         "/triggers",
+        "/conflicts",
+        "/offers",
     ];
 
     // This strips the leading slash from the uri of the passed request.
@@ -113,6 +118,10 @@ fn generate_resource_link(request: &Request) -> String {
     let uri = uri_path.trim_start_matches('/');
     trace!("uri used by AuthorizationPolicy == {:#?}", uri);
 
+    if let Some(offer_rid) = uri.strip_prefix("offers/") {
+        return offer_rid.trim_end_matches('/').to_lowercase();
+    }
+
     // We find the above resource names. If found, we strip it and eagerly return. Note that the
     // resource names have a leading slash so the suffix will match `test/users` but not
     // `test-users`.
@@ -234,6 +243,9 @@ fn string_to_sign(
             ResourceType::PartitionKeyRanges => "pkranges",
             ResourceType::UserDefinedFunctions => "udfs",
             ResourceType::Triggers => "triggers",
+            ResourceType::Conflicts => "conflicts",
+            ResourceType::Offers => "offers",
+            ResourceType::DatabaseAccount => "",
         },
         resource_link,
         date::to_rfc1123(&time_nonce).to_lowercase()
@@ -363,6 +375,21 @@ mon, 01 jan 1900 01:00:00 gmt
         assert_eq!(&generate_resource_link(&request), "dbs/test_db");
     }
 
+    #[test]
+    fn generate_resource_link_offers() {
+        let request = Request::new(
+            Url::parse("https://.documents.azure.com/offers").unwrap(),
+            azure_core::Method::Post,
+        );
+        assert_eq!(&generate_resource_link(&request), "");
+
+        let request = Request::new(
+            Url::parse("https://.documents.azure.com/offers/CV5G").unwrap(),
+            azure_core::Method::Put,
+        );
+        assert_eq!(&generate_resource_link(&request), "cv5g");
+    }
+
     #[test]
     fn scope_from_url_01() {
         let scope =
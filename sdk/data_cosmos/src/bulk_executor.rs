@@ -0,0 +1,1080 @@
+use crate::{
+    effective_partition_key,
+    headers::{self as cosmos_headers, HEADER_SUB_STATUS},
+    operations::{
+        BatchOperation, BatchOperationResult, PartitionKeyRange, TransactionalBatchResponse,
+        MAX_TRANSACTIONAL_BATCH_OPERATIONS, PARTITION_KEY_RANGE_GONE,
+    },
+    prelude::*,
+    resources::{collection::KeyKind, ResourceType},
+    CosmosEntity, DiagnosticsRecorder,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{self, HeaderValue, X_MS_RETRY_AFTER_MS},
+    Context, Method, StatusCode,
+};
+use futures::{
+    future::BoxFuture,
+    stream::{Fuse, FusedStream, FuturesUnordered},
+    FutureExt, Stream, StreamExt,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+/// The maximum size of the body of a batch request.
+const MAX_BATCH_SIZE: usize = 2 * 1024 * 1024;
+/// Room left for the partition key each operation carries in a batch.
+const OPERATION_OVERHEAD: usize = 64;
+
+/// An operation to be executed by a [`BulkExecutor`].
+#[derive(Debug, Clone)]
+pub struct BulkOperation {
+    partition_key_serialized: String,
+    operation: BatchOperation,
+    size: usize,
+}
+
+impl BulkOperation {
+    /// An operation on a document of the given logical partition.
+    pub fn new<PK: Serialize>(
+        partition_key: &PK,
+        operation: BatchOperation,
+    ) -> azure_core::Result<Self> {
+        let size = serde_json::to_vec(&operation)?.len() + OPERATION_OVERHEAD;
+        Ok(Self {
+            partition_key_serialized: crate::cosmos_entity::serialize_partition_key(partition_key)?,
+            operation,
+            size,
+        })
+    }
+
+    /// Create a document, failing if it already exists.
+    pub fn create<D: Serialize + CosmosEntity>(document: &D) -> azure_core::Result<Self> {
+        Self::new(&document.partition_key(), BatchOperation::create(document)?)
+    }
+
+    /// Create a document or replace it if it already exists.
+    pub fn upsert<D: Serialize + CosmosEntity>(document: &D) -> azure_core::Result<Self> {
+        Self::new(&document.partition_key(), BatchOperation::upsert(document)?)
+    }
+
+    /// Replace an existing document.
+    pub fn replace<S: Into<String>, D: Serialize + CosmosEntity>(
+        id: S,
+        document: &D,
+    ) -> azure_core::Result<Self> {
+        Self::new(
+            &document.partition_key(),
+            BatchOperation::replace(id, document)?,
+        )
+    }
+
+    /// Read a document.
+    pub fn read<S: Into<String>, PK: Serialize>(
+        id: S,
+        partition_key: &PK,
+    ) -> azure_core::Result<Self> {
+        Self::new(partition_key, BatchOperation::read(id))
+    }
+
+    /// Delete a document.
+    pub fn delete<S: Into<String>, PK: Serialize>(
+        id: S,
+        partition_key: &PK,
+    ) -> azure_core::Result<Self> {
+        Self::new(partition_key, BatchOperation::delete(id))
+    }
+
+    /// Patch a document.
+    pub fn patch<S: Into<String>, PK: Serialize>(
+        id: S,
+        partition_key: &PK,
+        operations: Vec<Operation>,
+    ) -> azure_core::Result<Self> {
+        Self::new(partition_key, BatchOperation::patch(id, operations))
+    }
+
+    /// The partition key of the document, serialized as Cosmos expects it.
+    pub fn partition_key_serialized(&self) -> &str {
+        &self.partition_key_serialized
+    }
+
+    /// The operation applied to the document.
+    pub fn operation(&self) -> &BatchOperation {
+        &self.operation
+    }
+}
+
+/// The outcome of an operation of a [`BulkExecutor`].
+#[derive(Debug)]
+pub struct BulkOperationResponse {
+    /// The position of the operation in the stream of operations.
+    pub index: usize,
+    /// The operation.
+    pub operation: BulkOperation,
+    /// The result returned by the service for the operation, which has a
+    /// failed status code when the operation could not be applied.
+    ///
+    /// An error is returned when the batch of the operation could not be
+    /// executed at all.
+    pub result: azure_core::Result<BatchOperationResult>,
+}
+
+/// Executes a stream of operations on the documents of a collection using
+/// batch requests.
+///
+/// Operations are grouped by the partition key range of their document into
+/// batches of up to 100 operations and 2 MiB, and batches run concurrently.
+/// Unlike a transactional batch, the operations of a bulk batch are not
+/// atomic: each of them succeeds or fails on its own.
+///
+/// Partition key ranges can only be computed for collections with a version
+/// 2 `Hash` partition key definition. The operations on other collections, or
+/// on endpoints which do not serve the partition key ranges, are grouped by
+/// logical partition instead. When the collection cannot be read at all, every
+/// operation fails with the error of the read.
+///
+/// Transient errors are retried by the retry policy of the client pipeline.
+/// Operations throttled with `429 Too Many Requests` are retried once the
+/// delay requested by the service has elapsed, up to `max_throttle_retries`
+/// times, and every throttled batch halves the number of concurrent batches.
+/// The concurrency then grows back by one batch for each batch which is not
+/// throttled.
+#[derive(Debug, Clone)]
+pub struct BulkExecutor {
+    collection_client: CollectionClient,
+    max_concurrent_batches: usize,
+    max_operations_per_batch: usize,
+    max_buffered_operations: usize,
+    max_throttle_retries: u32,
+    context: Context,
+}
+
+impl BulkExecutor {
+    pub(crate) fn new(collection_client: CollectionClient) -> Self {
+        Self {
+            collection_client,
+            max_concurrent_batches: 8,
+            max_operations_per_batch: MAX_TRANSACTIONAL_BATCH_OPERATIONS,
+            max_buffered_operations: 10 * MAX_TRANSACTIONAL_BATCH_OPERATIONS,
+            max_throttle_retries: 9,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        max_concurrent_batches: usize => max_concurrent_batches.max(1),
+        max_operations_per_batch: usize => max_operations_per_batch.clamp(1, MAX_TRANSACTIONAL_BATCH_OPERATIONS),
+        max_buffered_operations: usize => max_buffered_operations.max(1),
+        max_throttle_retries: u32 => max_throttle_retries,
+        context: Context => context,
+    }
+
+    /// Executes every operation of the stream, yielding the outcome of each
+    /// operation as soon as it is known.
+    ///
+    /// Batches are sent as soon as they are full. Once more than
+    /// `max_buffered_operations` are waiting for their batch to fill up, or
+    /// when no batch is in flight, the largest batch is sent as is.
+    pub fn execute<'a, S>(&'a self, operations: S) -> impl Stream<Item = BulkOperationResponse> + 'a
+    where
+        S: Stream<Item = BulkOperation> + 'a,
+    {
+        let execution = Execution {
+            executor: self,
+            operations: Box::pin(operations.fuse()),
+            count: 0,
+            routing: None,
+            in_flight: FuturesUnordered::new(),
+            scheduler: Scheduler::new(self),
+        };
+        futures::stream::unfold(execution, |mut execution| async move {
+            let response = execution.next().await?;
+            Some((response, execution))
+        })
+    }
+
+    /// Finds how the documents of the collection are distributed.
+    async fn routing(&self) -> Routing {
+        let routing = async {
+            let collection = self
+                .collection_client
+                .get_collection()
+                .context(self.context.clone())
+                .await?
+                .collection;
+            let partition_key = collection.parition_key;
+            if partition_key.kind != KeyKind::Hash || partition_key.version != Some(2) {
+                return Ok(Routing::LogicalPartitions);
+            }
+            match self.partition_key_ranges().await {
+                Ok(ranges) => Ok(Routing::Ranges(ranges)),
+                Err(error) if is_unsupported(&error) => {
+                    warn!("the endpoint does not serve the partition key ranges, grouping operations by logical partition: {error}");
+                    Ok(Routing::LogicalPartitions)
+                }
+                Err(error) => Err(error),
+            }
+        }
+        .await;
+        routing.unwrap_or_else(Routing::Unavailable)
+    }
+
+    async fn partition_key_ranges(&self) -> azure_core::Result<Vec<PartitionKeyRange>> {
+        Ok(self
+            .collection_client
+            .get_partition_key_ranges()
+            .context(self.context.clone())
+            .await?
+            .partition_key_ranges)
+    }
+
+    async fn send(&self, batch: Batch) -> (Batch, azure_core::Result<TransactionalBatchResponse>) {
+        if !batch.delay.is_zero() {
+            azure_core::sleep(batch.delay).await;
+        }
+
+        let outcome = async {
+            let mut request = self.collection_client.docs_request(Method::Post);
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_IS_BATCH_REQUEST,
+                HeaderValue::from_static("True"),
+            );
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_BATCH_ATOMIC,
+                HeaderValue::from_static("False"),
+            );
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_BATCH_CONTINUE_ON_ERROR,
+                HeaderValue::from_static("True"),
+            );
+            request.insert_header(
+                headers::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            );
+            match &batch.target {
+                Target::Range(id) => request.insert_headers(&PartitionRangeId::new(id.clone())),
+                Target::LogicalPartition(partition_key_serialized) => {
+                    crate::cosmos_entity::add_as_partition_key_header_serialized(
+                        partition_key_serialized,
+                        &mut request,
+                    );
+                }
+            }
+            let operations: Vec<&BatchOperation> = batch
+                .operations
+                .iter()
+                .map(|pending| &pending.operation.operation)
+                .collect();
+            request.set_json(&operations)?;
+
+            let recorder = DiagnosticsRecorder::default();
+            let response = self
+                .collection_client
+                .pipeline()
+                .send(
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
+                    &mut request,
+                )
+                .await?;
+            let mut response = TransactionalBatchResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
+        }
+        .await;
+        (batch, outcome)
+    }
+}
+
+/// How operations are grouped into batches.
+#[derive(Debug)]
+enum Routing {
+    /// By the partition key range of the effective partition key.
+    Ranges(Vec<PartitionKeyRange>),
+    LogicalPartitions,
+    /// The collection could not be read, so no operation can be routed.
+    Unavailable(Error),
+}
+
+impl Routing {
+    fn target(&self, operation: &mut BulkOperation) -> azure_core::Result<Target> {
+        let ranges = match self {
+            Routing::Ranges(ranges) => ranges,
+            Routing::LogicalPartitions => {
+                operation.operation.partition_key = None;
+                return Ok(Target::LogicalPartition(
+                    operation.partition_key_serialized.clone(),
+                ));
+            }
+            Routing::Unavailable(error) => {
+                return Err(Error::with_message(error.kind().clone(), || {
+                    format!("could not read the collection: {error}")
+                }))
+            }
+        };
+
+        let components: Vec<Value> = serde_json::from_str(&operation.partition_key_serialized)?;
+        let epk = effective_partition_key::hash_v2(&components)?;
+        let range = ranges
+            .iter()
+            .find(|range| range.min_exclusive <= epk && epk < range.max_exclusive)
+            .ok_or_else(|| {
+                Error::with_message(ErrorKind::Other, || {
+                    format!("no partition key range holds the effective partition key {epk}")
+                })
+            })?;
+        operation.operation.partition_key = Some(operation.partition_key_serialized.clone());
+        Ok(Target::Range(range.id.clone()))
+    }
+}
+
+/// Where a batch is sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Target {
+    /// A partition key range id.
+    Range(String),
+    /// A serialized partition key.
+    LogicalPartition(String),
+}
+
+#[derive(Debug)]
+struct Pending {
+    index: usize,
+    operation: BulkOperation,
+    /// The number of times the operation was throttled.
+    throttled: u32,
+}
+
+impl Pending {
+    fn respond(self, result: azure_core::Result<BatchOperationResult>) -> BulkOperationResponse {
+        BulkOperationResponse {
+            index: self.index,
+            operation: self.operation,
+            result,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Batch {
+    target: Target,
+    operations: Vec<Pending>,
+    size: usize,
+    /// How long to wait before sending the batch.
+    delay: Duration,
+}
+
+impl Batch {
+    fn new(target: Target) -> Self {
+        Self {
+            target,
+            operations: Vec::new(),
+            size: 0,
+            delay: Duration::ZERO,
+        }
+    }
+
+    fn fits(&self, pending: &Pending) -> bool {
+        self.size + pending.operation.size <= MAX_BATCH_SIZE
+    }
+
+    fn add(&mut self, pending: Pending) {
+        self.size += pending.operation.size;
+        self.operations.push(pending);
+    }
+}
+
+struct Execution<'a, S> {
+    executor: &'a BulkExecutor,
+    operations: Pin<Box<Fuse<S>>>,
+    /// The number of operations read from the stream.
+    count: usize,
+    /// `None` until the routing is known, or once it is stale.
+    routing: Option<Routing>,
+    in_flight:
+        FuturesUnordered<BoxFuture<'a, (Batch, azure_core::Result<TransactionalBatchResponse>)>>,
+    scheduler: Scheduler,
+}
+
+impl<'a, S> Execution<'a, S>
+where
+    S: Stream<Item = BulkOperation>,
+{
+    async fn next(&mut self) -> Option<BulkOperationResponse> {
+        loop {
+            if let Some(response) = self.scheduler.responses.pop_front() {
+                return Some(response);
+            }
+
+            let routing = match self.routing.take() {
+                Some(routing) => routing,
+                None => {
+                    let routing = self.executor.routing().await;
+                    for pending in std::mem::take(&mut self.scheduler.unrouted) {
+                        self.scheduler.push(&routing, pending);
+                    }
+                    if self.operations.is_terminated() {
+                        self.scheduler.flush();
+                    }
+                    routing
+                }
+            };
+
+            while self.in_flight.len() < self.scheduler.concurrency {
+                match self.scheduler.ready.pop_front() {
+                    Some(batch) => self.in_flight.push(self.executor.send(batch).boxed()),
+                    None => break,
+                }
+            }
+
+            if self.scheduler.ready.is_empty() && !self.operations.is_terminated() {
+                if self.in_flight.is_empty() && self.scheduler.buffered > 0 {
+                    // don't hold operations back while nothing is being sent
+                    if let Some(operation) = self.operations.next().now_or_never() {
+                        self.read(&routing, operation);
+                    } else {
+                        self.scheduler.send_largest();
+                    }
+                } else {
+                    futures::select! {
+                        operation = self.operations.next() => self.read(&routing, operation),
+                        (batch, outcome) = self.in_flight.select_next_some() => {
+                            self.scheduler.complete(batch, outcome);
+                        }
+                    }
+                }
+            } else if let Some((batch, outcome)) = self.in_flight.next().await {
+                self.scheduler.complete(batch, outcome);
+            } else if self.scheduler.ready.is_empty() && self.scheduler.unrouted.is_empty() {
+                return self.scheduler.responses.pop_front();
+            }
+
+            if self.scheduler.unrouted.is_empty() {
+                self.routing = Some(routing);
+            }
+        }
+    }
+
+    fn read(&mut self, routing: &Routing, operation: Option<BulkOperation>) {
+        match operation {
+            Some(operation) => {
+                let pending = Pending {
+                    index: self.count,
+                    operation,
+                    throttled: 0,
+                };
+                self.count += 1;
+                self.scheduler.push(routing, pending);
+            }
+            None => self.scheduler.flush(),
+        }
+    }
+}
+
+/// The operations waiting to be sent, and the outcomes waiting to be
+/// returned.
+#[derive(Debug)]
+struct Scheduler {
+    max_concurrent_batches: usize,
+    max_operations_per_batch: usize,
+    max_buffered_operations: usize,
+    max_throttle_retries: u32,
+    /// The number of batches which can currently be in flight.
+    concurrency: usize,
+    /// The batches waiting to fill up.
+    batches: HashMap<Target, Batch>,
+    buffered: usize,
+    /// The batches ready to be sent.
+    ready: VecDeque<Batch>,
+    /// The operations to route again once the partition key ranges have been
+    /// read again.
+    unrouted: Vec<Pending>,
+    responses: VecDeque<BulkOperationResponse>,
+}
+
+impl Scheduler {
+    fn new(executor: &BulkExecutor) -> Self {
+        Self {
+            max_concurrent_batches: executor.max_concurrent_batches,
+            max_operations_per_batch: executor.max_operations_per_batch,
+            max_buffered_operations: executor.max_buffered_operations,
+            max_throttle_retries: executor.max_throttle_retries,
+            concurrency: executor.max_concurrent_batches,
+            batches: HashMap::new(),
+            buffered: 0,
+            ready: VecDeque::new(),
+            unrouted: Vec::new(),
+            responses: VecDeque::new(),
+        }
+    }
+
+    /// Buffers an operation until its batch is full.
+    fn push(&mut self, routing: &Routing, mut pending: Pending) {
+        let target = match routing.target(&mut pending.operation) {
+            Ok(target) => target,
+            Err(error) => return self.responses.push_back(pending.respond(Err(error))),
+        };
+        let batch = self
+            .batches
+            .entry(target.clone())
+            .or_insert_with(|| Batch::new(target.clone()));
+
+        if !batch.operations.is_empty() && !batch.fits(&pending) {
+            self.buffered -= batch.operations.len();
+            let full = std::mem::replace(batch, Batch::new(target.clone()));
+            self.ready.push_back(full);
+        }
+        batch.add(pending);
+        self.buffered += 1;
+        if batch.operations.len() >= self.max_operations_per_batch {
+            self.buffered -= batch.operations.len();
+            let full = std::mem::replace(batch, Batch::new(target));
+            self.ready.push_back(full);
+        }
+
+        if self.buffered > self.max_buffered_operations {
+            self.send_largest();
+        }
+    }
+
+    /// Sends the batch with the most operations, full or not.
+    fn send_largest(&mut self) {
+        let Some(target) = self
+            .batches
+            .iter()
+            .filter(|(_, batch)| !batch.operations.is_empty())
+            .max_by_key(|(_, batch)| batch.operations.len())
+            .map(|(target, _)| target.clone())
+        else {
+            return;
+        };
+        if let Some(batch) = self.batches.remove(&target) {
+            self.buffered -= batch.operations.len();
+            self.ready.push_back(batch);
+        }
+    }
+
+    /// Sends every batch, full or not.
+    fn flush(&mut self) {
+        self.buffered = 0;
+        self.ready.extend(
+            self.batches
+                .drain()
+                .map(|(_, batch)| batch)
+                .filter(|batch| !batch.operations.is_empty()),
+        );
+    }
+
+    /// Records the outcome of a batch, queueing the operations to retry.
+    fn complete(&mut self, batch: Batch, outcome: azure_core::Result<TransactionalBatchResponse>) {
+        let error = match outcome {
+            Ok(response) if response.results.len() == batch.operations.len() => {
+                return self.complete_operations(batch, response.results);
+            }
+            Ok(response) => Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "the batch returned {} results for {} operations",
+                    response.results.len(),
+                    batch.operations.len()
+                )
+            }),
+            Err(error) => error,
+        };
+
+        match error.as_http_error() {
+            Some(http_error) if http_error.status() == StatusCode::TooManyRequests => {
+                let retry_after = http_error
+                    .header(&X_MS_RETRY_AFTER_MS)
+                    .and_then(|retry_after| retry_after.parse().ok())
+                    .map(Duration::from_millis)
+                    .unwrap_or_default();
+                self.throttled();
+                let mut retry = Batch::new(batch.target);
+                retry.delay = retry_after;
+                for mut pending in batch.operations {
+                    if pending.throttled < self.max_throttle_retries {
+                        pending.throttled += 1;
+                        retry.add(pending);
+                    } else {
+                        self.responses
+                            .push_back(pending.respond(Err(batch_error(&error))));
+                    }
+                }
+                if !retry.operations.is_empty() {
+                    self.ready.push_back(retry);
+                }
+            }
+            Some(http_error)
+                if http_error.status() == StatusCode::Gone
+                    && http_error
+                        .header(&HEADER_SUB_STATUS)
+                        .is_some_and(|sub_status| {
+                            PARTITION_KEY_RANGE_GONE.contains(&sub_status)
+                        }) =>
+            {
+                // the partition key range was split or merged
+                self.unrouted.extend(batch.operations);
+            }
+            _ => {
+                for pending in batch.operations {
+                    self.responses
+                        .push_back(pending.respond(Err(batch_error(&error))));
+                }
+            }
+        }
+    }
+
+    fn complete_operations(&mut self, batch: Batch, results: Vec<BatchOperationResult>) {
+        let mut retry = Batch::new(batch.target);
+        for (mut pending, result) in batch.operations.into_iter().zip(results) {
+            if result.status_code == StatusCode::TooManyRequests
+                && pending.throttled < self.max_throttle_retries
+            {
+                pending.throttled += 1;
+                retry.delay = retry.delay.max(result.retry_after.unwrap_or_default());
+                retry.add(pending);
+            } else {
+                self.responses.push_back(pending.respond(Ok(result)));
+            }
+        }
+
+        if retry.operations.is_empty() {
+            self.concurrency = (self.concurrency + 1).min(self.max_concurrent_batches);
+        } else {
+            self.throttled();
+            self.ready.push_back(retry);
+        }
+    }
+
+    fn throttled(&mut self) {
+        self.concurrency = (self.concurrency / 2).max(1);
+    }
+}
+
+/// Whether the error tells that the endpoint does not serve the partition key
+/// ranges, as some emulators and proxies do.
+fn is_unsupported(error: &Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::HttpResponse {
+            status: StatusCode::BadRequest
+                | StatusCode::MethodNotAllowed
+                | StatusCode::NotImplemented,
+            ..
+        }
+    )
+}
+
+fn batch_error(error: &Error) -> Error {
+    Error::with_message(error.kind().clone(), || format!("batch failed: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{
+        headers::{HeaderName, Headers},
+        BytesStream, Policy, PolicyResult, Request, Response, TransportOptions,
+    };
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn scheduler() -> Scheduler {
+        Scheduler {
+            max_concurrent_batches: 8,
+            max_operations_per_batch: MAX_TRANSACTIONAL_BATCH_OPERATIONS,
+            max_buffered_operations: 1000,
+            max_throttle_retries: 2,
+            concurrency: 8,
+            batches: HashMap::new(),
+            buffered: 0,
+            ready: VecDeque::new(),
+            unrouted: Vec::new(),
+            responses: VecDeque::new(),
+        }
+    }
+
+    fn pending(index: usize, partition_key: &str) -> Pending {
+        let document = json!({"id": index.to_string(), "pk": partition_key});
+        Pending {
+            index,
+            operation: BulkOperation::new(
+                &partition_key,
+                BatchOperation::upsert(&document).unwrap(),
+            )
+            .unwrap(),
+            throttled: 0,
+        }
+    }
+
+    fn range_json(id: &str, min: &str, max: &str) -> Value {
+        json!({
+            "_rid": "rid", "id": id, "_etag": "etag",
+            "minInclusive": min, "maxExclusive": max,
+            "ridPrefix": 0, "_self": "self", "throughputFraction": 0.5,
+            "status": "online", "_ts": 0
+        })
+    }
+
+    fn range(id: &str, min: &str, max: &str) -> PartitionKeyRange {
+        serde_json::from_value(range_json(id, min, max)).unwrap()
+    }
+
+    fn result(status: u16, retry_after: u64) -> BatchOperationResult {
+        serde_json::from_value(json!({
+            "statusCode": status, "requestCharge": 1.0, "retryAfterMilliseconds": retry_after
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn operations_are_grouped_by_partition_key_range() {
+        let routing = Routing::Ranges(vec![range("0", "", "20"), range("1", "20", "FF")]);
+        let mut scheduler = scheduler();
+        for i in 0..250 {
+            scheduler.push(&routing, pending(i, &format!("pk{}", i % 10)));
+        }
+        assert!(scheduler.responses.is_empty());
+
+        scheduler.flush();
+        let mut targets = Vec::new();
+        for batch in &scheduler.ready {
+            assert!(batch.operations.len() <= MAX_TRANSACTIONAL_BATCH_OPERATIONS);
+            for pending in &batch.operations {
+                let operation = &pending.operation;
+                assert_eq!(
+                    operation.operation.partition_key.as_deref(),
+                    Some(operation.partition_key_serialized())
+                );
+                let expected = routing.target(&mut operation.clone()).unwrap();
+                assert_eq!(expected, batch.target);
+            }
+            targets.push(batch.target.clone());
+        }
+        assert_eq!(
+            scheduler
+                .ready
+                .iter()
+                .map(|batch| batch.operations.len())
+                .sum::<usize>(),
+            250
+        );
+        targets.dedup();
+        assert!(targets.len() >= 2);
+    }
+
+    #[test]
+    fn throttled_operations_are_retried() {
+        let mut scheduler = scheduler();
+        let mut batch = Batch::new(Target::LogicalPartition(r#"["a"]"#.to_owned()));
+        for i in 0..3 {
+            batch.add(pending(i, "a"));
+        }
+        batch.operations[2].throttled = 2;
+
+        scheduler.complete_operations(
+            batch,
+            vec![result(201, 0), result(429, 50), result(429, 80)],
+        );
+
+        assert_eq!(scheduler.concurrency, 4);
+        let indexes: Vec<_> = scheduler
+            .responses
+            .iter()
+            .map(|response| {
+                (
+                    response.index,
+                    response.result.as_ref().unwrap().status_code,
+                )
+            })
+            .collect();
+        assert_eq!(
+            indexes,
+            [(0, StatusCode::Created), (2, StatusCode::TooManyRequests)]
+        );
+        let retry = scheduler.ready.pop_front().unwrap();
+        assert_eq!(retry.delay, Duration::from_millis(50));
+        assert_eq!(retry.operations.len(), 1);
+        assert_eq!(retry.operations[0].throttled, 1);
+
+        let mut batch = Batch::new(retry.target);
+        batch.add(pending(3, "a"));
+        scheduler.complete_operations(batch, vec![result(200, 0)]);
+        assert_eq!(scheduler.concurrency, 5);
+    }
+
+    const PRIMARY_KEY: &str = "ZmFrZSBjb3Ntb3MgcHJpbWFyeSBrZXkgZm9yIHRlc3Rz";
+
+    /// Marks the context given to the executor.
+    #[derive(Debug)]
+    struct UserContext;
+
+    /// A gateway serving a collection whose single partition key range is
+    /// split in two when the first batch is sent to it.
+    #[derive(Debug, Default)]
+    struct SplittingGateway {
+        state: Mutex<SplitState>,
+    }
+
+    #[derive(Debug, Default)]
+    struct SplitState {
+        /// The status of the reads of the collection, when they fail.
+        collection_status: Option<StatusCode>,
+        split: bool,
+        range_reads: usize,
+        /// The partition key range and the partition keys of each batch.
+        batches: Vec<(String, Vec<String>)>,
+    }
+
+    impl SplittingGateway {
+        const RANGES_BEFORE_SPLIT: [(&'static str, &'static str, &'static str); 1] =
+            [("0", "", "FF")];
+        const RANGES_AFTER_SPLIT: [(&'static str, &'static str, &'static str); 2] =
+            [("1", "", "20"), ("2", "20", "FF")];
+
+        fn state(&self) -> std::sync::MutexGuard<'_, SplitState> {
+            self.state.lock().unwrap()
+        }
+
+        fn response(status: StatusCode, body: &Value) -> Response {
+            let mut headers = Headers::new();
+            for (name, value) in [
+                ("content-type", "application/json"),
+                ("date", "Mon, 27 Jun 2022 13:38:48 GMT"),
+                ("etag", "\"00000000-0000-0000-0000-000000000000\""),
+                ("server", "gateway"),
+                ("collection-partition-index", "0"),
+                ("collection-service-index", "0"),
+                ("lsn", "1"),
+                ("x-ms-activity-id", "00000000-0000-0000-0000-000000000000"),
+                ("x-ms-alt-content-path", "dbs/db"),
+                ("x-ms-content-path", "rid"),
+                ("x-ms-cosmos-item-llsn", "1"),
+                ("x-ms-cosmos-llsn", "1"),
+                ("x-ms-gatewayversion", "version=2.14.0"),
+                ("x-ms-global-committed-lsn", "1"),
+                ("x-ms-item-count", "1"),
+                ("x-ms-item-lsn", "1"),
+                (
+                    "x-ms-last-state-change-utc",
+                    "Mon, 27 Jun 2022 13:38:48.000 GMT",
+                ),
+                ("x-ms-number-of-read-regions", "0"),
+                ("x-ms-request-charge", "1"),
+                ("x-ms-schemaversion", "1.13"),
+                ("x-ms-serviceversion", "version=2.14.0.0"),
+                ("x-ms-session-token", "0:-1#1"),
+                ("x-ms-transport-request-id", "1"),
+                ("x-ms-xp-role", "1"),
+            ] {
+                headers.insert(
+                    HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                );
+            }
+            Response::new(
+                status,
+                headers,
+                Box::pin(BytesStream::new(body.to_string().into_bytes())),
+            )
+        }
+
+        fn read_collection(&self) -> Response {
+            if let Some(status) = self.state().collection_status {
+                return Self::response(status, &json!({"code": "Error", "message": "failed"}));
+            }
+            Self::response(
+                StatusCode::Ok,
+                &json!({
+                    "id": "coll",
+                    "indexingPolicy": {
+                        "automatic": true,
+                        "indexingMode": "consistent",
+                        "includedPaths": [{ "path": "/*" }],
+                        "excludedPaths": [],
+                    },
+                    "partitionKey": { "paths": ["/pk"], "kind": "Hash", "version": 2 },
+                    "_rid": "rid", "_ts": 0, "_self": "dbs/db/colls/rid/", "_etag": "etag",
+                    "_docs": "docs/", "_sprocs": "sprocs/", "_triggers": "triggers/",
+                    "_udfs": "udfs/", "_conflicts": "conflicts/",
+                }),
+            )
+        }
+
+        fn read_ranges(&self) -> Response {
+            let mut state = self.state();
+            state.range_reads += 1;
+            let ranges: &[_] = if state.split {
+                &Self::RANGES_AFTER_SPLIT
+            } else {
+                &Self::RANGES_BEFORE_SPLIT
+            };
+            let ranges: Vec<_> = ranges
+                .iter()
+                .map(|(id, min, max)| range_json(id, min, max))
+                .collect();
+            Self::response(
+                StatusCode::Ok,
+                &json!({"_rid": "rid", "PartitionKeyRanges": ranges, "_count": ranges.len()}),
+            )
+        }
+
+        fn execute_batch(&self, request: &Request) -> azure_core::Result<Response> {
+            let range_id = request
+                .headers()
+                .get_str(&cosmos_headers::HEADER_DOCUMENTDB_PARTITIONRANGEID)?
+                .to_owned();
+            let operations: Vec<Value> = match request.body() {
+                azure_core::Body::Bytes(bytes) => serde_json::from_slice(bytes)?,
+                _ => Vec::new(),
+            };
+            let partition_keys = operations
+                .iter()
+                .map(|operation| operation["partitionKey"].as_str().unwrap().to_owned())
+                .collect();
+
+            let mut state = self.state();
+            state.batches.push((range_id.clone(), partition_keys));
+            let mut headers = Headers::new();
+            headers.insert(cosmos_headers::HEADER_REQUEST_CHARGE, "1");
+            headers.insert(cosmos_headers::HEADER_SESSION_TOKEN, "0:-1#1");
+            headers.insert(headers::ACTIVITY_ID, "00000000-0000-0000-0000-000000000000");
+            if range_id == "0" {
+                state.split = true;
+                headers.insert(HEADER_SUB_STATUS, "1002");
+                return Ok(Response::new(
+                    StatusCode::Gone,
+                    headers,
+                    Box::pin(BytesStream::new_empty()),
+                ));
+            }
+            let results: Vec<Value> = operations
+                .iter()
+                .map(|_| json!({"statusCode": 201, "requestCharge": 1.0}))
+                .collect();
+            Ok(Response::new(
+                StatusCode::Ok,
+                headers,
+                Box::pin(BytesStream::new(serde_json::to_vec(&results)?)),
+            ))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for SplittingGateway {
+        async fn send(
+            &self,
+            ctx: &Context,
+            request: &mut Request,
+            next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            assert_eq!(0, next.len());
+            assert!(ctx.get::<UserContext>().is_some());
+            let is_batch = request
+                .headers()
+                .get_optional_str(&HeaderName::from_static("x-ms-cosmos-is-batch-request"))
+                .is_some();
+            if is_batch {
+                self.execute_batch(request)
+            } else if request.url().path().ends_with("/pkranges") {
+                Ok(self.read_ranges())
+            } else {
+                Ok(self.read_collection())
+            }
+        }
+    }
+
+    async fn execute(gateway: Arc<SplittingGateway>) -> Vec<BulkOperationResponse> {
+        let client = CosmosClient::builder(
+            "fake",
+            AuthorizationToken::primary_key(PRIMARY_KEY).unwrap(),
+        )
+        .transport(TransportOptions::new_custom_policy(gateway))
+        .build();
+
+        let operations: Vec<_> = (0..30)
+            .map(|i| pending(i, &format!("pk{i}")).operation)
+            .collect();
+        let mut context = Context::new();
+        context.insert(UserContext);
+        client
+            .database_client("db")
+            .collection_client("coll")
+            .bulk_executor()
+            .context(context)
+            .execute(futures::stream::iter(operations))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn operations_are_routed_again_after_a_split() -> azure_core::Result<()> {
+        let gateway = Arc::new(SplittingGateway::default());
+        let responses = execute(gateway.clone()).await;
+
+        let mut indexes: Vec<_> = responses
+            .iter()
+            .map(|response| {
+                let result = response.result.as_ref().unwrap();
+                assert_eq!(result.status_code, StatusCode::Created);
+                response.index
+            })
+            .collect();
+        indexes.sort_unstable();
+        assert_eq!(indexes, (0..30).collect::<Vec<_>>());
+
+        let state = gateway.state();
+        assert_eq!(state.range_reads, 2);
+        let (gone, after_split) = state.batches.split_first().unwrap();
+        assert_eq!(gone.0, "0");
+        assert_eq!(gone.1.len(), 30);
+
+        // every operation is sent again once, to the range holding it
+        let ranges: Vec<_> = SplittingGateway::RANGES_AFTER_SPLIT
+            .iter()
+            .map(|(id, min, max)| range(id, min, max))
+            .collect();
+        let mut resent = 0;
+        for (range_id, partition_keys) in after_split {
+            let range = ranges.iter().find(|range| &range.id == range_id).unwrap();
+            for partition_key in partition_keys {
+                let components: Vec<Value> = serde_json::from_str(partition_key)?;
+                let epk = effective_partition_key::hash_v2(&components)?;
+                assert!(range.min_exclusive <= epk && epk < range.max_exclusive);
+            }
+            resent += partition_keys.len();
+        }
+        assert_eq!(resent, 30);
+        assert!(after_split.iter().any(|(range_id, _)| range_id == "1"));
+        assert!(after_split.iter().any(|(range_id, _)| range_id == "2"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn routing_errors_fail_the_operations() {
+        let gateway = Arc::new(SplittingGateway::default());
+        gateway.state().collection_status = Some(StatusCode::Unauthorized);
+        let responses = execute(gateway.clone()).await;
+
+        assert_eq!(responses.len(), 30);
+        for response in responses {
+            let error = response.result.unwrap_err();
+            assert!(matches!(
+                error.kind(),
+                ErrorKind::HttpResponse {
+                    status: StatusCode::Unauthorized,
+                    ..
+                }
+            ));
+        }
+        assert!(gateway.state().batches.is_empty());
+    }
+}
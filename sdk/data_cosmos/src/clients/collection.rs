@@ -3,8 +3,9 @@ use crate::clients::*;
 use crate::operations::*;
 use crate::resources::collection::PartitionKey;
 use crate::resources::document::Query;
-use crate::CosmosEntity;
+use crate::resources::ThroughputProperties;
 use crate::ReadonlyString;
+use crate::{BulkExecutor, CosmosEntity};
 use azure_core::{Pipeline, Request};
 use serde::Serialize;
 
@@ -62,6 +63,50 @@ impl CollectionClient {
         QueryDocumentsBuilder::new(self.clone(), query.into())
     }
 
+    /// Query documents across the partitions of a collection, executing the
+    /// parts of the query the gateway cannot run, such as cross-partition
+    /// `ORDER BY` or aggregates, on the client.
+    pub fn query_documents_cross_partition<Q: Into<Query>>(
+        &self,
+        query: Q,
+    ) -> QueryDocumentsCrossPartitionBuilder {
+        QueryDocumentsCrossPartitionBuilder::new(self.clone(), query.into())
+    }
+
+    /// Get the plan the service suggests to execute a query across partitions.
+    pub fn get_query_plan<Q: Into<Query>>(&self, query: Q) -> GetQueryPlanBuilder {
+        GetQueryPlanBuilder::new(self.clone(), query.into())
+    }
+
+    /// Start a transactional batch of operations on the documents of a
+    /// logical partition, which either all succeed or are all rolled back.
+    pub fn transactional_batch<PK: Serialize>(
+        &self,
+        partition_key: &PK,
+    ) -> azure_core::Result<TransactionalBatchBuilder> {
+        Ok(TransactionalBatchBuilder::new(
+            self.clone(),
+            crate::cosmos_entity::serialize_partition_key(partition_key)?,
+            Vec::new(),
+        ))
+    }
+
+    /// Get an executor of large numbers of document operations, which are
+    /// sent in batches grouped by partition key range.
+    pub fn bulk_executor(&self) -> BulkExecutor {
+        BulkExecutor::new(self.clone())
+    }
+
+    /// Read the throughput provisioned for the collection.
+    pub fn read_throughput(&self) -> ReadThroughputBuilder {
+        ReadThroughputBuilder::new(ThroughputScope::Collection(self.clone()))
+    }
+
+    /// Replace the throughput provisioned for the collection.
+    pub fn replace_throughput(&self, throughput: ThroughputProperties) -> ReplaceThroughputBuilder {
+        ReplaceThroughputBuilder::new(ThroughputScope::Collection(self.clone()), throughput)
+    }
+
     /// List stored procedures in a collection.
     pub fn list_stored_procedures(&self) -> ListStoredProceduresBuilder {
         ListStoredProceduresBuilder::new(self.clone())
@@ -77,11 +122,22 @@ impl CollectionClient {
         ListTriggersBuilder::new(self.clone())
     }
 
+    /// List the conflicts of a collection which its conflict resolution policy
+    /// left to the application.
+    pub fn list_conflicts(&self) -> ListConflictsBuilder {
+        ListConflictsBuilder::new(self.clone())
+    }
+
     /// List the partition key ranges in a collection.
     pub fn get_partition_key_ranges(&self) -> GetPartitionKeyRangesBuilder {
         GetPartitionKeyRangesBuilder::new(self.clone())
     }
 
+    /// Read the change feed of a collection across all its partition key ranges.
+    pub fn change_feed(&self) -> GetChangeFeedBuilder {
+        GetChangeFeedBuilder::new(self.clone())
+    }
+
     /// Convert into a [`DocumentClient`].
     pub fn document_client<S: Into<String>, PK: Serialize>(
         &self,
@@ -91,6 +147,15 @@ impl CollectionClient {
         DocumentClient::new(self.clone(), document_name, partition_key)
     }
 
+    /// Convert into a [`ConflictClient`].
+    pub fn conflict_client<S: Into<String>, PK: Serialize>(
+        &self,
+        conflict_name: S,
+        partition_key: &PK,
+    ) -> azure_core::Result<ConflictClient> {
+        ConflictClient::new(self.clone(), conflict_name, partition_key)
+    }
+
     /// Convert into a [`TriggerClient`].
     pub fn trigger_client<S: Into<ReadonlyString>>(&self, trigger_name: S) -> TriggerClient {
         TriggerClient::new(self.clone(), trigger_name)
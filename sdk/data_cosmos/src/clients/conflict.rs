@@ -0,0 +1,77 @@
+use crate::clients::*;
+use crate::operations::*;
+use azure_core::{Pipeline, Request};
+use serde::Serialize;
+
+/// A client for Cosmos conflict resources.
+#[derive(Debug, Clone)]
+pub struct ConflictClient {
+    collection: CollectionClient,
+    conflict_name: String,
+    partition_key_serialized: String,
+}
+
+impl ConflictClient {
+    /// Create a new instance of a `ConflictClient`.
+    ///
+    /// A conflict is identified by its id and the partition key of the
+    /// conflicting document.
+    pub(crate) fn new<S: Into<String>, PK: Serialize>(
+        collection: CollectionClient,
+        conflict_name: S,
+        partition_key: &PK,
+    ) -> azure_core::Result<Self> {
+        Ok(Self {
+            collection,
+            conflict_name: conflict_name.into(),
+            partition_key_serialized: crate::cosmos_entity::serialize_partition_key(partition_key)?,
+        })
+    }
+
+    /// Delete the conflict, once it has been resolved.
+    pub fn delete_conflict(&self) -> DeleteConflictBuilder {
+        DeleteConflictBuilder::new(self.clone())
+    }
+
+    /// Get a [`CosmosClient`].
+    pub fn cosmos_client(&self) -> &CosmosClient {
+        self.collection.cosmos_client()
+    }
+
+    /// Get a [`DatabaseClient`].
+    pub fn database_client(&self) -> &DatabaseClient {
+        self.collection.database_client()
+    }
+
+    /// Get a [`CollectionClient`].
+    pub fn collection_client(&self) -> &CollectionClient {
+        &self.collection
+    }
+
+    /// Get the conflict's name.
+    pub fn conflict_name(&self) -> &str {
+        &self.conflict_name
+    }
+
+    /// Get the partition key of the conflicting document.
+    pub fn partition_key_serialized(&self) -> &str {
+        &self.partition_key_serialized
+    }
+
+    pub(crate) fn conflict_request(&self, method: azure_core::Method) -> Request {
+        self.cosmos_client().request(
+            &format!(
+                "dbs/{}/colls/{}/conflicts/{}",
+                self.database_client().database_name(),
+                self.collection_client().collection_name(),
+                self.conflict_name()
+            ),
+            method,
+        )
+    }
+
+    /// Get a [`Pipeline`]
+    pub(crate) fn pipeline(&self) -> &Pipeline {
+        self.cosmos_client().pipeline()
+    }
+}
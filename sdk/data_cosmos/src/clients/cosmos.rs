@@ -2,7 +2,10 @@ use crate::clients::DatabaseClient;
 use crate::operations::*;
 use crate::resources::permission::AuthorizationToken;
 use crate::resources::ResourceType;
-use crate::ReadonlyString;
+use crate::{
+    AttemptDiagnosticsPolicy, DiagnosticsPolicy, DiagnosticsThresholds, GlobalEndpointPolicy,
+    ReadonlyString, SessionContainer, SessionTokenPolicy,
+};
 
 use azure_core::{ClientOptions, Context, Pipeline, Request, Response};
 
@@ -19,6 +22,10 @@ pub const EMULATOR_ACCOUNT_KEY: &str =
 pub struct CosmosClientBuilder {
     cloud_location: CloudLocation,
     options: ClientOptions,
+    preferred_regions: Option<Vec<String>>,
+    use_multiple_write_locations: bool,
+    session_container: Option<SessionContainer>,
+    diagnostics_thresholds: DiagnosticsThresholds,
 }
 
 impl CosmosClientBuilder {
@@ -37,16 +44,44 @@ impl CosmosClientBuilder {
         Self {
             options: ClientOptions::default(),
             cloud_location,
+            preferred_regions: None,
+            use_multiple_write_locations: true,
+            session_container: Some(SessionContainer::new()),
+            diagnostics_thresholds: DiagnosticsThresholds::default(),
         }
     }
 
     /// Convert the builder into a `CosmosClient` instance.
+    ///
+    /// Requests are not routed to the preferred regions if the url of the
+    /// cloud location is invalid.
     #[must_use]
     pub fn build(self) -> CosmosClient {
         let auth_token = self.cloud_location.auth_token();
+        let global_endpoint_policy =
+            self.preferred_regions.and_then(|preferred_regions| {
+                match self.cloud_location.url().parse() {
+                    Ok(default_endpoint) => Some(GlobalEndpointPolicy::new(
+                        default_endpoint,
+                        preferred_regions,
+                        self.use_multiple_write_locations,
+                    )),
+                    Err(error) => {
+                        warn!("not routing to the preferred regions of an invalid url: {error}");
+                        None
+                    }
+                }
+            });
         CosmosClient {
-            pipeline: new_pipeline_from_options(self.options, auth_token),
+            pipeline: new_pipeline_from_options(
+                self.options,
+                auth_token,
+                global_endpoint_policy,
+                self.session_container.clone(),
+                self.diagnostics_thresholds,
+            ),
             cloud_location: self.cloud_location,
+            session_container: self.session_container,
         }
     }
 
@@ -57,6 +92,68 @@ impl CosmosClientBuilder {
         self
     }
 
+    /// Route requests to the regions of a geo-replicated account.
+    ///
+    /// Reads are sent to the first available region of `preferred_regions`,
+    /// such as `West US` or `westus`, and then to the other regions of the
+    /// account. Writes are sent to the write region, or to the preferred
+    /// regions too when every region of the account accepts writes. Regions
+    /// which fail are avoided for a while, and the regions of the account are
+    /// read again every five minutes.
+    ///
+    /// Without preferred regions, every request is sent to the account
+    /// endpoint.
+    #[must_use]
+    pub fn preferred_regions<I, S>(mut self, preferred_regions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.preferred_regions = Some(preferred_regions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Whether writes are routed to the preferred regions when every region
+    /// of the account accepts writes, rather than to the first write region.
+    ///
+    /// Defaults to `true`. Only used along with `preferred_regions`.
+    #[must_use]
+    pub fn use_multiple_write_locations(mut self, use_multiple_write_locations: bool) -> Self {
+        self.use_multiple_write_locations = use_multiple_write_locations;
+        self
+    }
+
+    /// Whether the client keeps the session tokens of its responses and sends
+    /// them along with its reads, so that they see its writes.
+    ///
+    /// Defaults to `true`. Reads with an explicit session token or consistency
+    /// level are sent as they are.
+    #[must_use]
+    pub fn automatic_session_tokens(mut self, automatic_session_tokens: bool) -> Self {
+        self.session_container = match (automatic_session_tokens, self.session_container) {
+            (true, None) => Some(SessionContainer::new()),
+            (true, container) => container,
+            (false, _) => None,
+        };
+        self
+    }
+
+    /// Keep the session tokens in an existing container, such as the one of
+    /// another client or one imported from another process.
+    #[must_use]
+    pub fn session_container(mut self, session_container: SessionContainer) -> Self {
+        self.session_container = Some(session_container);
+        self
+    }
+
+    /// Log the diagnostics of the operations which take longer or cost more
+    /// than the thresholds.
+    #[must_use]
+    pub fn diagnostics_thresholds(mut self, diagnostics_thresholds: DiagnosticsThresholds) -> Self {
+        self.diagnostics_thresholds = diagnostics_thresholds;
+        self
+    }
+
     /// Set the retry options.
     #[must_use]
     pub fn retry(mut self, retry: impl Into<azure_core::RetryOptions>) -> Self {
@@ -86,6 +183,7 @@ impl CosmosClientBuilder {
 pub struct CosmosClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    session_container: Option<SessionContainer>,
 }
 
 impl CosmosClient {
@@ -117,6 +215,15 @@ impl CosmosClient {
         self
     }
 
+    /// The session tokens kept by the client, unless automatic session
+    /// tokens are disabled.
+    ///
+    /// The container can be exported to continue the sessions of the client
+    /// in another process.
+    pub fn session_container(&self) -> Option<&SessionContainer> {
+        self.session_container.as_ref()
+    }
+
     /// Create a database
     pub fn create_database<S: AsRef<str>>(&self, database_name: S) -> CreateDatabaseBuilder {
         CreateDatabaseBuilder::new(self.clone(), database_name.as_ref().to_owned())
@@ -127,6 +234,11 @@ impl CosmosClient {
         ListDatabasesBuilder::new(self.clone())
     }
 
+    /// Get the database account, with the regions it is replicated to.
+    pub fn get_database_account(&self) -> GetDatabaseAccountBuilder {
+        GetDatabaseAccountBuilder::new(self.clone())
+    }
+
     /// Create a [`DatabaseClient`].
     pub fn database_client<S: Into<ReadonlyString>>(&self, database_name: S) -> DatabaseClient {
         DatabaseClient::new(self.clone(), database_name)
@@ -164,20 +276,35 @@ impl CosmosClient {
 fn new_pipeline_from_options(
     options: ClientOptions,
     authorization_token: AuthorizationToken,
+    global_endpoint_policy: Option<GlobalEndpointPolicy>,
+    session_container: Option<SessionContainer>,
+    diagnostics_thresholds: DiagnosticsThresholds,
 ) -> Pipeline {
     let auth_policy: Arc<dyn azure_core::Policy> =
         Arc::new(crate::AuthorizationPolicy::new(authorization_token));
 
+    // The `GlobalEndpointPolicy` picks the region of each attempt, so it is a
+    // retry policy too.
+    let mut per_retry_policies: Vec<Arc<dyn azure_core::Policy>> = Vec::new();
+    if let Some(session_container) = session_container {
+        per_retry_policies.push(Arc::new(SessionTokenPolicy::new(session_container)));
+    }
+    if let Some(global_endpoint_policy) = global_endpoint_policy {
+        per_retry_policies.push(Arc::new(global_endpoint_policy));
+    }
+    // Attempts are recorded once their endpoint is known.
+    per_retry_policies.push(Arc::new(AttemptDiagnosticsPolicy));
+
     // The `AuthorizationPolicy` must be the **last** retry policy.
     // Policies can change the url and/or the headers, and the `AuthorizationPolicy`
     // must be able to inspect them or the resulting token will be invalid.
-    let per_retry_policies = vec![auth_policy];
+    per_retry_policies.push(auth_policy);
 
     Pipeline::new(
         option_env!("CARGO_PKG_NAME"),
         option_env!("CARGO_PKG_VERSION"),
         options,
-        Vec::new(),
+        vec![Arc::new(DiagnosticsPolicy::new(diagnostics_thresholds))],
         per_retry_policies,
     )
 }
@@ -23,6 +23,7 @@
 
 mod attachment;
 mod collection;
+mod conflict;
 mod cosmos;
 mod database;
 mod document;
@@ -34,6 +35,7 @@ mod user_defined_function;
 
 pub use attachment::AttachmentClient;
 pub use collection::CollectionClient;
+pub use conflict::ConflictClient;
 pub use cosmos::{CloudLocation, CosmosClient, CosmosClientBuilder};
 pub use database::DatabaseClient;
 pub use document::DocumentClient;
@@ -0,0 +1,360 @@
+use crate::headers::{
+    HEADER_REQUEST_CHARGE, HEADER_REQUEST_DURATION_MS, HEADER_SESSION_TOKEN, HEADER_SUB_STATUS,
+};
+use azure_core::headers::{Headers, ACTIVITY_ID};
+use azure_core::{Context, Policy, PolicyResult, Request, Response};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// What happened while a response was obtained from the service.
+///
+/// Every response of an operation carries its diagnostics. When the client
+/// has [`DiagnosticsThresholds`], the diagnostics of the operations
+/// exceeding them are also logged as warnings.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct CosmosDiagnostics {
+    /// The request units charged, summed over every attempt
+    pub request_charge: f64,
+    /// The activity id of the last attempt
+    pub activity_id: Option<uuid::Uuid>,
+    /// The session token returned with the response
+    pub session_token: Option<String>,
+    /// The endpoints of the regions contacted, in the order they were first
+    /// contacted
+    pub regions_contacted: Vec<String>,
+    /// How many times the request was sent again
+    pub retry_count: u32,
+    /// How long the operation took, retries included
+    pub elapsed: Duration,
+    /// How long the service took to handle the last attempt
+    pub server_duration: Option<Duration>,
+    /// Each time the request was sent
+    pub attempts: Vec<RequestAttempt>,
+}
+
+impl CosmosDiagnostics {
+    /// The diagnostics found in the headers of a response.
+    ///
+    /// The attempts and the duration of the operation are not part of the
+    /// response: they are added by [`DiagnosticsRecorder::complete`].
+    pub(crate) fn from_headers(headers: &Headers) -> Self {
+        Self {
+            request_charge: headers
+                .get_optional_as(&HEADER_REQUEST_CHARGE)
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+            activity_id: headers.get_optional_as(&ACTIVITY_ID).ok().flatten(),
+            session_token: headers.get_optional_string(&HEADER_SESSION_TOKEN),
+            server_duration: server_duration_from_headers(headers),
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, recorded: Recorded) {
+        for attempt in &recorded.attempts {
+            if !self.regions_contacted.contains(&attempt.endpoint) {
+                self.regions_contacted.push(attempt.endpoint.clone());
+            }
+        }
+        if !recorded.attempts.is_empty() {
+            self.request_charge = recorded
+                .attempts
+                .iter()
+                .map(|attempt| attempt.request_charge)
+                .sum();
+        }
+        self.retry_count = recorded.attempts.len().saturating_sub(1) as u32;
+        self.elapsed = recorded.elapsed;
+        self.attempts = recorded.attempts;
+    }
+
+    /// Adds the diagnostics of a later request made by the same operation.
+    pub(crate) fn merge(&mut self, other: CosmosDiagnostics) {
+        self.request_charge += other.request_charge;
+        if other.activity_id.is_some() {
+            self.activity_id = other.activity_id;
+        }
+        if other.session_token.is_some() {
+            self.session_token = other.session_token;
+        }
+        for region in other.regions_contacted {
+            if !self.regions_contacted.contains(&region) {
+                self.regions_contacted.push(region);
+            }
+        }
+        self.retry_count += other.retry_count;
+        self.elapsed += other.elapsed;
+        if other.server_duration.is_some() {
+            self.server_duration = other.server_duration;
+        }
+        self.attempts.extend(other.attempts);
+    }
+}
+
+/// One time a request was sent.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct RequestAttempt {
+    /// The endpoint the request was sent to
+    pub endpoint: String,
+    /// The status of the response, if one was received
+    pub status: Option<u16>,
+    /// The sub-status of the response, detailing some statuses
+    pub sub_status: Option<u32>,
+    /// The request units charged
+    pub request_charge: f64,
+    /// The activity id of the response
+    pub activity_id: Option<uuid::Uuid>,
+    /// How long the response took to arrive
+    pub latency: Duration,
+    /// How long the service took to handle the request
+    pub server_duration: Option<Duration>,
+}
+
+/// When the diagnostics of an operation are logged.
+///
+/// An operation is logged, as a warning, once it takes longer or costs more
+/// than the thresholds. Without thresholds nothing is logged.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsThresholds {
+    latency: Option<Duration>,
+    request_charge: Option<f64>,
+}
+
+impl DiagnosticsThresholds {
+    /// No thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setters! {
+        latency: Duration => Some(latency),
+        request_charge: f64 => Some(request_charge),
+    }
+
+    fn exceeded_by(&self, diagnostics: &CosmosDiagnostics) -> bool {
+        matches!(self.latency, Some(latency) if diagnostics.elapsed > latency)
+            || matches!(self.request_charge, Some(charge) if diagnostics.request_charge > charge)
+    }
+}
+
+/// The diagnostics recorded by the policies, before they are combined with
+/// the headers of the final response.
+#[derive(Debug, Clone, Default)]
+struct Recorded {
+    elapsed: Duration,
+    attempts: Vec<RequestAttempt>,
+}
+
+/// Records the diagnostics of an operation while its request goes through
+/// the pipeline.
+///
+/// Operations add a recorder to the `Context` of their request and complete
+/// the diagnostics of the response with it, as the headers of the response
+/// only describe the last attempt.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DiagnosticsRecorder(Arc<Mutex<Recorded>>);
+
+impl DiagnosticsRecorder {
+    /// A copy of `ctx` holding the recorder.
+    pub(crate) fn context(&self, ctx: &Context) -> Context {
+        let mut ctx = ctx.clone();
+        ctx.insert(self.clone());
+        ctx
+    }
+
+    /// Adds the attempts and the duration of the operation to the
+    /// diagnostics of its response.
+    pub(crate) fn complete(&self, diagnostics: &mut CosmosDiagnostics) {
+        diagnostics.record(self.take());
+    }
+
+    fn recorded(&self) -> std::sync::MutexGuard<'_, Recorded> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, attempt: RequestAttempt) {
+        self.recorded().attempts.push(attempt);
+    }
+
+    fn take(&self) -> Recorded {
+        std::mem::take(&mut *self.recorded())
+    }
+}
+
+/// The `DiagnosticsPolicy` records the [`CosmosDiagnostics`] of operations.
+///
+/// It times the whole operation into the [`DiagnosticsRecorder`] of the
+/// request `Context`, adding one when the operation did not, and the
+/// `AttemptDiagnosticsPolicy` adds the attempts to the same recorder. It must
+/// be the first per-call policy, and the `AttemptDiagnosticsPolicy` a
+/// per-retry policy following any policy choosing the endpoint of the
+/// request.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DiagnosticsPolicy {
+    thresholds: DiagnosticsThresholds,
+}
+
+impl DiagnosticsPolicy {
+    pub(crate) fn new(thresholds: DiagnosticsThresholds) -> Self {
+        Self { thresholds }
+    }
+
+    fn log(&self, request: &Request, diagnostics: &CosmosDiagnostics) {
+        if self.thresholds.exceeded_by(diagnostics) {
+            warn!(
+                "Cosmos operation {} {} exceeded the diagnostics thresholds: {diagnostics:?}",
+                request.method(),
+                request.url().path()
+            );
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for DiagnosticsPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let recorder = ctx
+            .get::<DiagnosticsRecorder>()
+            .cloned()
+            .unwrap_or_default();
+        let ctx = recorder.context(ctx);
+
+        let started_at = OffsetDateTime::now_utc();
+        let result = next[0].send(&ctx, request, &next[1..]).await;
+        recorder.recorded().elapsed = elapsed_since(started_at);
+
+        let mut diagnostics = result
+            .as_ref()
+            .map(|response| CosmosDiagnostics::from_headers(response.headers()))
+            .unwrap_or_default();
+        diagnostics.record(recorder.recorded().clone());
+        self.log(request, &diagnostics);
+        result
+    }
+}
+
+/// The `AttemptDiagnosticsPolicy` records each time a request is sent for
+/// the [`DiagnosticsPolicy`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AttemptDiagnosticsPolicy;
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for AttemptDiagnosticsPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let Some(recorder) = ctx.get::<DiagnosticsRecorder>() else {
+            return next[0].send(ctx, request, &next[1..]).await;
+        };
+
+        let started_at = OffsetDateTime::now_utc();
+        let result = next[0].send(ctx, request, &next[1..]).await;
+        let headers = result.as_ref().ok().map(Response::headers);
+        recorder.push(RequestAttempt {
+            endpoint: request.url().origin().ascii_serialization(),
+            status: result
+                .as_ref()
+                .ok()
+                .map(|response| u16::from(response.status())),
+            sub_status: headers
+                .and_then(|headers| headers.get_optional_as(&HEADER_SUB_STATUS).ok())
+                .flatten(),
+            request_charge: headers
+                .and_then(|headers| headers.get_optional_as(&HEADER_REQUEST_CHARGE).ok())
+                .flatten()
+                .unwrap_or_default(),
+            activity_id: headers
+                .and_then(|headers| headers.get_optional_as(&ACTIVITY_ID).ok())
+                .flatten(),
+            latency: elapsed_since(started_at),
+            server_duration: headers.and_then(server_duration_from_headers),
+        });
+        result
+    }
+}
+
+fn elapsed_since(started_at: OffsetDateTime) -> Duration {
+    (OffsetDateTime::now_utc() - started_at)
+        .try_into()
+        .unwrap_or_default()
+}
+
+fn server_duration_from_headers(headers: &Headers) -> Option<Duration> {
+    let milliseconds: f64 = headers
+        .get_optional_as(&HEADER_REQUEST_DURATION_MS)
+        .ok()
+        .flatten()?;
+    Duration::try_from_secs_f64(milliseconds / 1000.0).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(endpoint: &str, status: u16, request_charge: f64) -> RequestAttempt {
+        RequestAttempt {
+            endpoint: endpoint.to_owned(),
+            status: Some(status),
+            sub_status: None,
+            request_charge,
+            activity_id: Some(uuid::Uuid::nil()),
+            latency: Duration::from_millis(20),
+            server_duration: Some(Duration::from_millis(3)),
+        }
+    }
+
+    #[test]
+    fn recorded_diagnostics() {
+        let attempts = vec![
+            attempt("https://account-westus.documents.azure.com", 429, 0.0),
+            attempt("https://account-westus.documents.azure.com", 503, 0.0),
+            attempt("https://account-eastus.documents.azure.com", 200, 5.71),
+        ];
+        let recorder = DiagnosticsRecorder::default();
+        recorder.recorded().elapsed = Duration::from_millis(1500);
+        for attempt in &attempts {
+            recorder.push(attempt.clone());
+        }
+        let mut headers = Headers::new();
+        headers.insert(HEADER_REQUEST_CHARGE, "5.71");
+        headers.insert(HEADER_SESSION_TOKEN, "0:-1#12");
+        headers.insert(HEADER_REQUEST_DURATION_MS, "2.5");
+
+        let mut diagnostics = CosmosDiagnostics::from_headers(&headers);
+        recorder.complete(&mut diagnostics);
+        assert_eq!(diagnostics.retry_count, 2);
+        assert_eq!(diagnostics.request_charge, 5.71);
+        assert_eq!(
+            diagnostics.regions_contacted,
+            [
+                "https://account-westus.documents.azure.com",
+                "https://account-eastus.documents.azure.com"
+            ]
+        );
+        assert_eq!(diagnostics.session_token.as_deref(), Some("0:-1#12"));
+        assert_eq!(
+            diagnostics.server_duration,
+            Some(Duration::from_micros(2500))
+        );
+        assert_eq!(diagnostics.attempts, attempts);
+
+        let thresholds = DiagnosticsThresholds::new().latency(Duration::from_secs(1));
+        assert!(thresholds.exceeded_by(&diagnostics));
+        let thresholds = DiagnosticsThresholds::new()
+            .latency(Duration::from_secs(2))
+            .request_charge(10.0);
+        assert!(!thresholds.exceeded_by(&diagnostics));
+    }
+}
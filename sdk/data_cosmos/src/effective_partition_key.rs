@@ -0,0 +1,191 @@
+//! The effective partition keys used to map documents to partition key ranges.
+
+use azure_core::error::{Error, ErrorKind};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// The markers written before each component of a partition key when it is
+/// hashed.
+mod marker {
+    pub(super) const UNDEFINED: u8 = 0x00;
+    pub(super) const NULL: u8 = 0x01;
+    pub(super) const FALSE: u8 = 0x02;
+    pub(super) const TRUE: u8 = 0x03;
+    pub(super) const NUMBER: u8 = 0x05;
+    pub(super) const STRING: u8 = 0x08;
+    pub(super) const STRING_END: u8 = 0xFF;
+}
+
+/// Hashes the components of a partition key the way collections with a
+/// version 2 `Hash` partition key definition do.
+///
+/// `components` is the serialized partition key, such as `["a"]`. An empty
+/// object stands for an undefined component.
+pub(crate) fn hash_v2(components: &[Value]) -> azure_core::Result<String> {
+    if components.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut bytes = Vec::new();
+    for component in components {
+        match component {
+            Value::Null => bytes.push(marker::NULL),
+            Value::Bool(false) => bytes.push(marker::FALSE),
+            Value::Bool(true) => bytes.push(marker::TRUE),
+            Value::Number(number) => {
+                let number = number.as_f64().ok_or_else(|| {
+                    Error::with_message(ErrorKind::DataConversion, || {
+                        format!("partition key component {number} is not a valid number")
+                    })
+                })?;
+                bytes.push(marker::NUMBER);
+                bytes.extend_from_slice(&number.to_le_bytes());
+            }
+            Value::String(string) => {
+                bytes.push(marker::STRING);
+                bytes.extend_from_slice(string.as_bytes());
+                bytes.push(marker::STRING_END);
+            }
+            Value::Object(object) if object.is_empty() => bytes.push(marker::UNDEFINED),
+            _ => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("partition key component {component} is not a string, number, boolean or null")
+                }))
+            }
+        }
+    }
+
+    let (h1, h2) = murmurhash3_x64_128(&bytes, 0);
+    let mut hash = [0; 16];
+    hash[..8].copy_from_slice(&h1.to_le_bytes());
+    hash[8..].copy_from_slice(&h2.to_le_bytes());
+    hash.reverse();
+    // the two most significant bits are reserved
+    hash[0] &= 0x3F;
+
+    Ok(hash
+        .iter()
+        .fold(String::with_capacity(32), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02X}");
+            hex
+        }))
+}
+
+/// The x64 128-bit variant of MurmurHash3.
+fn murmurhash3_x64_128(data: &[u8], seed: u64) -> (u64, u64) {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    fn fmix(mut k: u64) -> u64 {
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        k ^ (k >> 33)
+    }
+
+    let mix_k1 = |k1: u64| k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+    let mix_k2 = |k2: u64| k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+
+    let (mut h1, mut h2) = (seed, seed);
+    let mut blocks = data.chunks_exact(16);
+    for block in &mut blocks {
+        let k1 = u64::from_le_bytes(block[..8].try_into().unwrap_or_default());
+        let k2 = u64::from_le_bytes(block[8..].try_into().unwrap_or_default());
+
+        h1 ^= mix_k1(k1);
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+        h2 ^= mix_k2(k2);
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+    }
+
+    let tail = blocks.remainder();
+    let read = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .rev()
+            .fold(0u64, |k, byte| (k << 8) | u64::from(*byte))
+    };
+    if tail.len() > 8 {
+        h2 ^= mix_k2(read(&tail[8..]));
+    }
+    if !tail.is_empty() {
+        h1 ^= mix_k1(read(&tail[..tail.len().min(8)]));
+    }
+
+    let len = data.len() as u64;
+    h1 ^= len;
+    h2 ^= len;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix(h1);
+    h2 = fmix(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    (h1, h2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn murmurhash3() {
+        assert_eq!(murmurhash3_x64_128(b"", 0), (0, 0));
+        assert_eq!(
+            murmurhash3_x64_128(b"hello", 0),
+            (0xcbd8_a7b3_41bd_9b02, 0x5b1e_906a_48ae_1d19)
+        );
+        assert_eq!(
+            murmurhash3_x64_128(b"The quick brown fox jumps over the lazy dog", 0),
+            (0xe34b_bc7b_bc07_1b6c, 0x7a43_3ca9_c49a_9347)
+        );
+    }
+
+    #[test]
+    fn effective_partition_keys() -> azure_core::Result<()> {
+        assert_eq!(hash_v2(&[])?, "");
+        assert_ne!(hash_v2(&[json!(1)])?, hash_v2(&[json!("1")])?);
+        assert!(hash_v2(&[json!([1])]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn effective_partition_keys_match_the_service() -> azure_core::Result<()> {
+        // the values expected by the Java SDK for version 2 hash partitioning
+        let expected = [
+            (json!(""), "32E9366E637A71B4E710384B2F4970A0"),
+            (json!("partitionKey"), "013AEFCF77FA271571CF665A58C933F1"),
+            (json!("a".repeat(1024)), "332BDF5512AE49615F32C7D98C2DB86C"),
+            (json!(null), "378867E4430E67857ACE5C908374FE16"),
+            (json!({}), "11622DAA78F835834610ABE56EFF5CB5"),
+            (json!(true), "0E711127C5B5A8E4726AC6DD306A3E59"),
+            (json!(false), "2FE1BE91E90A3439635E0E9E37361EF2"),
+            (json!(i8::MIN), "01DAEDABF913540367FE219B2AD06148"),
+            (json!(i8::MAX), "0C507ACAC853ECA7977BF4CEFB562A25"),
+            (json!(i32::MIN), "0B1660D5233C3171725B30D4A5F4CC1F"),
+            (json!(i32::MAX), "2D9349D64712AEB5EB1406E2F0BE2725"),
+            (json!(i64::MIN), "23D5C6395512BDFEAFADAD15328AD2BB"),
+            (json!(i64::MAX), "2EDB959178DFCCA18983F89384D1629B"),
+            (json!(5e-324), "0E6CBA63A280927DE485DEF865800139"),
+            (json!(f64::MAX), "31424D996457102634591FF245DBCC4D"),
+        ];
+        for (component, epk) in expected {
+            assert_eq!(
+                hash_v2(std::slice::from_ref(&component))?,
+                epk,
+                "{component}"
+            );
+        }
+        Ok(())
+    }
+}
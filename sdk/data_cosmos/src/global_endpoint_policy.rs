@@ -0,0 +1,400 @@
+use crate::diagnostics::DiagnosticsRecorder;
+use crate::headers::{HEADER_DOCUMENTDB_ISQUERY, HEADER_SUB_STATUS};
+use crate::resources::{AccountRegion, DatabaseAccount, ResourceType};
+use azure_core::error::{Error, ErrorKind};
+use azure_core::{Context, Method, Policy, PolicyResult, Request, StatusCode, Url};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use time::{Duration, OffsetDateTime};
+
+/// How often the regions of the account are read again.
+const REFRESH_INTERVAL: Duration = Duration::minutes(5);
+/// How long an endpoint which failed is avoided.
+const UNAVAILABLE_FOR: Duration = Duration::minutes(5);
+/// The sub-status of a `403 Forbidden` response sent by a region which no
+/// longer accepts writes.
+const WRITE_FORBIDDEN: &str = "3";
+
+/// The `GlobalEndpointPolicy` routes requests to the regions of a
+/// geo-replicated account.
+///
+/// Reads go to the first available region of the preferred regions, and
+/// writes to the write region, or to the first available preferred region
+/// when every region of the account accepts writes. Regions are listed by
+/// reading the database account through the account endpoint, and are read
+/// again every five minutes.
+///
+/// An endpoint is avoided for five minutes once it fails with a
+/// `503 Service Unavailable` or an I/O error, so that the retry policy sends
+/// the next attempt to another region. A write refused with a
+/// `403 Forbidden` of sub-status 3 means the write region changed, so the
+/// regions are read again and the write is sent once more.
+///
+/// The policy must be a per-retry policy placed before the
+/// `AuthorizationPolicy`, which it uses to read the database account.
+#[derive(Debug)]
+pub(crate) struct GlobalEndpointPolicy {
+    default_endpoint: Url,
+    preferred_regions: Vec<String>,
+    use_multiple_write_locations: bool,
+    state: RwLock<LocationState>,
+}
+
+#[derive(Debug, Default)]
+struct LocationState {
+    /// The endpoints serving reads, by order of preference.
+    read_endpoints: Vec<Url>,
+    /// The endpoints serving writes, by order of preference.
+    write_endpoints: Vec<Url>,
+    /// When the endpoints which failed can be used again.
+    unavailable_for_reads: HashMap<Url, OffsetDateTime>,
+    unavailable_for_writes: HashMap<Url, OffsetDateTime>,
+    /// When the regions were last read successfully.
+    refreshed_at: Option<OffsetDateTime>,
+    /// Whether a request is reading the regions.
+    refreshing: bool,
+}
+
+/// The claim of a request to read the regions, released when dropped so that
+/// a cancelled request does not prevent later refreshes.
+struct RefreshClaim<'a>(&'a GlobalEndpointPolicy);
+
+impl Drop for RefreshClaim<'_> {
+    fn drop(&mut self) {
+        let mut state = self.0.state.write().unwrap_or_else(|e| e.into_inner());
+        state.refreshing = false;
+    }
+}
+
+impl GlobalEndpointPolicy {
+    pub(crate) fn new(
+        default_endpoint: Url,
+        preferred_regions: Vec<String>,
+        use_multiple_write_locations: bool,
+    ) -> Self {
+        Self {
+            default_endpoint,
+            preferred_regions,
+            use_multiple_write_locations,
+            state: RwLock::new(LocationState::default()),
+        }
+    }
+
+    /// The endpoint to send a request to.
+    fn resolve(&self, is_read: bool, now: OffsetDateTime) -> Url {
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        let (endpoints, unavailable) = if is_read {
+            (&state.read_endpoints, &state.unavailable_for_reads)
+        } else {
+            (&state.write_endpoints, &state.unavailable_for_writes)
+        };
+        endpoints
+            .iter()
+            .find(|endpoint| !matches!(unavailable.get(*endpoint), Some(until) if *until > now))
+            .or_else(|| endpoints.first())
+            .unwrap_or(&self.default_endpoint)
+            .clone()
+    }
+
+    fn mark_unavailable(&self, endpoint: &Url, is_read: bool, now: OffsetDateTime) {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        let unavailable = if is_read {
+            &mut state.unavailable_for_reads
+        } else {
+            &mut state.unavailable_for_writes
+        };
+        unavailable.insert(endpoint.clone(), now + UNAVAILABLE_FOR);
+    }
+
+    /// Claims the refresh if the regions have to be read and no other request
+    /// is reading them.
+    fn claim_refresh(&self, now: OffsetDateTime) -> Option<RefreshClaim<'_>> {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        let fresh = matches!(
+            state.refreshed_at,
+            Some(refreshed_at) if now - refreshed_at < REFRESH_INTERVAL
+        );
+        if fresh || state.refreshing {
+            return None;
+        }
+        state.refreshing = true;
+        Some(RefreshClaim(self))
+    }
+
+    fn update(&self, account: &DatabaseAccount, now: OffsetDateTime) {
+        let read_endpoints = self.ordered(&account.readable_locations);
+        let write_endpoints =
+            if account.enable_multiple_write_locations && self.use_multiple_write_locations {
+                self.ordered(&account.writable_locations)
+            } else {
+                self.ordered(&account.writable_locations[..account.writable_locations.len().min(1)])
+            };
+
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        state.read_endpoints = read_endpoints;
+        state.write_endpoints = write_endpoints;
+        state.refreshed_at = Some(now);
+    }
+
+    /// The endpoints of the regions, preferred regions first.
+    fn ordered(&self, regions: &[AccountRegion]) -> Vec<Url> {
+        let mut regions: Vec<_> = regions
+            .iter()
+            .filter_map(|region| {
+                let endpoint = Url::parse(&region.database_account_endpoint).ok()?;
+                let preference = self
+                    .preferred_regions
+                    .iter()
+                    .position(|preferred| same_region(preferred, &region.name))
+                    .unwrap_or(self.preferred_regions.len());
+                Some((preference, endpoint))
+            })
+            .collect();
+        regions.sort_by_key(|(preference, _)| *preference);
+        regions.into_iter().map(|(_, endpoint)| endpoint).collect()
+    }
+
+    async fn refresh(&self, ctx: &Context, next: &[Arc<dyn Policy>]) -> azure_core::Result<()> {
+        let mut request = Request::new(self.default_endpoint.clone(), Method::Get);
+        let mut ctx = ctx.clone();
+        // reading the regions is not an attempt of the request
+        ctx.remove::<DiagnosticsRecorder>();
+        let response = next[0]
+            .send(
+                ctx.insert(ResourceType::DatabaseAccount),
+                &mut request,
+                &next[1..],
+            )
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::message(
+                ErrorKind::http_response(status, None),
+                "could not read the regions of the database account",
+            ));
+        }
+        let account: DatabaseAccount = response.json().await?;
+        self.update(&account, OffsetDateTime::now_utc());
+        Ok(())
+    }
+
+    async fn refresh_if_stale(&self, ctx: &Context, next: &[Arc<dyn Policy>]) {
+        if let Some(_claim) = self.claim_refresh(OffsetDateTime::now_utc()) {
+            if let Err(error) = self.refresh(ctx, next).await {
+                warn!("could not refresh the regions of the Cosmos account: {error}");
+            }
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for GlobalEndpointPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        self.refresh_if_stale(ctx, next).await;
+
+        let is_read = is_read(request);
+        let mut write_forbidden = false;
+        loop {
+            let endpoint = self.resolve(is_read, OffsetDateTime::now_utc());
+            route(request.url_mut(), &endpoint);
+
+            let result = next[0].send(ctx, request, &next[1..]).await;
+            let failed = match &result {
+                Ok(response) => {
+                    response.status() == StatusCode::ServiceUnavailable
+                        || (response.status() == StatusCode::Forbidden
+                            && !is_read
+                            && response.headers().get_optional_str(&HEADER_SUB_STATUS)
+                                == Some(WRITE_FORBIDDEN))
+                }
+                Err(error) => error.kind() == &ErrorKind::Io,
+            };
+            if !failed {
+                return result;
+            }
+
+            debug!("marking Cosmos endpoint {endpoint} as unavailable");
+            self.mark_unavailable(&endpoint, is_read, OffsetDateTime::now_utc());
+            let forbidden =
+                matches!(&result, Ok(response) if response.status() == StatusCode::Forbidden);
+            if !forbidden || write_forbidden {
+                return result;
+            }
+
+            // the write region changed
+            write_forbidden = true;
+            if let Err(error) = self.refresh(ctx, next).await {
+                warn!("could not refresh the regions of the Cosmos account: {error}");
+            }
+        }
+    }
+}
+
+/// Whether the request only reads resources.
+pub(crate) fn is_read(request: &Request) -> bool {
+    match request.method() {
+        Method::Get | Method::Head => true,
+        Method::Post => request
+            .headers()
+            .get_optional_str(&HEADER_DOCUMENTDB_ISQUERY)
+            .is_some_and(|is_query| is_query.eq_ignore_ascii_case("true")),
+        _ => false,
+    }
+}
+
+/// Points the url to the endpoint, keeping its path and query.
+fn route(url: &mut Url, endpoint: &Url) {
+    // setting the parts of an absolute url to those of another one cannot fail
+    let _ = url.set_scheme(endpoint.scheme());
+    let _ = url.set_host(endpoint.host_str());
+    let _ = url.set_port(endpoint.port());
+}
+
+/// Compares region names ignoring case and spaces, so that `West US` matches
+/// `westus`.
+fn same_region(a: &str, b: &str) -> bool {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    normalize(a) == normalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(multiple_writes: bool) -> DatabaseAccount {
+        let region = |name: &str, endpoint: &str| AccountRegion {
+            name: name.to_owned(),
+            database_account_endpoint: format!(
+                "https://account-{endpoint}.documents.azure.com:443/"
+            ),
+        };
+        DatabaseAccount {
+            id: "account".to_owned(),
+            rid: "account.documents.azure.com".to_owned(),
+            writable_locations: if multiple_writes {
+                vec![region("West US", "westus"), region("East US", "eastus")]
+            } else {
+                vec![region("West US", "westus")]
+            },
+            readable_locations: vec![
+                region("West US", "westus"),
+                region("East US", "eastus"),
+                region("North Europe", "northeurope"),
+            ],
+            enable_multiple_write_locations: multiple_writes,
+        }
+    }
+
+    fn policy() -> GlobalEndpointPolicy {
+        GlobalEndpointPolicy::new(
+            Url::parse("https://account.documents.azure.com").unwrap(),
+            vec!["northeurope".to_owned(), "East US".to_owned()],
+            true,
+        )
+    }
+
+    fn host(url: &Url) -> &str {
+        url.host_str().unwrap()
+    }
+
+    #[test]
+    fn route_to_preferred_regions() {
+        let now = OffsetDateTime::now_utc();
+        let policy = policy();
+        assert_eq!(
+            host(&policy.resolve(true, now)),
+            "account.documents.azure.com"
+        );
+
+        policy.update(&account(false), now);
+        assert_eq!(
+            host(&policy.resolve(true, now)),
+            "account-northeurope.documents.azure.com"
+        );
+        assert_eq!(
+            host(&policy.resolve(false, now)),
+            "account-westus.documents.azure.com"
+        );
+
+        policy.update(&account(true), now);
+        assert_eq!(
+            host(&policy.resolve(false, now)),
+            "account-eastus.documents.azure.com"
+        );
+    }
+
+    #[test]
+    fn fail_over_unavailable_endpoints() {
+        let now = OffsetDateTime::now_utc();
+        let policy = policy();
+        policy.update(&account(false), now);
+
+        let northeurope = policy.resolve(true, now);
+        policy.mark_unavailable(&northeurope, true, now);
+        assert_eq!(
+            host(&policy.resolve(true, now)),
+            "account-eastus.documents.azure.com"
+        );
+        // writes are tracked separately
+        assert_eq!(
+            host(&policy.resolve(false, now)),
+            "account-westus.documents.azure.com"
+        );
+        // the endpoint is used again once it is no longer marked unavailable
+        assert_eq!(
+            policy.resolve(true, now + UNAVAILABLE_FOR + Duration::seconds(1)),
+            northeurope
+        );
+    }
+
+    #[test]
+    fn refresh_until_the_regions_are_read() {
+        let now = OffsetDateTime::now_utc();
+        let policy = policy();
+
+        let claim = policy.claim_refresh(now);
+        assert!(claim.is_some());
+        // a single request reads the regions at a time
+        assert!(policy.claim_refresh(now).is_none());
+        // the regions could not be read
+        drop(claim);
+        assert!(policy.claim_refresh(now).is_some());
+
+        policy.update(&account(false), now);
+        assert!(policy.claim_refresh(now + Duration::minutes(1)).is_none());
+        assert!(policy
+            .claim_refresh(now + REFRESH_INTERVAL + Duration::seconds(1))
+            .is_some());
+    }
+
+    #[test]
+    fn classify_requests() {
+        let mut url =
+            Url::parse("https://account.documents.azure.com/dbs/db/colls/c/docs").unwrap();
+        let mut request = Request::new(url.clone(), Method::Post);
+        assert!(!is_read(&request));
+        request.insert_header(HEADER_DOCUMENTDB_ISQUERY, "true");
+        assert!(is_read(&request));
+        assert!(is_read(&Request::new(url.clone(), Method::Get)));
+
+        route(
+            &mut url,
+            &Url::parse("https://account-westus.documents.azure.com:443/").unwrap(),
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://account-westus.documents.azure.com/dbs/db/colls/c/docs"
+        );
+    }
+}
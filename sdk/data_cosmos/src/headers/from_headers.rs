@@ -1,7 +1,7 @@
 use crate::headers::*;
 use crate::resource_quota::resource_quotas_from_str;
 use crate::resources::document::IndexingDirective;
-use crate::ResourceQuota;
+use crate::{CosmosDiagnostics, IndexUtilization, QueryMetrics, ResourceQuota};
 
 use azure_core::date::{parse_last_state_change, parse_rfc1123};
 use azure_core::error::{Error, ErrorKind};
@@ -12,6 +12,10 @@ pub(crate) fn request_charge_from_headers(headers: &Headers) -> azure_core::Resu
     headers.get_as(&HEADER_REQUEST_CHARGE)
 }
 
+pub(crate) fn diagnostics_from_headers(headers: &Headers) -> azure_core::Result<CosmosDiagnostics> {
+    Ok(CosmosDiagnostics::from_headers(headers))
+}
+
 pub(crate) fn role_from_headers(headers: &Headers) -> azure_core::Result<u32> {
     headers.get_as(&HEADER_ROLE)
 }
@@ -183,3 +187,53 @@ pub(crate) fn date_from_headers(headers: &Headers) -> azure_core::Result<OffsetD
     let s = headers.get_str(&headers::DATE)?;
     parse_rfc1123(s)
 }
+
+/// The query metrics are diagnostics, so unparsable metrics are logged and
+/// dropped rather than failing the page they came with.
+pub(crate) fn query_metrics_from_headers_optional(headers: &Headers) -> Option<QueryMetrics> {
+    let header = headers.get_optional_str(&HEADER_DOCUMENTDB_QUERY_METRICS)?;
+    QueryMetrics::from_header(header)
+        .map_err(|error| warn!("ignoring unparsable query metrics: {}", error))
+        .ok()
+}
+
+/// Like the query metrics, unparsable index metrics are logged and dropped.
+pub(crate) fn index_utilization_from_headers_optional(
+    headers: &Headers,
+) -> Option<IndexUtilization> {
+    let header = headers.get_optional_str(&HEADER_COSMOS_INDEX_UTILIZATION)?;
+    IndexUtilization::from_header(header)
+        .map_err(|error| warn!("ignoring unparsable index metrics: {}", error))
+        .ok()
+}
+
+pub(crate) fn offer_replace_pending_from_headers(headers: &Headers) -> azure_core::Result<bool> {
+    Ok(headers
+        .get_optional_as(&HEADER_OFFER_REPLACE_PENDING)?
+        .unwrap_or_default())
+}
+
+pub(crate) fn min_throughput_from_headers_optional(
+    headers: &Headers,
+) -> azure_core::Result<Option<u64>> {
+    headers.get_optional_as(&HEADER_COSMOS_MIN_THROUGHPUT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unparsable_metrics_are_dropped() {
+        let mut headers = Headers::new();
+        headers.insert(
+            HEADER_DOCUMENTDB_QUERY_METRICS,
+            "retrievedDocumentCount=many",
+        );
+        assert_eq!(query_metrics_from_headers_optional(&headers), None);
+
+        headers.insert(HEADER_DOCUMENTDB_QUERY_METRICS, "retrievedDocumentCount=4");
+        let metrics = query_metrics_from_headers_optional(&headers).unwrap();
+        assert_eq!(metrics.retrieved_document_count, 4);
+    }
+}
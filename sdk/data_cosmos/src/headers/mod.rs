@@ -12,6 +12,7 @@ pub(crate) const HEADER_SESSION_TOKEN: HeaderName = HeaderName::from_static("x-m
 pub(crate) const HEADER_ALLOW_MULTIPLE_WRITES: HeaderName =
     HeaderName::from_static("x-ms-cosmos-allow-tentative-writes");
 pub(crate) const HEADER_A_IM: HeaderName = HeaderName::from_static("a-im");
+pub(crate) const HEADER_SUB_STATUS: HeaderName = HeaderName::from_static("x-ms-substatus");
 pub(crate) const HEADER_DOCUMENTDB_PARTITIONRANGEID: HeaderName =
     HeaderName::from_static("x-ms-documentdb-partitionkeyrangeid");
 pub(crate) const HEADER_DOCUMENTDB_PARTITIONKEY: HeaderName =
@@ -19,9 +20,19 @@ pub(crate) const HEADER_DOCUMENTDB_PARTITIONKEY: HeaderName =
 pub(crate) const HEADER_NUMBER_OF_READ_REGIONS: HeaderName =
     HeaderName::from_static("x-ms-number-of-read-regions");
 pub(crate) const HEADER_REQUEST_CHARGE: HeaderName = HeaderName::from_static("x-ms-request-charge");
+pub(crate) const HEADER_REQUEST_DURATION_MS: HeaderName =
+    HeaderName::from_static("x-ms-request-duration-ms");
 pub(crate) const HEADER_OFFER_THROUGHPUT: HeaderName =
     HeaderName::from_static("x-ms-offer-throughput");
 pub(crate) const HEADER_OFFER_TYPE: HeaderName = HeaderName::from_static("x-ms-offer-type");
+pub(crate) const HEADER_OFFER_REPLACE_PENDING: HeaderName =
+    HeaderName::from_static("x-ms-offer-replace-pending");
+pub(crate) const HEADER_COSMOS_MIN_THROUGHPUT: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-min-throughput");
+pub(crate) const HEADER_COSMOS_MIGRATE_OFFER_TO_AUTOPILOT: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-migrate-offer-to-autopilot");
+pub(crate) const HEADER_COSMOS_MIGRATE_OFFER_TO_MANUAL_THROUGHPUT: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-migrate-offer-to-manual-throughput");
 #[allow(dead_code)]
 pub(crate) const HEADER_DOCUMENTDB_ISQUERY: HeaderName =
     HeaderName::from_static("x-ms-documentdb-isquery");
@@ -29,6 +40,26 @@ pub(crate) const HEADER_DOCUMENTDB_QUERY_ENABLECROSSPARTITION: HeaderName =
     HeaderName::from_static("x-ms-documentdb-query-enablecrosspartition");
 pub(crate) const HEADER_DOCUMENTDB_QUERY_PARALLELIZECROSSPARTITIONQUERY: HeaderName =
     HeaderName::from_static("x-ms-documentdb-query-parallelizecrosspartitionquery");
+pub(crate) const HEADER_DOCUMENTDB_POPULATEQUERYMETRICS: HeaderName =
+    HeaderName::from_static("x-ms-documentdb-populatequerymetrics");
+pub(crate) const HEADER_DOCUMENTDB_QUERY_METRICS: HeaderName =
+    HeaderName::from_static("x-ms-documentdb-query-metrics");
+pub(crate) const HEADER_COSMOS_POPULATEINDEXMETRICS: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-populateindexmetrics");
+pub(crate) const HEADER_COSMOS_INDEX_UTILIZATION: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-index-utilization");
+pub(crate) const HEADER_COSMOS_IS_QUERY_PLAN_REQUEST: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-is-query-plan-request");
+pub(crate) const HEADER_COSMOS_SUPPORTED_QUERY_FEATURES: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-supported-query-features");
+pub(crate) const HEADER_COSMOS_QUERY_VERSION: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-query-version");
+pub(crate) const HEADER_COSMOS_IS_BATCH_REQUEST: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-is-batch-request");
+pub(crate) const HEADER_COSMOS_BATCH_ATOMIC: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-batch-atomic");
+pub(crate) const HEADER_COSMOS_BATCH_CONTINUE_ON_ERROR: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-batch-continue-on-error");
 pub(crate) const HEADER_DOCUMENTDB_EXPIRY_SECONDS: HeaderName =
     HeaderName::from_static("x-ms-documentdb-expiry-seconds");
 pub(crate) const HEADER_CONTENT_PATH: HeaderName = HeaderName::from_static("x-ms-content-path");
@@ -99,15 +99,31 @@ pub mod prelude;
 pub mod resources;
 
 mod authorization_policy;
+mod bulk_executor;
 mod consistency_level;
 mod cosmos_entity;
+mod diagnostics;
+mod effective_partition_key;
+mod global_endpoint_policy;
 mod headers;
+mod query_metrics;
 mod resource_quota;
+mod session_container;
+mod session_token_policy;
 
 pub(crate) use authorization_policy::AuthorizationPolicy;
+pub(crate) use diagnostics::{AttemptDiagnosticsPolicy, DiagnosticsPolicy, DiagnosticsRecorder};
+pub(crate) use global_endpoint_policy::GlobalEndpointPolicy;
+pub(crate) use session_token_policy::SessionTokenPolicy;
 
+pub use bulk_executor::{BulkExecutor, BulkOperation, BulkOperationResponse};
 pub use consistency_level::ConsistencyLevel;
 pub use cosmos_entity::CosmosEntity;
+pub use diagnostics::{CosmosDiagnostics, DiagnosticsThresholds, RequestAttempt};
+pub use query_metrics::{
+    CompositeIndexUtilization, IndexUtilization, QueryMetrics, SingleIndexUtilization,
+};
 pub use resource_quota::ResourceQuota;
+pub use session_container::SessionContainer;
 
 type ReadonlyString = std::borrow::Cow<'static, str>;
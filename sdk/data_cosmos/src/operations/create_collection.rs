@@ -1,7 +1,11 @@
 use crate::{
     headers::from_headers::*,
     prelude::*,
-    resources::collection::{IndexingPolicy, PartitionKey},
+    resources::collection::{
+        AdditionalIndexes, CollectionDefinition, ComputedProperty, ConflictResolutionPolicy,
+        GeospatialConfig, IndexingPolicy, PartitionKey, UniqueKeyPolicy, VectorEmbeddingPolicy,
+    },
+    DiagnosticsRecorder,
 };
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
@@ -16,10 +20,37 @@ operation! {
     partition_key: PartitionKey,
     ?consistency_level: ConsistencyLevel,
     ?indexing_policy: IndexingPolicy,
+    ?additional_indexes: AdditionalIndexes,
+    ?conflict_resolution_policy: ConflictResolutionPolicy,
+    ?unique_key_policy: UniqueKeyPolicy,
+    ?default_ttl: i64,
+    ?analytical_storage_ttl: i64,
+    ?vector_embedding_policy: VectorEmbeddingPolicy,
+    ?computed_properties: Vec<ComputedProperty>,
+    ?geospatial_config: GeospatialConfig,
     ?offer: Offer
 }
 
 impl CreateCollectionBuilder {
+    /// Take every setting of the collection, its name included, from a
+    /// definition.
+    pub fn definition(self, definition: CollectionDefinition) -> Self {
+        Self {
+            collection_name: definition.id,
+            partition_key: definition.partition_key,
+            indexing_policy: definition.indexing_policy,
+            additional_indexes: Some(definition.additional_indexes),
+            conflict_resolution_policy: definition.conflict_resolution_policy,
+            unique_key_policy: definition.unique_key_policy,
+            default_ttl: definition.default_ttl,
+            analytical_storage_ttl: definition.analytical_storage_ttl,
+            vector_embedding_policy: definition.vector_embedding_policy,
+            computed_properties: Some(definition.computed_properties),
+            geospatial_config: definition.geospatial_config,
+            ..self
+        }
+    }
+
     pub fn into_future(self) -> CreateCollection {
         Box::pin(async move {
             let mut request = self.client.collections_request(azure_core::Method::Post);
@@ -28,31 +59,36 @@ impl CreateCollectionBuilder {
                 request.insert_headers(cl);
             }
 
-            /// Body for the create collection request
-            #[derive(Serialize)]
-            struct CreateCollectionBody<'a> {
-                pub id: &'a str,
-                #[serde(rename = "indexingPolicy", skip_serializing_if = "Option::is_none")]
-                pub indexing_policy: &'a Option<IndexingPolicy>,
-                #[serde(rename = "partitionKey")]
-                pub partition_key: &'a PartitionKey,
-            }
-
-            let collection = CreateCollectionBody {
-                id: &self.collection_name,
-                indexing_policy: &self.indexing_policy,
-                partition_key: &self.partition_key,
+            let collection = CollectionDefinition {
+                id: self.collection_name,
+                indexing_policy: self.indexing_policy,
+                additional_indexes: self.additional_indexes.unwrap_or_default(),
+                partition_key: self.partition_key,
+                conflict_resolution_policy: self.conflict_resolution_policy,
+                unique_key_policy: self.unique_key_policy,
+                default_ttl: self.default_ttl,
+                analytical_storage_ttl: self.analytical_storage_ttl,
+                vector_embedding_policy: self.vector_embedding_policy,
+                computed_properties: self.computed_properties.unwrap_or_default(),
+                geospatial_config: self.geospatial_config,
             };
 
             request.set_json(&collection)?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
-                .send(request, self.context.clone(), ResourceType::Collections)
+                .send(
+                    request,
+                    recorder.context(&self.context),
+                    ResourceType::Collections,
+                )
                 .await?;
 
-            CreateCollectionResponse::try_from(response).await
+            let mut response = CreateCollectionResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -61,6 +97,7 @@ impl CreateCollectionBuilder {
 pub struct CreateCollectionResponse {
     pub collection: Collection,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub etag: String,
     pub session_token: String,
@@ -80,6 +117,7 @@ impl CreateCollectionResponse {
         Ok(Self {
             collection: body.json().await?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             etag: etag_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
@@ -1,4 +1,6 @@
-use crate::{headers::from_headers::*, prelude::*, resources::Database, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::Database, DiagnosticsRecorder, ResourceQuota,
+};
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
     Response as HttpResponse,
@@ -30,11 +32,18 @@ impl CreateDatabaseBuilder {
             }
             request.set_json(&body)?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
-                .send(request, self.context.clone(), ResourceType::Databases)
+                .send(
+                    request,
+                    recorder.context(&self.context),
+                    ResourceType::Databases,
+                )
                 .await?;
-            CreateDatabaseResponse::try_from(response).await
+            let mut response = CreateDatabaseResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -43,6 +52,7 @@ impl CreateDatabaseBuilder {
 pub struct CreateDatabaseResponse {
     pub database: Database,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub etag: String,
     pub session_token: String,
     pub last_state_change: OffsetDateTime,
@@ -65,6 +75,7 @@ impl CreateDatabaseResponse {
         Ok(Self {
             database,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             etag: etag_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             last_state_change: last_state_change_from_headers(&headers)?,
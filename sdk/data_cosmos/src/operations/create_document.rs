@@ -2,6 +2,7 @@ use crate::cosmos_entity::{add_as_partition_key_header_serialized, serialize_par
 use crate::headers::from_headers::*;
 use crate::prelude::*;
 use crate::resources::document::DocumentAttributes;
+use crate::DiagnosticsRecorder;
 use crate::ResourceQuota;
 use azure_core::headers::{etag_from_headers, session_token_from_headers};
 use azure_core::{prelude::*, StatusCode};
@@ -64,16 +65,21 @@ impl<D: Serialize + CosmosEntity + Send + 'static> CreateDocumentBuilder<D> {
             );
 
             request.set_json(&document)?;
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Documents),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
                     &mut request,
                 )
                 .await?;
 
-            CreateDocumentResponse::try_from(response).await
+            let mut response = CreateDocumentResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -101,6 +107,7 @@ pub struct CreateDocumentResponse {
     pub cosmos_quorum_acked_llsn: u64,
     pub session_token: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -134,6 +141,7 @@ impl CreateDocumentResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
@@ -1,4 +1,6 @@
-use crate::{headers::from_headers::*, prelude::*, resources::Attachment, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::Attachment, DiagnosticsRecorder, ResourceQuota,
+};
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
     Response as HttpResponse, SessionToken,
@@ -45,15 +47,20 @@ impl CreateOrReplaceAttachmentBuilder {
                 media: &self.media,
             })?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Attachments),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Attachments),
                     &mut req,
                 )
                 .await?;
-            CreateOrReplaceAttachmentResponse::try_from(response).await
+            let mut response = CreateOrReplaceAttachmentResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -80,6 +87,7 @@ pub struct CreateOrReplaceAttachmentResponse {
     pub cosmos_quorum_acked_llsn: u64,
     pub session_token: SessionToken,
     pub request_charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -111,6 +119,7 @@ impl CreateOrReplaceAttachmentResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             request_charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
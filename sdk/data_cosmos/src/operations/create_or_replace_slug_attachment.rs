@@ -1,4 +1,6 @@
-use crate::{headers::from_headers::*, prelude::*, resources::Attachment, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::Attachment, DiagnosticsRecorder, ResourceQuota,
+};
 use azure_core::{
     content_type,
     headers::{
@@ -57,16 +59,21 @@ impl CreateOrReplaceSlugAttachmentBuilder {
             );
 
             request.set_body(body);
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Attachments),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Attachments),
                     &mut request,
                 )
                 .await?;
 
-            CreateOrReplaceSlugAttachmentResponse::try_from(response).await
+            let mut response = CreateOrReplaceSlugAttachmentResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -92,6 +99,7 @@ pub struct CreateOrReplaceSlugAttachmentResponse {
     pub cosmos_quorum_acked_llsn: u64,
     pub session_token: SessionToken,
     pub request_charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -122,6 +130,7 @@ impl CreateOrReplaceSlugAttachmentResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             request_charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
@@ -1,5 +1,6 @@
 use crate::{
-    headers::from_headers::*, prelude::*, resources::trigger::*, resources::Trigger, ResourceQuota,
+    headers::from_headers::*, prelude::*, resources::trigger::*, resources::Trigger,
+    DiagnosticsRecorder, ResourceQuota,
 };
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
@@ -48,16 +49,21 @@ impl CreateOrReplaceTriggerBuilder {
             };
 
             request.set_json(&request_body)?;
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Triggers),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Triggers),
                     &mut request,
                 )
                 .await?;
 
-            CreateOrReplaceTriggerResponse::try_from(response).await
+            let mut response = CreateOrReplaceTriggerResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -85,6 +91,7 @@ pub struct CreateOrReplaceTriggerResponse {
     pub cosmos_quorum_acked_llsn: u64,
     pub session_token: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -117,6 +124,7 @@ impl CreateOrReplaceTriggerResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
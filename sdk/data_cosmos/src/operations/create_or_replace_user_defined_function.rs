@@ -1,4 +1,7 @@
-use crate::{headers::from_headers::*, prelude::*, resources::UserDefinedFunction, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::UserDefinedFunction, DiagnosticsRecorder,
+    ResourceQuota,
+};
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
     Response as HttpResponse,
@@ -36,18 +39,22 @@ impl CreateOrReplaceUserDefinedFunctionBuilder {
                 id: self.client.user_defined_function_name(),
             };
             request.set_json(&request_body)?;
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context
-                        .clone()
+                    recorder
+                        .context(&self.context)
                         .insert(ResourceType::UserDefinedFunctions),
                     &mut request,
                 )
                 .await?;
 
-            CreateOrReplaceUserDefinedFunctionResponse::try_from(response).await
+            let mut response =
+                CreateOrReplaceUserDefinedFunctionResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -75,6 +82,7 @@ pub struct CreateOrReplaceUserDefinedFunctionResponse {
     pub cosmos_quorum_acked_llsn: u64,
     pub session_token: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -107,6 +115,7 @@ impl CreateOrReplaceUserDefinedFunctionResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
@@ -3,6 +3,7 @@ use crate::{
     resources::permission::{
         ExpirySeconds, PermissionMode, PermissionResponse as CreatePermissionResponse,
     },
+    DiagnosticsRecorder,
 };
 
 operation! {
@@ -45,16 +46,21 @@ impl CreatePermissionBuilder {
             };
 
             request.set_json(&request_body)?;
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Permissions),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Permissions),
                     &mut request,
                 )
                 .await?;
 
-            CreatePermissionResponse::try_from(response).await
+            let mut response = CreatePermissionResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
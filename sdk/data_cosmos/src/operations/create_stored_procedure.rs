@@ -1,4 +1,7 @@
-use crate::{headers::from_headers::*, prelude::*, resources::StoredProcedure, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::StoredProcedure, DiagnosticsRecorder,
+    ResourceQuota,
+};
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
     Response as HttpResponse,
@@ -35,15 +38,20 @@ impl CreateStoredProcedureBuilder {
 
             req.set_json(&body)?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::StoredProcedures),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::StoredProcedures),
                     &mut req,
                 )
                 .await?;
-            CreateStoredProcedureResponse::try_from(response).await
+            let mut response = CreateStoredProcedureResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -53,6 +61,7 @@ impl CreateStoredProcedureBuilder {
 pub struct CreateStoredProcedureResponse {
     pub stored_procedure: StoredProcedure,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub etag: String,
     pub session_token: String,
@@ -71,6 +80,7 @@ impl CreateStoredProcedureResponse {
         Ok(Self {
             stored_procedure: body.json().await?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             etag: etag_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
@@ -1,4 +1,4 @@
-use crate::{prelude::*, resources::user::UserResponse as CreateUserResponse};
+use crate::{prelude::*, resources::user::UserResponse as CreateUserResponse, DiagnosticsRecorder};
 
 operation! {
     CreateUser,
@@ -24,16 +24,19 @@ impl CreateUserBuilder {
                 id: self.client.user_name(),
             };
             request.set_json(&body)?;
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Users),
+                    recorder.context(&self.context).insert(ResourceType::Users),
                     &mut request,
                 )
                 .await?;
 
-            CreateUserResponse::try_from(response).await
+            let mut response = CreateUserResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -1,5 +1,6 @@
 use crate::headers::from_headers::*;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 use crate::ResourceQuota;
 
 use azure_core::headers::session_token_from_headers;
@@ -29,16 +30,21 @@ impl DeleteAttachmentBuilder {
                 self.client.document_client().partition_key_serialized(),
                 &mut request,
             );
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Attachments),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Attachments),
                     &mut request,
                 )
                 .await?;
 
-            DeleteAttachmentResponse::try_from(response)
+            let mut response = DeleteAttachmentResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -63,6 +69,7 @@ pub struct DeleteAttachmentResponse {
     pub cosmos_quorum_acked_llsn: u64,
     pub session_token: SessionToken,
     pub request_charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -92,6 +99,7 @@ impl DeleteAttachmentResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers(headers)?,
             session_token: session_token_from_headers(headers)?,
             request_charge: request_charge_from_headers(headers)?,
+            diagnostics: diagnostics_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
             gateway_version: gateway_version_from_headers(headers)?,
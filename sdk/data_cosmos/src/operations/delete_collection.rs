@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 use crate::{headers::from_headers::*, ResourceQuota};
 use azure_core::headers::{content_type_from_headers, session_token_from_headers};
 use azure_core::Response as HttpResponse;
@@ -19,16 +20,21 @@ impl DeleteCollectionBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Collections),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Collections),
                     &mut request,
                 )
                 .await?;
 
-            DeleteCollectionResponse::try_from(response)
+            let mut response = DeleteCollectionResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -46,6 +52,7 @@ pub struct DeleteCollectionResponse {
     pub current_write_quorum: u64,
     pub current_replica_set_size: u64,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
@@ -72,6 +79,7 @@ impl DeleteCollectionResponse {
             schema_version: schema_version_from_headers(&headers)?,
             alt_content_path: alt_content_path_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
@@ -0,0 +1,65 @@
+use crate::headers::from_headers::*;
+use crate::prelude::*;
+use crate::DiagnosticsRecorder;
+
+use azure_core::headers::session_token_from_headers;
+use azure_core::Response as HttpResponse;
+
+operation! {
+    DeleteConflict,
+    client: ConflictClient,
+    ?consistency_level: ConsistencyLevel
+}
+
+impl DeleteConflictBuilder {
+    pub fn into_future(self) -> DeleteConflict {
+        Box::pin(async move {
+            let mut request = self.client.conflict_request(azure_core::Method::Delete);
+
+            if let Some(cl) = &self.consistency_level {
+                request.insert_headers(cl);
+            }
+            crate::cosmos_entity::add_as_partition_key_header_serialized(
+                self.client.partition_key_serialized(),
+                &mut request,
+            );
+
+            let recorder = DiagnosticsRecorder::default();
+            let response = self
+                .client
+                .pipeline()
+                .send(
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Conflicts),
+                    &mut request,
+                )
+                .await?;
+
+            let mut response = DeleteConflictResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteConflictResponse {
+    pub session_token: String,
+    pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
+    pub activity_id: uuid::Uuid,
+}
+
+impl DeleteConflictResponse {
+    pub fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, _pinned_stream) = response.deconstruct();
+
+        Ok(Self {
+            session_token: session_token_from_headers(&headers)?,
+            charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
+            activity_id: activity_id_from_headers(&headers)?,
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use crate::headers::from_headers::*;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 use crate::ResourceQuota;
 use azure_core::headers::session_token_from_headers;
 use azure_core::Response as HttpResponse;
@@ -18,12 +19,19 @@ impl DeleteDatabaseBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
-                .send(request, self.context.clone(), ResourceType::Databases)
+                .send(
+                    request,
+                    recorder.context(&self.context),
+                    ResourceType::Databases,
+                )
                 .await?;
-            DeleteDatabaseResponse::try_from(response)
+            let mut response = DeleteDatabaseResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -31,6 +39,7 @@ impl DeleteDatabaseBuilder {
 #[derive(Debug, Clone)]
 pub struct DeleteDatabaseResponse {
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
     pub resource_quota: Vec<ResourceQuota>,
@@ -46,6 +55,7 @@ impl DeleteDatabaseResponse {
 
         Ok(Self {
             charge,
+            diagnostics: diagnostics_from_headers(headers)?,
             activity_id,
             session_token: session_token_from_headers(headers)?,
             resource_quota: resource_quota_from_headers(headers)?,
@@ -1,5 +1,6 @@
 use crate::headers::from_headers::*;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 
 use azure_core::headers::session_token_from_headers;
 use azure_core::prelude::*;
@@ -35,17 +36,22 @@ impl DeleteDocumentBuilder {
                 &mut request,
             );
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Documents),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
                     &mut request,
                 )
                 .await?;
 
-            DeleteDocumentResponse::try_from(response)
+            let mut response = DeleteDocumentResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -53,6 +59,7 @@ impl DeleteDocumentBuilder {
 #[derive(Debug, Clone)]
 pub struct DeleteDocumentResponse {
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
 }
@@ -67,6 +74,7 @@ impl DeleteDocumentResponse {
 
         Ok(Self {
             charge,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id,
             session_token,
         })
@@ -1,5 +1,6 @@
 use crate::headers::from_headers::*;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 
 use azure_core::headers::session_token_from_headers;
 use azure_core::Response as HttpResponse;
@@ -19,16 +20,21 @@ impl DeletePermissionBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Permissions),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Permissions),
                     &mut request,
                 )
                 .await?;
 
-            DeletePermissionResponse::try_from(response)
+            let mut response = DeletePermissionResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -36,6 +42,7 @@ impl DeletePermissionBuilder {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DeletePermissionResponse {
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
     pub content_path: String,
@@ -48,6 +55,7 @@ impl DeletePermissionResponse {
 
         Ok(Self {
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             content_path: content_path_from_headers(&headers)?,
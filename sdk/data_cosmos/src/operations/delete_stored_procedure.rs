@@ -1,5 +1,6 @@
 use crate::headers::from_headers::*;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 use crate::ResourceQuota;
 use azure_core::headers::session_token_from_headers;
 use azure_core::Response as HttpResponse;
@@ -22,16 +23,21 @@ impl DeleteStoredProcedureBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::StoredProcedures),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::StoredProcedures),
                     &mut request,
                 )
                 .await?;
 
-            DeleteStoredProcedureResponse::try_from(response)
+            let mut response = DeleteStoredProcedureResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -39,6 +45,7 @@ impl DeleteStoredProcedureBuilder {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DeleteStoredProcedureResponse {
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
     pub last_change: OffsetDateTime,
@@ -52,6 +59,7 @@ impl DeleteStoredProcedureResponse {
 
         Ok(Self {
             charge: request_charge_from_headers(headers)?,
+            diagnostics: diagnostics_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
             session_token: session_token_from_headers(headers)?,
             last_change: last_state_change_from_headers(headers)?,
@@ -1,5 +1,6 @@
 use crate::headers::from_headers::*;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 use azure_core::{headers::session_token_from_headers, Response as HttpResponse};
 
 operation! {
@@ -16,16 +17,19 @@ impl DeleteUserBuilder {
                 request.insert_headers(cl);
             }
             request.set_body(bytes::Bytes::new());
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Users),
+                    recorder.context(&self.context).insert(ResourceType::Users),
                     &mut request,
                 )
                 .await?;
 
-            DeleteUserResponse::try_from(response)
+            let mut response = DeleteUserResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -33,6 +37,7 @@ impl DeleteUserBuilder {
 #[derive(Debug, Clone)]
 pub struct DeleteUserResponse {
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
 }
@@ -43,6 +48,7 @@ impl DeleteUserResponse {
 
         Ok(Self {
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
         })
@@ -1,5 +1,6 @@
 use crate::headers::from_headers::*;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 use crate::ResourceQuota;
 
 use azure_core::headers::session_token_from_headers;
@@ -21,18 +22,21 @@ impl DeleteUserDefinedFunctionBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context
-                        .clone()
+                    recorder
+                        .context(&self.context)
                         .insert(ResourceType::UserDefinedFunctions),
                     &mut request,
                 )
                 .await?;
 
-            DeleteUserDefinedFunctionResponse::try_from(response)
+            let mut response = DeleteUserDefinedFunctionResponse::try_from(response)?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -59,6 +63,7 @@ pub struct DeleteUserDefinedFunctionResponse {
     pub cosmos_quorum_acked_llsn: u64,
     pub session_token: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -90,6 +95,7 @@ impl DeleteUserDefinedFunctionResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
@@ -1,4 +1,7 @@
-use crate::{headers::from_headers::*, prelude::*, resources::stored_procedure::Parameters};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::stored_procedure::Parameters,
+    DiagnosticsRecorder,
+};
 use azure_core::{
     headers::session_token_from_headers, prelude::*, Response as HttpResponse, SessionToken,
 };
@@ -72,17 +75,22 @@ impl<T: DeserializeOwned + Send> ExecuteStoredProcedureBuilder<T> {
 
             request.set_body(body);
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::StoredProcedures),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::StoredProcedures),
                     &mut request,
                 )
                 .await?;
 
-            ExecuteStoredProcedureResponse::try_from(response).await
+            let mut response = ExecuteStoredProcedureResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -118,6 +126,7 @@ where
     pub cosmos_quorum_acked_llsn: Option<u64>,
     pub session_token: SessionToken,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -148,6 +157,7 @@ where
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers_optional(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
@@ -1,6 +1,6 @@
 use crate::{
     headers::from_headers::*, prelude::*, resources::document::IndexingDirective,
-    resources::Attachment, ResourceQuota,
+    resources::Attachment, DiagnosticsRecorder, ResourceQuota,
 };
 use azure_core::{
     headers::{content_type_from_headers, etag_from_headers, session_token_from_headers},
@@ -30,16 +30,21 @@ impl GetAttachmentBuilder {
                 self.client.document_client().partition_key_serialized(),
                 &mut request,
             );
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Attachments),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Attachments),
                     &mut request,
                 )
                 .await?;
 
-            GetAttachmentResponse::try_from(response).await
+            let mut response = GetAttachmentResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -65,6 +70,7 @@ pub struct GetAttachmentResponse {
     pub cosmos_item_llsn: u64,
     pub session_token: SessionToken,
     pub request_charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub indexing_directive: Option<IndexingDirective>,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
@@ -95,6 +101,7 @@ impl GetAttachmentResponse {
             cosmos_item_llsn: cosmos_item_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             request_charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             indexing_directive: indexing_directive_from_headers_optional(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
@@ -0,0 +1,473 @@
+use crate::headers::{from_headers::*, HEADER_SUB_STATUS};
+use crate::prelude::*;
+use crate::resources::document::Document;
+use crate::resources::ResourceType;
+use crate::DiagnosticsRecorder;
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use azure_core::headers::{etag_from_headers_optional, session_token_from_headers};
+use azure_core::{from_json, prelude::*, Pageable, Response, SessionToken, StatusCode};
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// The sub-status codes of a `410 Gone` response sent when a partition key
+/// range was split or merged.
+pub(crate) const PARTITION_KEY_RANGE_GONE: [&str; 3] = ["1002", "1007", "1008"];
+
+operation! {
+    #[stream]
+    GetChangeFeed,
+    client: CollectionClient,
+    ?start_from: ChangeFeedStartFrom,
+    ?max_item_count: MaxItemCount,
+    ?consistency_level: ConsistencyLevel
+}
+
+impl GetChangeFeedBuilder {
+    /// Reads the changes currently available in every partition key range.
+    ///
+    /// Each page holds the changes of a single partition key range and the
+    /// [`ChangeFeedContinuation`] to resume from once the page has been
+    /// processed. The stream ends with a page without documents once no range
+    /// has changes left, so the continuation of the last page can be used to
+    /// poll for later changes.
+    pub fn into_stream<T>(self) -> GetChangeFeed<T>
+    where
+        T: DeserializeOwned + Send + Sync,
+    {
+        let make_request = move |state: Option<ChangeFeedState>| {
+            let this = self.clone();
+            async move {
+                let state = match state {
+                    Some(state) => state,
+                    None => this.initial_state().await?,
+                };
+                this.next_page(state).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+
+    async fn initial_state(&self) -> azure_core::Result<ChangeFeedState> {
+        let continuation = match self.start_from.clone().unwrap_or_default() {
+            ChangeFeedStartFrom::Continuation(continuation) => continuation,
+            start_from => {
+                let (etag, start_time) = match start_from {
+                    ChangeFeedStartFrom::Now => (Some("*".to_owned()), None),
+                    ChangeFeedStartFrom::PointInTime(start_time) => (None, Some(start_time)),
+                    _ => (None, None),
+                };
+                let mut ranges: Vec<FeedRange> = self
+                    .partition_key_ranges()
+                    .await?
+                    .into_iter()
+                    .map(|range| FeedRange::new(range, etag.clone()))
+                    .collect();
+                ranges.sort_by(|a, b| a.min_inclusive.cmp(&b.min_inclusive));
+                ChangeFeedContinuation { ranges, start_time }
+            }
+        };
+
+        if continuation.ranges.is_empty() {
+            return Err(Error::message(
+                ErrorKind::DataConversion,
+                "the change feed continuation has no partition key ranges",
+            ));
+        }
+
+        Ok(ChangeFeedState {
+            continuation,
+            position: 0,
+            unchanged: 0,
+        })
+    }
+
+    async fn partition_key_ranges(&self) -> azure_core::Result<Vec<PartitionKeyRange>> {
+        let mut builder = self.client.get_partition_key_ranges();
+        if let Some(consistency_level) = &self.consistency_level {
+            builder = builder.consistency_level(consistency_level.clone());
+        }
+        Ok(builder
+            .context(self.context.clone())
+            .await?
+            .partition_key_ranges)
+    }
+
+    /// Reads ranges in turn until one of them has changes or all of them
+    /// have been caught up with.
+    async fn next_page<T>(
+        &self,
+        mut state: ChangeFeedState,
+    ) -> azure_core::Result<GetChangeFeedResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut charge = 0.0;
+        let mut diagnostics = CosmosDiagnostics::default();
+        loop {
+            if state.unchanged >= state.continuation.ranges.len() {
+                return Ok(GetChangeFeedResponse {
+                    documents: Vec::new(),
+                    partition_key_range_id: None,
+                    charge,
+                    diagnostics,
+                    session_token: None,
+                    continuation: state.continuation,
+                    next: None,
+                });
+            }
+
+            let range = &state.continuation.ranges[state.position];
+            let mut request = self.client.docs_request(azure_core::Method::Get);
+            request.insert_headers(&ChangeFeed::Incremental);
+            request.insert_headers(&PartitionRangeId::new(range.partition_key_range_id.clone()));
+            match &range.etag {
+                Some(etag) => request.insert_headers(&IfMatchCondition::NotMatch(etag.clone())),
+                None => {
+                    request.insert_headers(&state.continuation.start_time.map(IfModifiedSince::new))
+                }
+            }
+            if let Some(consistency_level) = &self.consistency_level {
+                request.insert_headers(consistency_level);
+            }
+            request.insert_headers(&self.max_item_count.unwrap_or_default());
+
+            let recorder = DiagnosticsRecorder::default();
+            let result = self
+                .client
+                .pipeline()
+                .send(
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
+                    &mut request,
+                )
+                .await;
+
+            match result {
+                Ok(response) => {
+                    let mut page = ChangeFeedPage::try_from(response).await?;
+                    recorder.complete(&mut page.diagnostics);
+                    charge += page.charge;
+                    diagnostics.merge(page.diagnostics);
+                    let range = &mut state.continuation.ranges[state.position];
+                    if page.etag.is_some() {
+                        range.etag = page.etag;
+                    }
+                    let partition_key_range_id = range.partition_key_range_id.clone();
+                    state.unchanged = if page.documents.is_empty() {
+                        state.unchanged + 1
+                    } else {
+                        0
+                    };
+                    state.advance();
+                    if page.documents.is_empty() {
+                        continue;
+                    }
+                    return Ok(GetChangeFeedResponse {
+                        documents: page.documents,
+                        partition_key_range_id: Some(partition_key_range_id),
+                        charge,
+                        diagnostics,
+                        session_token: Some(page.session_token),
+                        continuation: state.continuation.clone(),
+                        next: Some(state),
+                    });
+                }
+                Err(error) => match error.as_http_error() {
+                    Some(http_error) if http_error.status() == StatusCode::NotModified => {
+                        charge += http_error
+                            .header(&crate::headers::HEADER_REQUEST_CHARGE)
+                            .and_then(|charge| charge.parse::<f64>().ok())
+                            .unwrap_or_default();
+                        if let Some(etag) = http_error.header(&azure_core::headers::ETAG) {
+                            state.continuation.ranges[state.position].etag = Some(etag.to_owned());
+                        }
+                        state.unchanged += 1;
+                        state.advance();
+                    }
+                    Some(http_error)
+                        if http_error.status() == StatusCode::Gone
+                            && http_error.header(&HEADER_SUB_STATUS).is_some_and(
+                                |sub_status| PARTITION_KEY_RANGE_GONE.contains(&sub_status),
+                            ) =>
+                    {
+                        let ranges = self.partition_key_ranges().await?;
+                        state.split(ranges)?;
+                    }
+                    _ => return Err(error),
+                },
+            }
+        }
+    }
+}
+
+pub type GetChangeFeed<T> = Pageable<GetChangeFeedResponse<T>, azure_core::error::Error>;
+
+/// Where a change feed starts reading.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ChangeFeedStartFrom {
+    /// Read every change still retained by the collection.
+    #[default]
+    Beginning,
+    /// Only read the changes made after the feed was started.
+    Now,
+    /// Read the changes made after a point in time.
+    PointInTime(OffsetDateTime),
+    /// Resume from a continuation returned by an earlier feed.
+    Continuation(ChangeFeedContinuation),
+}
+
+impl From<ChangeFeedContinuation> for ChangeFeedStartFrom {
+    fn from(continuation: ChangeFeedContinuation) -> Self {
+        Self::Continuation(continuation)
+    }
+}
+
+/// The position of a change feed in every partition key range.
+///
+/// The continuation can be persisted as a string with [`ToString`] and read
+/// back with [`FromStr`] to resume processing later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeFeedContinuation {
+    ranges: Vec<FeedRange>,
+    #[serde(
+        rename = "startTime",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "azure_core::date::rfc1123::option"
+    )]
+    start_time: Option<OffsetDateTime>,
+}
+
+impl std::fmt::Display for ChangeFeedContinuation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl FromStr for ChangeFeedContinuation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        serde_json::from_str(s).context(
+            ErrorKind::DataConversion,
+            "invalid change feed continuation",
+        )
+    }
+}
+
+/// The position of a change feed in a range of effective partition keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FeedRange {
+    #[serde(rename = "min")]
+    min_inclusive: String,
+    #[serde(rename = "max")]
+    max_exclusive: String,
+    #[serde(rename = "id")]
+    partition_key_range_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+}
+
+impl FeedRange {
+    fn new(range: PartitionKeyRange, etag: Option<String>) -> Self {
+        Self {
+            min_inclusive: range.min_exclusive,
+            max_exclusive: range.max_exclusive,
+            partition_key_range_id: range.id,
+            etag,
+        }
+    }
+
+    /// Effective partition keys are hex strings where the empty string is
+    /// the minimum and `FF` the maximum, so they compare as strings.
+    fn overlaps(&self, range: &PartitionKeyRange) -> bool {
+        range.min_exclusive < self.max_exclusive && self.min_inclusive < range.max_exclusive
+    }
+}
+
+/// The state of a change feed stream between two pages.
+#[derive(Debug, Clone)]
+pub struct ChangeFeedState {
+    continuation: ChangeFeedContinuation,
+    position: usize,
+    /// The number of ranges read in a row without finding changes.
+    unchanged: usize,
+}
+
+impl ChangeFeedState {
+    fn advance(&mut self) {
+        self.position = (self.position + 1) % self.continuation.ranges.len();
+    }
+
+    /// Replaces the current range, which is gone, with the partition key
+    /// ranges now covering it. The new ranges resume from the same position.
+    fn split(&mut self, ranges: Vec<PartitionKeyRange>) -> azure_core::Result<()> {
+        let gone = self.continuation.ranges.remove(self.position);
+        let mut children: Vec<FeedRange> = ranges
+            .into_iter()
+            .filter(|range| gone.overlaps(range))
+            .map(|range| FeedRange::new(range, gone.etag.clone()))
+            .collect();
+        if children.is_empty() {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "no partition key range covers the range of gone partition key range {}",
+                    gone.partition_key_range_id
+                )
+            }));
+        }
+        children.sort_by(|a, b| a.min_inclusive.cmp(&b.min_inclusive));
+        self.continuation
+            .ranges
+            .splice(self.position..self.position, children);
+        self.unchanged = 0;
+        Ok(())
+    }
+}
+
+/// The changes read from a partition key range.
+#[derive(Debug, Clone)]
+pub struct GetChangeFeedResponse<T> {
+    pub documents: Vec<Document<T>>,
+    /// The partition key range the documents were read from, or `None` for
+    /// the last page of the stream.
+    pub partition_key_range_id: Option<String>,
+    /// The request charge of every request made to read the page.
+    pub charge: f64,
+    /// The diagnostics of the requests made to read the page which returned
+    /// a response.
+    pub diagnostics: CosmosDiagnostics,
+    pub session_token: Option<SessionToken>,
+    /// The position of the feed after this page.
+    pub continuation: ChangeFeedContinuation,
+    next: Option<ChangeFeedState>,
+}
+
+impl<T> Continuable for GetChangeFeedResponse<T> {
+    type Continuation = ChangeFeedState;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next.clone()
+    }
+}
+
+struct ChangeFeedPage<T> {
+    documents: Vec<Document<T>>,
+    etag: Option<String>,
+    charge: f64,
+    diagnostics: CosmosDiagnostics,
+    session_token: SessionToken,
+}
+
+impl<T> ChangeFeedPage<T>
+where
+    T: DeserializeOwned,
+{
+    async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+
+        let document_attributes: ListDocumentsResponseAttributes = from_json(&body)?;
+        let entries: ListDocumentsResponseEntities<T> = from_json(&body)?;
+        let documents = document_attributes
+            .documents
+            .into_iter()
+            .zip(entries.entities)
+            .map(|(document_attributes, document)| Document {
+                document_attributes,
+                document,
+            })
+            .collect();
+
+        Ok(Self {
+            documents,
+            etag: etag_from_headers_optional(&headers)?,
+            charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
+            session_token: session_token_from_headers(&headers)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partition_key_range(id: &str, min: &str, max: &str) -> PartitionKeyRange {
+        serde_json::from_value(serde_json::json!({
+            "_rid": "rid",
+            "id": id,
+            "_etag": "etag",
+            "minInclusive": min,
+            "maxExclusive": max,
+            "ridPrefix": 0,
+            "_self": "self",
+            "throughputFraction": 1.0,
+            "status": "online",
+            "_ts": 0
+        }))
+        .unwrap()
+    }
+
+    fn state() -> ChangeFeedState {
+        ChangeFeedState {
+            continuation: ChangeFeedContinuation {
+                ranges: vec![
+                    FeedRange::new(
+                        partition_key_range("0", "", "80"),
+                        Some("\"10\"".to_owned()),
+                    ),
+                    FeedRange::new(
+                        partition_key_range("1", "80", "FF"),
+                        Some("\"20\"".to_owned()),
+                    ),
+                ],
+                start_time: None,
+            },
+            position: 1,
+            unchanged: 1,
+        }
+    }
+
+    #[test]
+    fn continuation_round_trips_as_string() -> azure_core::Result<()> {
+        let continuation = state().continuation;
+        let parsed: ChangeFeedContinuation = continuation.to_string().parse()?;
+        assert_eq!(parsed, continuation);
+        assert!("not a continuation"
+            .parse::<ChangeFeedContinuation>()
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn split_ranges_inherit_position() -> azure_core::Result<()> {
+        let mut state = state();
+        state.split(vec![
+            partition_key_range("0", "", "80"),
+            partition_key_range("2", "80", "C0"),
+            partition_key_range("3", "C0", "FF"),
+        ])?;
+
+        let ranges: Vec<_> = state
+            .continuation
+            .ranges
+            .iter()
+            .map(|range| (range.partition_key_range_id.as_str(), range.etag.as_deref()))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("0", Some("\"10\"")),
+                ("2", Some("\"20\"")),
+                ("3", Some("\"20\"")),
+            ]
+        );
+        assert_eq!(state.position, 1);
+        assert_eq!(state.unchanged, 0);
+        Ok(())
+    }
+}
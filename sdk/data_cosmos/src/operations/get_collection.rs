@@ -1,4 +1,4 @@
-use crate::{headers::from_headers::*, prelude::*};
+use crate::{headers::from_headers::*, prelude::*, DiagnosticsRecorder};
 use azure_core::{
     headers::{content_type_from_headers, etag_from_headers, session_token_from_headers},
     Response as HttpResponse,
@@ -20,16 +20,21 @@ impl GetCollectionBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Collections),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Collections),
                     &mut request,
                 )
                 .await?;
 
-            GetCollectionResponse::try_from(response).await
+            let mut response = GetCollectionResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -52,6 +57,7 @@ pub struct GetCollectionResponse {
     pub cosmos_llsn: u64,
     pub cosmos_item_llsn: u64,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
@@ -84,6 +90,7 @@ impl GetCollectionResponse {
             cosmos_llsn: cosmos_llsn_from_headers(&headers)?,
             cosmos_item_llsn: cosmos_item_llsn_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
@@ -1,4 +1,4 @@
-use crate::{headers::from_headers::*, prelude::*, ResourceQuota};
+use crate::{headers::from_headers::*, prelude::*, DiagnosticsRecorder, ResourceQuota};
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
     Response as HttpResponse,
@@ -19,12 +19,19 @@ impl GetDatabaseBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
-                .send(request, self.context.clone(), ResourceType::Databases)
+                .send(
+                    request,
+                    recorder.context(&self.context),
+                    ResourceType::Databases,
+                )
                 .await?;
-            GetDatabaseResponse::try_from(response).await
+            let mut response = GetDatabaseResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -33,6 +40,7 @@ impl GetDatabaseBuilder {
 pub struct GetDatabaseResponse {
     pub database: Database,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
     pub etag: String,
@@ -50,6 +58,7 @@ impl GetDatabaseResponse {
         Ok(Self {
             database: body.json().await?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             etag: etag_from_headers(&headers)?,
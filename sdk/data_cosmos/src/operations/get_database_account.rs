@@ -0,0 +1,47 @@
+use crate::headers::from_headers::diagnostics_from_headers;
+use crate::prelude::*;
+use crate::resources::DatabaseAccount;
+use crate::DiagnosticsRecorder;
+use azure_core::{Method, Response as HttpResponse};
+
+operation! {
+    GetDatabaseAccount,
+    client: CosmosClient,
+}
+
+impl GetDatabaseAccountBuilder {
+    pub fn into_future(self) -> GetDatabaseAccount {
+        Box::pin(async move {
+            let request = self.client.request("", Method::Get);
+
+            let recorder = DiagnosticsRecorder::default();
+            let response = self
+                .client
+                .send(
+                    request,
+                    recorder.context(&self.context),
+                    ResourceType::DatabaseAccount,
+                )
+                .await?;
+            let mut response = GetDatabaseAccountResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetDatabaseAccountResponse {
+    pub account: DatabaseAccount,
+    pub diagnostics: CosmosDiagnostics,
+}
+
+impl GetDatabaseAccountResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let diagnostics = diagnostics_from_headers(response.headers())?;
+        Ok(Self {
+            account: response.json().await?,
+            diagnostics,
+        })
+    }
+}
@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 use crate::headers::from_headers::*;
 use crate::prelude::*;
 use crate::resources::Document;
+use crate::DiagnosticsRecorder;
 use crate::ResourceQuota;
 use azure_core::headers::{etag_from_headers, session_token_from_headers, Headers};
 use azure_core::{from_json, prelude::*, StatusCode};
@@ -62,17 +63,26 @@ impl<T: DeserializeOwned + Send> GetDocumentBuilder<T> {
 
             request.set_body(azure_core::EMPTY_BODY);
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Documents),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
                     &mut request,
                 )
                 .await?;
 
-            GetDocumentResponse::try_from(response).await
+            let mut response = GetDocumentResponse::try_from(response).await?;
+            let diagnostics = match &mut response {
+                GetDocumentResponse::Found(found) => &mut found.diagnostics,
+                GetDocumentResponse::NotFound(not_found) => &mut not_found.diagnostics,
+            };
+            recorder.complete(diagnostics);
+            Ok(response)
         })
     }
 }
@@ -141,6 +151,7 @@ pub struct FoundDocumentResponse<T> {
     pub cosmos_item_llsn: u64,
     pub session_token: SessionToken,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -172,6 +183,7 @@ where
             cosmos_item_llsn: cosmos_item_llsn_from_headers(headers)?,
             session_token: session_token_from_headers(headers)?,
             charge: request_charge_from_headers(headers)?,
+            diagnostics: diagnostics_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
             gateway_version: gateway_version_from_headers(headers)?,
@@ -196,6 +208,7 @@ pub struct NotFoundDocumentResponse {
     pub cosmos_quorum_acked_llsn: Option<u64>,
     pub session_token: SessionToken,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -219,6 +232,7 @@ impl NotFoundDocumentResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers_optional(headers)?,
             session_token: session_token_from_headers(headers)?,
             charge: request_charge_from_headers(headers)?,
+            diagnostics: diagnostics_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
             gateway_version: gateway_version_from_headers(headers)?,
@@ -1,4 +1,4 @@
-use crate::{headers::from_headers::*, prelude::*, resources::ResourceType};
+use crate::{headers::from_headers::*, prelude::*, resources::ResourceType, DiagnosticsRecorder};
 use azure_core::{
     headers::{item_count_from_headers, session_token_from_headers},
     prelude::*,
@@ -32,18 +32,21 @@ impl GetPartitionKeyRangesBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context
-                        .clone()
+                    recorder
+                        .context(&self.context)
                         .insert(ResourceType::PartitionKeyRanges),
                     &mut request,
                 )
                 .await?;
 
-            GetPartitionKeyRangesResponse::try_from(response).await
+            let mut response = GetPartitionKeyRangesResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -67,6 +70,7 @@ pub struct GetPartitionKeyRangesResponse {
     pub session_token: String,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: CosmosDiagnostics,
     pub gateway_version: String,
     pub date: OffsetDateTime,
     pub partition_key_ranges: Vec<PartitionKeyRange>,
@@ -103,6 +107,7 @@ impl GetPartitionKeyRangesResponse {
             cosmos_llsn: cosmos_llsn_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
             date: date_from_headers(&headers)?,
@@ -1,4 +1,7 @@
-use crate::{prelude::*, resources::permission::PermissionResponse as GetPermissionResponse};
+use crate::{
+    prelude::*, resources::permission::PermissionResponse as GetPermissionResponse,
+    DiagnosticsRecorder,
+};
 
 operation! {
     GetPermission,
@@ -15,16 +18,21 @@ impl GetPermissionBuilder {
                 request.insert_headers(cl);
             }
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Permissions),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Permissions),
                     &mut request,
                 )
                 .await?;
 
-            GetPermissionResponse::try_from(response).await
+            let mut response = GetPermissionResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -0,0 +1,194 @@
+use crate::headers::{self as cosmos_headers, from_headers::*};
+use crate::prelude::*;
+use crate::resources::{document::Query, ResourceType};
+use crate::DiagnosticsRecorder;
+use azure_core::{
+    headers::{self, HeaderValue},
+    Method, Response as HttpResponse,
+};
+use std::collections::BTreeMap;
+
+/// The query features this crate can execute on the client.
+const SUPPORTED_QUERY_FEATURES: &str =
+    "Aggregate, CompositeAggregate, Distinct, GroupBy, MultipleAggregates, MultipleOrderBy, OffsetAndLimit, OrderBy, Top";
+const QUERY_VERSION: &str = "1.4";
+
+operation! {
+    GetQueryPlan,
+    client: CollectionClient,
+    query: Query,
+    ?consistency_level: ConsistencyLevel
+}
+
+impl GetQueryPlanBuilder {
+    pub fn into_future(self) -> GetQueryPlan {
+        Box::pin(async move {
+            let mut request = self.client.docs_request(Method::Post);
+
+            request.insert_header(
+                cosmos_headers::HEADER_DOCUMENTDB_ISQUERY,
+                HeaderValue::from_static("true"),
+            );
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_IS_QUERY_PLAN_REQUEST,
+                HeaderValue::from_static("true"),
+            );
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_SUPPORTED_QUERY_FEATURES,
+                HeaderValue::from_static(SUPPORTED_QUERY_FEATURES),
+            );
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_QUERY_VERSION,
+                HeaderValue::from_static(QUERY_VERSION),
+            );
+            request.insert_header(
+                headers::CONTENT_TYPE,
+                HeaderValue::from_static("application/query+json"),
+            );
+            request.insert_headers(&QueryCrossPartition::Yes);
+            if let Some(cl) = &self.consistency_level {
+                request.insert_headers(cl);
+            }
+            request.set_json(&self.query)?;
+
+            let recorder = DiagnosticsRecorder::default();
+            let response = self
+                .client
+                .pipeline()
+                .send(
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
+                    &mut request,
+                )
+                .await?;
+
+            let mut response = GetQueryPlanResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetQueryPlanResponse {
+    pub query_plan: QueryPlan,
+    pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
+}
+
+impl GetQueryPlanResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        Ok(Self {
+            query_plan: body.json().await?,
+            charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
+        })
+    }
+}
+
+/// How the service asks the client to run a query across partitions.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlan {
+    pub query_info: QueryInfo,
+    /// The ranges of effective partition keys the query has to run against.
+    pub query_ranges: Vec<QueryRange>,
+}
+
+/// The parts of a query which have to be executed by the client.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QueryInfo {
+    pub distinct_type: DistinctType,
+    pub top: Option<u64>,
+    pub offset: Option<u64>,
+    pub limit: Option<u64>,
+    pub order_by: Vec<SortOrder>,
+    pub order_by_expressions: Vec<String>,
+    pub group_by_expressions: Vec<String>,
+    pub group_by_aliases: Vec<String>,
+    pub aggregates: Vec<AggregateKind>,
+    /// The aggregate computed by each alias of a `GROUP BY` query, or `None`
+    /// for aliases which are not aggregates.
+    pub group_by_alias_to_aggregate_type: BTreeMap<String, Option<AggregateKind>>,
+    /// The query to send to each partition key range, or an empty string to
+    /// send the original query.
+    pub rewritten_query: String,
+    pub has_select_value: bool,
+}
+
+/// A range of effective partition keys.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRange {
+    pub min: String,
+    pub max: String,
+    #[serde(default = "default_true")]
+    pub is_min_inclusive: bool,
+    #[serde(default)]
+    pub is_max_inclusive: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// How `DISTINCT` has to be applied to the results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum DistinctType {
+    #[default]
+    None,
+    /// Duplicates are adjacent since the results are ordered.
+    Ordered,
+    Unordered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum AggregateKind {
+    Average,
+    Count,
+    Max,
+    Min,
+    Sum,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_query_plan() -> azure_core::Result<()> {
+        let plan: QueryPlan = azure_core::from_json(
+            r#"{
+                "partitionedQueryExecutionInfoVersion": 2,
+                "queryInfo": {
+                    "distinctType": "None",
+                    "top": 10,
+                    "offset": null,
+                    "limit": null,
+                    "orderBy": ["Descending"],
+                    "orderByExpressions": ["c.age"],
+                    "groupByExpressions": [],
+                    "groupByAliases": [],
+                    "aggregates": [],
+                    "groupByAliasToAggregateType": {},
+                    "rewrittenQuery": "SELECT TOP 10 c._rid, [{\"item\": c.age}] AS orderByItems, c AS payload FROM c WHERE ({documentdb-formattableorderbyquery-filter}) ORDER BY c.age DESC",
+                    "hasSelectValue": false
+                },
+                "queryRanges": [{"min": "", "max": "FF", "isMinInclusive": true, "isMaxInclusive": false}]
+            }"#,
+        )?;
+        assert_eq!(plan.query_info.top, Some(10));
+        assert_eq!(plan.query_info.order_by, vec![SortOrder::Descending]);
+        assert_eq!(plan.query_ranges[0].max, "FF");
+        Ok(())
+    }
+}
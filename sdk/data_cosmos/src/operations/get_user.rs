@@ -1,4 +1,4 @@
-use crate::{prelude::*, resources::user::UserResponse as GetUserResponse};
+use crate::{prelude::*, resources::user::UserResponse as GetUserResponse, DiagnosticsRecorder};
 
 operation! {
     GetUser,
@@ -15,16 +15,19 @@ impl GetUserBuilder {
                 request.insert_headers(cl);
             }
             request.set_body(bytes::Bytes::new());
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Users),
+                    recorder.context(&self.context).insert(ResourceType::Users),
                     &mut request,
                 )
                 .await?;
 
-            GetUserResponse::try_from(response).await
+            let mut response = GetUserResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     headers::from_headers::*, prelude::*, resources::Attachment, resources::ResourceType,
-    ResourceQuota,
+    DiagnosticsRecorder, ResourceQuota,
 };
 use azure_core::{
     headers::{
@@ -53,13 +53,19 @@ impl ListAttachmentsBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .cosmos_client()
                     .pipeline()
-                    .send(ctx.clone().insert(ResourceType::Attachments), &mut request)
+                    .send(
+                        recorder.context(&ctx).insert(ResourceType::Attachments),
+                        &mut request,
+                    )
                     .await?;
-                ListAttachmentsResponse::try_from(response).await
+                let mut response = ListAttachmentsResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -100,6 +106,7 @@ pub struct ListAttachmentsResponse {
     pub cosmos_llsn: u64,
     pub session_token: SessionToken,
     pub request_charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -132,6 +139,7 @@ impl ListAttachmentsResponse {
             cosmos_llsn: cosmos_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             request_charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
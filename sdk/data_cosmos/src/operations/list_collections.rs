@@ -1,4 +1,6 @@
-use crate::{headers::from_headers::*, prelude::*, resources::Collection, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::Collection, DiagnosticsRecorder, ResourceQuota,
+};
 use azure_core::{
     headers::{continuation_token_from_headers_optional, session_token_from_headers},
     prelude::*,
@@ -30,12 +32,15 @@ impl ListCollectionsBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .cosmos_client()
-                    .send(request, ctx.clone(), ResourceType::Collections)
+                    .send(request, recorder.context(&ctx), ResourceType::Collections)
                     .await?;
-                ListCollectionsResponse::try_from(response).await
+                let mut response = ListCollectionsResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -57,6 +62,7 @@ pub struct ListCollectionsResponse {
     pub alt_content_path: String,
     pub content_path: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
@@ -90,6 +96,7 @@ impl ListCollectionsResponse {
             alt_content_path: alt_content_path_from_headers(&headers)?,
             content_path: content_path_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
@@ -0,0 +1,111 @@
+use crate::{
+    headers::from_headers::*, prelude::*, resources::Conflict, resources::ResourceType,
+    DiagnosticsRecorder,
+};
+use azure_core::{
+    headers::{
+        continuation_token_from_headers_optional, item_count_from_headers,
+        session_token_from_headers,
+    },
+    prelude::*,
+    Pageable, Response as HttpResponse,
+};
+
+operation! {
+    #[stream]
+    ListConflicts,
+    client: CollectionClient,
+    ?max_item_count: MaxItemCount,
+    ?consistency_level: ConsistencyLevel,
+    ?continuation: Continuation,
+}
+
+impl ListConflictsBuilder {
+    /// Reads the conflict feed of the collection.
+    pub fn into_stream(self) -> ListConflicts {
+        let make_request = move |continuation: Option<Continuation>| {
+            let this = self.clone();
+            let ctx = self.context.clone();
+            async move {
+                let mut request = this.client.cosmos_client().request(
+                    &format!(
+                        "dbs/{}/colls/{}/conflicts",
+                        this.client.database_client().database_name(),
+                        this.client.collection_name()
+                    ),
+                    azure_core::Method::Get,
+                );
+
+                if let Some(cl) = &this.consistency_level {
+                    request.insert_headers(cl);
+                }
+                request.insert_headers(&this.max_item_count.unwrap_or_default());
+
+                let continuation = continuation.or(this.continuation);
+                request.insert_headers(&continuation);
+
+                let recorder = DiagnosticsRecorder::default();
+                let response = this
+                    .client
+                    .pipeline()
+                    .send(
+                        recorder.context(&ctx).insert(ResourceType::Conflicts),
+                        &mut request,
+                    )
+                    .await?;
+                let mut response = ListConflictsResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+pub type ListConflicts = Pageable<ListConflictsResponse, azure_core::error::Error>;
+
+#[derive(Debug, Clone)]
+pub struct ListConflictsResponse {
+    pub rid: String,
+    pub conflicts: Vec<Conflict>,
+    pub continuation_token: Option<Continuation>,
+    pub item_count: u32,
+    pub session_token: String,
+    pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
+    pub activity_id: uuid::Uuid,
+}
+
+impl ListConflictsResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+
+        #[derive(Debug, Deserialize)]
+        struct Response {
+            #[serde(rename = "_rid")]
+            rid: String,
+            #[serde(rename = "Conflicts")]
+            conflicts: Vec<Conflict>,
+        }
+        let response: Response = body.json().await?;
+
+        Ok(Self {
+            rid: response.rid,
+            conflicts: response.conflicts,
+            continuation_token: continuation_token_from_headers_optional(&headers)?,
+            item_count: item_count_from_headers(&headers)?,
+            session_token: session_token_from_headers(&headers)?,
+            charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
+            activity_id: activity_id_from_headers(&headers)?,
+        })
+    }
+}
+
+impl Continuable for ListConflictsResponse {
+    type Continuation = Continuation;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.continuation_token.clone()
+    }
+}
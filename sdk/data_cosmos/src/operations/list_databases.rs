@@ -1,4 +1,6 @@
-use crate::{headers::from_headers::*, prelude::*, resources::Database, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::Database, DiagnosticsRecorder, ResourceQuota,
+};
 use azure_core::{
     headers::{continuation_token_from_headers_optional, session_token_from_headers},
     prelude::*,
@@ -31,13 +33,19 @@ impl ListDatabasesBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .pipeline()
-                    .send(ctx.clone().insert(ResourceType::Databases), &mut request)
+                    .send(
+                        recorder.context(&ctx).insert(ResourceType::Databases),
+                        &mut request,
+                    )
                     .await?;
 
-                ListDatabasesResponse::try_from(response).await
+                let mut response = ListDatabasesResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -54,6 +62,7 @@ pub struct ListDatabasesResponse {
     pub count: u32,
     pub activity_id: uuid::Uuid,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub session_token: String,
     pub last_state_change: OffsetDateTime,
     pub resource_quota: Vec<ResourceQuota>,
@@ -85,6 +94,7 @@ impl ListDatabasesResponse {
             databases: response.databases,
             count: response.count,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             last_state_change: last_state_change_from_headers(&headers)?,
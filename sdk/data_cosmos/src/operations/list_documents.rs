@@ -2,6 +2,7 @@ use crate::headers::from_headers::*;
 use crate::prelude::*;
 use crate::resources::document::{Document, DocumentAttributes};
 use crate::resources::ResourceType;
+use crate::DiagnosticsRecorder;
 use crate::ResourceQuota;
 use azure_core::headers::{
     continuation_token_from_headers_optional, item_count_from_headers, session_token_from_headers,
@@ -52,13 +53,19 @@ impl ListDocumentsBuilder {
                 let continuation = continuation.or(this.continuation);
                 req.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .pipeline()
-                    .send(ctx.clone().insert(ResourceType::Documents), &mut req)
+                    .send(
+                        recorder.context(&ctx).insert(ResourceType::Documents),
+                        &mut req,
+                    )
                     .await?;
 
-                ListDocumentsResponse::try_from(response).await
+                let mut response = ListDocumentsResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -100,6 +107,7 @@ pub struct ListDocumentsResponse<T> {
     pub cosmos_quorum_acked_llsn: Option<u64>,
     pub session_token: SessionToken,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -165,6 +173,7 @@ where
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers_optional(headers)?,
             session_token: session_token_from_headers(headers)?,
             charge: request_charge_from_headers(headers)?,
+            diagnostics: diagnostics_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
             gateway_version: gateway_version_from_headers(headers)?,
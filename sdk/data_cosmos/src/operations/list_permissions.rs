@@ -1,4 +1,7 @@
-use crate::{headers::from_headers::*, prelude::*, resources::Permission, resources::ResourceType};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::Permission, resources::ResourceType,
+    DiagnosticsRecorder,
+};
 use azure_core::{
     headers::{continuation_token_from_headers_optional, session_token_from_headers},
     prelude::*,
@@ -37,12 +40,18 @@ impl ListPermissionsBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .pipeline()
-                    .send(ctx.clone().insert(ResourceType::Permissions), &mut request)
+                    .send(
+                        recorder.context(&ctx).insert(ResourceType::Permissions),
+                        &mut request,
+                    )
                     .await?;
-                ListPermissionsResponse::try_from(response).await
+                let mut response = ListPermissionsResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -56,6 +65,7 @@ pub type ListPermissions = Pageable<ListPermissionsResponse, azure_core::error::
 pub struct ListPermissionsResponse {
     pub permissions: Vec<Permission>,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
     pub content_path: String,
@@ -81,6 +91,7 @@ impl ListPermissionsResponse {
         Ok(Self {
             permissions,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             content_path: content_path_from_headers(&headers)?,
@@ -1,6 +1,6 @@
 use crate::{
     headers::from_headers::*, prelude::*, resources::ResourceType, resources::StoredProcedure,
-    ResourceQuota,
+    DiagnosticsRecorder, ResourceQuota,
 };
 use azure_core::{
     headers::{continuation_token_from_headers_optional, session_token_from_headers},
@@ -41,15 +41,20 @@ impl ListStoredProceduresBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .pipeline()
                     .send(
-                        ctx.clone().insert(ResourceType::StoredProcedures),
+                        recorder
+                            .context(&ctx)
+                            .insert(ResourceType::StoredProcedures),
                         &mut request,
                     )
                     .await?;
-                ListStoredProceduresResponse::try_from(response).await
+                let mut response = ListStoredProceduresResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -63,6 +68,7 @@ pub type ListStoredProcedures = Pageable<ListStoredProceduresResponse, azure_cor
 pub struct ListStoredProceduresResponse {
     pub stored_procedures: Vec<StoredProcedure>,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: String,
     pub last_change: OffsetDateTime,
@@ -87,6 +93,7 @@ impl ListStoredProceduresResponse {
         Ok(Self {
             stored_procedures: response.stored_procedures,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             last_change: last_state_change_from_headers(&headers)?,
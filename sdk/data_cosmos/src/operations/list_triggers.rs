@@ -1,4 +1,7 @@
-use crate::{headers::from_headers::*, prelude::*, resources::ResourceType, ResourceQuota};
+use crate::{
+    headers::from_headers::*, prelude::*, resources::ResourceType, DiagnosticsRecorder,
+    ResourceQuota,
+};
 use azure_core::{
     headers::item_count_from_headers,
     headers::{continuation_token_from_headers_optional, session_token_from_headers},
@@ -41,12 +44,18 @@ impl ListTriggersBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .pipeline()
-                    .send(ctx.clone().insert(ResourceType::Triggers), &mut request)
+                    .send(
+                        recorder.context(&ctx).insert(ResourceType::Triggers),
+                        &mut request,
+                    )
                     .await?;
-                ListTriggersResponse::try_from(response).await
+                let mut response = ListTriggersResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -79,6 +88,7 @@ pub struct ListTriggersResponse {
     pub cosmos_llsn: u64,
     pub session_token: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -122,6 +132,7 @@ impl ListTriggersResponse {
             cosmos_llsn: cosmos_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
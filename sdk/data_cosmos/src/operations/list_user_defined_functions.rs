@@ -1,6 +1,6 @@
 use crate::{
     headers::from_headers::*, prelude::*, resources::ResourceType, resources::UserDefinedFunction,
-    ResourceQuota,
+    DiagnosticsRecorder, ResourceQuota,
 };
 use azure_core::{
     headers::{
@@ -46,15 +46,20 @@ impl ListUserDefinedFunctionsBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .pipeline()
                     .send(
-                        ctx.clone().insert(ResourceType::UserDefinedFunctions),
+                        recorder
+                            .context(&ctx)
+                            .insert(ResourceType::UserDefinedFunctions),
                         &mut request,
                     )
                     .await?;
-                ListUserDefinedFunctionsResponse::try_from(response).await
+                let mut response = ListUserDefinedFunctionsResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -87,6 +92,7 @@ pub struct ListUserDefinedFunctionsResponse {
     pub cosmos_llsn: u64,
     pub session_token: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -130,6 +136,7 @@ impl ListUserDefinedFunctionsResponse {
             cosmos_llsn: cosmos_llsn_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
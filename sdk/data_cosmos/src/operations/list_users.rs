@@ -1,7 +1,10 @@
 use crate::{
-    headers::from_headers::{activity_id_from_headers, request_charge_from_headers},
+    headers::from_headers::{
+        activity_id_from_headers, diagnostics_from_headers, request_charge_from_headers,
+    },
     prelude::*,
     resources::User,
+    DiagnosticsRecorder,
 };
 use azure_core::{
     headers::{continuation_token_from_headers_optional, session_token_from_headers},
@@ -38,12 +41,15 @@ impl ListUsersBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .cosmos_client()
-                    .send(request, ctx.clone(), ResourceType::Users)
+                    .send(request, recorder.context(&ctx), ResourceType::Users)
                     .await?;
-                ListUsersResponse::try_from(response).await
+                let mut response = ListUsersResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+                Ok(response)
             }
         };
 
@@ -59,6 +65,7 @@ pub struct ListUsersResponse {
     pub rid: String,
     pub count: u32,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub session_token: SessionToken,
     pub continuation_token: Option<Continuation>,
@@ -85,6 +92,7 @@ impl ListUsersResponse {
             rid: response.rid,
             count: response.count,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             continuation_token: continuation_token_from_headers_optional(&headers)?,
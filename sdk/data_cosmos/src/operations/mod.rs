@@ -14,6 +14,7 @@ mod create_stored_procedure;
 mod create_user;
 mod delete_attachment;
 mod delete_collection;
+mod delete_conflict;
 mod delete_database;
 mod delete_document;
 mod delete_permission;
@@ -23,14 +24,18 @@ mod delete_user;
 mod delete_user_defined_function;
 mod execute_stored_procedure;
 mod get_attachment;
+mod get_change_feed;
 mod get_collection;
 mod get_database;
+mod get_database_account;
 mod get_document;
 mod get_partition_key_ranges;
 mod get_permission;
+mod get_query_plan;
 mod get_user;
 mod list_attachments;
 mod list_collections;
+mod list_conflicts;
 mod list_databases;
 mod list_documents;
 mod list_permissions;
@@ -40,11 +45,15 @@ mod list_user_defined_functions;
 mod list_users;
 mod patch_document;
 mod query_documents;
+mod query_documents_cross_partition;
+mod read_throughput;
 mod replace_collection;
 mod replace_document;
 mod replace_permission;
 mod replace_stored_procedure;
+mod replace_throughput;
 mod replace_user;
+mod transactional_batch;
 
 pub use create_collection::*;
 pub use create_database::*;
@@ -58,6 +67,7 @@ pub use create_stored_procedure::*;
 pub use create_user::*;
 pub use delete_attachment::*;
 pub use delete_collection::*;
+pub use delete_conflict::*;
 pub use delete_database::*;
 pub use delete_document::*;
 pub use delete_permission::*;
@@ -67,14 +77,18 @@ pub use delete_user::*;
 pub use delete_user_defined_function::*;
 pub use execute_stored_procedure::*;
 pub use get_attachment::*;
+pub use get_change_feed::*;
 pub use get_collection::*;
 pub use get_database::*;
+pub use get_database_account::*;
 pub use get_document::*;
 pub use get_partition_key_ranges::*;
 pub use get_permission::*;
+pub use get_query_plan::*;
 pub use get_user::*;
 pub use list_attachments::*;
 pub use list_collections::*;
+pub use list_conflicts::*;
 pub use list_databases::*;
 pub use list_documents::*;
 pub use list_permissions::*;
@@ -84,8 +98,12 @@ pub use list_user_defined_functions::*;
 pub use list_users::*;
 pub use patch_document::*;
 pub use query_documents::*;
+pub use query_documents_cross_partition::*;
+pub use read_throughput::*;
 pub use replace_collection::*;
 pub use replace_document::*;
 pub use replace_permission::*;
 pub use replace_stored_procedure::*;
+pub use replace_throughput::*;
 pub use replace_user::*;
+pub use transactional_batch::*;
@@ -1,5 +1,6 @@
 use crate::{
-    headers::from_headers::*, prelude::*, resources::document::DocumentAttributes, ResourceQuota,
+    headers::from_headers::*, prelude::*, resources::document::DocumentAttributes,
+    DiagnosticsRecorder, ResourceQuota,
 };
 use azure_core::{headers::session_token_from_headers, Response as HttpResponse, SessionToken};
 use serde::Serialize;
@@ -30,17 +31,22 @@ impl PatchDocumentBuilder {
 
             request.set_json(&patch_request)?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Documents),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
                     &mut request,
                 )
                 .await?;
 
-            PatchDocumentResponse::try_from(response).await
+            let mut response = PatchDocumentResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -139,6 +145,7 @@ pub struct PatchDocumentResponse {
     pub cosmos_quorum_acked_llsn: Option<u64>,
     pub session_token: SessionToken,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -169,6 +176,7 @@ impl PatchDocumentResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers_optional(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
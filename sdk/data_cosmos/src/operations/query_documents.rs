@@ -2,7 +2,7 @@ use crate::{
     headers::from_headers::*,
     prelude::*,
     resources::{document::Query, ResourceType},
-    ResourceQuota,
+    DiagnosticsRecorder, IndexUtilization, QueryMetrics, ResourceQuota,
 };
 use azure_core::{
     from_json, headers,
@@ -15,6 +15,7 @@ use azure_core::{
 };
 use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::sync::{Arc, Mutex};
 use time::OffsetDateTime;
 
 operation! {
@@ -30,6 +31,8 @@ operation! {
     ?query_cross_partition: QueryCrossPartition,
     ?partition_range_id: PartitionRangeId,
     ?continuation: Continuation,
+    ?populate_query_metrics: PopulateQueryMetrics,
+    ?populate_index_metrics: PopulateIndexMetrics,
     #[skip]
     partition_key_serialized: String
 }
@@ -46,8 +49,10 @@ impl QueryDocumentsBuilder {
     where
         T: DeserializeOwned + Send + Sync,
     {
+        let total_query_metrics = Arc::new(Mutex::new(None));
         let make_request = move |continuation: Option<Continuation>| {
             let this = self.clone();
+            let total_query_metrics = total_query_metrics.clone();
             let ctx = self.context.clone();
             async move {
                 let mut request = this.client.cosmos_client().request(
@@ -77,6 +82,8 @@ impl QueryDocumentsBuilder {
                 request.insert_headers(&this.max_item_count.unwrap_or_default());
                 request.insert_headers(&this.query_cross_partition.unwrap_or_default());
                 request.insert_headers(&this.partition_range_id);
+                request.insert_headers(&this.populate_query_metrics);
+                request.insert_headers(&this.populate_index_metrics);
 
                 request.set_json(&this.query)?;
                 if let Some(partition_key_serialized) = this.partition_key_serialized.as_ref() {
@@ -89,12 +96,24 @@ impl QueryDocumentsBuilder {
                 let continuation = continuation.or(this.continuation);
                 request.insert_headers(&continuation);
 
+                let recorder = DiagnosticsRecorder::default();
                 let response = this
                     .client
                     .pipeline()
-                    .send(ctx.clone().insert(ResourceType::Documents), &mut request)
+                    .send(
+                        recorder.context(&ctx).insert(ResourceType::Documents),
+                        &mut request,
+                    )
                     .await?;
-                QueryDocumentsResponse::try_from(response).await
+                let mut response = QueryDocumentsResponse::try_from(response).await?;
+                recorder.complete(&mut response.diagnostics);
+
+                let mut total = total_query_metrics.lock().unwrap();
+                if let Some(metrics) = &response.query_metrics {
+                    *total.get_or_insert_with(QueryMetrics::default) += metrics;
+                }
+                response.total_query_metrics = total.clone();
+                Ok(response)
             }
         };
 
@@ -128,11 +147,21 @@ pub struct QueryDocumentsResponse<T> {
     pub cosmos_quorum_acked_llsn: Option<u64>,
     pub session_token: SessionToken,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
     pub date: OffsetDateTime,
     pub continuation_token: Option<Continuation>,
+    /// The execution metrics of the page, when requested with
+    /// [`QueryDocumentsBuilder::populate_query_metrics`].
+    pub query_metrics: Option<QueryMetrics>,
+    /// The execution metrics summed over this page and every earlier page of
+    /// the stream, so the last page holds the metrics of the whole query.
+    pub total_query_metrics: Option<QueryMetrics>,
+    /// The indexes used by and recommended for the query, when requested with
+    /// [`QueryDocumentsBuilder::populate_index_metrics`].
+    pub index_utilization: Option<IndexUtilization>,
 }
 
 impl<T> QueryDocumentsResponse<T> {
@@ -169,6 +198,13 @@ where
             Vec::new()
         };
 
+        let charge = request_charge_from_headers(&headers)?;
+        let query_metrics =
+            query_metrics_from_headers_optional(&headers).map(|metrics| QueryMetrics {
+                request_charge: charge,
+                ..metrics
+            });
+
         Ok(QueryDocumentsResponse {
             results,
             last_state_change: last_state_change_from_headers(&headers)?,
@@ -189,13 +225,17 @@ where
             cosmos_llsn: cosmos_llsn_from_headers(&headers)?,
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers_optional(&headers)?,
             session_token: session_token_from_headers(&headers)?,
-            charge: request_charge_from_headers(&headers)?,
+            charge,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
             continuation_token: continuation_token_from_headers_optional(&headers)?,
             date: date_from_headers(&headers)?,
             query_response_meta: from_json(&body)?,
+            total_query_metrics: query_metrics.clone(),
+            query_metrics,
+            index_utilization: index_utilization_from_headers_optional(&headers),
         })
     }
 }
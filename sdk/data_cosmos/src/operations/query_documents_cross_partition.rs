@@ -0,0 +1,1317 @@
+use crate::headers::{self as cosmos_headers, from_headers::*, HEADER_SUB_STATUS};
+use crate::operations::PARTITION_KEY_RANGE_GONE;
+use crate::prelude::*;
+use crate::resources::{document::Query, ResourceType};
+use crate::DiagnosticsRecorder;
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use azure_core::headers::{self, continuation_token_from_headers_optional, HeaderValue};
+use azure_core::{prelude::*, Header, Method, Pageable, Response as HttpResponse, StatusCode};
+use futures::future::try_join_all;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The placeholder of rewritten `ORDER BY` queries for the filter used to
+/// resume them. The whole range is read, so it is always replaced with `true`.
+const ORDER_BY_FILTER: &str = "{documentdb-formattableorderbyquery-filter}";
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+operation! {
+    #[stream]
+    QueryDocumentsCrossPartition,
+    client: CollectionClient,
+    query: Query,
+    ?max_item_count: MaxItemCount,
+    ?consistency_level: ConsistencyLevel,
+    ?continuation: Continuation
+}
+
+impl QueryDocumentsCrossPartitionBuilder {
+    /// Runs the query against every partition key range it targets and
+    /// combines the results on the client, as described by the query plan of
+    /// the service.
+    ///
+    /// `ORDER BY` results are merge sorted, aggregates and `GROUP BY` are
+    /// computed once every range has been read, and `DISTINCT`, `OFFSET`,
+    /// `LIMIT` and `TOP` are applied to the combined results.
+    ///
+    /// Each page has a continuation token which can be passed to
+    /// [`continuation`](Self::continuation) to resume the query. Unordered
+    /// `DISTINCT` queries only remove the duplicates seen since the query was
+    /// last resumed.
+    ///
+    /// Ranges which are split or merged while the query runs are replaced
+    /// with the partition key ranges now covering them, which resume from the
+    /// same continuation.
+    pub fn into_stream<T>(self) -> QueryDocumentsCrossPartition<T>
+    where
+        T: DeserializeOwned + Send + Sync,
+    {
+        let make_request = move |state: Option<QueryState>| {
+            let this = self.clone();
+            async move {
+                let mut diagnostics = CosmosDiagnostics::default();
+                let state = match state {
+                    Some(state) => state,
+                    None => this.initial_state(&mut diagnostics).await?,
+                };
+                this.next_page(state, diagnostics).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+
+    async fn initial_state(
+        &self,
+        diagnostics: &mut CosmosDiagnostics,
+    ) -> azure_core::Result<QueryState> {
+        let mut builder = self
+            .client
+            .get_query_plan(self.query.clone())
+            .context(self.context.clone());
+        if let Some(consistency_level) = &self.consistency_level {
+            builder = builder.consistency_level(consistency_level.clone());
+        }
+        let response = builder.await?;
+        diagnostics.merge(response.diagnostics);
+        let QueryPlan {
+            query_info,
+            query_ranges,
+        } = response.query_plan;
+
+        let text = if query_info.rewritten_query.is_empty() {
+            self.query.query()
+        } else {
+            &query_info.rewritten_query
+        }
+        .replace(ORDER_BY_FILTER, "true");
+        let query = Query::with_params(text, self.query.params().to_vec());
+
+        let (ranges, position, last) = match &self.continuation {
+            Some(continuation) => {
+                let continuation: CompositeContinuation =
+                    serde_json::from_str(continuation.value().as_str()).context(
+                        ErrorKind::DataConversion,
+                        "invalid cross partition query continuation",
+                    )?;
+                let ranges = continuation
+                    .ranges
+                    .into_iter()
+                    .map(RangeState::resume)
+                    .collect();
+                (ranges, continuation.position, continuation.last)
+            }
+            None => {
+                let mut ranges: Vec<RangeState> = self
+                    .partition_key_ranges()
+                    .await?
+                    .into_iter()
+                    .filter(|range| query_ranges.iter().any(|query| overlaps(range, query)))
+                    .map(RangeState::new)
+                    .collect();
+                ranges.sort_by(|a, b| a.min.cmp(&b.min));
+                (ranges, 0, None)
+            }
+        };
+
+        if ranges.is_empty() {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "the query does not target any partition key range",
+            ));
+        }
+
+        Ok(QueryState {
+            query,
+            info: query_info,
+            ranges,
+            position,
+            last,
+            seen: HashSet::new(),
+        })
+    }
+
+    async fn partition_key_ranges(&self) -> azure_core::Result<Vec<PartitionKeyRange>> {
+        let mut builder = self
+            .client
+            .get_partition_key_ranges()
+            .context(self.context.clone());
+        if let Some(consistency_level) = &self.consistency_level {
+            builder = builder.consistency_level(consistency_level.clone());
+        }
+        Ok(builder.await?.partition_key_ranges)
+    }
+
+    async fn next_page<T>(
+        &self,
+        mut state: QueryState,
+        mut diagnostics: CosmosDiagnostics,
+    ) -> azure_core::Result<QueryDocumentsCrossPartitionResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut values = Vec::new();
+
+        if state.info.is_aggregate() {
+            let mut items = Vec::new();
+            loop {
+                let QueryState { query, ranges, .. } = &mut state;
+                let drained =
+                    try_join_all(ranges.iter_mut().map(|range| self.drain(query, range))).await?;
+                for (range_items, range_diagnostics) in drained {
+                    items.extend(range_items);
+                    diagnostics.merge(range_diagnostics);
+                }
+                if !state.ranges.iter().any(|range| range.gone) {
+                    break;
+                }
+                state.split(self.partition_key_ranges().await?)?;
+            }
+            for value in aggregate(&state.info, items) {
+                if let Some(value) = state.accept(value) {
+                    values.push(value);
+                }
+                if state.limit_reached() {
+                    break;
+                }
+            }
+            return QueryDocumentsCrossPartitionResponse::new(values, diagnostics, None);
+        }
+
+        let page_size = self
+            .max_item_count
+            .and_then(|max_item_count| max_item_count.count())
+            .map_or(DEFAULT_PAGE_SIZE, |count| count as usize);
+
+        while values.len() < page_size && !state.limit_reached() {
+            let QueryState {
+                query,
+                info,
+                ranges,
+                ..
+            } = &mut state;
+            if info.order_by.is_empty() {
+                // ranges are read one after the other
+                if let Some(range) = ranges.iter_mut().find(|range| !range.is_done()) {
+                    diagnostics.merge(self.fill(query, range).await?);
+                }
+            } else {
+                // the next result can only be picked once every range has one
+                let fills = ranges
+                    .iter_mut()
+                    .filter(|range| range.buffer.is_empty() && !range.exhausted)
+                    .map(|range| self.fill(query, range));
+                for fill_diagnostics in try_join_all(fills).await? {
+                    diagnostics.merge(fill_diagnostics);
+                }
+            }
+            if state.ranges.iter().any(|range| range.gone) {
+                state.split(self.partition_key_ranges().await?)?;
+                continue;
+            }
+
+            match state.take_next() {
+                Some(Some(value)) => {
+                    if let Some(value) = state.accept(value) {
+                        values.push(value);
+                    }
+                }
+                Some(None) => {}
+                None => break,
+            }
+        }
+
+        let next = if state.limit_reached() || state.ranges.iter().all(RangeState::is_done) {
+            None
+        } else {
+            Some(state)
+        };
+        QueryDocumentsCrossPartitionResponse::new(values, diagnostics, next)
+    }
+
+    /// Fetches pages of a range until it has results, is exhausted or is
+    /// gone.
+    async fn fill(
+        &self,
+        query: &Query,
+        range: &mut RangeState,
+    ) -> azure_core::Result<CosmosDiagnostics> {
+        let mut diagnostics = CosmosDiagnostics::default();
+        while range.buffer.is_empty() && !range.exhausted && !range.gone {
+            let token = range.next_token.take();
+            let (documents, continuation, page_diagnostics) =
+                match self.query_range(query, &range.id, token.clone()).await {
+                    Ok(page) => page,
+                    Err(error) => match error.as_http_error() {
+                        Some(http_error)
+                            if http_error.status() == StatusCode::Gone
+                                && http_error.header(&HEADER_SUB_STATUS).is_some_and(
+                                    |sub_status| PARTITION_KEY_RANGE_GONE.contains(&sub_status),
+                                ) =>
+                        {
+                            // the partition key range was split or merged
+                            range.next_token = token;
+                            range.gone = true;
+                            break;
+                        }
+                        _ => return Err(error),
+                    },
+                };
+            diagnostics.merge(page_diagnostics);
+            range.consumed = range.skip.min(documents.len());
+            range.buffer = documents.into_iter().skip(range.skip).collect();
+            range.skip = 0;
+            range.page_token = token;
+            range.exhausted = continuation.is_none();
+            range.next_token = continuation;
+        }
+        Ok(diagnostics)
+    }
+
+    async fn drain(
+        &self,
+        query: &Query,
+        range: &mut RangeState,
+    ) -> azure_core::Result<(Vec<Value>, CosmosDiagnostics)> {
+        let mut items = Vec::new();
+        let mut diagnostics = CosmosDiagnostics::default();
+        while !range.is_done() && !range.gone {
+            diagnostics.merge(self.fill(query, range).await?);
+            items.extend(range.buffer.drain(..));
+        }
+        Ok((items, diagnostics))
+    }
+
+    async fn query_range(
+        &self,
+        query: &Query,
+        partition_key_range_id: &str,
+        continuation: Option<String>,
+    ) -> azure_core::Result<(Vec<Value>, Option<String>, CosmosDiagnostics)> {
+        let mut request = self.client.docs_request(Method::Post);
+        request.insert_header(
+            cosmos_headers::HEADER_DOCUMENTDB_ISQUERY,
+            HeaderValue::from_static("true"),
+        );
+        request.insert_header(
+            headers::CONTENT_TYPE,
+            HeaderValue::from_static("application/query+json"),
+        );
+        request.insert_headers(&QueryCrossPartition::Yes);
+        request.insert_headers(&PartitionRangeId::new(partition_key_range_id.to_owned()));
+        if let Some(consistency_level) = &self.consistency_level {
+            request.insert_headers(consistency_level);
+        }
+        request.insert_headers(&self.max_item_count.unwrap_or_default());
+        request.insert_headers(&continuation.map(Continuation::new));
+        request.set_json(query)?;
+
+        let recorder = DiagnosticsRecorder::default();
+        let response = self
+            .client
+            .pipeline()
+            .send(
+                recorder
+                    .context(&self.context)
+                    .insert(ResourceType::Documents),
+                &mut request,
+            )
+            .await?;
+        let (documents, continuation, mut diagnostics) = range_page_from_response(response).await?;
+        recorder.complete(&mut diagnostics);
+        Ok((documents, continuation, diagnostics))
+    }
+}
+
+async fn range_page_from_response(
+    response: HttpResponse,
+) -> azure_core::Result<(Vec<Value>, Option<String>, CosmosDiagnostics)> {
+    #[derive(Deserialize)]
+    struct Page {
+        #[serde(rename = "Documents")]
+        documents: Vec<Value>,
+    }
+
+    let (_status_code, headers, body) = response.deconstruct();
+    let page: Page = body.json().await?;
+    let continuation = continuation_token_from_headers_optional(&headers)?
+        .map(|continuation| continuation.value().as_str().to_owned());
+    Ok((
+        page.documents,
+        continuation,
+        diagnostics_from_headers(&headers)?,
+    ))
+}
+
+pub type QueryDocumentsCrossPartition<T> =
+    Pageable<QueryDocumentsCrossPartitionResponse<T>, azure_core::error::Error>;
+
+#[derive(Debug, Clone)]
+pub struct QueryDocumentsCrossPartitionResponse<T> {
+    pub results: Vec<T>,
+    /// The request charge of every request made to build the page.
+    pub charge: f64,
+    /// The diagnostics of every request made to build the page.
+    pub diagnostics: CosmosDiagnostics,
+    /// A token to resume the query after this page.
+    pub continuation_token: Option<Continuation>,
+    next: Option<QueryState>,
+}
+
+impl<T> QueryDocumentsCrossPartitionResponse<T>
+where
+    T: DeserializeOwned,
+{
+    fn new(
+        values: Vec<Value>,
+        diagnostics: CosmosDiagnostics,
+        next: Option<QueryState>,
+    ) -> azure_core::Result<Self> {
+        let results = values
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<T>, _>>()?;
+        let continuation_token = next.as_ref().map(QueryState::continuation).transpose()?;
+        Ok(Self {
+            results,
+            charge: diagnostics.request_charge,
+            diagnostics,
+            continuation_token,
+            next,
+        })
+    }
+}
+
+impl<T> Continuable for QueryDocumentsCrossPartitionResponse<T> {
+    type Continuation = QueryState;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next.clone()
+    }
+}
+
+/// The progress of a cross partition query between two pages.
+#[derive(Debug, Clone)]
+pub struct QueryState {
+    query: Query,
+    info: QueryInfo,
+    ranges: Vec<RangeState>,
+    /// The number of distinct results produced, including those skipped by
+    /// `OFFSET`.
+    position: u64,
+    last: Option<Value>,
+    seen: HashSet<String>,
+}
+
+impl QueryState {
+    /// Takes the next result out of the ranges: `None` once the ranges have
+    /// no buffered result, `Some(None)` for `ORDER BY` results without a
+    /// value.
+    fn take_next(&mut self) -> Option<Option<Value>> {
+        let order_by = &self.info.order_by;
+        let candidates = self
+            .ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, range)| !range.buffer.is_empty());
+        let index = if order_by.is_empty() {
+            candidates.map(|(index, _)| index).next()
+        } else {
+            candidates
+                .min_by(|(_, a), (_, b)| compare_order_by(order_by, &a.buffer[0], &b.buffer[0]))
+                .map(|(index, _)| index)
+        }?;
+
+        let range = &mut self.ranges[index];
+        let item = range.buffer.pop_front()?;
+        range.consumed += 1;
+        if order_by.is_empty() {
+            Some(Some(item))
+        } else {
+            Some(match item {
+                Value::Object(mut item) => item.remove("payload"),
+                _ => None,
+            })
+        }
+    }
+
+    /// Applies `DISTINCT` and `OFFSET` to a result.
+    fn accept(&mut self, value: Value) -> Option<Value> {
+        match self.info.distinct_type {
+            DistinctType::Ordered => {
+                if self.last.as_ref() == Some(&value) {
+                    return None;
+                }
+                self.last = Some(value.clone());
+            }
+            DistinctType::Unordered => {
+                if !self.seen.insert(value.to_string()) {
+                    return None;
+                }
+            }
+            DistinctType::None => {}
+        }
+        self.position += 1;
+        if self.position <= self.info.offset.unwrap_or_default() {
+            return None;
+        }
+        Some(value)
+    }
+
+    fn limit_reached(&self) -> bool {
+        match self.info.top.or(self.info.limit) {
+            Some(limit) => self.position >= self.info.offset.unwrap_or_default() + limit,
+            None => false,
+        }
+    }
+
+    /// Replaces the ranges which are gone with the partition key ranges now
+    /// covering them. The new ranges resume from the page the gone range was
+    /// about to read.
+    fn split(&mut self, ranges: Vec<PartitionKeyRange>) -> azure_core::Result<()> {
+        let mut resumed = Vec::with_capacity(self.ranges.len());
+        for range in std::mem::take(&mut self.ranges) {
+            if !range.gone {
+                resumed.push(range);
+                continue;
+            }
+            let mut children: Vec<RangeState> = ranges
+                .iter()
+                .filter(|child| child.min_exclusive < range.max && range.min < child.max_exclusive)
+                .map(|child| RangeState {
+                    next_token: range.next_token.clone(),
+                    ..RangeState::new(child.clone())
+                })
+                .collect();
+            if children.is_empty() {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!(
+                        "no partition key range covers the range of gone partition key range {}",
+                        range.id
+                    )
+                }));
+            }
+            children.sort_by(|a, b| a.min.cmp(&b.min));
+            resumed.extend(children);
+        }
+        self.ranges = resumed;
+        Ok(())
+    }
+
+    fn continuation(&self) -> azure_core::Result<Continuation> {
+        let continuation = CompositeContinuation {
+            ranges: self.ranges.iter().map(RangeState::continuation).collect(),
+            position: self.position,
+            last: self.last.clone(),
+        };
+        Ok(Continuation::new(serde_json::to_string(&continuation)?))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RangeState {
+    id: String,
+    min: String,
+    max: String,
+    /// The token of the page the buffered results come from.
+    page_token: Option<String>,
+    /// The token of the page after the buffered results.
+    next_token: Option<String>,
+    /// The number of results to skip in the next page fetched.
+    skip: usize,
+    /// The number of results taken from the current page.
+    consumed: usize,
+    buffer: VecDeque<Value>,
+    /// Whether there is no page after the buffered results.
+    exhausted: bool,
+    /// Whether the partition key range was split or merged, so the next page
+    /// has to be read from the ranges now covering it.
+    gone: bool,
+}
+
+impl RangeState {
+    fn new(range: PartitionKeyRange) -> Self {
+        Self {
+            id: range.id,
+            min: range.min_exclusive,
+            max: range.max_exclusive,
+            page_token: None,
+            next_token: None,
+            skip: 0,
+            consumed: 0,
+            buffer: VecDeque::new(),
+            exhausted: false,
+            gone: false,
+        }
+    }
+
+    fn resume(continuation: RangeContinuation) -> Self {
+        Self {
+            id: continuation.id,
+            min: continuation.min,
+            max: continuation.max,
+            page_token: None,
+            next_token: continuation.token,
+            skip: continuation.skip,
+            consumed: 0,
+            buffer: VecDeque::new(),
+            exhausted: continuation.done,
+            gone: false,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.buffer.is_empty() && self.exhausted
+    }
+
+    fn continuation(&self) -> RangeContinuation {
+        let (token, skip) = if self.buffer.is_empty() {
+            (self.next_token.clone(), 0)
+        } else {
+            (self.page_token.clone(), self.consumed)
+        };
+        RangeContinuation {
+            id: self.id.clone(),
+            min: self.min.clone(),
+            max: self.max.clone(),
+            token,
+            skip,
+            done: self.is_done(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CompositeContinuation {
+    ranges: Vec<RangeContinuation>,
+    #[serde(default)]
+    position: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RangeContinuation {
+    id: String,
+    min: String,
+    max: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(default)]
+    skip: usize,
+    #[serde(default)]
+    done: bool,
+}
+
+impl QueryInfo {
+    fn is_aggregate(&self) -> bool {
+        !self.aggregates.is_empty()
+            || !self.group_by_expressions.is_empty()
+            || !self.group_by_alias_to_aggregate_type.is_empty()
+    }
+}
+
+fn overlaps(range: &PartitionKeyRange, query_range: &QueryRange) -> bool {
+    let starts_before_max = if query_range.is_max_inclusive {
+        range.min_exclusive <= query_range.max
+    } else {
+        range.min_exclusive < query_range.max
+    };
+    starts_before_max && query_range.min < range.max_exclusive
+}
+
+fn compare_order_by(order_by: &[SortOrder], a: &Value, b: &Value) -> Ordering {
+    let order_by_item = |item: &'_ Value, index: usize| -> Option<Value> {
+        item.get("orderByItems")?.get(index)?.get("item").cloned()
+    };
+    for (index, sort_order) in order_by.iter().enumerate() {
+        let ordering = compare_values(
+            order_by_item(a, index).as_ref(),
+            order_by_item(b, index).as_ref(),
+        );
+        let ordering = match sort_order {
+            SortOrder::Ascending => ordering,
+            SortOrder::Descending => ordering.reverse(),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Orders values the way Cosmos DB does: undefined, null, booleans, numbers,
+/// strings, then arrays and objects.
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    fn rank(value: Option<&Value>) -> u8 {
+        match value {
+            None => 0,
+            Some(Value::Null) => 1,
+            Some(Value::Bool(_)) => 2,
+            Some(Value::Number(_)) => 3,
+            Some(Value::String(_)) => 4,
+            Some(Value::Array(_)) => 5,
+            Some(Value::Object(_)) => 6,
+        }
+    }
+
+    match (a, b) {
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Combines the partial aggregates computed by each partition key range.
+fn aggregate(info: &QueryInfo, items: Vec<Value>) -> Vec<Value> {
+    if info.group_by_expressions.is_empty() && info.group_by_alias_to_aggregate_type.is_empty() {
+        let mut aggregators: Vec<Aggregator> = info
+            .aggregates
+            .iter()
+            .copied()
+            .map(Aggregator::new)
+            .collect();
+        for item in &items {
+            for (index, aggregator) in aggregators.iter_mut().enumerate() {
+                aggregator.add(item.get(index).and_then(|item| item.get("item")));
+            }
+        }
+        let values: Vec<Value> = aggregators
+            .into_iter()
+            .filter_map(Aggregator::result)
+            .collect();
+        return match (info.has_select_value, values.len()) {
+            (true, 1) => values,
+            (true, _) => Vec::new(),
+            (false, _) => vec![Value::Array(values)],
+        };
+    }
+
+    struct Group {
+        values: serde_json::Map<String, Value>,
+        aggregators: Vec<(String, Aggregator)>,
+    }
+
+    let new_group = || Group {
+        values: serde_json::Map::new(),
+        aggregators: info
+            .group_by_alias_to_aggregate_type
+            .iter()
+            .filter_map(|(alias, kind)| kind.map(|kind| (alias.clone(), Aggregator::new(kind))))
+            .collect(),
+    };
+
+    let mut groups: Vec<Group> = Vec::new();
+    let mut group_indexes: HashMap<String, usize> = HashMap::new();
+    for item in items {
+        let key = item
+            .get("groupByItems")
+            .map_or_else(String::new, Value::to_string);
+        let index = *group_indexes.entry(key).or_insert_with(|| {
+            groups.push(new_group());
+            groups.len() - 1
+        });
+        let group = &mut groups[index];
+        let Some(Value::Object(payload)) = item.get("payload") else {
+            continue;
+        };
+        for (alias, aggregator) in &mut group.aggregators {
+            aggregator.add(payload.get(alias).and_then(|value| value.get("item")));
+        }
+        for (alias, value) in payload {
+            if !info
+                .group_by_alias_to_aggregate_type
+                .get(alias)
+                .is_some_and(Option::is_some)
+            {
+                group
+                    .values
+                    .entry(alias.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    // aggregates without GROUP BY have a result even without documents
+    if groups.is_empty() && info.group_by_expressions.is_empty() {
+        groups.push(new_group());
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|group| {
+            let mut values = group.values;
+            for (alias, aggregator) in group.aggregators {
+                if let Some(value) = aggregator.result() {
+                    values.insert(alias, value);
+                }
+            }
+            if info.has_select_value {
+                values.into_iter().next().map(|(_, value)| value)
+            } else {
+                Some(Value::Object(values))
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+enum Aggregator {
+    Count(u64),
+    Sum(Option<Sum>),
+    Min(Option<Value>),
+    Max(Option<Value>),
+    Average { sum: f64, count: f64 },
+}
+
+impl Aggregator {
+    fn new(kind: AggregateKind) -> Self {
+        match kind {
+            AggregateKind::Count => Self::Count(0),
+            AggregateKind::Sum => Self::Sum(None),
+            AggregateKind::Min => Self::Min(None),
+            AggregateKind::Max => Self::Max(None),
+            AggregateKind::Average => Self::Average {
+                sum: 0.0,
+                count: 0.0,
+            },
+        }
+    }
+
+    /// Adds the partial aggregate of a range, which is `None` when the range
+    /// had no value to aggregate.
+    fn add(&mut self, partial: Option<&Value>) {
+        let Some(partial) = partial else {
+            return;
+        };
+        match self {
+            Self::Count(count) => *count += partial.as_u64().unwrap_or_default(),
+            Self::Sum(sum) => {
+                let term = match partial.as_i64() {
+                    Some(value) => Some(Sum::Integer(value)),
+                    None => partial.as_f64().map(Sum::Float),
+                };
+                if let Some(term) = term {
+                    *sum = Some(match *sum {
+                        Some(sum) => sum.add(term),
+                        None => term,
+                    });
+                }
+            }
+            Self::Min(min) => {
+                if let Some(value) = Self::extremum(partial, "min") {
+                    let replace = match min {
+                        Some(min) => compare_values(Some(&value), Some(min)).is_lt(),
+                        None => true,
+                    };
+                    if replace {
+                        *min = Some(value);
+                    }
+                }
+            }
+            Self::Max(max) => {
+                if let Some(value) = Self::extremum(partial, "max") {
+                    let replace = match max {
+                        Some(max) => compare_values(Some(&value), Some(max)).is_gt(),
+                        None => true,
+                    };
+                    if replace {
+                        *max = Some(value);
+                    }
+                }
+            }
+            Self::Average { sum, count } => {
+                if let (Some(partial_sum), Some(partial_count)) = (
+                    partial.get("sum").and_then(Value::as_f64),
+                    partial.get("count").and_then(Value::as_f64),
+                ) {
+                    *sum += partial_sum;
+                    *count += partial_count;
+                }
+            }
+        }
+    }
+
+    /// Minimums and maximums are either sent as a value or, to tell an empty
+    /// range apart, as `{"min": value, "count": count}`.
+    fn extremum(partial: &Value, key: &str) -> Option<Value> {
+        match partial {
+            Value::Object(object) if object.contains_key("count") => {
+                if object.get("count").and_then(Value::as_u64) == Some(0) {
+                    None
+                } else {
+                    object.get(key).cloned()
+                }
+            }
+            value => Some(value.clone()),
+        }
+    }
+
+    fn result(self) -> Option<Value> {
+        match self {
+            Self::Count(count) => Some(count.into()),
+            Self::Sum(sum) => sum.map(|sum| match sum {
+                Sum::Integer(sum) => sum.into(),
+                Sum::Float(sum) => number(sum),
+            }),
+            Self::Min(value) | Self::Max(value) => value,
+            Self::Average { sum, count } => (count > 0.0).then(|| number(sum / count)),
+        }
+    }
+}
+
+/// A sum kept as an integer as long as its terms are integers and it does not
+/// overflow, so large integers keep their precision.
+#[derive(Debug, Clone, Copy)]
+enum Sum {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Sum {
+    fn add(self, term: Self) -> Self {
+        match (self, term) {
+            (Self::Integer(a), Self::Integer(b)) => a
+                .checked_add(b)
+                .map_or_else(|| Self::Float(a as f64 + b as f64), Self::Integer),
+            (a, b) => Self::Float(a.as_f64() + b.as_f64()),
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Integer(value) => value as f64,
+            Self::Float(value) => value,
+        }
+    }
+}
+
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        (value as i64).into()
+    } else {
+        value.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{
+        headers::{HeaderName, Headers},
+        BytesStream, Policy, PolicyResult, Request, Response, TransportOptions,
+    };
+    use futures::StreamExt;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn info(value: Value) -> QueryInfo {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn state(info: QueryInfo, ranges: Vec<Vec<Value>>) -> QueryState {
+        QueryState {
+            query: Query::new("SELECT * FROM c".to_owned()),
+            info,
+            ranges: ranges
+                .into_iter()
+                .enumerate()
+                .map(|(index, items)| RangeState {
+                    buffer: items.into(),
+                    exhausted: true,
+                    ..RangeState::resume(RangeContinuation {
+                        id: index.to_string(),
+                        min: String::new(),
+                        max: "FF".to_owned(),
+                        token: None,
+                        skip: 0,
+                        done: false,
+                    })
+                })
+                .collect(),
+            position: 0,
+            last: None,
+            seen: HashSet::new(),
+        }
+    }
+
+    fn collect(mut state: QueryState) -> Vec<Value> {
+        let mut values = Vec::new();
+        while !state.limit_reached() {
+            match state.take_next() {
+                Some(Some(value)) => values.extend(state.accept(value)),
+                Some(None) => {}
+                None => break,
+            }
+        }
+        values
+    }
+
+    fn order_by_item(age: i64) -> Value {
+        json!({"_rid": "rid", "orderByItems": [{"item": age}], "payload": {"age": age}})
+    }
+
+    #[test]
+    fn order_by_results_are_merge_sorted() {
+        let info = info(json!({"orderBy": ["Descending"], "top": 4, "distinctType": "Ordered"}));
+        let state = state(
+            info,
+            vec![
+                vec![order_by_item(9), order_by_item(5), order_by_item(5)],
+                vec![order_by_item(7), order_by_item(5), order_by_item(1)],
+            ],
+        );
+        assert_eq!(
+            collect(state),
+            vec![
+                json!({"age": 9}),
+                json!({"age": 7}),
+                json!({"age": 5}),
+                json!({"age": 1})
+            ]
+        );
+    }
+
+    #[test]
+    fn offset_and_limit_are_applied_across_ranges() {
+        let info = info(json!({"offset": 1, "limit": 2}));
+        let state = state(
+            info,
+            vec![vec![json!(1), json!(2)], vec![json!(3), json!(4)]],
+        );
+        assert_eq!(collect(state), vec![json!(2), json!(3)]);
+    }
+
+    #[test]
+    fn values_order_like_cosmos() {
+        let mut values = vec![
+            Some(json!("a")),
+            Some(json!(2)),
+            None,
+            Some(json!(true)),
+            Some(json!(null)),
+            Some(json!(1.5)),
+        ];
+        values.sort_by(|a, b| compare_values(a.as_ref(), b.as_ref()));
+        assert_eq!(
+            values,
+            vec![
+                None,
+                Some(json!(null)),
+                Some(json!(true)),
+                Some(json!(1.5)),
+                Some(json!(2)),
+                Some(json!("a"))
+            ]
+        );
+    }
+
+    #[test]
+    fn value_aggregates_are_combined() {
+        let count = info(json!({"aggregates": ["Count"], "hasSelectValue": true}));
+        assert_eq!(
+            aggregate(&count, vec![json!([{"item": 3}]), json!([{"item": 4}])]),
+            vec![json!(7)]
+        );
+
+        let average = info(json!({"aggregates": ["Average"], "hasSelectValue": true}));
+        assert_eq!(
+            aggregate(
+                &average,
+                vec![
+                    json!([{"item": {"sum": 6, "count": 2}}]),
+                    json!([{"item": {"sum": 3, "count": 1}}]),
+                    json!([{"item": null}])
+                ]
+            ),
+            vec![json!(3)]
+        );
+
+        let min = info(json!({"aggregates": ["Min"], "hasSelectValue": true}));
+        assert_eq!(
+            aggregate(
+                &min,
+                vec![
+                    json!([{"item": {"min": 5, "count": 2}}]),
+                    json!([{"item": {"min": null, "count": 0}}]),
+                    json!([{"item": {"min": 2, "count": 1}}])
+                ]
+            ),
+            vec![json!(2)]
+        );
+    }
+
+    #[test]
+    fn integer_sums_keep_their_precision() {
+        let sum = info(json!({"aggregates": ["Sum"], "hasSelectValue": true}));
+        assert_eq!(
+            aggregate(
+                &sum,
+                vec![
+                    json!([{"item": 9_007_199_254_740_993_i64}]),
+                    json!([{"item": 2}]),
+                    json!([{}])
+                ]
+            ),
+            vec![json!(9_007_199_254_740_995_i64)]
+        );
+        assert_eq!(
+            aggregate(
+                &sum,
+                vec![json!([{"item": i64::MAX}]), json!([{"item": 1}])]
+            ),
+            vec![json!(i64::MAX as f64 + 1.0)]
+        );
+        assert_eq!(
+            aggregate(&sum, vec![json!([{"item": 1}]), json!([{"item": 0.5}])]),
+            vec![json!(1.5)]
+        );
+    }
+
+    #[test]
+    fn group_by_results_are_combined() {
+        let info = info(json!({
+            "groupByExpressions": ["c.city"],
+            "groupByAliases": ["city", "total"],
+            "groupByAliasToAggregateType": {"city": null, "total": "Sum"}
+        }));
+        let group = |city: &str, total: i64| {
+            json!({
+                "groupByItems": [{"item": city}],
+                "payload": {"city": city, "total": {"item": total}}
+            })
+        };
+        assert_eq!(
+            aggregate(
+                &info,
+                vec![group("Milan", 1), group("Rome", 2), group("Milan", 3)]
+            ),
+            vec![
+                json!({"city": "Milan", "total": 4}),
+                json!({"city": "Rome", "total": 2})
+            ]
+        );
+    }
+
+    #[test]
+    fn continuation_resumes_the_current_page() -> azure_core::Result<()> {
+        let mut state = state(info(json!({})), vec![vec![json!(1), json!(2)], Vec::new()]);
+        state.ranges[0].page_token = Some("page".to_owned());
+        state.ranges[0].consumed = 1;
+        state.ranges[1].exhausted = false;
+        state.ranges[1].next_token = Some("next".to_owned());
+        state.position = 3;
+
+        let continuation = state.continuation()?;
+        let continuation: CompositeContinuation =
+            serde_json::from_str(continuation.value().as_str())?;
+        assert_eq!(continuation.position, 3);
+        assert_eq!(continuation.ranges[0].token.as_deref(), Some("page"));
+        assert_eq!(continuation.ranges[0].skip, 1);
+        assert_eq!(continuation.ranges[1].token.as_deref(), Some("next"));
+        assert!(!continuation.ranges[1].done);
+        Ok(())
+    }
+
+    fn partition_key_range(id: &str, min: &str, max: &str) -> PartitionKeyRange {
+        serde_json::from_value(range_json(id, min, max)).unwrap()
+    }
+
+    fn range_json(id: &str, min: &str, max: &str) -> Value {
+        json!({
+            "_rid": "rid", "id": id, "_etag": "etag",
+            "minInclusive": min, "maxExclusive": max,
+            "ridPrefix": 0, "_self": "self", "throughputFraction": 0.5,
+            "status": "online", "_ts": 0
+        })
+    }
+
+    #[test]
+    fn gone_ranges_are_replaced_by_their_children() -> azure_core::Result<()> {
+        let mut state = state(info(json!({})), vec![Vec::new(), Vec::new()]);
+        state.ranges[0].max = "80".to_owned();
+        state.ranges[1].min = "80".to_owned();
+        state.ranges[1].gone = true;
+        state.ranges[1].next_token = Some("token".to_owned());
+        state.split(vec![
+            partition_key_range("0", "", "80"),
+            partition_key_range("3", "C0", "FF"),
+            partition_key_range("2", "80", "C0"),
+        ])?;
+
+        let ranges: Vec<_> = state
+            .ranges
+            .iter()
+            .map(|range| (range.id.as_str(), range.next_token.as_deref(), range.gone))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("0", None, false),
+                ("2", Some("token"), false),
+                ("3", Some("token"), false),
+            ]
+        );
+
+        state.ranges[0].gone = true;
+        assert!(state.split(Vec::new()).is_err());
+        Ok(())
+    }
+
+    /// A gateway serving a collection whose single partition key range is
+    /// split in two once its first page has been read.
+    #[derive(Debug, Default)]
+    struct SplittingGateway {
+        split: Mutex<bool>,
+    }
+
+    impl SplittingGateway {
+        fn response(
+            status: StatusCode,
+            headers: &[(&'static str, &'static str)],
+            body: &Value,
+        ) -> Response {
+            let mut response_headers = Headers::new();
+            for (name, value) in [
+                ("content-type", "application/json"),
+                ("date", "Mon, 27 Jun 2022 13:38:48 GMT"),
+                ("server", "gateway"),
+                ("lsn", "1"),
+                ("x-ms-activity-id", "00000000-0000-0000-0000-000000000000"),
+                ("x-ms-alt-content-path", "dbs/db"),
+                ("x-ms-content-path", "rid"),
+                ("x-ms-cosmos-llsn", "1"),
+                ("x-ms-gatewayversion", "version=2.14.0"),
+                ("x-ms-global-committed-lsn", "1"),
+                ("x-ms-item-count", "1"),
+                (
+                    "x-ms-last-state-change-utc",
+                    "Mon, 27 Jun 2022 13:38:48.000 GMT",
+                ),
+                ("x-ms-number-of-read-regions", "0"),
+                ("x-ms-request-charge", "1"),
+                ("x-ms-schemaversion", "1.13"),
+                ("x-ms-serviceversion", "version=2.14.0.0"),
+                ("x-ms-session-token", "0:-1#1"),
+                ("x-ms-transport-request-id", "1"),
+                ("x-ms-xp-role", "1"),
+            ]
+            .iter()
+            .chain(headers)
+            {
+                response_headers.insert(
+                    HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                );
+            }
+            Response::new(
+                status,
+                response_headers,
+                Box::pin(BytesStream::new(body.to_string().into_bytes())),
+            )
+        }
+
+        fn read_ranges(&self) -> Response {
+            let ranges: Vec<_> = if *self.split.lock().unwrap() {
+                vec![range_json("1", "", "80"), range_json("2", "80", "FF")]
+            } else {
+                vec![range_json("0", "", "FF")]
+            };
+            Self::response(
+                StatusCode::Ok,
+                &[],
+                &json!({"_rid": "rid", "PartitionKeyRanges": ranges, "_count": ranges.len()}),
+            )
+        }
+
+        fn query_range(&self, request: &Request) -> azure_core::Result<Response> {
+            let range_id = request
+                .headers()
+                .get_str(&cosmos_headers::HEADER_DOCUMENTDB_PARTITIONRANGEID)?;
+            let continuation = request.headers().get_optional_str(&headers::CONTINUATION);
+            let page = |documents: Value, continuation: &'static [(&'static str, &'static str)]| {
+                Self::response(
+                    StatusCode::Ok,
+                    continuation,
+                    &json!({"Documents": documents}),
+                )
+            };
+            Ok(match (range_id, continuation) {
+                ("0", None) => page(json!([1, 2]), &[("x-ms-continuation", "next")]),
+                ("0", Some(_)) => {
+                    *self.split.lock().unwrap() = true;
+                    Self::response(
+                        StatusCode::Gone,
+                        &[("x-ms-substatus", "1002")],
+                        &json!({"code": "Gone", "message": "partition key range is gone"}),
+                    )
+                }
+                ("1", Some("next")) => page(json!([3]), &[]),
+                ("2", Some("next")) => page(json!([4]), &[]),
+                (range_id, continuation) => {
+                    panic!("unexpected query of range {range_id} from {continuation:?}")
+                }
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for SplittingGateway {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            assert_eq!(0, next.len());
+            let is_query_plan = request
+                .headers()
+                .get_optional_str(&cosmos_headers::HEADER_COSMOS_IS_QUERY_PLAN_REQUEST)
+                .is_some();
+            if is_query_plan {
+                Ok(Self::response(
+                    StatusCode::Ok,
+                    &[],
+                    &json!({
+                        "partitionedQueryExecutionInfoVersion": 2,
+                        "queryInfo": {},
+                        "queryRanges": [{"min": "", "max": "FF", "isMinInclusive": true, "isMaxInclusive": false}],
+                    }),
+                ))
+            } else if request.url().path().ends_with("/pkranges") {
+                Ok(self.read_ranges())
+            } else {
+                self.query_range(request)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn queries_resume_on_the_ranges_of_a_split_range() -> azure_core::Result<()> {
+        let client = CosmosClient::builder(
+            "fake",
+            AuthorizationToken::primary_key("ZmFrZSBjb3Ntb3MgcHJpbWFyeSBrZXkgZm9yIHRlc3Rz")?,
+        )
+        .transport(TransportOptions::new_custom_policy(Arc::new(
+            SplittingGateway::default(),
+        )))
+        .build();
+
+        let mut pages = client
+            .database_client("db")
+            .collection_client("coll")
+            .query_documents_cross_partition("SELECT VALUE c.n FROM c")
+            .into_stream::<u64>();
+        let mut results = Vec::new();
+        while let Some(page) = pages.next().await {
+            results.extend(page?.results);
+        }
+        assert_eq!(results, vec![1, 2, 3, 4]);
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::headers::{self as cosmos_headers, from_headers::*};
+use crate::prelude::*;
+use crate::resources::document::{Param, Query};
+use crate::resources::{ResourceType, ThroughputOffer};
+use crate::DiagnosticsRecorder;
+use azure_core::error::{Error, ErrorKind};
+use azure_core::{
+    headers::{self, HeaderValue},
+    Context, Method, Response as HttpResponse, StatusCode,
+};
+
+operation! {
+    ReadThroughput,
+    client: ThroughputScope,
+}
+
+impl ReadThroughputBuilder {
+    pub fn into_future(self) -> ReadThroughput {
+        Box::pin(async move {
+            let offer = self.client.find_offer(&self.context).await?;
+            let request = self
+                .client
+                .cosmos_client()
+                .request(&format!("offers/{}", offer.rid), Method::Get);
+
+            let recorder = DiagnosticsRecorder::default();
+            let response = self
+                .client
+                .cosmos_client()
+                .send(
+                    request,
+                    recorder.context(&self.context),
+                    ResourceType::Offers,
+                )
+                .await?;
+            let mut response = ReadThroughputResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
+        })
+    }
+}
+
+/// The database or collection whose throughput is managed.
+#[derive(Debug, Clone)]
+pub(crate) enum ThroughputScope {
+    Database(DatabaseClient),
+    Collection(CollectionClient),
+}
+
+impl ThroughputScope {
+    pub(crate) fn cosmos_client(&self) -> &CosmosClient {
+        match self {
+            ThroughputScope::Database(client) => client.cosmos_client(),
+            ThroughputScope::Collection(client) => client.cosmos_client(),
+        }
+    }
+
+    /// Finds the offer of the database or collection.
+    pub(crate) async fn find_offer(
+        &self,
+        context: &Context,
+    ) -> azure_core::Result<ThroughputOffer> {
+        let resource_id = match self {
+            ThroughputScope::Database(client) => {
+                client
+                    .get_database()
+                    .context(context.clone())
+                    .await?
+                    .database
+                    .rid
+            }
+            ThroughputScope::Collection(client) => {
+                client
+                    .get_collection()
+                    .context(context.clone())
+                    .await?
+                    .collection
+                    .rid
+            }
+        };
+
+        let mut request = self.cosmos_client().request("offers", Method::Post);
+        request.insert_header(
+            cosmos_headers::HEADER_DOCUMENTDB_ISQUERY,
+            HeaderValue::from_static("true"),
+        );
+        request.insert_header(
+            headers::CONTENT_TYPE,
+            HeaderValue::from_static("application/query+json"),
+        );
+        request.set_json(&Query::with_params(
+            "SELECT * FROM root WHERE root.offerResourceId = @resourceId".to_owned(),
+            vec![Param::new("@resourceId".to_owned(), resource_id)],
+        ))?;
+
+        let response = self
+            .cosmos_client()
+            .send(request, context.clone(), ResourceType::Offers)
+            .await?;
+
+        #[derive(Deserialize)]
+        struct Offers {
+            #[serde(rename = "Offers")]
+            offers: Vec<ThroughputOffer>,
+        }
+        let Offers { offers } = response.into_body().json().await?;
+        offers.into_iter().next().ok_or_else(|| {
+            Error::with_message(
+                ErrorKind::http_response(StatusCode::NotFound, None),
+                || match self {
+                    ThroughputScope::Database(client) => format!(
+                        "database {} has no throughput provisioned",
+                        client.database_name()
+                    ),
+                    ThroughputScope::Collection(client) => format!(
+                        "collection {} has no dedicated throughput provisioned",
+                        client.collection_name()
+                    ),
+                },
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadThroughputResponse {
+    pub offer: ThroughputOffer,
+    /// The lowest throughput the offer can be replaced with.
+    pub min_throughput: Option<u64>,
+    /// Whether the last change of throughput is still being applied.
+    pub offer_replace_pending: bool,
+    pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
+    pub activity_id: uuid::Uuid,
+}
+
+impl ReadThroughputResponse {
+    /// The throughput provisioned by the offer.
+    pub fn throughput(&self) -> Option<ThroughputProperties> {
+        self.offer.throughput()
+    }
+
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        Ok(Self {
+            offer: body.json().await?,
+            min_throughput: min_throughput_from_headers_optional(&headers)?,
+            offer_replace_pending: offer_replace_pending_from_headers(&headers)?,
+            charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
+            activity_id: activity_id_from_headers(&headers)?,
+        })
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
     headers::from_headers::*,
     prelude::*,
-    resources::collection::{IndexingPolicy, PartitionKey},
+    resources::collection::{
+        AdditionalIndexes, CollectionDefinition, ComputedProperty, ConflictResolutionPolicy,
+        GeospatialConfig, IndexingPolicy, PartitionKey, UniqueKeyPolicy, VectorEmbeddingPolicy,
+    },
+    DiagnosticsRecorder,
 };
 use azure_core::{
     headers::{content_type_from_headers, etag_from_headers, session_token_from_headers},
@@ -14,10 +18,38 @@ operation! {
     client: CollectionClient,
     partition_key: PartitionKey,
     ?indexing_policy: IndexingPolicy,
+    ?additional_indexes: AdditionalIndexes,
+    ?conflict_resolution_policy: ConflictResolutionPolicy,
+    ?unique_key_policy: UniqueKeyPolicy,
+    ?default_ttl: i64,
+    ?analytical_storage_ttl: i64,
+    ?vector_embedding_policy: VectorEmbeddingPolicy,
+    ?computed_properties: Vec<ComputedProperty>,
+    ?geospatial_config: GeospatialConfig,
     ?consistency_level: ConsistencyLevel
 }
 
 impl ReplaceCollectionBuilder {
+    /// Take every setting of the collection from a definition, such as one
+    /// made from the collection as it was read.
+    ///
+    /// The settings missing from the definition are reset to their default.
+    pub fn definition(self, definition: CollectionDefinition) -> Self {
+        Self {
+            partition_key: definition.partition_key,
+            indexing_policy: definition.indexing_policy,
+            additional_indexes: Some(definition.additional_indexes),
+            conflict_resolution_policy: definition.conflict_resolution_policy,
+            unique_key_policy: definition.unique_key_policy,
+            default_ttl: definition.default_ttl,
+            analytical_storage_ttl: definition.analytical_storage_ttl,
+            vector_embedding_policy: definition.vector_embedding_policy,
+            computed_properties: Some(definition.computed_properties),
+            geospatial_config: definition.geospatial_config,
+            ..self
+        }
+    }
+
     pub fn into_future(self) -> ReplaceCollection {
         Box::pin(async move {
             let mut request = self.client.collection_request(azure_core::Method::Put);
@@ -26,37 +58,41 @@ impl ReplaceCollectionBuilder {
                 request.insert_headers(cl);
             }
 
-            let collection = ReplaceCollectionBody {
-                id: self.client.collection_name(),
-                indexing_policy: &self.indexing_policy,
-                partition_key: &self.partition_key,
+            let collection = CollectionDefinition {
+                id: self.client.collection_name().to_owned(),
+                indexing_policy: self.indexing_policy,
+                additional_indexes: self.additional_indexes.unwrap_or_default(),
+                partition_key: self.partition_key,
+                conflict_resolution_policy: self.conflict_resolution_policy,
+                unique_key_policy: self.unique_key_policy,
+                default_ttl: self.default_ttl,
+                analytical_storage_ttl: self.analytical_storage_ttl,
+                vector_embedding_policy: self.vector_embedding_policy,
+                computed_properties: self.computed_properties.unwrap_or_default(),
+                geospatial_config: self.geospatial_config,
             };
 
             request.set_json(&collection)?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Collections),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Collections),
                     &mut request,
                 )
                 .await?;
 
-            ReplaceCollectionResponse::try_from(response).await
+            let mut response = ReplaceCollectionResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
 
-#[derive(Serialize, Debug)]
-struct ReplaceCollectionBody<'a> {
-    pub id: &'a str,
-    #[serde(rename = "indexingPolicy", skip_serializing_if = "Option::is_none")]
-    pub indexing_policy: &'a Option<IndexingPolicy>,
-    #[serde(rename = "partitionKey")]
-    pub partition_key: &'a PartitionKey,
-}
-
 #[derive(Debug, Clone)]
 pub struct ReplaceCollectionResponse {
     pub collection: Collection,
@@ -66,6 +102,7 @@ pub struct ReplaceCollectionResponse {
     pub number_of_read_regions: u32,
     pub etag: String,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub current_write_quorum: u64,
     pub server: String,
     pub collection_partition_index: u64,
@@ -99,6 +136,7 @@ impl ReplaceCollectionResponse {
             schema_version: schema_version_from_headers(&headers)?,
             alt_content_path: alt_content_path_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
@@ -3,7 +3,7 @@ use crate::{
     headers::from_headers::*,
     prelude::*,
     resources::document::DocumentAttributes,
-    ResourceQuota,
+    DiagnosticsRecorder, ResourceQuota,
 };
 use azure_core::{
     headers::session_token_from_headers, prelude::*, Response as HttpResponse, SessionToken,
@@ -50,17 +50,22 @@ impl<D: Serialize + Send + 'static> ReplaceDocumentBuilder<D> {
 
             request.set_json(&self.document)?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .cosmos_client()
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Documents),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
                     &mut request,
                 )
                 .await?;
 
-            ReplaceDocumentResponse::try_from(response).await
+            let mut response = ReplaceDocumentResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -87,6 +92,7 @@ pub struct ReplaceDocumentResponse {
     pub cosmos_quorum_acked_llsn: Option<u64>,
     pub session_token: SessionToken,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
     pub gateway_version: String,
@@ -117,6 +123,7 @@ impl ReplaceDocumentResponse {
             cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers_optional(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             service_version: service_version_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             gateway_version: gateway_version_from_headers(&headers)?,
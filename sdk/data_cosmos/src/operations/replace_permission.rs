@@ -3,6 +3,7 @@ use crate::{
     resources::permission::{
         ExpirySeconds, PermissionMode, PermissionResponse as ReplacePermissionResponse,
     },
+    DiagnosticsRecorder,
 };
 
 operation! {
@@ -38,16 +39,21 @@ impl ReplacePermissionBuilder {
             };
 
             request.set_json(&request_body)?;
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Permissions),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Permissions),
                     &mut request,
                 )
                 .await?;
 
-            ReplacePermissionResponse::try_from(response).await
+            let mut response = ReplacePermissionResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
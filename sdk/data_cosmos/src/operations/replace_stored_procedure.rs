@@ -1,5 +1,6 @@
 use super::CreateStoredProcedureResponse;
 use crate::prelude::*;
+use crate::DiagnosticsRecorder;
 
 operation! {
     ReplaceStoredProcedure,
@@ -31,15 +32,20 @@ impl ReplaceStoredProcedureBuilder {
 
             req.set_json(&body)?;
 
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::StoredProcedures),
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::StoredProcedures),
                     &mut req,
                 )
                 .await?;
-            ReplaceStoredProcedureResponse::try_from(response).await
+            let mut response = ReplaceStoredProcedureResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -0,0 +1,106 @@
+use crate::headers::{self as cosmos_headers, from_headers::*};
+use crate::prelude::*;
+use crate::resources::{ResourceType, ThroughputOffer};
+use crate::DiagnosticsRecorder;
+use azure_core::{headers::HeaderValue, Method, Response as HttpResponse};
+
+operation! {
+    ReplaceThroughput,
+    client: ThroughputScope,
+    throughput: ThroughputProperties,
+}
+
+impl ReplaceThroughputBuilder {
+    /// Replaces the throughput of the offer.
+    ///
+    /// An offer switching between manual and autoscale throughput is first
+    /// migrated, the service picking the throughput it migrates to, and then
+    /// replaced with the requested throughput.
+    pub fn into_future(self) -> ReplaceThroughput {
+        Box::pin(async move {
+            let mut offer = self.client.find_offer(&self.context).await?;
+            let mut charge = 0.0;
+
+            let migrate_to = offer
+                .throughput()
+                .filter(|current| current.is_autoscale() != self.throughput.is_autoscale())
+                .map(|_| self.throughput);
+            if let Some(target) = migrate_to {
+                let migrate = if target.is_autoscale() {
+                    cosmos_headers::HEADER_COSMOS_MIGRATE_OFFER_TO_AUTOPILOT
+                } else {
+                    cosmos_headers::HEADER_COSMOS_MIGRATE_OFFER_TO_MANUAL_THROUGHPUT
+                };
+                let migrated = self.replace(&offer, Some(migrate)).await?;
+                if migrated.throughput() == Some(self.throughput) {
+                    return Ok(migrated);
+                }
+                charge += migrated.charge;
+                offer = migrated.offer;
+            }
+
+            offer.set_throughput(self.throughput);
+            let mut response = self.replace(&offer, None).await?;
+            response.charge += charge;
+            Ok(response)
+        })
+    }
+
+    async fn replace(
+        &self,
+        offer: &ThroughputOffer,
+        migrate: Option<azure_core::headers::HeaderName>,
+    ) -> azure_core::Result<ReplaceThroughputResponse> {
+        let mut request = self
+            .client
+            .cosmos_client()
+            .request(&format!("offers/{}", offer.rid), Method::Put);
+        if let Some(migrate) = migrate {
+            request.insert_header(migrate, HeaderValue::from_static("true"));
+        }
+        request.set_json(offer)?;
+
+        let recorder = DiagnosticsRecorder::default();
+        let response = self
+            .client
+            .cosmos_client()
+            .send(
+                request,
+                recorder.context(&self.context),
+                ResourceType::Offers,
+            )
+            .await?;
+        let mut response = ReplaceThroughputResponse::try_from(response).await?;
+        recorder.complete(&mut response.diagnostics);
+        Ok(response)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplaceThroughputResponse {
+    pub offer: ThroughputOffer,
+    /// Whether the new throughput is still being applied, which can take
+    /// hours when it requires partitions to be split.
+    pub offer_replace_pending: bool,
+    pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
+    pub activity_id: uuid::Uuid,
+}
+
+impl ReplaceThroughputResponse {
+    /// The throughput provisioned by the offer.
+    pub fn throughput(&self) -> Option<ThroughputProperties> {
+        self.offer.throughput()
+    }
+
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        Ok(Self {
+            offer: body.json().await?,
+            offer_replace_pending: offer_replace_pending_from_headers(&headers)?,
+            charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
+            activity_id: activity_id_from_headers(&headers)?,
+        })
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{prelude::*, resources::user::UserResponse as ReplaceUserResponse};
+use crate::{
+    prelude::*, resources::user::UserResponse as ReplaceUserResponse, DiagnosticsRecorder,
+};
 
 operation! {
     ReplaceUser,
@@ -19,16 +21,19 @@ impl ReplaceUserBuilder {
                 id: &self.user_name,
             };
             request.set_json(&body)?;
+            let recorder = DiagnosticsRecorder::default();
             let response = self
                 .client
                 .pipeline()
                 .send(
-                    self.context.clone().insert(ResourceType::Users),
+                    recorder.context(&self.context).insert(ResourceType::Users),
                     &mut request,
                 )
                 .await?;
 
-            ReplaceUserResponse::try_from(response).await
+            let mut response = ReplaceUserResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
         })
     }
 }
@@ -0,0 +1,412 @@
+use crate::headers::{self as cosmos_headers, from_headers::*};
+use crate::prelude::*;
+use crate::resources::ResourceType;
+use crate::DiagnosticsRecorder;
+use azure_core::error::{Error, ErrorKind};
+use azure_core::{
+    headers::{self, session_token_from_headers, HeaderValue},
+    Method, Response as HttpResponse, SessionToken, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// The maximum number of operations of a transactional batch.
+pub const MAX_TRANSACTIONAL_BATCH_OPERATIONS: usize = 100;
+
+operation! {
+    TransactionalBatch,
+    client: CollectionClient,
+    partition_key_serialized: String,
+    operations: Vec<BatchOperation>,
+    ?consistency_level: ConsistencyLevel
+}
+
+impl TransactionalBatchBuilder {
+    /// Add an operation to the batch.
+    pub fn operation(mut self, operation: BatchOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Create a document, failing if it already exists.
+    pub fn create_document<D: Serialize>(self, document: &D) -> azure_core::Result<Self> {
+        Ok(self.operation(BatchOperation::create(document)?))
+    }
+
+    /// Create a document or replace it if it already exists.
+    pub fn upsert_document<D: Serialize>(self, document: &D) -> azure_core::Result<Self> {
+        Ok(self.operation(BatchOperation::upsert(document)?))
+    }
+
+    /// Replace an existing document.
+    pub fn replace_document<S: Into<String>, D: Serialize>(
+        self,
+        id: S,
+        document: &D,
+    ) -> azure_core::Result<Self> {
+        Ok(self.operation(BatchOperation::replace(id, document)?))
+    }
+
+    /// Read a document.
+    pub fn read_document<S: Into<String>>(self, id: S) -> Self {
+        self.operation(BatchOperation::read(id))
+    }
+
+    /// Delete a document.
+    pub fn delete_document<S: Into<String>>(self, id: S) -> Self {
+        self.operation(BatchOperation::delete(id))
+    }
+
+    /// Patch a document.
+    pub fn patch_document<S: Into<String>>(self, id: S, operations: Vec<Operation>) -> Self {
+        self.operation(BatchOperation::patch(id, operations))
+    }
+
+    pub fn into_future(self) -> TransactionalBatch {
+        Box::pin(async move {
+            if self.operations.is_empty()
+                || self.operations.len() > MAX_TRANSACTIONAL_BATCH_OPERATIONS
+            {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!(
+                        "a transactional batch must have between 1 and {MAX_TRANSACTIONAL_BATCH_OPERATIONS} operations, not {}",
+                        self.operations.len()
+                    )
+                }));
+            }
+
+            let mut request = self.client.docs_request(Method::Post);
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_IS_BATCH_REQUEST,
+                HeaderValue::from_static("True"),
+            );
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_BATCH_ATOMIC,
+                HeaderValue::from_static("True"),
+            );
+            request.insert_header(
+                cosmos_headers::HEADER_COSMOS_BATCH_CONTINUE_ON_ERROR,
+                HeaderValue::from_static("False"),
+            );
+            request.insert_header(
+                headers::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            );
+            crate::cosmos_entity::add_as_partition_key_header_serialized(
+                &self.partition_key_serialized,
+                &mut request,
+            );
+            if let Some(cl) = &self.consistency_level {
+                request.insert_headers(cl);
+            }
+            request.set_json(&self.operations)?;
+
+            let recorder = DiagnosticsRecorder::default();
+            let response = self
+                .client
+                .pipeline()
+                .send(
+                    recorder
+                        .context(&self.context)
+                        .insert(ResourceType::Documents),
+                    &mut request,
+                )
+                .await
+                .map_err(TransactionalBatchError::from_error)?;
+
+            let mut response = TransactionalBatchResponse::try_from(response).await?;
+            recorder.complete(&mut response.diagnostics);
+            Ok(response)
+        })
+    }
+}
+
+/// An operation of a transactional batch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOperation {
+    operation_type: BatchOperationType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    if_match: Option<String>,
+    /// The serialized partition key of the document, for the batches of a
+    /// bulk execution which span several logical partitions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) partition_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+enum BatchOperationType {
+    Create,
+    Upsert,
+    Replace,
+    Read,
+    Delete,
+    Patch,
+}
+
+impl BatchOperation {
+    fn new(operation_type: BatchOperationType, id: Option<String>, body: Option<Value>) -> Self {
+        Self {
+            operation_type,
+            id,
+            resource_body: body,
+            if_match: None,
+            partition_key: None,
+        }
+    }
+
+    /// Create a document, failing if it already exists.
+    pub fn create<D: Serialize>(document: &D) -> azure_core::Result<Self> {
+        Ok(Self::new(
+            BatchOperationType::Create,
+            None,
+            Some(serde_json::to_value(document)?),
+        ))
+    }
+
+    /// Create a document or replace it if it already exists.
+    pub fn upsert<D: Serialize>(document: &D) -> azure_core::Result<Self> {
+        Ok(Self::new(
+            BatchOperationType::Upsert,
+            None,
+            Some(serde_json::to_value(document)?),
+        ))
+    }
+
+    /// Replace an existing document.
+    pub fn replace<S: Into<String>, D: Serialize>(id: S, document: &D) -> azure_core::Result<Self> {
+        Ok(Self::new(
+            BatchOperationType::Replace,
+            Some(id.into()),
+            Some(serde_json::to_value(document)?),
+        ))
+    }
+
+    /// Read a document.
+    pub fn read<S: Into<String>>(id: S) -> Self {
+        Self::new(BatchOperationType::Read, Some(id.into()), None)
+    }
+
+    /// Delete a document.
+    pub fn delete<S: Into<String>>(id: S) -> Self {
+        Self::new(BatchOperationType::Delete, Some(id.into()), None)
+    }
+
+    /// Patch a document.
+    pub fn patch<S: Into<String>>(id: S, operations: Vec<Operation>) -> Self {
+        #[derive(Serialize)]
+        struct PatchBody<'a> {
+            operations: &'a [Operation],
+        }
+        // patch operations always serialize
+        let body = serde_json::to_value(PatchBody {
+            operations: &operations,
+        })
+        .ok();
+        Self::new(BatchOperationType::Patch, Some(id.into()), body)
+    }
+
+    /// Only apply the operation if the document has the given ETag.
+    #[must_use]
+    pub fn if_match<S: Into<String>>(mut self, etag: S) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionalBatchResponse {
+    /// The results of the operations, in the order they were added.
+    pub results: Vec<BatchOperationResult>,
+    pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
+    pub session_token: SessionToken,
+    pub activity_id: uuid::Uuid,
+}
+
+impl TransactionalBatchResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        Ok(Self {
+            results: body.json().await?,
+            charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
+            session_token: session_token_from_headers(&headers)?,
+            activity_id: activity_id_from_headers(&headers)?,
+        })
+    }
+}
+
+/// The result of an operation of a transactional batch.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOperationResult {
+    #[serde(deserialize_with = "deserialize_status_code")]
+    pub status_code: StatusCode,
+    #[serde(default)]
+    pub sub_status_code: Option<u32>,
+    #[serde(default, rename = "eTag")]
+    pub etag: Option<String>,
+    #[serde(default)]
+    pub request_charge: f64,
+    #[serde(default)]
+    pub resource_body: Option<Value>,
+    #[serde(
+        default,
+        rename = "retryAfterMilliseconds",
+        deserialize_with = "deserialize_retry_after"
+    )]
+    pub retry_after: Option<Duration>,
+}
+
+impl BatchOperationResult {
+    /// Whether the operation succeeded.
+    pub fn is_success(&self) -> bool {
+        self.status_code.is_success()
+    }
+
+    /// The document returned by the operation, if any.
+    pub fn resource<T: DeserializeOwned>(&self) -> azure_core::Result<Option<T>> {
+        self.resource_body
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+fn deserialize_status_code<'de, D>(deserializer: D) -> Result<StatusCode, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let status_code = u16::deserialize(deserializer)?;
+    StatusCode::try_from(status_code)
+        .map_err(|_| serde::de::Error::custom(format!("invalid status code {status_code}")))
+}
+
+fn deserialize_retry_after<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+}
+
+/// A transactional batch which was rolled back because one of its
+/// operations failed.
+///
+/// It is the source of the error returned for the batch, and can be
+/// retrieved with [`azure_core::error::Error::downcast_ref`].
+#[derive(Debug, Clone)]
+pub struct TransactionalBatchError {
+    pub status_code: StatusCode,
+    /// The index of the operation which made the batch fail.
+    pub failed_operation_index: Option<usize>,
+    /// The result of every operation. The operations which did not fail are
+    /// reported as `424 Failed Dependency`.
+    pub results: Vec<BatchOperationResult>,
+}
+
+impl TransactionalBatchError {
+    /// Converts the error returned for a failed batch, keeping other errors
+    /// as they are.
+    fn from_error(error: Error) -> Error {
+        let Some(http_error) = error.as_http_error() else {
+            return error;
+        };
+        let Ok(results) = serde_json::from_slice::<Vec<BatchOperationResult>>(http_error.body())
+        else {
+            return error;
+        };
+        let status_code = http_error.status();
+        let error_kind = error.kind().clone();
+        let failed_operation_index = results
+            .iter()
+            .position(|result| result.status_code != StatusCode::FailedDependency);
+        let batch_error = TransactionalBatchError {
+            status_code,
+            failed_operation_index,
+            results,
+        };
+        let message = batch_error.to_string();
+        Error::full(error_kind, batch_error, message)
+    }
+
+    /// The result of the operation which made the batch fail.
+    pub fn failed_operation(&self) -> Option<&BatchOperationResult> {
+        self.results.get(self.failed_operation_index?)
+    }
+}
+
+impl std::fmt::Display for TransactionalBatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.failed_operation() {
+            Some(result) => write!(
+                f,
+                "transactional batch rolled back: operation {} failed with status {}",
+                self.failed_operation_index.unwrap_or_default(),
+                result.status_code
+            ),
+            None => write!(
+                f,
+                "transactional batch rolled back with status {}",
+                self.status_code
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransactionalBatchError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_operations() -> azure_core::Result<()> {
+        let operations = vec![
+            BatchOperation::create(&json!({"id": "1", "pk": "a"}))?,
+            BatchOperation::replace("2", &json!({"id": "2", "pk": "a"}))?.if_match("\"etag\""),
+            BatchOperation::read("3"),
+            BatchOperation::patch("4", vec![Operation::remove("/color")]),
+        ];
+        assert_eq!(
+            serde_json::to_value(&operations)?,
+            json!([
+                {"operationType": "Create", "resourceBody": {"id": "1", "pk": "a"}},
+                {"operationType": "Replace", "id": "2", "resourceBody": {"id": "2", "pk": "a"}, "ifMatch": "\"etag\""},
+                {"operationType": "Read", "id": "3"},
+                {"operationType": "Patch", "id": "4", "resourceBody": {"operations": [{"op": "remove", "path": "/color"}]}}
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rolled_back_results() -> azure_core::Result<()> {
+        let results: Vec<BatchOperationResult> = serde_json::from_value(json!([
+            {"statusCode": 424, "requestCharge": 0.0},
+            {"statusCode": 409, "requestCharge": 1.24, "retryAfterMilliseconds": 0},
+            {"statusCode": 424, "requestCharge": 0.0}
+        ]))?;
+        let error = TransactionalBatchError {
+            status_code: StatusCode::Conflict,
+            failed_operation_index: Some(1),
+            results,
+        };
+        assert_eq!(
+            error.failed_operation().map(|result| result.status_code),
+            Some(StatusCode::Conflict)
+        );
+        assert_eq!(
+            error.to_string(),
+            "transactional batch rolled back: operation 1 failed with status 409"
+        );
+        Ok(())
+    }
+}
@@ -12,7 +12,10 @@
 //! ```
 
 #[doc(inline)]
-pub use crate::{ConsistencyLevel, CosmosEntity};
+pub use crate::{
+    BulkExecutor, BulkOperation, ConsistencyLevel, CosmosDiagnostics, CosmosEntity,
+    DiagnosticsThresholds, SessionContainer,
+};
 
 #[doc(inline)]
 pub use crate::clients::*;
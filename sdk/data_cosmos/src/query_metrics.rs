@@ -0,0 +1,312 @@
+//! Diagnostics returned by queries run with metrics enabled.
+
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use std::ops::{Add, AddAssign};
+use std::time::Duration;
+
+/// Execution statistics of a query, as reported by the `x-ms-documentdb-query-metrics` header.
+///
+/// The metrics of a single page are returned with the page. The metrics of
+/// every page of a query can be added up to get the metrics of the whole query:
+///
+/// ```
+/// # use azure_data_cosmos::QueryMetrics;
+/// # let pages: Vec<QueryMetrics> = Vec::new();
+/// let total: QueryMetrics = pages.iter().sum();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryMetrics {
+    /// The number of documents loaded by the query engine.
+    pub retrieved_document_count: u64,
+    /// The size in bytes of the documents loaded by the query engine.
+    pub retrieved_document_size: u64,
+    /// The number of documents returned by the query.
+    pub output_document_count: u64,
+    /// The size in bytes of the documents returned by the query.
+    pub output_document_size: u64,
+    /// The ratio of the retrieved documents that matched the filter.
+    ///
+    /// For aggregated metrics, the ratio is weighted by the number of
+    /// retrieved documents.
+    pub index_hit_ratio: f64,
+    /// The total time spent executing the query.
+    pub total_execution_time: Duration,
+    /// The time spent compiling the query.
+    pub query_compile_time: Duration,
+    /// The time spent building the logical query plan.
+    pub logical_plan_build_time: Duration,
+    /// The time spent building the physical query plan.
+    pub physical_plan_build_time: Duration,
+    /// The time spent optimizing the query.
+    pub query_optimization_time: Duration,
+    /// The time spent in the index looking up documents.
+    pub index_lookup_time: Duration,
+    /// The time spent loading documents.
+    pub document_load_time: Duration,
+    /// The time spent running the query in the query virtual machine.
+    pub vm_execution_time: Duration,
+    /// The time spent in system functions.
+    pub system_function_execution_time: Duration,
+    /// The time spent in user defined functions.
+    pub user_function_execution_time: Duration,
+    /// The time spent writing the results.
+    pub document_write_time: Duration,
+    /// The request units charged for the query.
+    pub request_charge: f64,
+}
+
+impl QueryMetrics {
+    /// Parses the `key=value;...` format of the query metrics header.
+    ///
+    /// Unknown keys are ignored so that metrics added by the service do not
+    /// break parsing.
+    pub(crate) fn from_header(header: &str) -> azure_core::Result<Self> {
+        let mut metrics = Self::default();
+        for token in header.split(';').filter(|token| !token.is_empty()) {
+            let (key, value) = token.split_once('=').ok_or_else(|| {
+                Error::with_message(ErrorKind::DataConversion, || {
+                    format!("invalid query metric '{token}' in '{header}'")
+                })
+            })?;
+            let number = || {
+                value
+                    .parse::<f64>()
+                    .with_context(ErrorKind::DataConversion, || {
+                        format!("invalid value of query metric '{key}' in '{header}'")
+                    })
+            };
+            let millis = || number().map(|ms| Duration::from_secs_f64(ms.max(0.0) / 1000.0));
+            let count = || number().map(|count| count as u64);
+            match key {
+                "retrievedDocumentCount" => metrics.retrieved_document_count = count()?,
+                "retrievedDocumentSize" => metrics.retrieved_document_size = count()?,
+                "outputDocumentCount" => metrics.output_document_count = count()?,
+                "outputDocumentSize" => metrics.output_document_size = count()?,
+                "indexUtilizationRatio" => metrics.index_hit_ratio = number()?,
+                "totalExecutionTimeInMs" => metrics.total_execution_time = millis()?,
+                "queryCompileTimeInMs" => metrics.query_compile_time = millis()?,
+                "queryLogicalPlanBuildTimeInMs" => metrics.logical_plan_build_time = millis()?,
+                "queryPhysicalPlanBuildTimeInMs" => metrics.physical_plan_build_time = millis()?,
+                "queryOptimizationTimeInMs" => metrics.query_optimization_time = millis()?,
+                "indexLookupTimeInMs" => metrics.index_lookup_time = millis()?,
+                "documentLoadTimeInMs" => metrics.document_load_time = millis()?,
+                "VMExecutionTimeInMs" => metrics.vm_execution_time = millis()?,
+                "systemFunctionExecuteTimeInMs" => {
+                    metrics.system_function_execution_time = millis()?;
+                }
+                "userFunctionExecuteTimeInMs" => metrics.user_function_execution_time = millis()?,
+                "documentWriteTimeInMs" | "writeOutputTimeInMs" => {
+                    metrics.document_write_time = millis()?;
+                }
+                _ => trace!("ignoring unknown query metric {}", key),
+            }
+        }
+        Ok(metrics)
+    }
+}
+
+impl AddAssign<&QueryMetrics> for QueryMetrics {
+    fn add_assign(&mut self, other: &QueryMetrics) {
+        let retrieved = self.retrieved_document_count + other.retrieved_document_count;
+        if retrieved > 0 {
+            self.index_hit_ratio = (self.index_hit_ratio * self.retrieved_document_count as f64
+                + other.index_hit_ratio * other.retrieved_document_count as f64)
+                / retrieved as f64;
+        }
+        self.retrieved_document_count = retrieved;
+        self.retrieved_document_size += other.retrieved_document_size;
+        self.output_document_count += other.output_document_count;
+        self.output_document_size += other.output_document_size;
+        self.total_execution_time += other.total_execution_time;
+        self.query_compile_time += other.query_compile_time;
+        self.logical_plan_build_time += other.logical_plan_build_time;
+        self.physical_plan_build_time += other.physical_plan_build_time;
+        self.query_optimization_time += other.query_optimization_time;
+        self.index_lookup_time += other.index_lookup_time;
+        self.document_load_time += other.document_load_time;
+        self.vm_execution_time += other.vm_execution_time;
+        self.system_function_execution_time += other.system_function_execution_time;
+        self.user_function_execution_time += other.user_function_execution_time;
+        self.document_write_time += other.document_write_time;
+        self.request_charge += other.request_charge;
+    }
+}
+
+impl AddAssign for QueryMetrics {
+    fn add_assign(&mut self, other: QueryMetrics) {
+        *self += &other;
+    }
+}
+
+impl Add for QueryMetrics {
+    type Output = QueryMetrics;
+
+    fn add(mut self, other: QueryMetrics) -> QueryMetrics {
+        self += &other;
+        self
+    }
+}
+
+impl<'a> std::iter::Sum<&'a QueryMetrics> for QueryMetrics {
+    fn sum<I: Iterator<Item = &'a QueryMetrics>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut total, metrics| {
+            total += metrics;
+            total
+        })
+    }
+}
+
+impl std::iter::Sum for QueryMetrics {
+    fn sum<I: Iterator<Item = QueryMetrics>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// The indexes a query used and the indexes that would have made it cheaper,
+/// as reported by the `x-ms-cosmos-index-utilization` header.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct IndexUtilization {
+    /// The single indexes the query used.
+    #[serde(default)]
+    pub utilized_single_indexes: Vec<SingleIndexUtilization>,
+    /// The single indexes which are recommended for the query.
+    #[serde(default)]
+    pub potential_single_indexes: Vec<SingleIndexUtilization>,
+    /// The composite indexes the query used.
+    #[serde(default)]
+    pub utilized_composite_indexes: Vec<CompositeIndexUtilization>,
+    /// The composite indexes which are recommended for the query.
+    #[serde(default)]
+    pub potential_composite_indexes: Vec<CompositeIndexUtilization>,
+}
+
+impl IndexUtilization {
+    /// Decodes the base64 encoded JSON of the index utilization header.
+    pub(crate) fn from_header(header: &str) -> azure_core::Result<Self> {
+        let json = azure_core::base64::decode(header).context(
+            ErrorKind::DataConversion,
+            "invalid index utilization header",
+        )?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// Adds the indexes of another page of the query, skipping those already
+    /// reported.
+    pub fn extend(&mut self, other: &IndexUtilization) {
+        fn extend_unique<T: Clone + PartialEq>(indexes: &mut Vec<T>, other: &[T]) {
+            for index in other {
+                if !indexes.contains(index) {
+                    indexes.push(index.clone());
+                }
+            }
+        }
+        extend_unique(
+            &mut self.utilized_single_indexes,
+            &other.utilized_single_indexes,
+        );
+        extend_unique(
+            &mut self.potential_single_indexes,
+            &other.potential_single_indexes,
+        );
+        extend_unique(
+            &mut self.utilized_composite_indexes,
+            &other.utilized_composite_indexes,
+        );
+        extend_unique(
+            &mut self.potential_composite_indexes,
+            &other.potential_composite_indexes,
+        );
+    }
+}
+
+/// A single index used by or recommended for a query.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SingleIndexUtilization {
+    /// The filter expression of the query the index applies to.
+    #[serde(default)]
+    pub filter_expression: String,
+    /// The indexed path, such as `/name/?`.
+    pub index_spec: String,
+    /// Whether the filter can be evaluated exactly with the index.
+    #[serde(default)]
+    pub filter_precise_set: bool,
+    /// Whether the index covers the filter exactly.
+    #[serde(default)]
+    pub index_precise_set: bool,
+    /// How much the index affects the cost of the query.
+    #[serde(default)]
+    pub index_impact_score: Option<String>,
+}
+
+/// A composite index used by or recommended for a query.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompositeIndexUtilization {
+    /// The indexed paths and their order, such as `/name ASC`.
+    pub index_specs: Vec<String>,
+    /// Whether the index covers the filter exactly.
+    #[serde(default)]
+    pub index_precise_set: bool,
+    /// How much the index affects the cost of the query.
+    #[serde(default)]
+    pub index_impact_score: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_sum_query_metrics() -> azure_core::Result<()> {
+        let metrics = QueryMetrics::from_header(
+            "totalExecutionTimeInMs=0.50;queryCompileTimeInMs=0.03;queryLogicalPlanBuildTimeInMs=0.01;\
+             queryPhysicalPlanBuildTimeInMs=0.02;queryOptimizationTimeInMs=0.00;VMExecutionTimeInMs=0.25;\
+             indexLookupTimeInMs=0.12;documentLoadTimeInMs=0.08;systemFunctionExecuteTimeInMs=0.00;\
+             userFunctionExecuteTimeInMs=0.00;retrievedDocumentCount=4;retrievedDocumentSize=1024;\
+             outputDocumentCount=2;outputDocumentSize=512;writeOutputTimeInMs=0.01;indexUtilizationRatio=0.50",
+        )?;
+        assert_eq!(metrics.retrieved_document_count, 4);
+        assert_eq!(metrics.output_document_count, 2);
+        assert_eq!(metrics.index_lookup_time, Duration::from_micros(120));
+        assert_eq!(metrics.vm_execution_time, Duration::from_micros(250));
+
+        let other = QueryMetrics {
+            retrieved_document_count: 4,
+            index_hit_ratio: 1.0,
+            index_lookup_time: Duration::from_micros(80),
+            request_charge: 2.5,
+            ..QueryMetrics::default()
+        };
+        let total: QueryMetrics = [metrics, other].iter().sum();
+        assert_eq!(total.retrieved_document_count, 8);
+        assert_eq!(total.index_hit_ratio, 0.75);
+        assert_eq!(total.index_lookup_time, Duration::from_micros(200));
+        assert_eq!(total.request_charge, 2.5);
+
+        assert!(QueryMetrics::from_header("retrievedDocumentCount=many").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn decode_index_utilization() -> azure_core::Result<()> {
+        let json = r#"{
+            "UtilizedSingleIndexes": [{"FilterExpression": "", "IndexSpec": "/name/?", "FilterPreciseSet": true, "IndexPreciseSet": true, "IndexImpactScore": "High"}],
+            "PotentialSingleIndexes": [],
+            "UtilizedCompositeIndexes": [],
+            "PotentialCompositeIndexes": [{"IndexSpecs": ["/name ASC", "/age ASC"], "IndexPreciseSet": false, "IndexImpactScore": "High"}]
+        }"#;
+        let mut utilization = IndexUtilization::from_header(&azure_core::base64::encode(json))?;
+        assert_eq!(utilization.utilized_single_indexes[0].index_spec, "/name/?");
+        assert_eq!(
+            utilization.potential_composite_indexes[0].index_specs,
+            vec!["/name ASC", "/age ASC"]
+        );
+
+        let page = utilization.clone();
+        utilization.extend(&page);
+        assert_eq!(utilization, page);
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use super::{
+    AdditionalIndexes, Collection, ConflictResolutionPolicy, IndexingPolicy, PartitionKey,
+};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+/// The settings of a [`Collection`] which can be chosen when it is created or
+/// replaced.
+///
+/// A definition can be built from scratch, or from a collection read from
+/// the service to replace it with a few changes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(remote = "Self", rename_all = "camelCase")]
+pub struct CollectionDefinition {
+    /// The collection id
+    pub id: String,
+    /// The indexing policy, the default one of the service when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexing_policy: Option<IndexingPolicy>,
+    /// The composite, spatial and vector indexes of the indexing policy,
+    /// added to the default indexing policy when there is none
+    #[serde(skip)]
+    pub additional_indexes: AdditionalIndexes,
+    /// The partition key
+    pub partition_key: PartitionKey,
+    /// How conflicts between writes to different regions are resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict_resolution_policy: Option<ConflictResolutionPolicy>,
+    /// The properties whose values are unique within a logical partition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_key_policy: Option<UniqueKeyPolicy>,
+    /// How many seconds documents live by default, `-1` for no expiry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_ttl: Option<i64>,
+    /// How many seconds documents live in the analytical store, `-1` for no
+    /// expiry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analytical_storage_ttl: Option<i64>,
+    /// The vector embeddings of the documents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_embedding_policy: Option<VectorEmbeddingPolicy>,
+    /// The properties computed from the other properties of the documents
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub computed_properties: Vec<ComputedProperty>,
+    /// How spatial data is interpreted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geospatial_config: Option<GeospatialConfig>,
+}
+
+impl CollectionDefinition {
+    /// The definition of a collection with the default settings of the
+    /// service.
+    pub fn new<S: Into<String>, P: Into<PartitionKey>>(id: S, partition_key: P) -> Self {
+        Self {
+            id: id.into(),
+            indexing_policy: None,
+            additional_indexes: AdditionalIndexes::default(),
+            partition_key: partition_key.into(),
+            conflict_resolution_policy: None,
+            unique_key_policy: None,
+            default_ttl: None,
+            analytical_storage_ttl: None,
+            vector_embedding_policy: None,
+            computed_properties: Vec::new(),
+            geospatial_config: None,
+        }
+    }
+
+    setters! {
+        indexing_policy: IndexingPolicy => Some(indexing_policy),
+        additional_indexes: AdditionalIndexes => additional_indexes,
+        conflict_resolution_policy: ConflictResolutionPolicy => Some(conflict_resolution_policy),
+        unique_key_policy: UniqueKeyPolicy => Some(unique_key_policy),
+        default_ttl: i64 => Some(default_ttl),
+        analytical_storage_ttl: i64 => Some(analytical_storage_ttl),
+        vector_embedding_policy: VectorEmbeddingPolicy => Some(vector_embedding_policy),
+        computed_properties: Vec<ComputedProperty> => computed_properties,
+        geospatial_config: GeospatialConfig => Some(geospatial_config),
+    }
+}
+
+impl Serialize for CollectionDefinition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.additional_indexes.is_empty() {
+            return CollectionDefinition::serialize(self, serializer);
+        }
+        let mut definition = CollectionDefinition::serialize(self, serde_json::value::Serializer)
+            .map_err(ser::Error::custom)?;
+        self.additional_indexes
+            .add_to_collection(&mut definition)
+            .map_err(ser::Error::custom)?;
+        definition.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CollectionDefinition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let definition = Value::deserialize(deserializer)?;
+        let additional_indexes =
+            AdditionalIndexes::from_collection(&definition).map_err(de::Error::custom)?;
+        Ok(Self {
+            additional_indexes,
+            ..CollectionDefinition::deserialize(definition).map_err(de::Error::custom)?
+        })
+    }
+}
+
+impl From<Collection> for CollectionDefinition {
+    fn from(collection: Collection) -> Self {
+        Self {
+            id: collection.id,
+            indexing_policy: Some(collection.indexing_policy),
+            additional_indexes: collection.additional_indexes,
+            partition_key: collection.parition_key,
+            conflict_resolution_policy: collection.conflict_resolution_policy,
+            unique_key_policy: collection.unique_key_policy,
+            default_ttl: collection.default_ttl,
+            analytical_storage_ttl: collection.analytical_storage_ttl,
+            vector_embedding_policy: collection.vector_embedding_policy,
+            computed_properties: collection.computed_properties,
+            geospatial_config: collection.geospatial_config,
+        }
+    }
+}
+
+/// The properties whose values are unique within a logical partition of a
+/// collection
+///
+/// The unique keys of a collection can only be set when it is created.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UniqueKeyPolicy {
+    /// The unique keys
+    pub unique_keys: Vec<UniqueKey>,
+}
+
+impl<K: Into<UniqueKey>> FromIterator<K> for UniqueKeyPolicy {
+    fn from_iter<I: IntoIterator<Item = K>>(unique_keys: I) -> Self {
+        Self {
+            unique_keys: unique_keys.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A set of paths, such as `/firstName` and `/lastName`, whose combined
+/// values are unique
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+pub struct UniqueKey {
+    /// The paths of the key
+    pub paths: Vec<String>,
+}
+
+impl<T: AsRef<str>> From<T> for UniqueKey {
+    fn from(path: T) -> Self {
+        Self {
+            paths: vec![path.as_ref().to_owned()],
+        }
+    }
+}
+
+/// The vector embeddings of the documents of a collection
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorEmbeddingPolicy {
+    /// The vector embeddings
+    pub vector_embeddings: Vec<VectorEmbedding>,
+}
+
+impl FromIterator<VectorEmbedding> for VectorEmbeddingPolicy {
+    fn from_iter<I: IntoIterator<Item = VectorEmbedding>>(vector_embeddings: I) -> Self {
+        Self {
+            vector_embeddings: vector_embeddings.into_iter().collect(),
+        }
+    }
+}
+
+/// A path of the documents holding a vector
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorEmbedding {
+    /// The path of the vector, such as `/embedding`
+    pub path: String,
+    /// The type of the components of the vector
+    pub data_type: VectorDataType,
+    /// The number of components of the vector
+    pub dimensions: u32,
+    /// How the similarity of two vectors is measured
+    pub distance_function: DistanceFunction,
+}
+
+/// The type of the components of a vector
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum VectorDataType {
+    Float32,
+    Uint8,
+    Int8,
+}
+
+/// How the similarity of two vectors is measured
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum DistanceFunction {
+    Cosine,
+    DotProduct,
+    Euclidean,
+}
+
+/// A property computed from the other properties of the documents, which
+/// can be queried and indexed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+pub struct ComputedProperty {
+    /// The name of the property, such as `cp_lowerName`
+    pub name: String,
+    /// The query computing the property, such as
+    /// `SELECT VALUE LOWER(c.name) FROM c`
+    pub query: String,
+}
+
+impl ComputedProperty {
+    /// A property computed by the query.
+    pub fn new<N: Into<String>, Q: Into<String>>(name: N, query: Q) -> Self {
+        Self {
+            name: name.into(),
+            query: query.into(),
+        }
+    }
+}
+
+/// How the spatial data of a collection is interpreted
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+pub struct GeospatialConfig {
+    /// The type of spatial data
+    #[serde(rename = "type")]
+    pub kind: GeospatialType,
+}
+
+impl From<GeospatialType> for GeospatialConfig {
+    fn from(kind: GeospatialType) -> Self {
+        Self { kind }
+    }
+}
+
+/// The type of the spatial data of a collection
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+pub enum GeospatialType {
+    /// Coordinates on the round earth, in degrees of longitude and latitude
+    Geography,
+    /// Coordinates on a flat plane, within a bounding box
+    Geometry,
+}
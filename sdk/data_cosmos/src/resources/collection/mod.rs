@@ -1,20 +1,31 @@
 //! Utilities for interacting with [`Collection`]s.
 
+mod definition;
 mod offer;
 
 use super::Resource;
+pub use definition::{
+    CollectionDefinition, ComputedProperty, DistanceFunction, GeospatialConfig, GeospatialType,
+    UniqueKey, UniqueKeyPolicy, VectorDataType, VectorEmbedding, VectorEmbeddingPolicy,
+};
 pub use offer::Offer;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
 /// A container of JSON documents and associated JavaScript application logic.
 ///
 /// You can learn more about Collections [here](https://docs.microsoft.com/rest/api/cosmos-db/collections).
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(remote = "Self")]
 pub struct Collection {
     /// The collection id
     pub id: String,
     /// The indexing policy
     #[serde(rename = "indexingPolicy")]
     pub indexing_policy: IndexingPolicy,
+    /// The composite, spatial and vector indexes of the indexing policy
+    #[serde(skip)]
+    pub additional_indexes: AdditionalIndexes,
     /// The partition key
     #[serde(rename = "partitionKey")]
     pub parition_key: PartitionKey,
@@ -45,6 +56,83 @@ pub struct Collection {
     /// the addressable path of the conflicts resource
     #[serde(rename = "_conflicts")]
     pub conflicts: String,
+    /// How conflicts between writes to different regions are resolved
+    #[serde(
+        rename = "conflictResolutionPolicy",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub conflict_resolution_policy: Option<ConflictResolutionPolicy>,
+    /// The properties whose values are unique within a logical partition
+    #[serde(
+        rename = "uniqueKeyPolicy",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unique_key_policy: Option<UniqueKeyPolicy>,
+    /// How many seconds documents live by default, `-1` for no expiry, with
+    /// time to live disabled when absent
+    #[serde(
+        rename = "defaultTtl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_ttl: Option<i64>,
+    /// How many seconds documents live in the analytical store, `-1` for no
+    /// expiry, with the analytical store disabled when absent
+    #[serde(
+        rename = "analyticalStorageTtl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub analytical_storage_ttl: Option<i64>,
+    /// The vector embeddings of the documents
+    #[serde(
+        rename = "vectorEmbeddingPolicy",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub vector_embedding_policy: Option<VectorEmbeddingPolicy>,
+    /// The properties computed from the other properties of the documents
+    #[serde(
+        rename = "computedProperties",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub computed_properties: Vec<ComputedProperty>,
+    /// How spatial data is interpreted
+    #[serde(
+        rename = "geospatialConfig",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub geospatial_config: Option<GeospatialConfig>,
+}
+
+impl Serialize for Collection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.additional_indexes.is_empty() {
+            return Collection::serialize(self, serializer);
+        }
+        let mut collection = Collection::serialize(self, serde_json::value::Serializer)
+            .map_err(ser::Error::custom)?;
+        self.additional_indexes
+            .add_to_collection(&mut collection)
+            .map_err(ser::Error::custom)?;
+        collection.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Collection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let collection = Value::deserialize(deserializer)?;
+        let additional_indexes =
+            AdditionalIndexes::from_collection(&collection).map_err(de::Error::custom)?;
+        Ok(Self {
+            additional_indexes,
+            ..Collection::deserialize(collection).map_err(de::Error::custom)?
+        })
+    }
 }
 
 impl Resource for Collection {
@@ -136,6 +224,9 @@ pub struct PartitionKey {
     pub paths: Vec<String>,
     /// The algorithm used for partitioning
     pub kind: KeyKind,
+    /// The version of the hashing of the partition keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u8>,
 }
 
 impl std::default::Default for PartitionKey {
@@ -143,6 +234,7 @@ impl std::default::Default for PartitionKey {
         Self {
             paths: vec![],
             kind: KeyKind::Hash,
+            version: None,
         }
     }
 }
@@ -155,6 +247,7 @@ where
         Self {
             paths: vec![t.as_ref().to_owned()],
             kind: KeyKind::Hash,
+            version: None,
         }
     }
 }
@@ -172,3 +265,330 @@ pub struct IndexingPolicy {
     /// Array containing document paths to be excluded from indexing
     pub excluded_paths: Vec<ExcludedPath>,
 }
+
+impl std::default::Default for IndexingPolicy {
+    fn default() -> Self {
+        Self {
+            automatic: true,
+            indexing_mode: IndexingMode::Consistent,
+            included_paths: vec![IncludedPath {
+                path: "/*".to_owned(),
+                indexes: None,
+            }],
+            excluded_paths: vec![],
+        }
+    }
+}
+
+/// The composite, spatial and vector indexes of a collection
+///
+/// They belong to the indexing policy of the collection, and are sent and
+/// received in the same `indexingPolicy` object as the [`IndexingPolicy`].
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AdditionalIndexes {
+    /// Indexes on several paths, for queries filtering or sorting on all of them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub composite_indexes: Vec<Vec<CompositePath>>,
+    /// Indexes of the spatial data of paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spatial_indexes: Vec<SpatialIndex>,
+    /// Indexes of the vector embeddings of paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vector_indexes: Vec<VectorIndex>,
+}
+
+impl AdditionalIndexes {
+    /// No additional indexes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setters! {
+        composite_indexes: Vec<Vec<CompositePath>> => composite_indexes,
+        spatial_indexes: Vec<SpatialIndex> => spatial_indexes,
+        vector_indexes: Vec<VectorIndex> => vector_indexes,
+    }
+
+    fn is_empty(&self) -> bool {
+        self.composite_indexes.is_empty()
+            && self.spatial_indexes.is_empty()
+            && self.vector_indexes.is_empty()
+    }
+
+    /// The additional indexes in the indexing policy of a serialized
+    /// collection.
+    fn from_collection(collection: &Value) -> serde_json::Result<Self> {
+        match collection.get("indexingPolicy") {
+            Some(indexing_policy) => Self::deserialize(indexing_policy),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Adds the indexes to the indexing policy of a serialized collection,
+    /// which gets the default indexing policy if it has none.
+    fn add_to_collection(&self, collection: &mut Value) -> serde_json::Result<()> {
+        let Value::Object(collection) = collection else {
+            return Ok(());
+        };
+        let indexing_policy = match collection.entry("indexingPolicy") {
+            serde_json::map::Entry::Occupied(entry) => entry.into_mut(),
+            serde_json::map::Entry::Vacant(entry) => {
+                entry.insert(serde_json::to_value(IndexingPolicy::default())?)
+            }
+        };
+        if let (Value::Object(indexing_policy), Value::Object(indexes)) =
+            (indexing_policy, serde_json::to_value(self)?)
+        {
+            indexing_policy.extend(indexes);
+        }
+        Ok(())
+    }
+}
+
+/// A path of a composite index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+pub struct CompositePath {
+    /// The indexed path, such as `/name`
+    pub path: String,
+    /// The order the path is sorted in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<CompositePathOrder>,
+}
+
+impl CompositePath {
+    /// A path sorted in ascending order.
+    pub fn ascending<P: Into<String>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            order: Some(CompositePathOrder::Ascending),
+        }
+    }
+
+    /// A path sorted in descending order.
+    pub fn descending<P: Into<String>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            order: Some(CompositePathOrder::Descending),
+        }
+    }
+}
+
+/// The order of a path of a composite index
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum CompositePathOrder {
+    Ascending,
+    Descending,
+}
+
+/// The spatial index of a path
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+pub struct SpatialIndex {
+    /// The indexed path, such as `/location/?`
+    pub path: String,
+    /// The types of spatial data indexed
+    pub types: Vec<SpatialType>,
+}
+
+/// A type of spatial data
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum SpatialType {
+    Point,
+    Polygon,
+    LineString,
+    MultiPolygon,
+}
+
+/// The vector index of a path, which must have a vector embedding
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+pub struct VectorIndex {
+    /// The indexed path, such as `/embedding`
+    pub path: String,
+    /// The type of index
+    #[serde(rename = "type")]
+    pub kind: VectorIndexType,
+}
+
+/// The type of a vector index
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VectorIndexType {
+    /// Exact search over vectors of up to 505 dimensions
+    Flat,
+    /// Exact search over compressed vectors of up to 4096 dimensions
+    QuantizedFlat,
+    /// Approximate search over vectors of up to 4096 dimensions
+    #[serde(rename = "diskANN")]
+    DiskAnn,
+}
+
+/// How the conflicts between concurrent writes to different regions of a
+/// multi-write account are resolved
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictResolutionPolicy {
+    /// The way conflicts are resolved
+    pub mode: ConflictResolutionMode,
+    /// The numeric document path compared by `LastWriterWins`, `/_ts` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict_resolution_path: Option<String>,
+    /// The link of the stored procedure resolving `Custom` conflicts, such as
+    /// `dbs/db/colls/coll/sprocs/resolver`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict_resolution_procedure: Option<String>,
+}
+
+impl ConflictResolutionPolicy {
+    /// The write with the highest value at `path`, such as `/_ts`, wins.
+    pub fn last_writer_wins<P: Into<String>>(path: P) -> Self {
+        Self {
+            mode: ConflictResolutionMode::LastWriterWins,
+            conflict_resolution_path: Some(path.into()),
+            conflict_resolution_procedure: None,
+        }
+    }
+
+    /// Conflicts are resolved by a stored procedure of the collection.
+    ///
+    /// The link of the procedure has the form `dbs/{database}/colls/{collection}/sprocs/{procedure}`.
+    pub fn custom<P: Into<String>>(procedure_link: P) -> Self {
+        Self {
+            mode: ConflictResolutionMode::Custom,
+            conflict_resolution_path: None,
+            conflict_resolution_procedure: Some(procedure_link.into()),
+        }
+    }
+
+    /// Conflicts are recorded in the conflict feed of the collection, to be
+    /// resolved by the application.
+    pub fn manual() -> Self {
+        Self {
+            mode: ConflictResolutionMode::Custom,
+            conflict_resolution_path: None,
+            conflict_resolution_procedure: None,
+        }
+    }
+}
+
+/// The way conflicts are resolved
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq, Eq)]
+pub enum ConflictResolutionMode {
+    /// The write with the highest value of the conflict resolution path wins
+    LastWriterWins,
+    /// A stored procedure resolves conflicts, or the application does through
+    /// the conflict feed when there is none
+    Custom,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_conflict_resolution_policies() -> azure_core::Result<()> {
+        assert_eq!(
+            serde_json::to_value(ConflictResolutionPolicy::last_writer_wins("/_ts"))?,
+            json!({"mode": "LastWriterWins", "conflictResolutionPath": "/_ts"})
+        );
+        assert_eq!(
+            serde_json::to_value(ConflictResolutionPolicy::custom(
+                "dbs/db/colls/coll/sprocs/resolver"
+            ))?,
+            json!({
+                "mode": "Custom",
+                "conflictResolutionProcedure": "dbs/db/colls/coll/sprocs/resolver"
+            })
+        );
+        let policy: ConflictResolutionPolicy = serde_json::from_value(json!({
+            "mode": "Custom",
+            "conflictResolutionPath": "",
+            "conflictResolutionProcedure": ""
+        }))?;
+        assert_eq!(policy.mode, ConflictResolutionMode::Custom);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_collection_definition() -> azure_core::Result<()> {
+        let body = json!({
+            "id": "products",
+            "indexingPolicy": {
+                "automatic": true,
+                "indexingMode": "consistent",
+                "includedPaths": [{"path": "/*"}],
+                "excludedPaths": [{"path": "/embedding/*"}],
+                "compositeIndexes": [[
+                    {"path": "/category", "order": "ascending"},
+                    {"path": "/price", "order": "descending"}
+                ]],
+                "spatialIndexes": [{"path": "/location/*", "types": ["Point", "Polygon"]}],
+                "vectorIndexes": [{"path": "/embedding", "type": "diskANN"}]
+            },
+            "partitionKey": {"paths": ["/category"], "kind": "Hash", "version": 2},
+            "uniqueKeyPolicy": {"uniqueKeys": [{"paths": ["/sku"]}]},
+            "defaultTtl": -1,
+            "analyticalStorageTtl": 86400,
+            "vectorEmbeddingPolicy": {"vectorEmbeddings": [{
+                "path": "/embedding",
+                "dataType": "float32",
+                "dimensions": 1536,
+                "distanceFunction": "cosine"
+            }]},
+            "computedProperties": [
+                {"name": "cp_lowerName", "query": "SELECT VALUE LOWER(c.name) FROM c"}
+            ],
+            "geospatialConfig": {"type": "Geography"}
+        });
+
+        let definition: CollectionDefinition = serde_json::from_value(body.clone())?;
+        assert_eq!(serde_json::to_value(&definition)?, body);
+
+        let indexing_policy = IndexingPolicy {
+            excluded_paths: vec!["/embedding/*".to_owned().into()],
+            ..Default::default()
+        };
+        let additional_indexes = AdditionalIndexes::new()
+            .composite_indexes(vec![vec![
+                CompositePath::ascending("/category"),
+                CompositePath::descending("/price"),
+            ]])
+            .spatial_indexes(vec![SpatialIndex {
+                path: "/location/*".to_owned(),
+                types: vec![SpatialType::Point, SpatialType::Polygon],
+            }])
+            .vector_indexes(vec![VectorIndex {
+                path: "/embedding".to_owned(),
+                kind: VectorIndexType::DiskAnn,
+            }]);
+        let built = CollectionDefinition::new(
+            "products",
+            PartitionKey {
+                version: Some(2),
+                ..PartitionKey::from("/category")
+            },
+        )
+        .indexing_policy(indexing_policy)
+        .additional_indexes(additional_indexes)
+        .unique_key_policy(["/sku"].into_iter().collect::<UniqueKeyPolicy>())
+        .default_ttl(-1)
+        .analytical_storage_ttl(86400)
+        .vector_embedding_policy(VectorEmbeddingPolicy::from_iter([VectorEmbedding {
+            path: "/embedding".to_owned(),
+            data_type: VectorDataType::Float32,
+            dimensions: 1536,
+            distance_function: DistanceFunction::Cosine,
+        }]))
+        .computed_properties(vec![ComputedProperty::new(
+            "cp_lowerName",
+            "SELECT VALUE LOWER(c.name) FROM c",
+        )])
+        .geospatial_config(GeospatialType::Geography);
+        assert_eq!(built, definition);
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! Utilities for interacting with [`Conflict`]s.
+
+#![allow(missing_docs)]
+
+use super::Resource;
+use serde::de::DeserializeOwned;
+
+/// A version of a resource which lost a conflict between concurrent writes
+/// to different regions of a multi-write account.
+///
+/// Conflicts are only recorded when the collection's conflict resolution
+/// policy could not resolve them, and stay in the conflict feed of the
+/// collection until they are deleted.
+///
+/// You can learn more about conflicts [here](https://docs.microsoft.com/azure/cosmos-db/conflict-resolution-policies).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conflict {
+    /// The conflict id
+    pub id: String,
+    /// The resource id
+    #[serde(rename = "_rid")]
+    pub rid: String,
+    /// The last updated timestamp
+    #[serde(rename = "_ts")]
+    pub ts: u64,
+    /// The unique uri for this resource
+    #[serde(rename = "_self")]
+    pub _self: String,
+    /// The conflict's etag used for concurrency control
+    #[serde(rename = "_etag")]
+    pub etag: String,
+    /// The type of the conflicting resource, such as `document`
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    /// The write which conflicted
+    #[serde(rename = "operationType")]
+    pub operation_type: ConflictOperation,
+    /// The resource id of the conflicting resource
+    #[serde(rename = "resourceId")]
+    pub resource_id: String,
+    /// The conflicting version of the resource, as JSON
+    #[serde(default)]
+    pub content: String,
+    /// The logical sequence number of the conflicting write
+    #[serde(rename = "conflict_lsn", default)]
+    pub conflict_lsn: Option<u64>,
+}
+
+impl Conflict {
+    /// The conflicting version of the resource.
+    pub fn content<T: DeserializeOwned>(&self) -> azure_core::Result<T> {
+        Ok(serde_json::from_str(&self.content)?)
+    }
+}
+
+impl Resource for Conflict {
+    fn uri(&self) -> &str {
+        &self._self
+    }
+}
+
+impl Resource for &Conflict {
+    fn uri(&self) -> &str {
+        &self._self
+    }
+}
+
+create_enum!(
+    ConflictOperation,
+    (Create, "create"),
+    (Replace, "replace"),
+    (Delete, "delete")
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_conflict() -> azure_core::Result<()> {
+        let conflict: Conflict = serde_json::from_str(
+            r#"{
+                "id": "k6d9ALgBmD8BAAAAAAAAQA==",
+                "_rid": "k6d9ALgBmD8BAAAAAAAAQA==",
+                "_self": "dbs/k6d9AA==/colls/k6d9ALgBmD8=/conflicts/k6d9ALgBmD8BAAAAAAAAQA==/",
+                "_etag": "\"00000000-0000-0000-9a15-3bdb4d6401d7\"",
+                "_ts": 1692368000,
+                "resourceType": "document",
+                "operationType": "replace",
+                "resourceId": "k6d9ALgBmD8BAAAAAAAAAA==",
+                "content": "{\"id\":\"item\",\"pk\":\"a\",\"_ts\":1692367999}",
+                "conflict_lsn": 42
+            }"#,
+        )?;
+        assert_eq!(conflict.operation_type, ConflictOperation::Replace);
+        assert_eq!(conflict.conflict_lsn, Some(42));
+
+        #[derive(Deserialize)]
+        struct Item {
+            id: String,
+            pk: String,
+        }
+        let item: Item = conflict.content()?;
+        assert_eq!((item.id.as_str(), item.pk.as_str()), ("item", "a"));
+        Ok(())
+    }
+}
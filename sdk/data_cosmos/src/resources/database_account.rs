@@ -0,0 +1,31 @@
+//! Utilities for interacting with the [`DatabaseAccount`].
+
+/// The Cosmos account, and the regions it is replicated to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DatabaseAccount {
+    /// The account name
+    pub id: String,
+    /// The resource id
+    #[serde(rename = "_rid")]
+    pub rid: String,
+    /// The regions accepting writes, the first one being the write region of
+    /// single-region write accounts
+    #[serde(rename = "writableLocations", default)]
+    pub writable_locations: Vec<AccountRegion>,
+    /// The regions accepting reads
+    #[serde(rename = "readableLocations", default)]
+    pub readable_locations: Vec<AccountRegion>,
+    /// Whether every region accepts writes
+    #[serde(rename = "enableMultipleWriteLocations", default)]
+    pub enable_multiple_write_locations: bool,
+}
+
+/// A region of a [`DatabaseAccount`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AccountRegion {
+    /// The name of the region, such as `West US`
+    pub name: String,
+    /// The endpoint of the account in the region
+    #[serde(rename = "databaseAccountEndpoint")]
+    pub database_account_endpoint: String,
+}
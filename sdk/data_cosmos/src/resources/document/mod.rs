@@ -141,6 +141,80 @@ impl Header for ParallelizeCrossPartition {
     }
 }
 
+/// Whether to return the execution metrics of a query
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopulateQueryMetrics {
+    Yes,
+    No,
+}
+
+impl PopulateQueryMetrics {
+    fn as_bool_str(&self) -> &str {
+        match self {
+            Self::Yes => "true",
+            Self::No => "false",
+        }
+    }
+}
+
+impl From<bool> for PopulateQueryMetrics {
+    fn from(b: bool) -> Self {
+        if b {
+            Self::Yes
+        } else {
+            Self::No
+        }
+    }
+}
+
+impl Header for PopulateQueryMetrics {
+    fn name(&self) -> HeaderName {
+        headers::HEADER_DOCUMENTDB_POPULATEQUERYMETRICS
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.as_bool_str().to_owned().into()
+    }
+}
+
+/// Whether to return the indexes used by and recommended for a query
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopulateIndexMetrics {
+    Yes,
+    No,
+}
+
+impl PopulateIndexMetrics {
+    fn as_bool_str(&self) -> &str {
+        match self {
+            Self::Yes => "true",
+            Self::No => "false",
+        }
+    }
+}
+
+impl From<bool> for PopulateIndexMetrics {
+    fn from(b: bool) -> Self {
+        if b {
+            Self::Yes
+        } else {
+            Self::No
+        }
+    }
+}
+
+impl Header for PopulateIndexMetrics {
+    fn name(&self) -> HeaderName {
+        headers::HEADER_COSMOS_POPULATEINDEXMETRICS
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.as_bool_str().to_owned().into()
+    }
+}
+
 /// Whether the operation is an upsert
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(missing_docs)]
@@ -3,6 +3,7 @@
 //! You can learn about the Cosmos DB resource model [here](https://docs.microsoft.com/azure/cosmos-db/account-databases-containers-items).
 
 pub mod collection;
+pub mod conflict;
 pub mod document;
 pub mod permission;
 pub mod stored_procedure;
@@ -11,6 +12,8 @@ pub mod user;
 
 mod attachment;
 mod database;
+mod database_account;
+mod offer;
 mod user_defined_function;
 
 #[doc(inline)]
@@ -18,10 +21,19 @@ pub use attachment::Attachment;
 #[doc(inline)]
 pub use collection::Collection;
 #[doc(inline)]
+pub use conflict::Conflict;
+#[doc(inline)]
 pub use database::Database;
 #[doc(inline)]
+pub use database_account::{AccountRegion, DatabaseAccount};
+#[doc(inline)]
 pub use document::Document;
 #[doc(inline)]
+pub use offer::{
+    AutoscaleSettings, MinimumThroughputParameters, OfferContent, ThroughputOffer,
+    ThroughputProperties,
+};
+#[doc(inline)]
 pub use permission::Permission;
 #[doc(inline)]
 pub use stored_procedure::StoredProcedure;
@@ -62,4 +74,7 @@ pub(crate) enum ResourceType {
     PartitionKeyRanges,
     UserDefinedFunctions,
     Triggers,
+    Conflicts,
+    Offers,
+    DatabaseAccount,
 }
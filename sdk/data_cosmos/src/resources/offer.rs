@@ -0,0 +1,213 @@
+//! Utilities for interacting with the throughput [`ThroughputOffer`]s of databases and collections.
+
+use super::Resource;
+use serde_json::{Map, Value};
+
+/// The throughput provisioned for a database or a collection.
+///
+/// You can learn more about offers [here](https://docs.microsoft.com/rest/api/cosmos-db/offers).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ThroughputOffer {
+    /// The offer id
+    pub id: String,
+    /// The resource id
+    #[serde(rename = "_rid")]
+    pub rid: String,
+    /// The last updated timestamp
+    #[serde(rename = "_ts")]
+    pub ts: u64,
+    /// The resource's uri
+    #[serde(rename = "_self")]
+    pub _self: String,
+    /// The resource's etag used for concurrency control
+    #[serde(rename = "_etag")]
+    pub etag: String,
+    /// The version of the offer, `V2` for offers with a custom throughput
+    #[serde(rename = "offerVersion")]
+    pub offer_version: String,
+    /// The legacy performance level, `Invalid` for `V2` offers
+    #[serde(rename = "offerType")]
+    pub offer_type: String,
+    /// The uri of the database or collection the offer applies to
+    pub resource: String,
+    /// The resource id of the database or collection the offer applies to
+    #[serde(rename = "offerResourceId")]
+    pub offer_resource_id: String,
+    /// The throughput settings
+    pub content: OfferContent,
+}
+
+impl ThroughputOffer {
+    /// The throughput provisioned by the offer.
+    pub fn throughput(&self) -> Option<ThroughputProperties> {
+        match (
+            &self.content.offer_autopilot_settings,
+            self.content.offer_throughput,
+        ) {
+            (Some(autoscale), _) => Some(ThroughputProperties::Autoscale {
+                max_throughput: autoscale.max_throughput,
+            }),
+            (None, Some(throughput)) => Some(ThroughputProperties::Manual(throughput)),
+            (None, None) => None,
+        }
+    }
+
+    /// Change the throughput provisioned by the offer.
+    ///
+    /// The offer still has to be replaced for the change to take effect.
+    pub fn set_throughput(&mut self, throughput: ThroughputProperties) {
+        match throughput {
+            ThroughputProperties::Manual(throughput) => {
+                self.content.offer_throughput = Some(throughput);
+                self.content.offer_autopilot_settings = None;
+            }
+            ThroughputProperties::Autoscale { max_throughput } => {
+                // the service computes the current throughput of autoscale offers
+                self.content.offer_throughput = None;
+                match &mut self.content.offer_autopilot_settings {
+                    Some(autoscale) => autoscale.max_throughput = max_throughput,
+                    None => {
+                        self.content.offer_autopilot_settings =
+                            Some(AutoscaleSettings::new(max_throughput));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Resource for ThroughputOffer {
+    fn uri(&self) -> &str {
+        &self._self
+    }
+}
+
+impl Resource for &ThroughputOffer {
+    fn uri(&self) -> &str {
+        &self._self
+    }
+}
+
+/// The throughput settings of a [`ThroughputOffer`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferContent {
+    /// The request units per second provisioned, or currently scaled to for
+    /// autoscale offers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offer_throughput: Option<u64>,
+    /// The autoscale settings, for autoscale offers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offer_autopilot_settings: Option<AutoscaleSettings>,
+    /// What the minimum throughput allowed for the offer is computed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offer_minimum_throughput_parameters: Option<MinimumThroughputParameters>,
+    /// The other settings, sent back as they are when the offer is replaced
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The settings of an autoscale [`ThroughputOffer`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoscaleSettings {
+    /// The request units per second the offer scales up to
+    pub max_throughput: u64,
+    /// The other settings, sent back as they are when the offer is replaced
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+impl AutoscaleSettings {
+    /// Autoscale settings scaling up to the given throughput.
+    pub fn new(max_throughput: u64) -> Self {
+        Self {
+            max_throughput,
+            other: Map::new(),
+        }
+    }
+}
+
+/// What the minimum throughput allowed for a [`ThroughputOffer`] is computed
+/// from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MinimumThroughputParameters {
+    /// The highest throughput ever provisioned for the offer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_throughput_ever_provisioned: Option<u64>,
+    /// The highest storage ever used by the resource of the offer
+    #[serde(
+        default,
+        rename = "maxConsumedStorageEverInKB",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_consumed_storage_ever_in_kb: Option<u64>,
+}
+
+/// The throughput provisioned for a database or a collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThroughputProperties {
+    /// A fixed number of request units per second
+    Manual(u64),
+    /// A number of request units per second scaling between 10% of
+    /// `max_throughput` and `max_throughput` depending on the usage
+    Autoscale {
+        /// The request units per second the throughput scales up to
+        max_throughput: u64,
+    },
+}
+
+impl ThroughputProperties {
+    /// Whether the throughput scales automatically.
+    pub fn is_autoscale(&self) -> bool {
+        matches!(self, ThroughputProperties::Autoscale { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn switch_to_autoscale() -> azure_core::Result<()> {
+        let mut offer: ThroughputOffer = serde_json::from_value(json!({
+            "resource": "dbs/rVJzAA==/colls/rVJzAMHmAgA=/",
+            "offerType": "Invalid",
+            "offerResourceId": "rVJzAMHmAgA=",
+            "offerVersion": "V2",
+            "content": {
+                "offerThroughput": 400,
+                "offerIsRUPerMinuteThroughputEnabled": false,
+                "offerMinimumThroughputParameters": {
+                    "maxThroughputEverProvisioned": 400,
+                    "maxConsumedStorageEverInKB": 0
+                }
+            },
+            "id": "ZpIS",
+            "_rid": "ZpIS",
+            "_self": "offers/ZpIS/",
+            "_etag": "\"00000000-0000-0000-9a15-3bdb4d6401d7\"",
+            "_ts": 1_692_368_000
+        }))?;
+        assert_eq!(offer.throughput(), Some(ThroughputProperties::Manual(400)));
+
+        offer.set_throughput(ThroughputProperties::Autoscale {
+            max_throughput: 4000,
+        });
+        assert_eq!(
+            serde_json::to_value(&offer.content)?,
+            json!({
+                "offerAutopilotSettings": {"maxThroughput": 4000},
+                "offerIsRUPerMinuteThroughputEnabled": false,
+                "offerMinimumThroughputParameters": {
+                    "maxThroughputEverProvisioned": 400,
+                    "maxConsumedStorageEverInKB": 0
+                }
+            })
+        );
+        assert!(offer.throughput().is_some_and(|t| t.is_autoscale()));
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use super::Permission;
 use crate::headers::from_headers::*;
+use crate::CosmosDiagnostics;
 use azure_core::{
     headers::{etag_from_headers, session_token_from_headers},
     Response as HttpResponse,
@@ -9,6 +10,7 @@ use azure_core::{
 pub struct PermissionResponse {
     pub permission: Permission,
     pub charge: f64,
+    pub diagnostics: CosmosDiagnostics,
     pub activity_id: uuid::Uuid,
     pub etag: String,
     pub session_token: String,
@@ -23,6 +25,7 @@ impl PermissionResponse {
         Ok(Self {
             permission: body.json().await?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             etag: etag_from_headers(&headers)?,
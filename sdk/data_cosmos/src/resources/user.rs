@@ -2,6 +2,7 @@
 
 use super::Resource;
 use crate::headers::from_headers::*;
+use crate::CosmosDiagnostics;
 use azure_core::{
     from_json,
     headers::{etag_from_headers, session_token_from_headers},
@@ -56,6 +57,8 @@ pub struct UserResponse {
     pub user: User,
     /// The charge for this request from the Cosmos service
     pub charge: f64,
+    /// The diagnostics of the request
+    pub diagnostics: CosmosDiagnostics,
     /// Represents a unique identifier for the operation
     pub activity_id: uuid::Uuid,
     /// The etag for the resource retrieved
@@ -72,6 +75,7 @@ impl UserResponse {
         Ok(Self {
             user: body.json().await?,
             charge: request_charge_from_headers(&headers)?,
+            diagnostics: diagnostics_from_headers(&headers)?,
             activity_id: activity_id_from_headers(&headers)?,
             session_token: session_token_from_headers(&headers)?,
             etag: etag_from_headers(&headers)?,
@@ -0,0 +1,239 @@
+use azure_core::error::{ErrorKind, ResultExt};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+/// The session tokens of the collections of an account.
+///
+/// A `CosmosClient` records the session token of every response of a
+/// collection, and sends it along with the reads of the collection, so that
+/// they see the writes made by the client with session consistency. Tokens are
+/// kept for each partition key range of a collection.
+///
+/// The container is shared by the clones of a client. It can be exported, to
+/// continue the sessions in another process with
+/// [`CosmosClientBuilder::session_container`](crate::prelude::CosmosClientBuilder::session_container).
+#[derive(Debug, Clone, Default)]
+pub struct SessionContainer {
+    /// The tokens by partition key range id, by collection link.
+    collections: Arc<RwLock<HashMap<String, BTreeMap<String, String>>>>,
+}
+
+impl SessionContainer {
+    /// An empty container.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The session token of a collection, such as `dbs/db/colls/coll`, for
+    /// every partition key range.
+    pub fn session_token(&self, collection_link: &str) -> Option<String> {
+        self.token(collection_link, None)
+    }
+
+    /// Merges a session token, as returned in a `x-ms-session-token` header,
+    /// into the tokens of a collection.
+    pub fn set_session_token(&self, collection_link: &str, session_token: &str) {
+        let mut collections = self.collections.write().unwrap_or_else(|e| e.into_inner());
+        let ranges = collections
+            .entry(collection_link.trim_matches('/').to_owned())
+            .or_default();
+        for token in session_token.split(',').map(str::trim) {
+            let (range_id, token) = token.split_once(':').unwrap_or(("0", token));
+            if token.is_empty() {
+                continue;
+            }
+            let merged = match ranges.get(range_id) {
+                Some(current) => merge(current, token),
+                None => token.to_owned(),
+            };
+            ranges.insert(range_id.to_owned(), merged);
+        }
+    }
+
+    /// Forgets the session tokens of a collection.
+    pub fn clear(&self, collection_link: &str) {
+        self.collections
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(collection_link.trim_matches('/'));
+    }
+
+    /// Forgets the session tokens of the collections of a database, such as
+    /// `dbs/db`.
+    pub(crate) fn clear_database(&self, database_link: &str) {
+        let prefix = format!("{}/", database_link.trim_matches('/'));
+        self.collections
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|collection_link, _| !collection_link.starts_with(&prefix));
+    }
+
+    /// Exports the session tokens of every collection as JSON.
+    pub fn export(&self) -> String {
+        let collections = self.collections.read().unwrap_or_else(|e| e.into_inner());
+        let tokens: BTreeMap<&str, String> = collections
+            .keys()
+            .filter_map(|link| Some((link.as_str(), join(collections.get(link)?, None)?)))
+            .collect();
+        // a map of strings always serializes
+        serde_json::to_string(&tokens).unwrap_or_default()
+    }
+
+    /// Merges session tokens exported with [`SessionContainer::export`].
+    pub fn import(&self, exported: &str) -> azure_core::Result<()> {
+        let tokens: BTreeMap<String, String> = serde_json::from_str(exported).context(
+            ErrorKind::DataConversion,
+            "could not parse the exported session tokens",
+        )?;
+        for (collection_link, session_token) in tokens {
+            self.set_session_token(&collection_link, &session_token);
+        }
+        Ok(())
+    }
+
+    /// The session token to send with a request, for a single partition key
+    /// range if the request targets one.
+    pub(crate) fn token(&self, collection_link: &str, range_id: Option<&str>) -> Option<String> {
+        let collections = self.collections.read().unwrap_or_else(|e| e.into_inner());
+        join(
+            collections.get(collection_link.trim_matches('/'))?,
+            range_id,
+        )
+    }
+}
+
+fn join(ranges: &BTreeMap<String, String>, range_id: Option<&str>) -> Option<String> {
+    let tokens: Vec<String> = ranges
+        .iter()
+        .filter(|(id, _)| !matches!(range_id, Some(range_id) if range_id != id.as_str()))
+        .map(|(id, token)| format!("{id}:{token}"))
+        .collect();
+    (!tokens.is_empty()).then(|| tokens.join(","))
+}
+
+/// Merges two session tokens of a partition key range, keeping the most
+/// recent progress of each region.
+fn merge(current: &str, other: &str) -> String {
+    match (
+        VectorSessionToken::parse(current),
+        VectorSessionToken::parse(other),
+    ) {
+        (Some(current), Some(other)) => current.merge(other).to_string(),
+        _ => match (current.parse::<u64>(), other.parse::<u64>()) {
+            (Ok(current), Ok(other)) => current.max(other).to_string(),
+            _ => other.to_owned(),
+        },
+    }
+}
+
+/// A session token of the form `version#global_lsn#region=lsn#...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VectorSessionToken {
+    version: i64,
+    global_lsn: u64,
+    region_lsns: BTreeMap<u32, u64>,
+}
+
+impl VectorSessionToken {
+    fn parse(token: &str) -> Option<Self> {
+        let mut parts = token.split('#');
+        let version = parts.next()?.parse().ok()?;
+        let global_lsn = parts.next()?.parse().ok()?;
+        let region_lsns = parts
+            .map(|part| {
+                let (region, lsn) = part.split_once('=')?;
+                Some((region.parse().ok()?, lsn.parse().ok()?))
+            })
+            .collect::<Option<_>>()?;
+        Some(Self {
+            version,
+            global_lsn,
+            region_lsns,
+        })
+    }
+
+    fn merge(self, other: Self) -> Self {
+        if self.version != other.version {
+            // the regions of the partition changed, so the newest token wins
+            return if self.version > other.version {
+                self
+            } else {
+                other
+            };
+        }
+        let mut region_lsns = self.region_lsns;
+        for (region, lsn) in other.region_lsns {
+            let current = region_lsns.entry(region).or_default();
+            *current = (*current).max(lsn);
+        }
+        Self {
+            version: self.version,
+            global_lsn: self.global_lsn.max(other.global_lsn),
+            region_lsns,
+        }
+    }
+}
+
+impl std::fmt::Display for VectorSessionToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.version, self.global_lsn)?;
+        for (region, lsn) in &self.region_lsns {
+            write!(f, "#{region}={lsn}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_session_tokens() {
+        let container = SessionContainer::new();
+        container.set_session_token("dbs/db/colls/coll", "0:1#100#1=20#2=5");
+        container.set_session_token("/dbs/db/colls/coll/", "0:1#90#1=25, 1:1#7");
+        assert_eq!(
+            container.session_token("dbs/db/colls/coll").as_deref(),
+            Some("0:1#100#1=25#2=5,1:1#7")
+        );
+        assert_eq!(
+            container.token("dbs/db/colls/coll", Some("1")).as_deref(),
+            Some("1:1#7")
+        );
+
+        // a newer version of the partition replaces the token
+        container.set_session_token("dbs/db/colls/coll", "1:2#3");
+        assert_eq!(
+            container.token("dbs/db/colls/coll", Some("1")).as_deref(),
+            Some("1:2#3")
+        );
+        assert_eq!(container.session_token("dbs/db/colls/other"), None);
+
+        container.set_session_token("dbs/other/colls/coll", "0:1#1");
+        container.clear_database("dbs/db");
+        assert_eq!(container.session_token("dbs/db/colls/coll"), None);
+        assert!(container.session_token("dbs/other/colls/coll").is_some());
+    }
+
+    #[test]
+    fn export_and_import() -> azure_core::Result<()> {
+        let container = SessionContainer::new();
+        container.set_session_token("dbs/db/colls/a", "0:-1#12");
+        container.set_session_token("dbs/db/colls/b", "3:-1#4");
+        let exported = container.export();
+        assert_eq!(
+            exported,
+            r#"{"dbs/db/colls/a":"0:-1#12","dbs/db/colls/b":"3:-1#4"}"#
+        );
+
+        let imported = SessionContainer::new();
+        imported.import(&exported)?;
+        assert_eq!(
+            imported.session_token("dbs/db/colls/b").as_deref(),
+            Some("3:-1#4")
+        );
+        assert!(imported.import("not json").is_err());
+        Ok(())
+    }
+}
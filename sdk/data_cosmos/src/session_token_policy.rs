@@ -0,0 +1,132 @@
+use crate::global_endpoint_policy::is_read;
+use crate::headers::{
+    HEADER_CONSISTENCY_LEVEL, HEADER_DOCUMENTDB_PARTITIONRANGEID, HEADER_SESSION_TOKEN,
+};
+use crate::SessionContainer;
+use azure_core::headers::HeaderValue;
+use azure_core::{Context, Method, Policy, PolicyResult, Request, Url};
+use std::sync::Arc;
+
+/// The `SessionTokenPolicy` gives session consistency to the reads of a
+/// client.
+///
+/// The session token of every response of a collection resource, such as a
+/// document, is recorded in the [`SessionContainer`], and the reads of the
+/// collection are sent with the token, unless they already have one or ask
+/// for another consistency level. Reads of a single partition key range only
+/// carry the token of that range.
+///
+/// The tokens of a collection are forgotten once it, or its database, is
+/// deleted. Databases,
+/// collections and the other resources of the account do not use sessions.
+///
+/// The policy is a retry policy so that it sees the responses of failed
+/// attempts too.
+#[derive(Debug, Clone)]
+pub(crate) struct SessionTokenPolicy {
+    container: SessionContainer,
+}
+
+impl SessionTokenPolicy {
+    pub(crate) fn new(container: SessionContainer) -> Self {
+        Self { container }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for SessionTokenPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let Some((collection_link, in_collection)) = collection_link(request.url()) else {
+            let response = next[0].send(ctx, request, &next[1..]).await?;
+            if let Some(database_link) = database_link(request.url()) {
+                if request.method() == &Method::Delete && response.status().is_success() {
+                    self.container.clear_database(&database_link);
+                }
+            }
+            return Ok(response);
+        };
+
+        let headers = request.headers();
+        let session_consistency = !matches!(
+            headers.get_optional_str(&HEADER_CONSISTENCY_LEVEL),
+            Some(level) if level != "Session"
+        );
+        if in_collection
+            && is_read(request)
+            && session_consistency
+            && headers.get_optional_str(&HEADER_SESSION_TOKEN).is_none()
+        {
+            let range_id = headers.get_optional_str(&HEADER_DOCUMENTDB_PARTITIONRANGEID);
+            if let Some(token) = self.container.token(&collection_link, range_id) {
+                request.insert_header(HEADER_SESSION_TOKEN, HeaderValue::from(token));
+            }
+        }
+
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        if in_collection {
+            if let Some(token) = response.headers().get_optional_str(&HEADER_SESSION_TOKEN) {
+                self.container.set_session_token(&collection_link, token);
+            }
+        } else if request.method() == &Method::Delete && response.status().is_success() {
+            self.container.clear(&collection_link);
+        }
+        Ok(response)
+    }
+}
+
+/// The link of the collection a request targets, such as
+/// `dbs/db/colls/coll`, and whether it targets a resource of the collection
+/// using sessions rather than the collection itself.
+fn collection_link(url: &Url) -> Option<(String, bool)> {
+    let segments: Vec<_> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    match segments.as_slice() {
+        ["dbs", database, "colls", collection, rest @ ..] => Some((
+            format!("dbs/{database}/colls/{collection}"),
+            !rest.is_empty() && rest[0] != "pkranges",
+        )),
+        _ => None,
+    }
+}
+
+/// The link of the database a request targets itself, such as `dbs/db`.
+fn database_link(url: &Url) -> Option<String> {
+    let segments: Vec<_> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    match segments.as_slice() {
+        ["dbs", database] => Some(format!("dbs/{database}")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_collection_link() {
+        let link = |path: &str| {
+            collection_link(
+                &Url::parse(&format!("https://account.documents.azure.com/{path}")).unwrap(),
+            )
+        };
+        assert_eq!(
+            link("dbs/db/colls/coll/docs/doc"),
+            Some(("dbs/db/colls/coll".to_owned(), true))
+        );
+        assert_eq!(
+            link("dbs/db/colls/coll/"),
+            Some(("dbs/db/colls/coll".to_owned(), false))
+        );
+        assert_eq!(
+            link("dbs/db/colls/coll/pkranges"),
+            Some(("dbs/db/colls/coll".to_owned(), false))
+        );
+        assert_eq!(link("dbs/db/users/user"), None);
+        assert_eq!(link("offers"), None);
+    }
+}
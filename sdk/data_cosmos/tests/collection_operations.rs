@@ -37,6 +37,25 @@ async fn collection_operations() -> azure_core::Result<()> {
 
     assert_eq!(get_collection.collection.id, collection_name);
 
+    // the collection as recorded survives a round trip through its definition
+    let definition = CollectionDefinition::from(get_collection.collection.clone());
+    assert_eq!(
+        definition.conflict_resolution_policy,
+        Some(ConflictResolutionPolicy {
+            mode: ConflictResolutionMode::LastWriterWins,
+            conflict_resolution_path: Some("/_ts".to_owned()),
+            conflict_resolution_procedure: Some(String::new()),
+        })
+    );
+    assert_eq!(
+        definition.geospatial_config,
+        Some(GeospatialType::Geography.into())
+    );
+    assert_eq!(
+        serde_json::from_value::<CollectionDefinition>(serde_json::to_value(&definition)?)?,
+        definition
+    );
+
     log::info!("Successfully got a collection");
     log::debug!("The get_collection response: {:#?}", get_collection);
 
@@ -55,10 +55,13 @@ async fn document_operations() {
         id: DOCUMENT_NAME.to_owned(),
         hello: 42,
     };
-    collection
+    let created = collection
         .create_document(document_data.clone())
         .await
         .unwrap();
+    assert_eq!(created.diagnostics.attempts.len(), 1);
+    assert_eq!(created.diagnostics.retry_count, 0);
+    assert_eq!(created.diagnostics.request_charge, created.charge);
 
     let documents = collection
         .list_documents()
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+mod setup_fake;
+
+use azure_core::prelude::*;
+use azure_core::StatusCode;
+use azure_data_cosmos::prelude::*;
+use futures::StreamExt;
+
+const DATABASE_NAME: &str = "fake-database";
+const COLLECTION_NAME: &str = "fake-collection";
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct MyDocument {
+    id: String,
+    tenant: String,
+    hello: u32,
+}
+
+impl azure_data_cosmos::CosmosEntity for MyDocument {
+    type Entity = String;
+
+    fn partition_key(&self) -> Self::Entity {
+        self.tenant.clone()
+    }
+}
+
+fn document(id: usize, tenant: &str) -> MyDocument {
+    MyDocument {
+        id: format!("document-{id}"),
+        tenant: tenant.to_owned(),
+        hello: id as u32,
+    }
+}
+
+fn status_of(error: &azure_core::Error) -> Option<StatusCode> {
+    error.as_http_error().map(|error| error.status())
+}
+
+async fn create_collection(client: &CosmosClient) -> CollectionClient {
+    client.create_database(DATABASE_NAME).await.unwrap();
+    let database = client.database_client(DATABASE_NAME);
+    database
+        .create_collection(COLLECTION_NAME, "/tenant")
+        .await
+        .unwrap();
+    database.collection_client(COLLECTION_NAME)
+}
+
+#[tokio::test]
+async fn fake_gateway_conflicts() {
+    let (client, _fake) = setup_fake::initialize().unwrap();
+    let collection = create_collection(&client).await;
+
+    let error = client.create_database(DATABASE_NAME).await.unwrap_err();
+    assert_eq!(status_of(&error), Some(StatusCode::Conflict));
+
+    collection.create_document(document(1, "a")).await.unwrap();
+    let error = collection
+        .create_document(document(1, "a"))
+        .await
+        .unwrap_err();
+    assert_eq!(status_of(&error), Some(StatusCode::Conflict));
+
+    // the same id in another partition is another document
+    collection.create_document(document(1, "b")).await.unwrap();
+    let upserted = collection
+        .create_document(document(1, "b"))
+        .is_upsert(true)
+        .await
+        .unwrap();
+    assert!(upserted.is_update);
+}
+
+#[tokio::test]
+async fn fake_gateway_optimistic_concurrency() {
+    let (client, _fake) = setup_fake::initialize().unwrap();
+    let collection = create_collection(&client).await;
+
+    let mut data = document(1, "a");
+    let created = collection.create_document(data.clone()).await.unwrap();
+    let document_client = collection.document_client(&data.id, &data.tenant).unwrap();
+
+    data.hello = 2;
+    let replaced = document_client
+        .replace_document(data.clone())
+        .if_match_condition(IfMatchCondition::Match(created.etag.clone()))
+        .await
+        .unwrap();
+    assert_ne!(replaced.document_attributes.etag(), created.etag);
+
+    // the etag of the creation is stale once the document is replaced
+    data.hello = 3;
+    let error = document_client
+        .replace_document(data)
+        .if_match_condition(IfMatchCondition::Match(created.etag))
+        .await
+        .unwrap_err();
+    assert_eq!(status_of(&error), Some(StatusCode::PreconditionFailed));
+
+    match document_client.get_document::<MyDocument>().await.unwrap() {
+        GetDocumentResponse::Found(found) => assert_eq!(found.document.document.hello, 2),
+        GetDocumentResponse::NotFound(_) => panic!("document not found"),
+    }
+
+    document_client.delete_document().await.unwrap();
+    let error = document_client
+        .get_document::<MyDocument>()
+        .await
+        .unwrap_err();
+    assert_eq!(status_of(&error), Some(StatusCode::NotFound));
+}
+
+#[tokio::test]
+async fn fake_gateway_query_pages() {
+    let (client, _fake) = setup_fake::initialize().unwrap();
+    let collection = create_collection(&client).await;
+
+    for id in 0..5 {
+        collection.create_document(document(id, "a")).await.unwrap();
+    }
+    collection.create_document(document(5, "b")).await.unwrap();
+
+    let mut pages = collection
+        .query_documents(Query::with_params(
+            "SELECT * FROM c WHERE c.hello >= @min ORDER BY c.hello".into(),
+            vec![Param::new("@min".into(), 1)],
+        ))
+        .partition_key(&"a")
+        .unwrap()
+        .max_item_count(2)
+        .into_stream::<MyDocument>();
+
+    let mut hellos = Vec::new();
+    let mut page_count = 0;
+    while let Some(page) = pages.next().await {
+        let page = page.unwrap();
+        assert!(page.results.len() <= 2);
+        hellos.extend(page.results.into_iter().map(|(document, _)| document.hello));
+        page_count += 1;
+    }
+    assert_eq!(hellos, vec![1, 2, 3, 4]);
+    assert_eq!(page_count, 2);
+}
+
+#[tokio::test]
+async fn fake_gateway_query_metrics_add_up() {
+    let (client, _fake) = setup_fake::initialize().unwrap();
+    let collection = create_collection(&client).await;
+
+    for id in 0..5 {
+        collection.create_document(document(id, "a")).await.unwrap();
+    }
+
+    let mut pages = collection
+        .query_documents(Query::new("SELECT * FROM c".into()))
+        .partition_key(&"a")
+        .unwrap()
+        .max_item_count(2)
+        .populate_query_metrics(true)
+        .into_stream::<MyDocument>();
+
+    let mut last = None;
+    while let Some(page) = pages.next().await {
+        let page = page.unwrap();
+        let metrics = page.query_metrics.as_ref().unwrap();
+        assert_eq!(metrics.output_document_count, page.results.len() as u64);
+        last = Some(page);
+    }
+
+    let total = last.unwrap().total_query_metrics.unwrap();
+    assert_eq!(total.output_document_count, 5);
+    assert_eq!(total.request_charge, 7.5);
+}
+
+#[tokio::test]
+async fn fake_gateway_throttling_is_retried() {
+    let (client, fake) = setup_fake::initialize().unwrap();
+    let collection = create_collection(&client).await;
+
+    fake.throttle_next(2);
+    let before = fake.request_count();
+    let created = collection.create_document(document(1, "a")).await.unwrap();
+
+    assert_eq!(fake.request_count() - before, 3);
+    assert_eq!(created.diagnostics.retry_count, 2);
+    assert_eq!(created.diagnostics.attempts.len(), 3);
+}
+
+#[tokio::test]
+async fn fake_gateway_rejects_wrong_key() {
+    let (client, _fake) =
+        setup_fake::initialize_with_key("d3Jvbmcga2V5IGZvciB0aGUgZmFrZSBnYXRld2F5").unwrap();
+
+    let error = client.create_database(DATABASE_NAME).await.unwrap_err();
+    assert_eq!(status_of(&error), Some(StatusCode::Unauthorized));
+}
@@ -0,0 +1,21 @@
+use azure_core::TransportOptions;
+use azure_data_cosmos::prelude::*;
+use fake_cosmos::FakeCosmos;
+
+/// The primary key the fake gateway signs requests with.
+pub const PRIMARY_KEY: &str = "ZmFrZSBjb3Ntb3MgcHJpbWFyeSBrZXkgZm9yIHRlc3Rz";
+
+/// A client talking to a fresh fake gateway, signing its requests with `key`.
+pub fn initialize_with_key(key: &str) -> azure_core::Result<(CosmosClient, FakeCosmos)> {
+    let fake = FakeCosmos::new(PRIMARY_KEY);
+    let client = CosmosClient::builder("fake", AuthorizationToken::primary_key(key)?)
+        .transport(TransportOptions::new_custom_policy(fake.transport()))
+        .build();
+
+    Ok((client, fake))
+}
+
+/// A client talking to a fresh fake gateway.
+pub fn initialize() -> azure_core::Result<(CosmosClient, FakeCosmos)> {
+    initialize_with_key(PRIMARY_KEY)
+}
@@ -0,0 +1,544 @@
+use crate::{
+    edm,
+    operations::{TransactionBuilder, TransactionResponse},
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    Method,
+};
+use bytes::Bytes;
+use futures::{
+    stream::{FusedStream, FuturesUnordered},
+    Stream, StreamExt,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The maximum number of operations of an entity group transaction.
+const MAX_TRANSACTION_OPERATIONS: usize = 100;
+/// The maximum size of the payload of an entity group transaction.
+const MAX_TRANSACTION_SIZE: usize = 4 * 1024 * 1024;
+/// Room left for the multipart headers of each operation of a transaction.
+const OPERATION_OVERHEAD: usize = 1024;
+
+/// A write to be applied by a `BulkWriter`.
+#[derive(Debug, Clone)]
+pub struct BulkOperation {
+    partition_key: String,
+    row_key: String,
+    kind: BulkOperationKind,
+}
+
+#[derive(Debug, Clone)]
+enum BulkOperationKind {
+    Upsert(Bytes),
+    Merge(Bytes),
+    Delete(IfMatchCondition),
+}
+
+impl BulkOperation {
+    /// Inserts the entity, or replaces it if it already exists.
+    ///
+    /// The keys are read from the `PartitionKey` and `RowKey` properties of
+    /// the serialized entity.
+    pub fn upsert<E: Serialize>(entity: &E) -> azure_core::Result<Self> {
+        let body = edm::to_json(entity)?;
+        let (partition_key, row_key) = keys(&body)?;
+        Ok(Self {
+            partition_key,
+            row_key,
+            kind: BulkOperationKind::Upsert(body),
+        })
+    }
+
+    /// Inserts the entity, or merges its properties into the existing entity.
+    pub fn merge<E: Serialize>(entity: &E) -> azure_core::Result<Self> {
+        let body = edm::to_json(entity)?;
+        let (partition_key, row_key) = keys(&body)?;
+        Ok(Self {
+            partition_key,
+            row_key,
+            kind: BulkOperationKind::Merge(body),
+        })
+    }
+
+    pub fn delete(partition_key: impl Into<String>, row_key: impl Into<String>) -> Self {
+        Self {
+            partition_key: partition_key.into(),
+            row_key: row_key.into(),
+            kind: BulkOperationKind::Delete(IfMatchCondition::Any),
+        }
+    }
+
+    pub fn partition_key(&self) -> &str {
+        &self.partition_key
+    }
+
+    pub fn row_key(&self) -> &str {
+        &self.row_key
+    }
+
+    fn size(&self) -> usize {
+        let body = match &self.kind {
+            BulkOperationKind::Upsert(body) | BulkOperationKind::Merge(body) => body.len(),
+            BulkOperationKind::Delete(_) => 0,
+        };
+        body + OPERATION_OVERHEAD
+    }
+
+    fn add_to(&self, transaction: TransactionBuilder) -> azure_core::Result<TransactionBuilder> {
+        let row_key = self.row_key.clone();
+        match &self.kind {
+            BulkOperationKind::Upsert(body) => {
+                transaction.serialized_entity_operation(row_key, body.clone(), Method::Put, None)
+            }
+            BulkOperationKind::Merge(body) => {
+                transaction.serialized_entity_operation(row_key, body.clone(), Method::Merge, None)
+            }
+            BulkOperationKind::Delete(if_match) => {
+                transaction.delete(row_key, Some(if_match.clone()))
+            }
+        }
+    }
+}
+
+fn keys(body: &[u8]) -> azure_core::Result<(String, String)> {
+    let entity: Value = serde_json::from_slice(body)?;
+    let key = |name: &str| {
+        entity
+            .get(name)
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| {
+                Error::with_message(ErrorKind::DataConversion, || {
+                    format!("entity has no {name} string property")
+                })
+            })
+    };
+    Ok((key("PartitionKey")?, key("RowKey")?))
+}
+
+/// An operation of a `BulkWriter` that could not be applied.
+#[derive(Debug)]
+pub struct BulkWriteFailure {
+    pub partition_key: String,
+    pub row_key: String,
+    pub error: Error,
+}
+
+impl BulkWriteFailure {
+    fn new(operation: BulkOperation, error: Error) -> Self {
+        Self {
+            partition_key: operation.partition_key,
+            row_key: operation.row_key,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BulkWriteResponse {
+    /// The number of operations applied.
+    pub succeeded: usize,
+    pub failures: Vec<BulkWriteFailure>,
+}
+
+/// Writes a stream of operations to a table using entity group transactions.
+///
+/// Operations are grouped by partition key into transactions of up to 100
+/// operations and 4 MiB, and a transaction never holds two operations on the
+/// same entity. Transactions of different partitions run concurrently, while
+/// those of a partition are sent one at a time and in order, so the operations
+/// on an entity are applied in the order of the stream.
+///
+/// A transaction is atomic, so when one of its operations fails the service
+/// rejects all of them. The failed operation is then reported, and the other
+/// operations are retried in a new transaction, ahead of the later
+/// transactions of their partition.
+#[derive(Debug, Clone)]
+pub struct BulkWriter {
+    table_client: TableClient,
+    max_concurrent_transactions: usize,
+    max_operations_per_transaction: usize,
+    max_buffered_operations: usize,
+}
+
+impl BulkWriter {
+    pub(crate) fn new(table_client: TableClient) -> Self {
+        Self {
+            table_client,
+            max_concurrent_transactions: 8,
+            max_operations_per_transaction: MAX_TRANSACTION_OPERATIONS,
+            max_buffered_operations: 10 * MAX_TRANSACTION_OPERATIONS,
+        }
+    }
+
+    setters! {
+        max_concurrent_transactions: usize => max_concurrent_transactions.max(1),
+        max_operations_per_transaction: usize => max_operations_per_transaction.clamp(1, MAX_TRANSACTION_OPERATIONS),
+        max_buffered_operations: usize => max_buffered_operations.max(1),
+    }
+
+    /// Applies every operation of the stream, returning once all of them
+    /// succeeded or failed.
+    ///
+    /// Partitions are sent as soon as they fill a transaction. Once more than
+    /// `max_buffered_operations` are waiting for their partition to fill up,
+    /// the largest partition is sent as is.
+    pub async fn write<S>(&self, operations: S) -> BulkWriteResponse
+    where
+        S: Stream<Item = BulkOperation>,
+    {
+        let operations = operations.fuse();
+        futures::pin_mut!(operations);
+
+        let mut batches = Batches::default();
+        let mut ready = Ready::default();
+        let mut in_flight = FuturesUnordered::new();
+        let mut response = BulkWriteResponse::default();
+
+        loop {
+            while in_flight.len() < self.max_concurrent_transactions {
+                match ready.next() {
+                    Some(batch) => in_flight.push(self.execute(batch)),
+                    None => break,
+                }
+            }
+
+            // stop reading operations while sent transactions are lagging
+            if ready.is_empty() && !operations.is_terminated() {
+                futures::select! {
+                    operation = operations.next() => match operation {
+                        Some(operation) => {
+                            ready.extend(batches.push(operation, self.max_operations_per_transaction));
+                            if batches.buffered > self.max_buffered_operations {
+                                ready.extend(batches.take_largest());
+                            }
+                        }
+                        None => ready.extend(batches.drain()),
+                    },
+                    (batch, outcome) = in_flight.select_next_some() => {
+                        complete(batch, outcome, &mut response, &mut ready);
+                    }
+                }
+            } else if let Some((batch, outcome)) = in_flight.next().await {
+                complete(batch, outcome, &mut response, &mut ready);
+            } else if ready.is_empty() {
+                break;
+            }
+        }
+
+        response
+    }
+
+    async fn execute(
+        &self,
+        batch: Vec<BulkOperation>,
+    ) -> (Vec<BulkOperation>, azure_core::Result<TransactionResponse>) {
+        let outcome = async {
+            let partition_key = batch[0].partition_key.clone();
+            let mut transaction = self
+                .table_client
+                .partition_key_client(partition_key)
+                .transaction();
+            for operation in &batch {
+                transaction = operation.add_to(transaction)?;
+            }
+            transaction.await
+        }
+        .await;
+        (batch, outcome)
+    }
+}
+
+/// Records the outcome of a transaction, queueing the operations to retry.
+fn complete(
+    mut batch: Vec<BulkOperation>,
+    outcome: azure_core::Result<TransactionResponse>,
+    response: &mut BulkWriteResponse,
+    ready: &mut Ready,
+) {
+    ready.release(&batch[0].partition_key);
+    let transaction = match outcome {
+        Ok(transaction) => transaction,
+        Err(err) => return fail_all(batch, &err, response),
+    };
+
+    let Some(failed) = transaction
+        .operation_responses
+        .iter()
+        .find(|operation_response| !operation_response.status_code.is_success())
+    else {
+        response.succeeded += batch.len();
+        return;
+    };
+
+    let error = Error::with_message(
+        ErrorKind::http_response(failed.status_code, failed.error_code.clone()),
+        || {
+            failed
+                .error_message
+                .clone()
+                .unwrap_or_else(|| format!("operation failed with {}", failed.status_code))
+        },
+    );
+    match failed
+        .failed_operation_index()
+        .filter(|index| *index < batch.len())
+    {
+        Some(index) => {
+            let operation = batch.remove(index);
+            response
+                .failures
+                .push(BulkWriteFailure::new(operation, error));
+            if !batch.is_empty() {
+                ready.retry(batch);
+            }
+        }
+        None => fail_all(batch, &error, response),
+    }
+}
+
+fn fail_all(batch: Vec<BulkOperation>, error: &Error, response: &mut BulkWriteResponse) {
+    for operation in batch {
+        let error = Error::with_message(error.kind().clone(), || {
+            format!("transaction failed: {error}")
+        });
+        response
+            .failures
+            .push(BulkWriteFailure::new(operation, error));
+    }
+}
+
+/// The transactions waiting to be sent.
+///
+/// A partition has at most one transaction in flight, and its transactions are
+/// sent in the order they were queued.
+#[derive(Debug, Default)]
+struct Ready {
+    queue: VecDeque<Vec<BulkOperation>>,
+    in_flight: HashSet<String>,
+}
+
+impl Ready {
+    fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Takes the first transaction whose partition has none in flight.
+    fn next(&mut self) -> Option<Vec<BulkOperation>> {
+        let index = self
+            .queue
+            .iter()
+            .position(|batch| !self.in_flight.contains(&batch[0].partition_key))?;
+        let batch = self.queue.remove(index)?;
+        self.in_flight.insert(batch[0].partition_key.clone());
+        Some(batch)
+    }
+
+    /// Queues the remaining operations of a transaction ahead of the later
+    /// transactions of its partition.
+    fn retry(&mut self, batch: Vec<BulkOperation>) {
+        self.queue.push_front(batch);
+    }
+
+    fn release(&mut self, partition_key: &str) {
+        self.in_flight.remove(partition_key);
+    }
+}
+
+impl Extend<Vec<BulkOperation>> for Ready {
+    fn extend<T: IntoIterator<Item = Vec<BulkOperation>>>(&mut self, batches: T) {
+        self.queue.extend(batches);
+    }
+}
+
+/// The operations waiting for their partition to fill a transaction.
+#[derive(Debug, Default)]
+struct Batches {
+    partitions: HashMap<String, Batch>,
+    buffered: usize,
+}
+
+#[derive(Debug, Default)]
+struct Batch {
+    operations: Vec<BulkOperation>,
+    row_keys: HashSet<String>,
+    size: usize,
+}
+
+impl Batch {
+    fn fits(&self, operation: &BulkOperation) -> bool {
+        self.size + operation.size() <= MAX_TRANSACTION_SIZE
+            && !self.row_keys.contains(&operation.row_key)
+    }
+
+    fn add(&mut self, operation: BulkOperation) {
+        self.size += operation.size();
+        self.row_keys.insert(operation.row_key.clone());
+        self.operations.push(operation);
+    }
+}
+
+impl Batches {
+    /// Buffers an operation, returning the transactions ready to be sent.
+    fn push(&mut self, operation: BulkOperation, max_operations: usize) -> Vec<Vec<BulkOperation>> {
+        let mut ready = Vec::new();
+        let batch = self
+            .partitions
+            .entry(operation.partition_key.clone())
+            .or_default();
+
+        if !batch.operations.is_empty() && !batch.fits(&operation) {
+            ready.push(std::mem::take(batch).operations);
+        }
+        batch.add(operation);
+        if batch.operations.len() >= max_operations {
+            ready.push(std::mem::take(batch).operations);
+        }
+
+        self.buffered = self.buffered + 1 - ready.iter().map(Vec::len).sum::<usize>();
+        ready
+    }
+
+    fn take_largest(&mut self) -> Option<Vec<BulkOperation>> {
+        let partition_key = self
+            .partitions
+            .iter()
+            .max_by_key(|(_, batch)| batch.operations.len())
+            .map(|(partition_key, _)| partition_key.clone())?;
+        let batch = self.partitions.remove(&partition_key)?;
+        self.buffered -= batch.operations.len();
+        Some(batch.operations)
+    }
+
+    fn drain(&mut self) -> Vec<Vec<BulkOperation>> {
+        self.buffered = 0;
+        self.partitions
+            .drain()
+            .map(|(_, batch)| batch.operations)
+            .filter(|operations| !operations.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upsert(partition_key: &str, row_key: &str, payload_size: usize) -> BulkOperation {
+        let mut entity = TableEntity::new(partition_key, row_key);
+        entity.insert("Payload", "x".repeat(payload_size));
+        BulkOperation::upsert(&entity).unwrap()
+    }
+
+    #[test]
+    fn operations_are_grouped_by_partition() {
+        let mut batches = Batches::default();
+        let mut ready = Vec::new();
+        for i in 0..250 {
+            let partition_key = if i % 2 == 0 { "even" } else { "odd" };
+            ready.extend(batches.push(upsert(partition_key, &i.to_string(), 0), 100));
+        }
+
+        assert_eq!(ready.len(), 2);
+        assert!(ready.iter().all(|batch| batch.len() == 100));
+        assert_eq!(batches.buffered, 50);
+
+        let mut rest = batches.drain();
+        rest.sort_by_key(Vec::len);
+        assert_eq!(rest.iter().map(Vec::len).collect::<Vec<_>>(), [25, 25]);
+        for batch in ready.iter().chain(&rest) {
+            let partition_key = batch[0].partition_key();
+            assert!(batch.iter().all(|op| op.partition_key() == partition_key));
+        }
+    }
+
+    #[test]
+    fn transactions_respect_size_and_unique_entities() {
+        let mut batches = Batches::default();
+
+        assert!(batches.push(upsert("p", "1", 0), 100).is_empty());
+        // the same entity cannot appear twice in a transaction
+        let ready = batches.push(BulkOperation::delete("p", "1"), 100);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0][0].row_key(), "1");
+
+        // two payloads of 3 MiB do not fit in a transaction
+        let mut batches = Batches::default();
+        assert!(batches.push(upsert("p", "1", 3 << 20), 100).is_empty());
+        assert_eq!(batches.push(upsert("p", "2", 3 << 20), 100).len(), 1);
+        assert_eq!(batches.buffered, 1);
+    }
+
+    #[test]
+    fn failed_operation_is_reported_and_others_retried() {
+        use crate::operations::OperationResponse;
+        use azure_core::{headers::Headers, StatusCode};
+
+        let batch = vec![
+            upsert("p", "1", 0),
+            upsert("p", "2", 0),
+            upsert("p", "3", 0),
+        ];
+        let mut headers = Headers::new();
+        for (name, value) in [
+            ("x-ms-request-id", "00000000-0000-0000-0000-000000000000"),
+            ("x-ms-version", "2019-02-02"),
+            ("date", "Mon, 27 Jun 2022 13:38:48 GMT"),
+            ("server", "Windows-Azure-Table/1.0"),
+        ] {
+            headers.insert(name, value);
+        }
+        let transaction = TransactionResponse {
+            common_storage_response_headers: (&headers).try_into().unwrap(),
+            operation_responses: vec![OperationResponse {
+                status_code: StatusCode::Conflict,
+                error_code: Some("EntityAlreadyExists".to_owned()),
+                error_message: Some("1:The specified entity already exists.".to_owned()),
+                ..OperationResponse::default()
+            }],
+        };
+
+        let mut response = BulkWriteResponse::default();
+        let mut ready = Ready::default();
+        ready.extend([batch, vec![upsert("p", "1", 0)]]);
+        let batch = ready.next().unwrap();
+        complete(batch, Ok(transaction), &mut response, &mut ready);
+
+        assert_eq!(response.failures.len(), 1);
+        assert_eq!(response.failures[0].row_key, "2");
+        assert_eq!(
+            response.failures[0].error.kind(),
+            &ErrorKind::http_response(StatusCode::Conflict, Some("EntityAlreadyExists".to_owned()))
+        );
+        // the retried operations go before the later transaction of "p"
+        let retried = ready.next().unwrap();
+        let retried: Vec<_> = retried.iter().map(BulkOperation::row_key).collect();
+        assert_eq!(retried, ["1", "3"]);
+    }
+
+    #[test]
+    fn partitions_have_one_transaction_in_flight() {
+        let mut ready = Ready::default();
+        ready.extend([
+            vec![upsert("p", "1", 0)],
+            vec![BulkOperation::delete("p", "1")],
+            vec![upsert("q", "1", 0)],
+        ]);
+
+        let first = ready.next().unwrap();
+        assert_eq!(first[0].partition_key(), "p");
+        // the second transaction of "p" waits for the first one
+        let second = ready.next().unwrap();
+        assert_eq!(second[0].partition_key(), "q");
+        assert!(ready.next().is_none());
+
+        ready.release("p");
+        let third = ready.next().unwrap();
+        assert!(matches!(third[0].kind, BulkOperationKind::Delete(_)));
+        assert!(ready.is_empty());
+    }
+}
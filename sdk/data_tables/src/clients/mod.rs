@@ -5,4 +5,4 @@ mod table_service_client;
 pub use entity_client::EntityClient;
 pub use partition_key_client::PartitionKeyClient;
 pub use table_client::TableClient;
-pub use table_service_client::{TableServiceClient, TableServiceClientBuilder};
+pub use table_service_client::{TableEndpointKind, TableServiceClient, TableServiceClientBuilder};
@@ -1,6 +1,17 @@
-use crate::{clients::*, operations::*};
-use azure_core::{headers::Headers, to_json, Body, Context, Method, Request, Response, Url};
+use crate::{clients::*, edm, operations::*, BulkWriter};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::Headers,
+    Body, Context, Method, Request, Response, Url,
+};
+use azure_storage::{
+    prelude::TableSasPermissions,
+    shared_access_signature::{service_sas::TableSharedAccessSignature, SasToken},
+    PartialStoredAccessPolicy, StorageCredentialsInner,
+};
 use serde::{de::DeserializeOwned, Serialize};
+use std::ops::Deref;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct TableClient {
@@ -32,18 +43,118 @@ impl TableClient {
         DeleteTableBuilder::new(self.clone())
     }
 
+    /// Get a writer applying streams of operations to the table with entity
+    /// group transactions.
+    pub fn bulk_writer(&self) -> BulkWriter {
+        BulkWriter::new(self.clone())
+    }
+
+    /// Get the stored access policies of the table.
+    ///
+    /// More info here:
+    /// <https://docs.microsoft.com/rest/api/storageservices/get-table-acl>
+    pub fn get_acl(&self) -> GetTableACLBuilder {
+        GetTableACLBuilder::new(self.clone())
+    }
+
+    /// Replace the stored access policies of the table. Policies with typed
+    /// permissions can be built with `TableAccessPolicyBuilder`.
+    ///
+    /// More info here:
+    /// <https://docs.microsoft.com/rest/api/storageservices/set-table-acl>
+    pub fn set_acl<I, P>(&self, policies: I) -> SetTableACLBuilder
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PartialStoredAccessPolicy>,
+    {
+        SetTableACLBuilder::new(self.clone(), policies.into_iter().map(Into::into).collect())
+    }
+
     pub fn insert<E: Serialize, R: DeserializeOwned + Send>(
         &self,
         entity: E,
     ) -> azure_core::Result<InsertEntityBuilder<R>> {
-        let body = to_json(&entity)?.into();
+        let body = edm::to_json(&entity)?.into();
         Ok(InsertEntityBuilder::new(self.clone(), body))
     }
 
+    /// Create a shared access signature scoped to the table.
+    ///
+    /// The signature can be further restricted to a range of entities with
+    /// the partition and row key setters of [`TableSharedAccessSignature`].
+    pub async fn shared_access_signature(
+        &self,
+        permissions: TableSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<TableSharedAccessSignature> {
+        let creds = self.table_service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        // the table name must be lowercase in the canonicalized resource
+        let canonicalized_resource =
+            format!("/table/{}/{}", account, self.table_name.to_lowercase());
+        Ok(TableSharedAccessSignature::new(
+            key.clone(),
+            canonicalized_resource,
+            self.table_name.clone(),
+            permissions,
+            expiry,
+        ))
+    }
+
+    /// Create a shared access signature bound to a stored access policy of
+    /// the table.
+    ///
+    /// The permissions and expiry come from the policy, so revoking the policy
+    /// revokes the signature.
+    pub async fn stored_policy_shared_access_signature(
+        &self,
+        identifier: impl Into<String>,
+    ) -> azure_core::Result<TableSharedAccessSignature> {
+        let creds = self.table_service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource =
+            format!("/table/{}/{}", account, self.table_name.to_lowercase());
+        Ok(TableSharedAccessSignature::with_stored_policy(
+            key.clone(),
+            canonicalized_resource,
+            self.table_name.clone(),
+            identifier.into(),
+        ))
+    }
+
+    /// Create a signed table url
+    pub fn generate_signed_table_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.table_service_client.service_url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::message(ErrorKind::DataConversion, "Invalid url"))?
+            .push(&self.table_name);
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
     pub(crate) fn url(&self) -> azure_core::Result<Url> {
         self.table_service_client.url()
     }
 
+    pub(crate) fn table_service_client(&self) -> &TableServiceClient {
+        &self.table_service_client
+    }
+
     pub fn partition_key_client<PK: Into<String>>(&self, partition_key: PK) -> PartitionKeyClient {
         PartitionKeyClient::new(self.clone(), partition_key)
     }
@@ -1,17 +1,105 @@
-use crate::operations::ListTablesBuilder;
+use crate::{operations::*, TableServiceProperties};
+use azure_core::auth::{AccessToken, TokenCredential};
+use azure_core::error::{Error, ErrorKind};
 use azure_core::{
     headers::Headers, Body, ClientOptions, Context, Method, Pipeline, Request, Response, Url,
 };
-use azure_storage::{clients::ServiceType, prelude::StorageCredentials, CloudLocation};
+use azure_storage::{
+    clients::ServiceType, prelude::StorageCredentials, CloudLocation, ConnectionString,
+    StorageCredentialsInner,
+};
+use std::sync::Arc;
 
 use super::TableClient;
 
+/// The kind of service behind a table endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableEndpointKind {
+    /// Azure Table storage.
+    Storage,
+    /// The Table API of Azure Cosmos DB.
+    ///
+    /// The Table API does not support the `MERGE` verb, so merges are sent as
+    /// `PATCH` requests, and it has no service properties, statistics or table
+    /// ACLs.
+    ///
+    /// It accepts the Shared Key and SAS authorization of Table storage, signed
+    /// with the keys of the Cosmos DB account, but Microsoft Entra ID tokens
+    /// are requested for the Cosmos DB scope rather than the storage one.
+    Cosmos,
+}
+
+impl TableEndpointKind {
+    /// Detects Cosmos DB endpoints from their host name.
+    ///
+    /// Only the `.table.cosmos.` and `.table.cosmosdb.` hosts of Cosmos DB
+    /// accounts are recognized, so the kind of other endpoints, such as the
+    /// `localhost:8902` of the Cosmos DB emulator, must be set with
+    /// [`TableServiceClientBuilder::endpoint_kind`].
+    fn detect(cloud_location: &CloudLocation) -> Self {
+        let is_cosmos = cloud_location
+            .url(ServiceType::Table)
+            .ok()
+            .and_then(|url| url.host_str().map(ToOwned::to_owned))
+            .is_some_and(|host| {
+                host.contains(".table.cosmos.") || host.contains(".table.cosmosdb.")
+            });
+        if is_cosmos {
+            Self::Cosmos
+        } else {
+            Self::Storage
+        }
+    }
+}
+
+/// The scope of the tokens authorizing requests to the Table API of Cosmos DB.
+const COSMOS_TOKEN_SCOPE: &str = "https://cosmos.azure.com/.default";
+
+/// A token credential requesting tokens for the Cosmos DB scope, whatever the
+/// scope asked for by the storage authorization policy.
+#[derive(Debug)]
+struct CosmosTokenCredential(Arc<dyn TokenCredential>);
+
+impl CosmosTokenCredential {
+    /// Makes token credentials request tokens for the Cosmos DB scope, and
+    /// leaves the other credentials unchanged.
+    ///
+    /// Waits for the credentials to be readable, should they be being replaced.
+    fn wrap(credentials: StorageCredentials) -> StorageCredentials {
+        let token_credential = match &*futures::executor::block_on(credentials.0.read()) {
+            StorageCredentialsInner::TokenCredential(token_credential) => {
+                Some(token_credential.clone())
+            }
+            _ => None,
+        };
+        match token_credential {
+            Some(token_credential) => {
+                StorageCredentials::token_credential(Arc::new(Self(token_credential)))
+            }
+            None => credentials,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for CosmosTokenCredential {
+    async fn get_token(&self, _scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.0.get_token(&[COSMOS_TOKEN_SCOPE]).await
+    }
+
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.0.clear_cache().await
+    }
+}
+
 /// A builder for the table service client.
 #[derive(Debug, Clone)]
 pub struct TableServiceClientBuilder {
     cloud_location: CloudLocation,
     options: ClientOptions,
     credentials: StorageCredentials,
+    endpoint_kind: Option<TableEndpointKind>,
 }
 
 impl TableServiceClientBuilder {
@@ -40,7 +128,70 @@ impl TableServiceClientBuilder {
             options: ClientOptions::default(),
             cloud_location,
             credentials: credentials.into(),
+            endpoint_kind: None,
+        }
+    }
+
+    /// Create a new instance of `TableServiceClientBuilder` for the Table API
+    /// of an Azure Cosmos DB account.
+    #[must_use]
+    pub fn cosmos<A, C>(account: A, credentials: C) -> Self
+    where
+        A: Into<String>,
+        C: Into<StorageCredentials>,
+    {
+        let account = account.into();
+        let uri = format!("https://{account}.table.cosmos.azure.com");
+        Self::with_location(CloudLocation::Custom { account, uri }, credentials)
+            .endpoint_kind(TableEndpointKind::Cosmos)
+    }
+
+    /// Create a new instance of `TableServiceClientBuilder` from a storage or
+    /// Cosmos DB connection string.
+    ///
+    /// The `TableEndpoint` of the connection string is used when present, so
+    /// Cosmos DB Table API connection strings are supported. An `AccountName`
+    /// is required either way.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        let connection_string = ConnectionString::new(connection_string)?;
+        if connection_string.use_development_storage == Some(true) {
+            return Ok(Self::emulator());
         }
+
+        let credentials = connection_string.storage_credentials()?;
+        let account = connection_string.account_name.map(ToOwned::to_owned);
+        let cloud_location = match (
+            connection_string.table_endpoint,
+            connection_string.endpoint_suffix,
+            account,
+        ) {
+            (Some(endpoint), _, Some(account)) => CloudLocation::Custom {
+                account,
+                uri: endpoint.trim_end_matches('/').to_owned(),
+            },
+            (Some(_), _, None) => {
+                return Err(Error::message(
+                    ErrorKind::Other,
+                    "connection string specifies a table endpoint but no account name",
+                ))
+            }
+            (None, Some(suffix), Some(account)) => {
+                let protocol = connection_string
+                    .default_endpoints_protocol
+                    .as_ref()
+                    .map_or_else(|| "https".to_owned(), ToString::to_string);
+                let uri = format!("{protocol}://{account}.table.{suffix}");
+                CloudLocation::Custom { account, uri }
+            }
+            (None, None, Some(account)) => CloudLocation::Public { account },
+            (None, _, None) => {
+                return Err(Error::message(
+                    ErrorKind::Other,
+                    "connection string specifies neither an account name nor a table endpoint",
+                ))
+            }
+        };
+        Ok(Self::with_location(cloud_location, credentials))
     }
 
     /// Use the emulator with default settings
@@ -56,16 +207,32 @@ impl TableServiceClientBuilder {
     }
 
     /// Convert the builder into a `TableServiceClient` instance.
+    ///
+    /// The token credential of a Cosmos DB endpoint is wrapped to request
+    /// tokens for the Cosmos DB scope, so replacing it later with
+    /// [`StorageCredentials::replace`] does not affect the client.
     #[must_use]
     pub fn build(self) -> TableServiceClient {
         let Self {
             cloud_location,
             options,
             credentials,
+            endpoint_kind,
         } = self;
+        let endpoint_kind =
+            endpoint_kind.unwrap_or_else(|| TableEndpointKind::detect(&cloud_location));
+        let credentials = match endpoint_kind {
+            TableEndpointKind::Storage => credentials,
+            TableEndpointKind::Cosmos => CosmosTokenCredential::wrap(credentials),
+        };
         TableServiceClient {
-            pipeline: azure_storage::clients::new_pipeline_from_options(options, credentials),
+            endpoint_kind,
+            pipeline: azure_storage::clients::new_pipeline_from_options(
+                options,
+                credentials.clone(),
+            ),
             cloud_location,
+            credentials,
         }
     }
 
@@ -76,6 +243,14 @@ impl TableServiceClientBuilder {
         self
     }
 
+    /// Set the kind of service behind the endpoint, rather than detecting it
+    /// from the host name. Useful for the Cosmos DB emulator.
+    #[must_use]
+    pub fn endpoint_kind(mut self, endpoint_kind: TableEndpointKind) -> Self {
+        self.endpoint_kind = Some(endpoint_kind);
+        self
+    }
+
     /// Set the retry options.
     #[must_use]
     pub fn retry(mut self, retry: impl Into<azure_core::RetryOptions>) -> Self {
@@ -104,6 +279,8 @@ impl TableServiceClientBuilder {
 pub struct TableServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    credentials: StorageCredentials,
+    endpoint_kind: TableEndpointKind,
 }
 
 impl TableServiceClient {
@@ -111,16 +288,76 @@ impl TableServiceClient {
         TableServiceClientBuilder::new(account, credentials).build()
     }
 
+    pub fn endpoint_kind(&self) -> TableEndpointKind {
+        self.endpoint_kind
+    }
+
     pub fn list(&self) -> ListTablesBuilder {
         ListTablesBuilder::new(self.clone())
     }
 
+    pub fn get_table_service_properties(&self) -> GetTableServicePropertiesBuilder {
+        GetTableServicePropertiesBuilder::new(self.clone())
+    }
+
+    /// Set table service properties.
+    ///
+    /// More info here:
+    /// <https://docs.microsoft.com/rest/api/storageservices/set-table-service-properties>
+    pub fn set_table_service_properties(
+        &self,
+        properties: TableServiceProperties,
+    ) -> SetTableServicePropertiesBuilder {
+        SetTableServicePropertiesBuilder::new(self.clone(), properties)
+    }
+
+    /// Get the replication statistics of the service, from the secondary
+    /// endpoint of an account with read-access geo-redundant replication.
+    ///
+    /// More info here:
+    /// <https://docs.microsoft.com/rest/api/storageservices/get-table-service-stats>
+    pub fn get_table_service_stats(&self) -> GetTableServiceStatsBuilder {
+        GetTableServiceStatsBuilder::new(self.clone())
+    }
+
     pub(crate) fn url(&self) -> azure_core::Result<Url> {
-        let mut url = self.cloud_location.url(ServiceType::Table)?;
+        let mut url = self.service_url()?;
         url.path_segments_mut().unwrap().push("Tables");
         Ok(url)
     }
 
+    /// The root URL of the table service, without the `Tables` resource.
+    pub(crate) fn service_url(&self) -> azure_core::Result<Url> {
+        self.cloud_location.url(ServiceType::Table)
+    }
+
+    pub(crate) fn secondary_service_url(&self) -> azure_core::Result<Url> {
+        self.cloud_location.secondary_url(ServiceType::Table)
+    }
+
+    /// The verb of merge operations, which the Cosmos DB Table API only
+    /// accepts as `PATCH`.
+    pub(crate) fn merge_method(&self) -> Method {
+        match self.endpoint_kind {
+            TableEndpointKind::Storage => Method::Merge,
+            TableEndpointKind::Cosmos => Method::Patch,
+        }
+    }
+
+    /// Fails operations only supported by Azure Table storage.
+    pub(crate) fn ensure_storage_endpoint(&self, operation: &str) -> azure_core::Result<()> {
+        match self.endpoint_kind {
+            TableEndpointKind::Storage => Ok(()),
+            TableEndpointKind::Cosmos => Err(Error::with_message(ErrorKind::Other, || {
+                format!("{operation} is not supported by the Cosmos DB Table API")
+            })),
+        }
+    }
+
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
     pub fn table_client<S: Into<String>>(&self, table_name: S) -> TableClient {
         TableClient::new(self.clone(), table_name)
     }
@@ -145,6 +382,151 @@ impl TableServiceClient {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[test]
+    fn endpoint_kind_from_connection_string() -> azure_core::Result<()> {
+        let key = azure_storage::EMULATOR_ACCOUNT_KEY;
+
+        let client = TableServiceClientBuilder::from_connection_string(&format!(
+            "DefaultEndpointsProtocol=https;AccountName=account;AccountKey={key};TableEndpoint=https://account.table.cosmos.azure.com:443/;"
+        ))?
+        .build();
+        assert_eq!(client.endpoint_kind(), TableEndpointKind::Cosmos);
+        assert_eq!(
+            client.url()?.as_str(),
+            "https://account.table.cosmos.azure.com/Tables"
+        );
+        assert_eq!(client.merge_method(), Method::Patch);
+        assert!(client.ensure_storage_endpoint("Get Table ACL").is_err());
+
+        let client = TableServiceClientBuilder::from_connection_string(&format!(
+            "DefaultEndpointsProtocol=https;AccountName=account;AccountKey={key};EndpointSuffix=core.windows.net"
+        ))?
+        .build();
+        assert_eq!(client.endpoint_kind(), TableEndpointKind::Storage);
+        assert_eq!(
+            client.url()?.as_str(),
+            "https://account.table.core.windows.net/Tables"
+        );
+        assert_eq!(client.merge_method(), Method::Merge);
+
+        let error = TableServiceClientBuilder::from_connection_string(
+            "SharedAccessSignature=sv=2019-12-12&sig=signature;TableEndpoint=https://account.table.cosmos.azure.com:443/;",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("no account name"));
+        Ok(())
+    }
+
+    #[test]
+    fn endpoint_kind_can_be_set() {
+        let client = TableServiceClientBuilder::with_location(
+            CloudLocation::Custom {
+                account: "localhost".to_owned(),
+                uri: "http://localhost:8902".to_owned(),
+            },
+            StorageCredentials::anonymous(),
+        )
+        .endpoint_kind(TableEndpointKind::Cosmos)
+        .build();
+        assert_eq!(client.endpoint_kind(), TableEndpointKind::Cosmos);
+    }
+
+    /// A token credential recording the scopes of the tokens requested.
+    #[derive(Debug, Default)]
+    struct RecordingCredential(std::sync::Mutex<Vec<String>>);
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl TokenCredential for RecordingCredential {
+        async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+            let mut requested = self.0.lock().unwrap();
+            requested.extend(scopes.iter().map(ToString::to_string));
+            Ok(AccessToken::new(
+                format!("token-{}", requested.len()),
+                time::OffsetDateTime::now_utc() + time::Duration::hours(1),
+            ))
+        }
+
+        async fn clear_cache(&self) -> azure_core::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A transport answering every request with an empty list of tables.
+    #[derive(Debug, Default)]
+    struct EmptyTables(std::sync::Mutex<Vec<Request>>);
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl azure_core::Policy for EmptyTables {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            next: &[Arc<dyn azure_core::Policy>],
+        ) -> azure_core::PolicyResult {
+            assert_eq!(0, next.len());
+            self.0.lock().unwrap().push(request.clone());
+            let mut headers = Headers::new();
+            for (name, value) in [
+                ("x-ms-request-id", "a3a0b1d4-f01a-0043-4f2c-8ba78e000000"),
+                ("x-ms-version", "2019-02-02"),
+                ("date", "Mon, 27 Jun 2022 13:38:48 GMT"),
+                ("server", "Microsoft-HTTPAPI/2.0"),
+            ] {
+                headers.insert(name, value);
+            }
+            Ok(Response::new(
+                azure_core::StatusCode::Ok,
+                headers,
+                Box::pin(azure_core::BytesStream::new(
+                    r#"{"odata.metadata":"https://account.table.core.windows.net/$metadata#Tables","value":[]}"#,
+                )),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn tokens_are_requested_for_the_scope_of_the_endpoint() -> azure_core::Result<()> {
+        for (endpoint_kind, scope) in [
+            (TableEndpointKind::Cosmos, COSMOS_TOKEN_SCOPE),
+            (
+                TableEndpointKind::Storage,
+                "https://storage.azure.com/.default",
+            ),
+        ] {
+            let credential = Arc::new(RecordingCredential::default());
+            let transport = Arc::new(EmptyTables::default());
+            let client = TableServiceClientBuilder::new(
+                "account",
+                StorageCredentials::token_credential(credential.clone()),
+            )
+            .endpoint_kind(endpoint_kind)
+            .transport(azure_core::TransportOptions::new_custom_policy(
+                transport.clone(),
+            ))
+            .build();
+
+            client.list().into_stream().next().await.unwrap()?;
+
+            assert_eq!(*credential.0.lock().unwrap(), [scope]);
+            let requests = transport.0.lock().unwrap();
+            assert_eq!(
+                requests[0]
+                    .headers()
+                    .get_optional_str(&azure_core::headers::AUTHORIZATION),
+                Some("Bearer token-1")
+            );
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "test_integration")]
 mod integration_tests {
@@ -0,0 +1,224 @@
+//! Entity properties typed with an [Entity Data Model](https://learn.microsoft.com/rest/api/storageservices/payload-format-for-table-service-operations#property-types-in-a-json-feed) type.
+//!
+//! The Table service infers the type of untyped JSON properties, so 64-bit
+//! integers, dates, GUIDs and binary values have to be sent along with a
+//! `Property@odata.type` annotation or they are stored as strings. Wrapping a
+//! field in one of the types of this module makes the SDK emit the annotation:
+//!
+//! ```
+//! use azure_data_tables::edm;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Reading {
+//!     #[serde(rename = "PartitionKey")]
+//!     sensor: String,
+//!     #[serde(rename = "RowKey")]
+//!     id: String,
+//!     ticks: edm::Int64,
+//!     taken_at: edm::DateTime,
+//! }
+//! ```
+//!
+//! The annotations are only added by the entity operations of this crate;
+//! serializing an entity with `serde_json` directly produces an intermediate
+//! representation the service does not understand.
+
+use crate::EntityProperty;
+use bytes::Bytes;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+const TYPE_MARKER: &str = "$edm.type";
+const VALUE_MARKER: &str = "$edm.value";
+const ANNOTATION_SUFFIX: &str = "@odata.type";
+
+macro_rules! edm_type {
+    ($(#[$meta:meta])* $name:ident($type:ty) => $variant:ident, $edm_type:literal) => {
+        $(#[$meta])*
+        pub struct $name(pub $type);
+
+        impl From<$type> for $name {
+            fn from(value: $type) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $type {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let property = EntityProperty::$variant(Clone::clone(&self.0));
+                let mut marker = Map::new();
+                marker.insert(TYPE_MARKER.to_owned(), property.edm_type().into());
+                marker.insert(VALUE_MARKER.to_owned(), property.to_json());
+                marker.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let value = Value::deserialize(deserializer)?;
+                match EntityProperty::from_json(value, Some($edm_type))
+                    .map_err(de::Error::custom)?
+                {
+                    EntityProperty::$variant(value) => Ok(Self(value)),
+                    _ => unreachable!("properties are converted to their annotated type"),
+                }
+            }
+        }
+    };
+}
+
+edm_type! {
+    /// An `Edm.Int64` property, sent as a string since JSON numbers cannot
+    /// hold every 64-bit integer.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    Int64(i64) => Int64, "Edm.Int64"
+}
+
+edm_type! {
+    /// An `Edm.Double` property. Not-a-number and infinite values are
+    /// supported.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+    Double(f64) => Double, "Edm.Double"
+}
+
+edm_type! {
+    /// An `Edm.DateTime` property.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    DateTime(OffsetDateTime) => DateTime, "Edm.DateTime"
+}
+
+edm_type! {
+    /// An `Edm.Guid` property.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    Guid(Uuid) => Guid, "Edm.Guid"
+}
+
+edm_type! {
+    /// An `Edm.Binary` property, sent base64 encoded.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    Binary(Vec<u8>) => Binary, "Edm.Binary"
+}
+
+/// Serializes an entity, replacing the typed properties with their value and
+/// an `@odata.type` annotation.
+pub(crate) fn to_json<T>(entity: &T) -> azure_core::Result<Bytes>
+where
+    T: ?Sized + Serialize,
+{
+    let value = match serde_json::to_value(entity)? {
+        Value::Object(properties) => Value::Object(annotate(properties)),
+        value => value,
+    };
+    Ok(Bytes::from(serde_json::to_vec(&value)?))
+}
+
+fn annotate(properties: Map<String, Value>) -> Map<String, Value> {
+    let mut annotated = Map::with_capacity(properties.len());
+    for (name, value) in properties {
+        match value {
+            Value::Object(mut marker)
+                if marker.len() == 2
+                    && marker.contains_key(TYPE_MARKER)
+                    && marker.contains_key(VALUE_MARKER) =>
+            {
+                let edm_type = marker.remove(TYPE_MARKER).unwrap_or_default();
+                let value = marker.remove(VALUE_MARKER).unwrap_or_default();
+                annotated.insert(annotation(&name), edm_type);
+                annotated.insert(name, value);
+            }
+            value => {
+                annotated.insert(name, value);
+            }
+        }
+    }
+    annotated
+}
+
+/// Returns the name of the property an `@odata.type` annotation refers to.
+pub(crate) fn annotated_property(name: &str) -> Option<&str> {
+    name.strip_suffix(ANNOTATION_SUFFIX)
+}
+
+pub(crate) fn annotation(name: &str) -> String {
+    format!("{name}{ANNOTATION_SUFFIX}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading {
+        #[serde(rename = "PartitionKey")]
+        sensor: String,
+        #[serde(rename = "RowKey")]
+        id: String,
+        ticks: Int64,
+        ratio: Double,
+        device: Guid,
+        raw: Binary,
+        taken_at: DateTime,
+        count: i32,
+    }
+
+    fn reading() -> Reading {
+        Reading {
+            sensor: "s1".to_owned(),
+            id: "1".to_owned(),
+            ticks: Int64(i64::MAX),
+            ratio: Double(f64::INFINITY),
+            device: Guid(Uuid::nil()),
+            raw: Binary(vec![1, 2, 3]),
+            taken_at: DateTime(datetime!(2024-01-02 03:04:05 UTC)),
+            count: 7,
+        }
+    }
+
+    #[test]
+    fn typed_properties_are_annotated() -> azure_core::Result<()> {
+        let json: Value = serde_json::from_slice(&to_json(&reading())?)?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "PartitionKey": "s1",
+                "RowKey": "1",
+                "ticks": "9223372036854775807",
+                "ticks@odata.type": "Edm.Int64",
+                "ratio": "Infinity",
+                "ratio@odata.type": "Edm.Double",
+                "device": "00000000-0000-0000-0000-000000000000",
+                "device@odata.type": "Edm.Guid",
+                "raw": "AQID",
+                "raw@odata.type": "Edm.Binary",
+                "taken_at": "2024-01-02T03:04:05Z",
+                "taken_at@odata.type": "Edm.DateTime",
+                "count": 7,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn typed_properties_are_read_back() -> azure_core::Result<()> {
+        let json = to_json(&reading())?;
+        let read: Reading = serde_json::from_slice(&json)?;
+        assert_eq!(read, reading());
+        Ok(())
+    }
+}
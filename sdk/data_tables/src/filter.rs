@@ -1,5 +1,11 @@
-use azure_core::{AppendToUrlQuery, Url};
-use std::borrow::Cow;
+use azure_core::{
+    date,
+    error::{Error, ErrorKind},
+    AppendToUrlQuery, Url,
+};
+use std::{borrow::Cow, fmt, ops::Not};
+use time::OffsetDateTime;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Filter(Cow<'static, str>);
@@ -25,3 +31,386 @@ where
         Self::new(s)
     }
 }
+
+impl TryFrom<FilterExpression> for Filter {
+    type Error = Error;
+
+    fn try_from(expression: FilterExpression) -> azure_core::Result<Self> {
+        expression.to_filter()
+    }
+}
+
+/// A property of the entities, to compare against a value in a filter.
+///
+/// ```
+/// # use azure_data_tables::prelude::*;
+/// let filter = Property::new("PartitionKey")
+///     .eq("Milan")
+///     .and(Property::new("Age").ge(FilterValue::parameter("min_age")))
+///     .bind("min_age", 30i64)
+///     .to_filter()
+///     .unwrap();
+///
+/// assert_eq!(filter, Filter::new("(PartitionKey eq 'Milan') and (Age ge 30L)"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property(String);
+
+impl Property {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn eq(self, value: impl Into<FilterValue>) -> FilterExpression {
+        self.compare("eq", value.into())
+    }
+
+    pub fn ne(self, value: impl Into<FilterValue>) -> FilterExpression {
+        self.compare("ne", value.into())
+    }
+
+    pub fn gt(self, value: impl Into<FilterValue>) -> FilterExpression {
+        self.compare("gt", value.into())
+    }
+
+    pub fn ge(self, value: impl Into<FilterValue>) -> FilterExpression {
+        self.compare("ge", value.into())
+    }
+
+    pub fn lt(self, value: impl Into<FilterValue>) -> FilterExpression {
+        self.compare("lt", value.into())
+    }
+
+    pub fn le(self, value: impl Into<FilterValue>) -> FilterExpression {
+        self.compare("le", value.into())
+    }
+
+    fn compare(self, operator: &'static str, value: FilterValue) -> FilterExpression {
+        FilterExpression(Node::Comparison {
+            property: self.0,
+            operator,
+            value,
+        })
+    }
+}
+
+/// A literal value of a filter, or a named parameter bound later on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    String(String),
+    Int32(i32),
+    Int64(i64),
+    /// A finite number, as filters have no literal for NaN or the infinities.
+    Double(f64),
+    Bool(bool),
+    DateTime(OffsetDateTime),
+    Guid(Uuid),
+    Binary(Vec<u8>),
+    Parameter(String),
+}
+
+impl FilterValue {
+    pub fn parameter(name: impl Into<String>) -> Self {
+        Self::Parameter(name.into())
+    }
+
+    fn write_literal(&self, f: &mut String, placeholders: bool) -> azure_core::Result<()> {
+        match self {
+            Self::String(value) => {
+                f.push('\'');
+                f.push_str(&value.replace('\'', "''"));
+                f.push('\'');
+            }
+            Self::Int32(value) => f.push_str(&value.to_string()),
+            Self::Int64(value) => {
+                f.push_str(&value.to_string());
+                f.push('L');
+            }
+            Self::Double(value) if !value.is_finite() => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("filter value {value} is not a finite number")
+                }))
+            }
+            Self::Double(value) => f.push_str(&format!("{value:?}")),
+            Self::Bool(value) => f.push_str(&value.to_string()),
+            Self::DateTime(value) => {
+                f.push_str("datetime'");
+                f.push_str(&date::to_rfc3339(value));
+                f.push('\'');
+            }
+            Self::Guid(value) => {
+                f.push_str("guid'");
+                f.push_str(&value.hyphenated().to_string());
+                f.push('\'');
+            }
+            Self::Binary(value) => {
+                f.push_str("X'");
+                for byte in value {
+                    f.push_str(&format!("{byte:02x}"));
+                }
+                f.push('\'');
+            }
+            Self::Parameter(name) if placeholders => {
+                f.push('@');
+                f.push_str(name);
+            }
+            Self::Parameter(name) => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("filter parameter {name} is not bound to a value")
+                }))
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! filter_value_from {
+    ($($type:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$type> for FilterValue {
+                fn from(value: $type) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+filter_value_from! {
+    &str => String,
+    String => String,
+    i32 => Int32,
+    i64 => Int64,
+    f64 => Double,
+    bool => Bool,
+    OffsetDateTime => DateTime,
+    Uuid => Guid,
+    Vec<u8> => Binary,
+    &[u8] => Binary,
+}
+
+/// A typed filter, combining property comparisons with `and`, `or` and `!`.
+///
+/// Literals are escaped and formatted following the OData conventions of the
+/// Table service. Values can be left as named parameters with
+/// `FilterValue::parameter` and bound later on, so the same expression can be
+/// reused across queries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpression(Node);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Comparison {
+        property: String,
+        operator: &'static str,
+        value: FilterValue,
+    },
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+}
+
+impl FilterExpression {
+    #[must_use]
+    pub fn and(self, other: FilterExpression) -> Self {
+        Self(Node::And(Box::new(self.0), Box::new(other.0)))
+    }
+
+    #[must_use]
+    pub fn or(self, other: FilterExpression) -> Self {
+        Self(Node::Or(Box::new(self.0), Box::new(other.0)))
+    }
+
+    /// Binds every occurrence of the parameter `name` to `value`.
+    #[must_use]
+    pub fn bind(self, name: &str, value: impl Into<FilterValue>) -> Self {
+        let value = value.into();
+        Self(self.0.bind(name, &value))
+    }
+
+    /// Renders the expression, failing if any of its parameters is not bound.
+    pub fn to_filter(&self) -> azure_core::Result<Filter> {
+        let mut filter = String::new();
+        self.0.write(&mut filter, false)?;
+        Ok(Filter::new(filter))
+    }
+}
+
+impl Not for FilterExpression {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(Node::Not(Box::new(self.0)))
+    }
+}
+
+impl Node {
+    fn bind(self, name: &str, value: &FilterValue) -> Self {
+        match self {
+            Self::Comparison {
+                property,
+                operator,
+                value: FilterValue::Parameter(parameter),
+            } if parameter == name => Self::Comparison {
+                property,
+                operator,
+                value: value.clone(),
+            },
+            Self::And(left, right) => Self::And(
+                Box::new(left.bind(name, value)),
+                Box::new(right.bind(name, value)),
+            ),
+            Self::Or(left, right) => Self::Or(
+                Box::new(left.bind(name, value)),
+                Box::new(right.bind(name, value)),
+            ),
+            Self::Not(inner) => Self::Not(Box::new(inner.bind(name, value))),
+            comparison @ Self::Comparison { .. } => comparison,
+        }
+    }
+
+    fn write(&self, f: &mut String, placeholders: bool) -> azure_core::Result<()> {
+        match self {
+            Self::Comparison {
+                property,
+                operator,
+                value,
+            } => {
+                f.push_str(property);
+                f.push(' ');
+                f.push_str(operator);
+                f.push(' ');
+                value.write_literal(f, placeholders)?;
+            }
+            Self::And(left, right) => Self::write_binary(f, left, "and", right, placeholders)?,
+            Self::Or(left, right) => Self::write_binary(f, left, "or", right, placeholders)?,
+            Self::Not(inner) => {
+                f.push_str("not (");
+                inner.write(f, placeholders)?;
+                f.push(')');
+            }
+        }
+        Ok(())
+    }
+
+    fn write_binary(
+        f: &mut String,
+        left: &Node,
+        operator: &str,
+        right: &Node,
+        placeholders: bool,
+    ) -> azure_core::Result<()> {
+        f.push('(');
+        left.write(f, placeholders)?;
+        f.push_str(") ");
+        f.push_str(operator);
+        f.push_str(" (");
+        right.write(f, placeholders)?;
+        f.push(')');
+        Ok(())
+    }
+}
+
+impl fmt::Display for FilterExpression {
+    /// Formats the expression, with unbound parameters shown as `@name`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut filter = String::new();
+        self.0.write(&mut filter, true).map_err(|_| fmt::Error)?;
+        write!(f, "{filter}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn literals_are_escaped() -> azure_core::Result<()> {
+        let cases = [
+            (Property::new("Name").eq("O'Brien"), "Name eq 'O''Brien'"),
+            (Property::new("Age").gt(42), "Age gt 42"),
+            (Property::new("Views").le(42i64), "Views le 42L"),
+            (Property::new("Score").ge(2.0), "Score ge 2.0"),
+            (Property::new("Active").ne(true), "Active ne true"),
+            (
+                Property::new("Created").lt(datetime!(2024-01-02 03:04:05 UTC)),
+                "Created lt datetime'2024-01-02T03:04:05Z'",
+            ),
+            (
+                Property::new("Id").eq(Uuid::nil()),
+                "Id eq guid'00000000-0000-0000-0000-000000000000'",
+            ),
+            (
+                Property::new("Hash").eq(&[0x0a_u8, 0xff][..]),
+                "Hash eq X'0aff'",
+            ),
+        ];
+
+        for (expression, expected) in cases {
+            assert_eq!(expression.to_filter()?, Filter::new(expected));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn non_finite_doubles_are_rejected() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let expression = Property::new("Score").eq(value);
+            assert!(expression.to_filter().is_err());
+
+            let expression = Property::new("Score")
+                .eq(FilterValue::parameter("score"))
+                .bind("score", value);
+            assert!(expression.to_filter().is_err());
+        }
+    }
+
+    #[test]
+    fn logical_operators_are_grouped() -> azure_core::Result<()> {
+        let expression = Property::new("PartitionKey").eq("Milan").and(
+            !Property::new("Age")
+                .lt(18)
+                .or(Property::new("Banned").eq(true)),
+        );
+
+        assert_eq!(
+            expression.to_filter()?,
+            Filter::new("(PartitionKey eq 'Milan') and (not ((Age lt 18) or (Banned eq true)))")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parameters_must_be_bound() -> azure_core::Result<()> {
+        let expression = Property::new("PartitionKey")
+            .eq(FilterValue::parameter("city"))
+            .and(Property::new("RowKey").ge(FilterValue::parameter("from")));
+
+        assert!(expression.to_filter().is_err());
+        assert_eq!(
+            expression.to_string(),
+            "(PartitionKey eq @city) and (RowKey ge @from)"
+        );
+
+        let filter = expression
+            .clone()
+            .bind("city", "Milan")
+            .bind("from", "A")
+            .to_filter()?;
+        assert_eq!(
+            filter,
+            Filter::new("(PartitionKey eq 'Milan') and (RowKey ge 'A')")
+        );
+
+        let filter = expression
+            .bind("city", "Rome")
+            .bind("from", "B")
+            .to_filter()?;
+        assert_eq!(
+            filter,
+            Filter::new("(PartitionKey eq 'Rome') and (RowKey ge 'B')")
+        );
+        Ok(())
+    }
+}
@@ -68,7 +68,9 @@ extern crate log;
 #[macro_use]
 extern crate azure_core;
 
+mod bulk_writer;
 pub mod clients;
+pub mod edm;
 mod entity_metadata;
 mod entity_with_metadata;
 mod filter;
@@ -78,14 +80,21 @@ pub mod operations;
 pub mod prelude;
 mod return_entity;
 mod select;
+mod table_entity;
+mod table_service_properties;
 mod top;
 mod transaction;
 mod transaction_operation;
+pub use bulk_writer::{BulkOperation, BulkWriteFailure, BulkWriteResponse, BulkWriter};
 pub use entity_metadata::EntityMetadata;
 pub use entity_with_metadata::EntityWithMetadata;
-pub use filter::Filter;
+pub use filter::{Filter, FilterExpression, FilterValue, Property};
 pub use if_match_condition::IfMatchCondition;
 pub use model::Table;
 pub use return_entity::ReturnEntity;
 pub use select::Select;
+pub use table_entity::{EntityProperty, TableEntity};
+pub use table_service_properties::{
+    Cors, CorsRule, Logging, Metrics, RetentionPolicy, TableServiceProperties,
+};
 pub use top::Top;
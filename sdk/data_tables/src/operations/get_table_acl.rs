@@ -0,0 +1,58 @@
+use crate::prelude::*;
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::Headers,
+    Method, Response,
+};
+use azure_storage::{
+    headers::CommonStorageResponseHeaders, PartialStoredAccessPolicy, PartialStoredAccessPolicyList,
+};
+use std::convert::TryInto;
+
+operation! {
+    GetTableACL,
+    client: TableClient,
+}
+
+impl GetTableACLBuilder {
+    pub fn into_future(mut self) -> GetTableACL {
+        Box::pin(async move {
+            self.client
+                .table_service_client()
+                .ensure_storage_endpoint("Get Table ACL")?;
+
+            let mut url = self.client.url()?;
+            url.path_segments_mut()
+                .map_err(|()| Error::message(ErrorKind::Other, "invalid table URL"))?
+                .pop()
+                .push(self.client.table_name());
+
+            url.query_pairs_mut().append_pair("comp", "acl");
+
+            let mut request =
+                TableClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetTableACLResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetTableACLResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub stored_access_policies: Vec<PartialStoredAccessPolicy>,
+}
+
+impl GetTableACLResponse {
+    async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+
+        Ok(GetTableACLResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            stored_access_policies: PartialStoredAccessPolicyList::from_xml(&body)?.stored_access,
+        })
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{prelude::*, TableServiceProperties};
+use azure_core::{headers::Headers, Method, Response};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    GetTableServiceProperties,
+    client: TableServiceClient,
+}
+
+impl GetTableServicePropertiesBuilder {
+    pub fn into_future(mut self) -> GetTableServiceProperties {
+        Box::pin(async move {
+            self.client
+                .ensure_storage_endpoint("Get Table Service Properties")?;
+
+            let mut url = self.client.service_url()?;
+
+            url.query_pairs_mut().append_pair("restype", "service");
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let mut request =
+                TableServiceClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetTableServicePropertiesResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetTableServicePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub table_service_properties: TableServiceProperties,
+}
+
+impl GetTableServicePropertiesResponse {
+    async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let table_service_properties = body.xml().await?;
+
+        Ok(GetTableServicePropertiesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            table_service_properties,
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use crate::prelude::*;
+use azure_core::{
+    date,
+    error::{ErrorKind, ResultExt},
+    headers::Headers,
+    Method, Response,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    GetTableServiceStats,
+    client: TableServiceClient,
+}
+
+impl GetTableServiceStatsBuilder {
+    pub fn into_future(mut self) -> GetTableServiceStats {
+        Box::pin(async move {
+            self.client
+                .ensure_storage_endpoint("Get Table Service Stats")?;
+
+            // statistics are only available from the secondary endpoint
+            let mut url = self.client.secondary_service_url()?;
+
+            url.query_pairs_mut().append_pair("restype", "service");
+            url.query_pairs_mut().append_pair("comp", "stats");
+
+            let mut request =
+                TableServiceClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetTableServiceStatsResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GeoReplicationStatus {
+    Live,
+    Bootstrap,
+    Unavailable,
+}
+
+#[derive(Debug, Clone)]
+pub struct GetTableServiceStatsResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub status: GeoReplicationStatus,
+    pub last_sync_time: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetTableServiceStatsResponseInternal {
+    pub geo_replication: GeoReplication,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GeoReplication {
+    pub status: GeoReplicationStatus,
+    pub last_sync_time: Option<String>,
+}
+
+impl GetTableServiceStatsResponse {
+    async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: GetTableServiceStatsResponseInternal = body.xml().await?;
+
+        Ok(GetTableServiceStatsResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            status: response.geo_replication.status,
+            last_sync_time: response
+                .geo_replication
+                .last_sync_time
+                .filter(|t| !t.is_empty())
+                .map(|t| {
+                    date::parse_rfc1123(&t)
+                        .context(ErrorKind::DataConversion, "failed to parse last sync time")
+                })
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_stats() -> azure_core::Result<()> {
+        let stats: GetTableServiceStatsResponseInternal = azure_core::xml::read_xml(
+            b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+            <StorageServiceStats><GeoReplication>\
+            <Status>live</Status>\
+            <LastSyncTime>Wed, 19 Jan 2022 22:28:43 GMT</LastSyncTime>\
+            </GeoReplication></StorageServiceStats>",
+        )?;
+        assert_eq!(stats.geo_replication.status, GeoReplicationStatus::Live);
+        assert_eq!(
+            stats.geo_replication.last_sync_time.as_deref(),
+            Some("Wed, 19 Jan 2022 22:28:43 GMT")
+        );
+        Ok(())
+    }
+}
@@ -20,7 +20,7 @@ impl InsertOrReplaceOrMergeEntityBuilder {
             let mut request = EntityClient::finalize_request(
                 url,
                 match self.operation {
-                    InsertOperation::InsertOrMerge => Method::Merge,
+                    InsertOperation::InsertOrMerge => self.client.merge_method(),
                     InsertOperation::InsertOrReplace => Method::Put,
                 },
                 headers,
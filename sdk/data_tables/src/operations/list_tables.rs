@@ -80,7 +80,8 @@ impl TryFrom<CollectedResponse> for ListTablesResponse {
 
         let continuation_next_table_name = response
             .headers()
-            .get_optional_string(&HeaderName::from_static("x-ms-continuation-nexttablename"));
+            .get_optional_string(&HeaderName::from_static("x-ms-continuation-nexttablename"))
+            .filter(|table_name| !table_name.is_empty());
 
         Ok(ListTablesResponse {
             common_storage_response_headers: response.headers().try_into()?,
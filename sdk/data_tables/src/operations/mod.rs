@@ -2,21 +2,37 @@ mod create_table;
 mod delete_entity;
 mod delete_table;
 mod get_entity;
+mod get_table_acl;
+mod get_table_service_properties;
+mod get_table_service_stats;
 mod insert_entity;
 pub(crate) mod insert_or_replace_or_merge_entity;
 mod list_tables;
 mod query_entity;
+mod set_table_acl;
+mod set_table_service_properties;
 mod transaction;
 pub(crate) mod update_or_merge_entity;
 pub use create_table::CreateTableBuilder;
 pub use delete_entity::DeleteEntityBuilder;
 pub use delete_table::DeleteTableBuilder;
 pub use get_entity::GetEntityBuilder;
+pub use get_table_acl::{GetTableACLBuilder, GetTableACLResponse};
+pub use get_table_service_properties::{
+    GetTableServicePropertiesBuilder, GetTableServicePropertiesResponse,
+};
+pub use get_table_service_stats::{
+    GeoReplicationStatus, GetTableServiceStatsBuilder, GetTableServiceStatsResponse,
+};
 pub use insert_entity::InsertEntityBuilder;
 pub use insert_or_replace_or_merge_entity::InsertOrReplaceOrMergeEntityBuilder;
 pub use list_tables::ListTablesBuilder;
 pub use query_entity::{QueryEntityBuilder, QueryEntityResponse};
-pub use transaction::TransactionBuilder;
+pub use set_table_acl::{SetTableACLBuilder, SetTableACLResponse};
+pub use set_table_service_properties::{
+    SetTableServicePropertiesBuilder, SetTableServicePropertiesResponse,
+};
+pub use transaction::{OperationResponse, TransactionBuilder, TransactionResponse};
 pub use update_or_merge_entity::UpdateOrMergeEntityBuilder;
 
 use crate::EntityWithMetadata;
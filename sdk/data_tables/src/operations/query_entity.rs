@@ -111,12 +111,16 @@ impl<E: DeserializeOwned + Send + Sync> TryFrom<CollectedResponse> for QueryEnti
 
         let headers = response.headers();
 
-        let next_partition_key = headers.get_optional_string(&HeaderName::from_static(
-            "x-ms-continuation-nextpartitionkey",
-        ));
-
-        let next_row_key =
-            headers.get_optional_string(&HeaderName::from_static("x-ms-continuation-nextrowkey"));
+        // the Cosmos DB Table API may send empty continuation headers
+        let next_partition_key = headers
+            .get_optional_string(&HeaderName::from_static(
+                "x-ms-continuation-nextpartitionkey",
+            ))
+            .filter(|partition_key| !partition_key.is_empty());
+
+        let next_row_key = headers
+            .get_optional_string(&HeaderName::from_static("x-ms-continuation-nextrowkey"))
+            .filter(|row_key| !row_key.is_empty());
 
         Ok(QueryEntityResponse {
             common_storage_response_headers: response.headers().try_into()?,
@@ -0,0 +1,62 @@
+use crate::prelude::*;
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::Headers,
+    Method, Response,
+};
+use azure_storage::{
+    headers::CommonStorageResponseHeaders, PartialStoredAccessPolicy, PartialStoredAccessPolicyList,
+};
+use std::convert::TryInto;
+
+operation! {
+    SetTableACL,
+    client: TableClient,
+    policies: Vec<PartialStoredAccessPolicy>,
+}
+
+impl SetTableACLBuilder {
+    pub fn into_future(mut self) -> SetTableACL {
+        Box::pin(async move {
+            self.client
+                .table_service_client()
+                .ensure_storage_endpoint("Set Table ACL")?;
+
+            let mut url = self.client.url()?;
+            url.path_segments_mut()
+                .map_err(|()| Error::message(ErrorKind::Other, "invalid table URL"))?
+                .pop()
+                .push(self.client.table_name());
+
+            url.query_pairs_mut().append_pair("comp", "acl");
+
+            let xml_body = PartialStoredAccessPolicyList::new(self.policies).to_xml();
+
+            let mut request = TableClient::finalize_request(
+                url,
+                Method::Put,
+                Headers::new(),
+                Some(xml_body.into()),
+            )?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetTableACLResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl std::convert::TryFrom<Response> for SetTableACLResponse {
+    type Error = Error;
+
+    fn try_from(response: Response) -> azure_core::Result<Self> {
+        Ok(SetTableACLResponse {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
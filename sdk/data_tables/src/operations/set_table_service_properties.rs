@@ -0,0 +1,52 @@
+use crate::{prelude::*, TableServiceProperties};
+use azure_core::{error::Error, headers::Headers, xml::to_xml_with_root, Method, Response};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    SetTableServiceProperties,
+    client: TableServiceClient,
+    properties: TableServiceProperties,
+}
+
+impl SetTableServicePropertiesBuilder {
+    pub fn into_future(mut self) -> SetTableServiceProperties {
+        Box::pin(async move {
+            self.client
+                .ensure_storage_endpoint("Set Table Service Properties")?;
+
+            let mut url = self.client.service_url()?;
+
+            url.query_pairs_mut().append_pair("restype", "service");
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let xml_body = to_xml_with_root("StorageServiceProperties", &self.properties)?;
+
+            let mut request = TableServiceClient::finalize_request(
+                url,
+                Method::Put,
+                Headers::new(),
+                Some(xml_body.into()),
+            )?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetTableServicePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl std::convert::TryFrom<Response> for SetTableServicePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: Response) -> azure_core::Result<Self> {
+        Ok(SetTableServicePropertiesResponse {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    prelude::*, transaction::TransactionOperations, transaction_operation::TransactionOperation,
-    IfMatchCondition,
+    edm, prelude::*, transaction::TransactionOperations,
+    transaction_operation::TransactionOperation, IfMatchCondition,
 };
 use azure_core::{
     error::{Error, ErrorKind},
@@ -9,7 +9,8 @@ use azure_core::{
     CollectedResponse, Etag, Method, Request, StatusCode, Url,
 };
 use azure_storage::headers::CommonStorageResponseHeaders;
-use serde::Serialize;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
 
 operation! {
@@ -33,7 +34,7 @@ impl TransactionBuilder {
         let mut request = Request::new(url, Method::Post);
         request.insert_header(ACCEPT, "application/json;odata=fullmetadata");
         request.insert_headers(&ContentType::APPLICATION_JSON);
-        request.set_json(&entity)?;
+        request.set_body(edm::to_json(&entity)?);
 
         self.transaction.add(TransactionOperation::new(request));
 
@@ -147,19 +148,35 @@ impl TransactionBuilder {
     }
 
     fn entity_operation<RK: Into<String>, E: Serialize>(
-        mut self,
+        self,
         row_key: RK,
         entity: E,
         method: Method,
         match_condition: Option<IfMatchCondition>,
+    ) -> azure_core::Result<Self> {
+        let body = edm::to_json(&entity)?;
+        self.serialized_entity_operation(row_key, body, method, match_condition)
+    }
+
+    /// Adds an operation on an entity already serialized with `edm::to_json`.
+    pub(crate) fn serialized_entity_operation<RK: Into<String>>(
+        mut self,
+        row_key: RK,
+        body: Bytes,
+        method: Method,
+        match_condition: Option<IfMatchCondition>,
     ) -> azure_core::Result<Self> {
         let entity_client = self.client.entity_client(row_key);
         let url = entity_client.url()?;
+        let method = match method {
+            Method::Merge => entity_client.merge_method(),
+            method => method,
+        };
 
         let mut request = Request::new(url, method);
         request.insert_header(ACCEPT, "application/json;odata=fullmetadata");
         request.insert_headers(&ContentType::APPLICATION_JSON);
-        request.set_json(&entity)?;
+        request.set_body(body);
         request.add_optional_header(&match_condition);
 
         self.transaction.add(TransactionOperation::new(request));
@@ -173,6 +190,19 @@ pub struct OperationResponse {
     pub location: Option<Url>,
     pub data_service_id: Option<String>,
     pub etag: Option<Etag>,
+    /// The error code returned for a failed operation.
+    pub error_code: Option<String>,
+    /// The error message returned for a failed operation. The service
+    /// prefixes it with the index of the failed operation in the transaction.
+    pub error_message: Option<String>,
+}
+
+impl OperationResponse {
+    /// The index, within the transaction, of the operation that made it fail.
+    pub fn failed_operation_index(&self) -> Option<usize> {
+        let (index, _) = self.error_message.as_deref()?.split_once(':')?;
+        index.parse().ok()
+    }
 }
 
 impl Default for OperationResponse {
@@ -182,6 +212,8 @@ impl Default for OperationResponse {
             location: None,
             data_service_id: None,
             etag: None,
+            error_code: None,
+            error_message: None,
         }
     }
 }
@@ -249,6 +281,11 @@ impl TryFrom<CollectedResponse> for TransactionResponse {
                             })?
                             .to_owned(),
                     );
+                } else if line.starts_with('{') {
+                    if let Ok(body) = serde_json::from_str::<ODataErrorBody>(line) {
+                        operation_response.error_code = Some(body.error.code);
+                        operation_response.error_message = Some(body.error.message.value);
+                    }
                 } else if line.starts_with("ETag:") {
                     operation_response.etag = Some(
                         line.split_whitespace()
@@ -268,3 +305,20 @@ impl TryFrom<CollectedResponse> for TransactionResponse {
         })
     }
 }
+
+#[derive(Debug, Deserialize)]
+struct ODataErrorBody {
+    #[serde(rename = "odata.error")]
+    error: ODataError,
+}
+
+#[derive(Debug, Deserialize)]
+struct ODataError {
+    code: String,
+    message: ODataErrorMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ODataErrorMessage {
+    value: String,
+}
@@ -22,7 +22,7 @@ impl UpdateOrMergeEntityBuilder {
             let mut request = EntityClient::finalize_request(
                 url,
                 match self.operation {
-                    UpdateOperation::Merge => Method::Merge,
+                    UpdateOperation::Merge => self.client.merge_method(),
                     UpdateOperation::Update => Method::Put,
                 },
                 headers,
@@ -1,4 +1,7 @@
 pub use crate::{
-    clients::{EntityClient, PartitionKeyClient, TableClient, TableServiceClient},
-    Filter, IfMatchCondition, ReturnEntity, Select, Table, Top,
+    clients::{
+        EntityClient, PartitionKeyClient, TableClient, TableEndpointKind, TableServiceClient,
+    },
+    BulkOperation, BulkWriter, EntityProperty, Filter, FilterExpression, FilterValue,
+    IfMatchCondition, Property, ReturnEntity, Select, Table, TableEntity, Top,
 };
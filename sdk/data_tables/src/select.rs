@@ -1,4 +1,11 @@
-use azure_core::{AppendToUrlQuery, Url};
+use azure_core::{
+    error::{Error, ErrorKind},
+    AppendToUrlQuery, Url,
+};
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
 use std::borrow::Cow;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -8,6 +15,42 @@ impl Select {
     pub fn new(s: impl Into<Cow<'static, str>>) -> Self {
         Self(s.into())
     }
+
+    /// Selects the properties deserialized into `T`.
+    ///
+    /// The property names are the serialized names of the fields of `T`, so
+    /// `#[serde(rename)]` is honored. `T` must be a struct without flattened
+    /// fields.
+    ///
+    /// ```
+    /// # use azure_data_tables::prelude::*;
+    /// #[derive(serde::Deserialize)]
+    /// struct Person {
+    ///     #[serde(rename = "RowKey")]
+    ///     id: String,
+    ///     name: String,
+    /// }
+    ///
+    /// assert_eq!(Select::from_entity::<Person>().unwrap(), Select::new("RowKey,name"));
+    /// ```
+    pub fn from_entity<T>() -> azure_core::Result<Self>
+    where
+        T: DeserializeOwned,
+    {
+        let mut fields = None;
+        // the deserializer only records the fields, so the result is always
+        // an error
+        let _ = T::deserialize(FieldNames(&mut fields));
+        match fields {
+            Some(fields) if !fields.is_empty() => Ok(Self::new(fields.join(","))),
+            _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "unable to select the fields of {}, it is not a struct with named fields",
+                    std::any::type_name::<T>()
+                )
+            })),
+        }
+    }
 }
 
 impl AppendToUrlQuery for Select {
@@ -25,3 +68,63 @@ where
         Self::new(s)
     }
 }
+
+/// A deserializer recording the fields of the struct asking to be
+/// deserialized.
+struct FieldNames<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        *self.0 = Some(fields);
+        Err(de::Error::custom("fields recorded"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Person {
+        #[serde(rename = "PartitionKey")]
+        city: String,
+        #[serde(rename = "RowKey")]
+        surname: String,
+        name: String,
+    }
+
+    #[test]
+    fn select_from_entity() -> azure_core::Result<()> {
+        assert_eq!(
+            Select::from_entity::<Person>()?,
+            Select::new("PartitionKey,RowKey,name")
+        );
+        assert!(Select::from_entity::<String>().is_err());
+        Ok(())
+    }
+}
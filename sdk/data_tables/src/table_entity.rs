@@ -0,0 +1,416 @@
+use crate::{edm, FilterValue};
+use azure_core::{
+    base64, date,
+    error::{Error, ErrorKind},
+};
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::collections::{btree_map, BTreeMap, HashMap};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// The value of a property of a `TableEntity`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntityProperty {
+    String(String),
+    Int32(i32),
+    Int64(i64),
+    Double(f64),
+    Bool(bool),
+    DateTime(OffsetDateTime),
+    Guid(Uuid),
+    Binary(Vec<u8>),
+}
+
+impl EntityProperty {
+    pub fn edm_type(&self) -> &'static str {
+        match self {
+            Self::String(_) => "Edm.String",
+            Self::Int32(_) => "Edm.Int32",
+            Self::Int64(_) => "Edm.Int64",
+            Self::Double(_) => "Edm.Double",
+            Self::Bool(_) => "Edm.Boolean",
+            Self::DateTime(_) => "Edm.DateTime",
+            Self::Guid(_) => "Edm.Guid",
+            Self::Binary(_) => "Edm.Binary",
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Self::Int32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of `Int32` and `Int64` properties.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int32(value) => Some((*value).into()),
+            Self::Int64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Double(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_datetime(&self) -> Option<OffsetDateTime> {
+        match self {
+            Self::DateTime(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_guid(&self) -> Option<Uuid> {
+        match self {
+            Self::Guid(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary(&self) -> Option<&[u8]> {
+        match self {
+            Self::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Whether the service needs an `@odata.type` annotation to store the
+    /// property with its type, rather than the type inferred from the JSON
+    /// value.
+    pub(crate) fn needs_annotation(&self) -> bool {
+        !matches!(self, Self::String(_) | Self::Int32(_) | Self::Bool(_))
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        match self {
+            Self::String(value) => value.as_str().into(),
+            Self::Int32(value) => (*value).into(),
+            Self::Int64(value) => value.to_string().into(),
+            Self::Double(value) if value.is_nan() => "NaN".into(),
+            Self::Double(value) if value.is_infinite() && *value > 0.0 => "Infinity".into(),
+            Self::Double(value) if value.is_infinite() => "-Infinity".into(),
+            Self::Double(value) => (*value).into(),
+            Self::Bool(value) => (*value).into(),
+            Self::DateTime(value) => date::to_rfc3339(value).into(),
+            Self::Guid(value) => value.hyphenated().to_string().into(),
+            Self::Binary(value) => base64::encode(value).into(),
+        }
+    }
+
+    /// Converts a JSON value to a property of the annotated type, or of the
+    /// type the service infers for unannotated values.
+    pub(crate) fn from_json(value: Value, edm_type: Option<&str>) -> azure_core::Result<Self> {
+        let invalid = |edm_type: &str, value: &Value| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("{value} is not a valid {edm_type} value")
+            })
+        };
+
+        let property = match (edm_type, value) {
+            (None | Some("Edm.String"), Value::String(value)) => Self::String(value),
+            (None | Some("Edm.Boolean"), Value::Bool(value)) => Self::Bool(value),
+            (None, Value::Number(number)) => match number.as_i64() {
+                Some(value) => i32::try_from(value)
+                    .map(Self::Int32)
+                    .unwrap_or(Self::Int64(value)),
+                None => Self::Double(number.as_f64().unwrap_or(f64::NAN)),
+            },
+            (Some(edm_type @ "Edm.Int32"), value) => value
+                .as_i64()
+                .and_then(|value| i32::try_from(value).ok())
+                .map(Self::Int32)
+                .ok_or_else(|| invalid(edm_type, &value))?,
+            (Some(edm_type @ "Edm.Int64"), value) => match &value {
+                Value::String(s) => s.parse().ok(),
+                Value::Number(number) => number.as_i64(),
+                _ => None,
+            }
+            .map(Self::Int64)
+            .ok_or_else(|| invalid(edm_type, &value))?,
+            (Some(edm_type @ "Edm.Double"), value) => match &value {
+                Value::Number(number) => number.as_f64(),
+                Value::String(s) => match s.as_str() {
+                    "NaN" => Some(f64::NAN),
+                    "Infinity" => Some(f64::INFINITY),
+                    "-Infinity" => Some(f64::NEG_INFINITY),
+                    s => s.parse().ok(),
+                },
+                _ => None,
+            }
+            .map(Self::Double)
+            .ok_or_else(|| invalid(edm_type, &value))?,
+            (Some(edm_type @ "Edm.DateTime"), value) => value
+                .as_str()
+                .and_then(|s| date::parse_rfc3339(s).ok())
+                .map(Self::DateTime)
+                .ok_or_else(|| invalid(edm_type, &value))?,
+            (Some(edm_type @ "Edm.Guid"), value) => value
+                .as_str()
+                .and_then(|s| s.parse().ok())
+                .map(Self::Guid)
+                .ok_or_else(|| invalid(edm_type, &value))?,
+            (Some(edm_type @ "Edm.Binary"), value) => value
+                .as_str()
+                .and_then(|s| base64::decode(s).ok())
+                .map(Self::Binary)
+                .ok_or_else(|| invalid(edm_type, &value))?,
+            (edm_type, value) => {
+                return Err(invalid(edm_type.unwrap_or("entity property"), &value))
+            }
+        };
+        Ok(property)
+    }
+}
+
+macro_rules! entity_property_from {
+    ($($type:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$type> for EntityProperty {
+                fn from(value: $type) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+entity_property_from! {
+    &str => String,
+    String => String,
+    i32 => Int32,
+    i64 => Int64,
+    f64 => Double,
+    bool => Bool,
+    OffsetDateTime => DateTime,
+    Uuid => Guid,
+    Vec<u8> => Binary,
+    &[u8] => Binary,
+}
+
+impl From<EntityProperty> for FilterValue {
+    fn from(property: EntityProperty) -> Self {
+        match property {
+            EntityProperty::String(value) => Self::String(value),
+            EntityProperty::Int32(value) => Self::Int32(value),
+            EntityProperty::Int64(value) => Self::Int64(value),
+            EntityProperty::Double(value) => Self::Double(value),
+            EntityProperty::Bool(value) => Self::Bool(value),
+            EntityProperty::DateTime(value) => Self::DateTime(value),
+            EntityProperty::Guid(value) => Self::Guid(value),
+            EntityProperty::Binary(value) => Self::Binary(value),
+        }
+    }
+}
+
+/// An entity without a schema, as a map of typed properties.
+///
+/// The types of the properties are read from, and written as, `@odata.type`
+/// annotations, so they round trip through the service.
+///
+/// ```
+/// # use azure_data_tables::prelude::*;
+/// let mut entity = TableEntity::new("Milan", "1");
+/// entity.insert("Visits", 42i64);
+///
+/// assert_eq!(entity.partition_key(), Some("Milan"));
+/// assert_eq!(entity.get("Visits").and_then(EntityProperty::as_i64), Some(42));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableEntity {
+    properties: BTreeMap<String, EntityProperty>,
+}
+
+impl TableEntity {
+    pub fn new(partition_key: impl Into<String>, row_key: impl Into<String>) -> Self {
+        let mut entity = Self::default();
+        entity.insert("PartitionKey", partition_key.into());
+        entity.insert("RowKey", row_key.into());
+        entity
+    }
+
+    pub fn partition_key(&self) -> Option<&str> {
+        self.get("PartitionKey").and_then(EntityProperty::as_str)
+    }
+
+    pub fn row_key(&self) -> Option<&str> {
+        self.get("RowKey").and_then(EntityProperty::as_str)
+    }
+
+    /// The time the entity was last modified, as set by the service.
+    pub fn timestamp(&self) -> Option<OffsetDateTime> {
+        self.get("Timestamp").and_then(EntityProperty::as_datetime)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EntityProperty> {
+        self.properties.get(name)
+    }
+
+    /// Sets a property, returning its previous value.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<EntityProperty>,
+    ) -> Option<EntityProperty> {
+        self.properties.insert(name.into(), value.into())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<EntityProperty> {
+        self.properties.remove(name)
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, String, EntityProperty> {
+        self.properties.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a TableEntity {
+    type Item = (&'a String, &'a EntityProperty);
+    type IntoIter = btree_map::Iter<'a, String, EntityProperty>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Serialize for TableEntity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        for (name, property) in &self.properties {
+            map.serialize_entry(name, &property.to_json())?;
+            if property.needs_annotation() {
+                map.serialize_entry(&edm::annotation(name), property.edm_type())?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TableEntity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values = Map::<String, Value>::deserialize(deserializer)?;
+
+        let edm_types: HashMap<&str, &str> = values
+            .iter()
+            .filter_map(|(name, value)| Some((edm::annotated_property(name)?, value.as_str()?)))
+            .collect();
+
+        let mut properties = BTreeMap::new();
+        for (name, value) in &values {
+            // skip the metadata of the entity and the annotations
+            if name.starts_with("odata.") || edm::annotated_property(name).is_some() {
+                continue;
+            }
+            let edm_type = edm_types.get(name.as_str()).copied();
+            let property =
+                EntityProperty::from_json(value.clone(), edm_type).map_err(de::Error::custom)?;
+            properties.insert(name.clone(), property);
+        }
+        Ok(Self { properties })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn entity_round_trip() -> azure_core::Result<()> {
+        let mut entity = TableEntity::new("Milan", "1");
+        entity.insert("Name", "Francesco");
+        entity.insert("Age", 42);
+        entity.insert("Visits", 1i64 << 40);
+        entity.insert("Score", 2.0);
+        entity.insert("Active", true);
+        entity.insert("Since", datetime!(2024-01-02 03:04:05 UTC));
+        entity.insert("Id", Uuid::nil());
+        entity.insert("Avatar", vec![0xffu8, 0x00]);
+
+        let json: Value = serde_json::from_slice(&edm::to_json(&entity)?)?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "Active": true,
+                "Age": 42,
+                "Avatar": "/wA=",
+                "Avatar@odata.type": "Edm.Binary",
+                "Id": "00000000-0000-0000-0000-000000000000",
+                "Id@odata.type": "Edm.Guid",
+                "Name": "Francesco",
+                "PartitionKey": "Milan",
+                "RowKey": "1",
+                "Score": 2.0,
+                "Score@odata.type": "Edm.Double",
+                "Since": "2024-01-02T03:04:05Z",
+                "Since@odata.type": "Edm.DateTime",
+                "Visits": "1099511627776",
+                "Visits@odata.type": "Edm.Int64",
+            })
+        );
+
+        let read: TableEntity = serde_json::from_value(json)?;
+        assert_eq!(read, entity);
+        Ok(())
+    }
+
+    #[test]
+    fn read_service_entity() -> azure_core::Result<()> {
+        let entity: TableEntity = serde_json::from_str(
+            r#"{
+                "odata.type": "account.people",
+                "odata.etag": "W/\"datetime'2024-01-02T03%3A04%3A05.1234567Z'\"",
+                "PartitionKey": "Milan",
+                "RowKey": "1",
+                "Timestamp@odata.type": "Edm.DateTime",
+                "Timestamp": "2024-01-02T03:04:05.1234567Z",
+                "Ratio": 0.5,
+                "Total@odata.type": "Edm.Int64",
+                "Total": "12"
+            }"#,
+        )?;
+
+        assert_eq!(entity.len(), 5);
+        assert_eq!(entity.row_key(), Some("1"));
+        assert_eq!(
+            entity.timestamp(),
+            Some(datetime!(2024-01-02 03:04:05.1234567 UTC))
+        );
+        assert_eq!(entity.get("Ratio"), Some(&EntityProperty::Double(0.5)));
+        assert_eq!(entity.get("Total"), Some(&EntityProperty::Int64(12)));
+        Ok(())
+    }
+}
@@ -169,7 +169,11 @@ fn canonicalize_header(headers: &Headers) -> String {
 }
 
 fn canonicalized_resource_table(account: &str, u: &Url) -> String {
-    format!("/{}{}", account, u.path())
+    // the comp parameter is the only one part of the resource
+    match u.query_pairs().find(|(name, _)| name == "comp") {
+        Some((_, comp)) => format!("/{}{}?comp={}", account, u.path(), comp),
+        None => format!("/{}{}", account, u.path()),
+    }
 }
 
 fn canonicalized_resource(account: &str, uri: &Url) -> String {
@@ -259,6 +263,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn table_canonicalized_resource_includes_comp() -> azure_core::Result<()> {
+        let url =
+            Url::parse("https://account.table.core.windows.net/?restype=service&comp=properties")?;
+        assert_eq!(
+            canonicalized_resource_table("account", &url),
+            "/account/?comp=properties"
+        );
+
+        let url = Url::parse("https://account.table.core.windows.net/Tables('people')?$top=1")?;
+        assert_eq!(
+            canonicalized_resource_table("account", &url),
+            "/account/Tables('people')"
+        );
+        Ok(())
+    }
+
     const SAMPLE_SAS_TOKEN: &str = "sp=r&st=1970-01-01T00:00:00Z&se=1970-01-01T00:00:00Z&spr=https&sv=1970-01-01&sr=c&sig=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
 
     #[tokio::test]
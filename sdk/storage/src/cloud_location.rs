@@ -50,6 +50,36 @@ impl CloudLocation {
         };
         Ok(Url::parse(&url)?)
     }
+
+    /// The base URL of the read-only secondary endpoint of a geo-redundant
+    /// account, for a given cloud location.
+    ///
+    /// Custom and emulator locations have no well-known secondary endpoint.
+    pub fn secondary_url(&self, service_type: ServiceType) -> azure_core::Result<Url> {
+        let url = match self {
+            CloudLocation::Public { account, .. } => {
+                format!(
+                    "https://{}-secondary.{}.core.windows.net",
+                    account,
+                    service_type.subdomain()
+                )
+            }
+            CloudLocation::China { account, .. } => {
+                format!(
+                    "https://{}-secondary.{}.core.chinacloudapi.cn",
+                    account,
+                    service_type.subdomain()
+                )
+            }
+            CloudLocation::Custom { .. } | CloudLocation::Emulator { .. } => {
+                return Err(azure_core::Error::message(
+                    azure_core::error::ErrorKind::Other,
+                    "the cloud location has no well-known secondary endpoint",
+                ))
+            }
+        };
+        Ok(Url::parse(&url)?)
+    }
 }
 
 impl TryFrom<&Url> for CloudLocation {
@@ -131,4 +161,22 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_secondary_url() -> azure_core::Result<()> {
+        let cloud_location = CloudLocation::Public {
+            account: "test".to_owned(),
+        };
+        assert_eq!(
+            cloud_location.secondary_url(ServiceType::Table)?,
+            Url::parse("https://test-secondary.table.core.windows.net")?
+        );
+
+        let emulator = CloudLocation::Emulator {
+            address: "127.0.0.1".to_owned(),
+            port: 10002,
+        };
+        assert!(emulator.secondary_url(ServiceType::Table).is_err());
+        Ok(())
+    }
 }
@@ -34,6 +34,8 @@ impl TryFrom<&Headers> for CommonStorageResponseHeaders {
 pub const CONTENT_CRC64: HeaderName = HeaderName::from_static("x-ms-content-crc64");
 pub const COPY_ID: HeaderName = HeaderName::from_static("x-ms-copy-id");
 pub const RENAME_SOURCE: HeaderName = HeaderName::from_static("x-ms-rename-source");
+pub const RESOURCE_TYPE: HeaderName = HeaderName::from_static("x-ms-resource-type");
+pub const UNDELETE_SOURCE: HeaderName = HeaderName::from_static("x-ms-undelete-source");
 
 pub fn content_crc64_from_headers(headers: &Headers) -> azure_core::Result<ConsistencyCRC64> {
     headers.get_as(&CONTENT_CRC64)
@@ -10,6 +10,7 @@ Please use these crates for additional functionality:
 - [`azure_data_tables`](https://crates.io/crates/azure_data_tables)
 - [`azure_storage_blobs`](https://crates.io/crates/azure_storage_blobs)
 - [`azure_storage_datalake`](https://crates.io/crates/azure_storage_datalake)
+- [`azure_storage_files`](https://crates.io/crates/azure_storage_files)
 - [`azure_storage_queues`](https://crates.io/crates/azure_storage_queues)
 */
 
@@ -44,7 +45,7 @@ pub mod headers;
 pub use copy_id::{copy_id_from_headers, CopyId};
 pub use copy_progress::CopyProgress;
 pub mod parsing_xml;
-mod stored_access_policy;
+pub mod stored_access_policy;
 pub use azure_core::error::{Error, ErrorKind, ResultExt};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -53,7 +54,10 @@ pub struct IPRange {
     pub end: std::net::IpAddr,
 }
 
-pub use stored_access_policy::{StoredAccessPolicy, StoredAccessPolicyList};
+pub use stored_access_policy::{
+    PartialStoredAccessPolicy, PartialStoredAccessPolicyList, StoredAccessPolicy,
+    StoredAccessPolicyBuilder, StoredAccessPolicyList,
+};
 
 pub use consistency::{ConsistencyCRC64, ConsistencyMD5};
 
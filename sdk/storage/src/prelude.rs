@@ -2,8 +2,15 @@ pub use crate::{
     consistency::{ConsistencyCRC64, ConsistencyMD5},
     shared_access_signature::{
         account_sas::{AccountSasPermissions, AccountSasResource, AccountSasResourceType},
-        service_sas::{BlobSasPermissions, BlobSignedResource},
+        service_sas::{
+            BlobSasPermissions, BlobSignedResource, FileSasPermissions, FileSignedResource,
+            QueueSasPermissions, TableSasPermissions,
+        },
         SasProtocol, SasToken,
     },
+    stored_access_policy::{
+        ContainerAccessPolicyBuilder, QueueAccessPolicyBuilder, ShareAccessPolicyBuilder,
+        StoredAccessPolicyBuilder, TableAccessPolicyBuilder,
+    },
     CopyId, IPRange, StorageCredentials,
 };
@@ -1,6 +1,11 @@
 use crate::shared_access_signature::{format_date, SasProtocol, SasToken};
-use azure_core::{auth::Secret, date::iso8601, hmac::hmac_sha256};
-use std::fmt;
+use azure_core::{
+    auth::Secret,
+    date::iso8601,
+    error::{Error, ErrorKind},
+    hmac::hmac_sha256,
+};
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 use url::form_urlencoded;
 use uuid::Uuid;
@@ -28,7 +33,7 @@ impl fmt::Display for BlobSignedResource {
 }
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct BlobSasPermissions {
     pub read: bool,             // r - Container | Directory | Blob
     pub add: bool,              // a - Container | Directory | Blob
@@ -91,6 +96,33 @@ impl fmt::Display for BlobSasPermissions {
     }
 }
 
+impl FromStr for BlobSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.read = true,
+                'a' => permissions.add = true,
+                'c' => permissions.create = true,
+                'w' => permissions.write = true,
+                'd' => permissions.delete = true,
+                'x' => permissions.delete_version = true,
+                'y' => permissions.permanent_delete = true,
+                'l' => permissions.list = true,
+                't' => permissions.tags = true,
+                'm' => permissions.move_ = true,
+                'e' => permissions.execute = true,
+                'o' => permissions.ownership = true,
+                'p' => permissions.permissions = true,
+                _ => return Err(unknown_permission(c)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct UserDeligationKey {
@@ -126,13 +158,16 @@ pub struct BlobSharedAccessSignature {
     key: SasKey,
     canonicalized_resource: String,
     resource: BlobSignedResource,
-    permissions: BlobSasPermissions, // sp
-    expiry: OffsetDateTime,          // se
-    start: Option<OffsetDateTime>,   // st
-    identifier: Option<String>,
+    permissions: Option<BlobSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,          // se
+    start: Option<OffsetDateTime>,           // st
+    identifier: Option<String>,              // si
     ip: Option<String>,
     protocol: Option<SasProtocol>,
-    signed_directory_depth: Option<usize>, // sdd
+    signed_directory_depth: Option<usize>,   // sdd
+    signed_authorized_oid: Option<String>,   // saoid
+    signed_unauthorized_oid: Option<String>, // suoid
+    signed_correlation_id: Option<String>,   // scid
 }
 
 impl BlobSharedAccessSignature {
@@ -150,29 +185,65 @@ impl BlobSharedAccessSignature {
             key: key.into(),
             canonicalized_resource,
             resource,
-            permissions,
-            expiry,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
             start: None,
             identifier: None,
             ip: None,
             protocol: None,
             signed_directory_depth: None,
+            signed_authorized_oid: None,
+            signed_unauthorized_oid: None,
+            signed_correlation_id: None,
+        }
+    }
+
+    /// Creates a signature bound to the stored access policy `identifier` of
+    /// the container.
+    ///
+    /// The permissions and expiry are taken from the policy unless set on the
+    /// signature, so revoking the policy revokes every token issued with it.
+    pub fn with_stored_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        identifier: String,
+        resource: BlobSignedResource,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            canonicalized_resource,
+            resource,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+            signed_directory_depth: None,
+            signed_authorized_oid: None,
+            signed_unauthorized_oid: None,
+            signed_correlation_id: None,
         }
     }
 
     setters! {
+        permissions: BlobSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
         start: OffsetDateTime => Some(start),
         identifier: String => Some(identifier),
         ip: String => Some(ip),
         protocol: SasProtocol => Some(protocol),
         signed_directory_depth: usize => Some(signed_directory_depth),
+        signed_authorized_oid: String => Some(signed_authorized_oid),
+        signed_unauthorized_oid: String => Some(signed_unauthorized_oid),
+        signed_correlation_id: String => Some(signed_correlation_id),
     }
 
     fn sign(&self) -> azure_core::Result<String> {
         let mut content = vec![
-            self.permissions.to_string(),
+            self.permissions.map(|p| p.to_string()).unwrap_or_default(),
             self.start.map_or(String::new(), format_date),
-            format_date(self.expiry),
+            self.expiry.map_or(String::new(), format_date),
             self.canonicalized_resource.clone(),
         ];
 
@@ -193,9 +264,9 @@ impl BlobSharedAccessSignature {
                     format_date(key.signed_expiry),
                     key.signed_service.to_string(),
                     key.signed_version.to_string(),
-                    String::new(), // SIGNED AUTHORIZED_OID
-                    String::new(), // SIGNED UNAUTHORIZED_OID
-                    String::new(), // SIGNED CORRELATION ID
+                    self.signed_authorized_oid.clone().unwrap_or_default(),
+                    self.signed_unauthorized_oid.clone().unwrap_or_default(),
+                    self.signed_correlation_id.clone().unwrap_or_default(),
                 ];
 
                 content.extend(user_delegated);
@@ -234,19 +305,36 @@ impl SasToken for BlobSharedAccessSignature {
                 ("sks", &key.signed_service),
                 ("skv", &key.signed_version),
             ]);
+
+            // these are only meaningful, and only signed, for user delegation SAS
+            if let Some(signed_authorized_oid) = &self.signed_authorized_oid {
+                form.append_pair("saoid", signed_authorized_oid);
+            }
+            if let Some(signed_unauthorized_oid) = &self.signed_unauthorized_oid {
+                form.append_pair("suoid", signed_unauthorized_oid);
+            }
+            if let Some(signed_correlation_id) = &self.signed_correlation_id {
+                form.append_pair("scid", signed_correlation_id);
+            }
         }
 
-        form.extend_pairs(&[
-            ("sv", SERVICE_SAS_VERSION),
-            ("sp", &self.permissions.to_string()),
-            ("sr", &self.resource.to_string()),
-            ("se", &format_date(self.expiry)),
-        ]);
+        form.append_pair("sv", SERVICE_SAS_VERSION);
+        if let Some(permissions) = self.permissions {
+            form.append_pair("sp", &permissions.to_string());
+        }
+        form.append_pair("sr", &self.resource.to_string());
+        if let Some(expiry) = self.expiry {
+            form.append_pair("se", &format_date(expiry));
+        }
 
         if let Some(start) = &self.start {
             form.append_pair("st", &format_date(*start));
         }
 
+        if let Some(identifier) = &self.identifier {
+            form.append_pair("si", identifier);
+        }
+
         if let Some(ip) = &self.ip {
             form.append_pair("sip", ip);
         }
@@ -265,6 +353,541 @@ impl SasToken for BlobSharedAccessSignature {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueSasPermissions {
+    pub read: bool,    // r - peek messages, get queue metadata
+    pub add: bool,     // a - put messages
+    pub update: bool,  // u - update messages
+    pub process: bool, // p - get and delete messages
+}
+
+impl fmt::Display for QueueSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.read {
+            write!(f, "r")?;
+        };
+        if self.add {
+            write!(f, "a")?;
+        };
+        if self.update {
+            write!(f, "u")?;
+        };
+        if self.process {
+            write!(f, "p")?;
+        };
+        Ok(())
+    }
+}
+
+impl FromStr for QueueSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.read = true,
+                'a' => permissions.add = true,
+                'u' => permissions.update = true,
+                'p' => permissions.process = true,
+                _ => return Err(unknown_permission(c)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
+pub struct QueueSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    permissions: Option<QueueSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,           // se
+    start: Option<OffsetDateTime>,            // st
+    identifier: Option<String>,               // si
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+}
+
+impl QueueSharedAccessSignature {
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        permissions: QueueSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    /// Creates a signature bound to the stored access policy `identifier` of
+    /// the queue.
+    pub fn with_stored_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        identifier: String,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    setters! {
+        permissions: QueueSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+    }
+
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.map(|p| p.to_string()).unwrap_or_default(),
+            self.start.map_or(String::new(), format_date),
+            self.expiry.map_or(String::new(), format_date),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+        ];
+
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for QueueSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+
+        form.append_pair("sv", SERVICE_SAS_VERSION);
+        append_policy_pairs(&mut form, self.permissions, self.expiry);
+
+        append_common_pairs(
+            &mut form,
+            self.start,
+            self.identifier.as_deref(),
+            self.ip.as_deref(),
+            self.protocol,
+        );
+
+        let sig = self.sign()?;
+        form.append_pair("sig", &sig);
+        Ok(form.finish())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TableSasPermissions {
+    pub read: bool,   // r - query entities
+    pub add: bool,    // a - insert entities
+    pub update: bool, // u - update entities
+    pub delete: bool, // d - delete entities
+}
+
+impl fmt::Display for TableSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.read {
+            write!(f, "r")?;
+        };
+        if self.add {
+            write!(f, "a")?;
+        };
+        if self.update {
+            write!(f, "u")?;
+        };
+        if self.delete {
+            write!(f, "d")?;
+        };
+        Ok(())
+    }
+}
+
+impl FromStr for TableSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.read = true,
+                'a' => permissions.add = true,
+                'u' => permissions.update = true,
+                'd' => permissions.delete = true,
+                _ => return Err(unknown_permission(c)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
+pub struct TableSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    table_name: String,                       // tn
+    permissions: Option<TableSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,           // se
+    start: Option<OffsetDateTime>,            // st
+    identifier: Option<String>,               // si
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+    start_partition_key: Option<String>, // spk
+    start_row_key: Option<String>,       // srk
+    end_partition_key: Option<String>,   // epk
+    end_row_key: Option<String>,         // erk
+}
+
+impl TableSharedAccessSignature {
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        table_name: String,
+        permissions: TableSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            table_name,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+            start_partition_key: None,
+            start_row_key: None,
+            end_partition_key: None,
+            end_row_key: None,
+        }
+    }
+
+    /// Creates a signature bound to the stored access policy `identifier` of
+    /// the table.
+    pub fn with_stored_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        table_name: String,
+        identifier: String,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            table_name,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+            start_partition_key: None,
+            start_row_key: None,
+            end_partition_key: None,
+            end_row_key: None,
+        }
+    }
+
+    setters! {
+        permissions: TableSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+        start_partition_key: String => Some(start_partition_key),
+        start_row_key: String => Some(start_row_key),
+        end_partition_key: String => Some(end_partition_key),
+        end_row_key: String => Some(end_row_key),
+    }
+
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.map(|p| p.to_string()).unwrap_or_default(),
+            self.start.map_or(String::new(), format_date),
+            self.expiry.map_or(String::new(), format_date),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+            self.start_partition_key.clone().unwrap_or_default(),
+            self.start_row_key.clone().unwrap_or_default(),
+            self.end_partition_key.clone().unwrap_or_default(),
+            self.end_row_key.clone().unwrap_or_default(),
+        ];
+
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for TableSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+
+        form.extend_pairs(&[("sv", SERVICE_SAS_VERSION), ("tn", &self.table_name)]);
+        append_policy_pairs(&mut form, self.permissions, self.expiry);
+
+        append_common_pairs(
+            &mut form,
+            self.start,
+            self.identifier.as_deref(),
+            self.ip.as_deref(),
+            self.protocol,
+        );
+
+        for (key, value) in [
+            ("spk", &self.start_partition_key),
+            ("srk", &self.start_row_key),
+            ("epk", &self.end_partition_key),
+            ("erk", &self.end_row_key),
+        ] {
+            if let Some(value) = value {
+                form.append_pair(key, value);
+            }
+        }
+
+        let sig = self.sign()?;
+        form.append_pair("sig", &sig);
+        Ok(form.finish())
+    }
+}
+
+pub enum FileSignedResource {
+    File,  // f
+    Share, // s
+}
+
+impl fmt::Display for FileSignedResource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::File => write!(f, "f"),
+            Self::Share => write!(f, "s"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileSasPermissions {
+    pub read: bool,   // r - Share | File
+    pub create: bool, // c - Share | File
+    pub write: bool,  // w - Share | File
+    pub delete: bool, // d - Share | File
+    pub list: bool,   // l - Share
+}
+
+impl fmt::Display for FileSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.read {
+            write!(f, "r")?;
+        };
+        if self.create {
+            write!(f, "c")?;
+        };
+        if self.write {
+            write!(f, "w")?;
+        };
+        if self.delete {
+            write!(f, "d")?;
+        };
+        if self.list {
+            write!(f, "l")?;
+        };
+        Ok(())
+    }
+}
+
+impl FromStr for FileSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.read = true,
+                'c' => permissions.create = true,
+                'w' => permissions.write = true,
+                'd' => permissions.delete = true,
+                'l' => permissions.list = true,
+                _ => return Err(unknown_permission(c)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
+pub struct FileSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    resource: FileSignedResource,            // sr
+    permissions: Option<FileSasPermissions>, // sp
+    expiry: Option<OffsetDateTime>,          // se
+    start: Option<OffsetDateTime>,           // st
+    identifier: Option<String>,              // si
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+}
+
+impl FileSharedAccessSignature {
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+        resource: FileSignedResource,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            resource,
+            permissions: Some(permissions),
+            expiry: Some(expiry),
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    /// Creates a signature bound to the stored access policy `identifier` of
+    /// the share.
+    pub fn with_stored_policy(
+        key: Secret,
+        canonicalized_resource: String,
+        identifier: String,
+        resource: FileSignedResource,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            resource,
+            permissions: None,
+            expiry: None,
+            start: None,
+            identifier: Some(identifier),
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    setters! {
+        permissions: FileSasPermissions => Some(permissions),
+        expiry: OffsetDateTime => Some(expiry),
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+    }
+
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.map(|p| p.to_string()).unwrap_or_default(),
+            self.start.map_or(String::new(), format_date),
+            self.expiry.map_or(String::new(), format_date),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+            String::new(), // SIGNED CACHE CONTROL
+            String::new(), // SIGNED CONTENT DISPOSITION
+            String::new(), // SIGNED CONTENT ENCODING
+            String::new(), // SIGNED CONTENT LANGUAGE
+            String::new(), // SIGNED CONTENT TYPE
+        ];
+
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for FileSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+
+        form.append_pair("sv", SERVICE_SAS_VERSION);
+        if let Some(permissions) = self.permissions {
+            form.append_pair("sp", &permissions.to_string());
+        }
+        form.append_pair("sr", &self.resource.to_string());
+        if let Some(expiry) = self.expiry {
+            form.append_pair("se", &format_date(expiry));
+        }
+
+        append_common_pairs(
+            &mut form,
+            self.start,
+            self.identifier.as_deref(),
+            self.ip.as_deref(),
+            self.protocol,
+        );
+
+        let sig = self.sign()?;
+        form.append_pair("sig", &sig);
+        Ok(form.finish())
+    }
+}
+
+fn unknown_permission(permission: char) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!("unknown SAS permission {permission}")
+    })
+}
+
+/// Appends the permissions and expiry, which may instead come from a stored
+/// access policy.
+fn append_policy_pairs<P: fmt::Display>(
+    form: &mut form_urlencoded::Serializer<String>,
+    permissions: Option<P>,
+    expiry: Option<OffsetDateTime>,
+) {
+    if let Some(permissions) = permissions {
+        form.append_pair("sp", &permissions.to_string());
+    }
+
+    if let Some(expiry) = expiry {
+        form.append_pair("se", &format_date(expiry));
+    }
+}
+
+/// Appends the optional query pairs shared by all the service SAS flavors.
+fn append_common_pairs(
+    form: &mut form_urlencoded::Serializer<String>,
+    start: Option<OffsetDateTime>,
+    identifier: Option<&str>,
+    ip: Option<&str>,
+    protocol: Option<SasProtocol>,
+) {
+    if let Some(start) = start {
+        form.append_pair("st", &format_date(start));
+    }
+
+    if let Some(identifier) = identifier {
+        form.append_pair("si", identifier);
+    }
+
+    if let Some(ip) = ip {
+        form.append_pair("sip", ip);
+    }
+
+    if let Some(protocol) = protocol {
+        form.append_pair("spr", &protocol.to_string());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -327,4 +950,197 @@ mod test {
         assert!(parsed.find(|(k, v)| k == "sdd" && v == "2").is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_user_delegation_sas_token_with_authorized_oid() -> azure_core::Result<()> {
+        let user_delegation_key = UserDeligationKey {
+            signed_oid: Uuid::nil(),
+            signed_tid: Uuid::nil(),
+            signed_start: OffsetDateTime::UNIX_EPOCH,
+            signed_expiry: OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            signed_service: "b".to_string(),
+            signed_version: SERVICE_SAS_VERSION.to_string(),
+            value: Secret::new(MOCK_SECRET_KEY),
+        };
+        let permissions = || BlobSasPermissions {
+            read: true,
+            ..Default::default()
+        };
+        let sas = || {
+            BlobSharedAccessSignature::new(
+                user_delegation_key.clone(),
+                String::from(MOCK_CANONICALIZED_RESOURCE),
+                permissions(),
+                OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+                BlobSignedResource::Directory,
+            )
+            .signed_directory_depth(1_usize)
+        };
+
+        let plain_token = sas().token()?;
+        let signed_token = sas()
+            .signed_authorized_oid("authorized-oid")
+            .signed_correlation_id("correlation-id")
+            .token()?;
+
+        let parsed = url::form_urlencoded::parse(signed_token.as_bytes()).collect::<Vec<_>>();
+        assert!(parsed
+            .iter()
+            .any(|(k, v)| k == "saoid" && v == "authorized-oid"));
+        assert!(parsed
+            .iter()
+            .any(|(k, v)| k == "scid" && v == "correlation-id"));
+        assert!(!parsed.iter().any(|(k, _)| k == "suoid"));
+
+        // the authorized object id is part of the string to sign
+        let sig = |token: &str| {
+            url::form_urlencoded::parse(token.as_bytes())
+                .find(|(k, _)| k == "sig")
+                .map(|(_, v)| v.into_owned())
+        };
+        assert_ne!(sig(&plain_token), sig(&signed_token));
+        Ok(())
+    }
+
+    #[test]
+    fn test_authorized_oid_ignored_for_key_sas() -> azure_core::Result<()> {
+        let signed_token = BlobSharedAccessSignature::new(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from(MOCK_CANONICALIZED_RESOURCE),
+            BlobSasPermissions {
+                read: true,
+                ..Default::default()
+            },
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            BlobSignedResource::Blob,
+        )
+        .signed_authorized_oid("authorized-oid")
+        .token()?;
+
+        assert_eq!(signed_token, "sv=2022-11-02&sp=r&sr=b&se=1970-01-08T00%3A00%3A00Z&sig=VRZjVZ1c%2FLz7IXCp17Sdx9%2BR9JDrnJdzE3NW56DMjNs%3D");
+        Ok(())
+    }
+
+    #[test]
+    fn test_stored_policy_sas_token() -> azure_core::Result<()> {
+        let signed_token = BlobSharedAccessSignature::with_stored_policy(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from(MOCK_CANONICALIZED_RESOURCE),
+            String::from("readers"),
+            BlobSignedResource::Container,
+        )
+        .token()?;
+
+        assert_eq!(
+            signed_token,
+            "sv=2022-11-02&sr=c&si=readers&sig=1gvNzn%2BgYeTb0jEywNis%2BT4hJGfHPsrsYgjVfOB8A5E%3D"
+        );
+
+        let signed_token = QueueSharedAccessSignature::with_stored_policy(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/queue/account/myqueue"),
+            String::from("policy"),
+        )
+        .expiry(OffsetDateTime::UNIX_EPOCH + Duration::days(7))
+        .token()?;
+
+        assert_eq!(signed_token, "sv=2022-11-02&se=1970-01-08T00%3A00%3A00Z&si=policy&sig=mEPhko15v%2FmwUL3JDHalBoqxpejyKWkQHVzc6QpMfbQ%3D");
+        Ok(())
+    }
+
+    #[test]
+    fn test_permissions_round_trip() -> azure_core::Result<()> {
+        let permissions: BlobSasPermissions = "racwdl".parse()?;
+        assert_eq!(permissions.to_string(), "racwdl");
+        assert_eq!("rp".parse::<QueueSasPermissions>()?.to_string(), "rp");
+        assert!("rz".parse::<TableSasPermissions>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_queue_sas_token() -> azure_core::Result<()> {
+        let permissions = QueueSasPermissions {
+            read: true,
+            add: true,
+            update: true,
+            process: true,
+        };
+        let signed_token = QueueSharedAccessSignature::new(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/queue/account/myqueue"),
+            permissions,
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+        )
+        .identifier("policy")
+        .token()?;
+
+        assert_eq!(signed_token, "sv=2022-11-02&sp=raup&se=1970-01-08T00%3A00%3A00Z&si=policy&sig=VKXxfEJajg1atqfmJPysPkVg%2BP2jogG%2BqAD3iZOvDCU%3D");
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_sas_token() -> azure_core::Result<()> {
+        let permissions = TableSasPermissions {
+            read: true,
+            ..Default::default()
+        };
+        let signed_token = TableSharedAccessSignature::new(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/table/account/mytable"),
+            String::from("mytable"),
+            permissions,
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+        )
+        .start_partition_key("pk1")
+        .end_partition_key("pk2")
+        .token()?;
+
+        assert_eq!(signed_token, "sv=2022-11-02&tn=mytable&sp=r&se=1970-01-08T00%3A00%3A00Z&spk=pk1&epk=pk2&sig=N2dC5X8RL%2FMl5eWJE93iWWx6aI0kZjIm0rGPy2p6Nw4%3D");
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_sas_token() -> azure_core::Result<()> {
+        let permissions = FileSasPermissions {
+            read: true,
+            write: true,
+            ..Default::default()
+        };
+        let signed_token = FileSharedAccessSignature::new(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/file/account/share/dir/file.txt"),
+            permissions,
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            FileSignedResource::File,
+        )
+        .token()?;
+
+        assert_eq!(signed_token, "sv=2022-11-02&sp=rw&sr=f&se=1970-01-08T00%3A00%3A00Z&sig=Ttoed15hdnh84OYhipaKY3qIFOtDy73dKNi5nmPIXdg%3D");
+        Ok(())
+    }
+
+    #[test]
+    fn test_stored_policy_sas_leaves_policy_fields_out() -> azure_core::Result<()> {
+        let table_token = TableSharedAccessSignature::with_stored_policy(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/table/account/mytable"),
+            String::from("mytable"),
+            String::from("policy"),
+        )
+        .token()?;
+        let share_token = FileSharedAccessSignature::with_stored_policy(
+            Secret::new(MOCK_SECRET_KEY),
+            String::from("/file/account/myshare"),
+            String::from("policy"),
+            FileSignedResource::Share,
+        )
+        .token()?;
+
+        for token in [table_token, share_token] {
+            let parsed = url::form_urlencoded::parse(token.as_bytes()).collect::<Vec<_>>();
+            assert!(parsed.iter().any(|(k, v)| k == "si" && v == "policy"));
+            assert!(!parsed.iter().any(|(k, _)| k == "sp" || k == "se"));
+        }
+        Ok(())
+    }
 }
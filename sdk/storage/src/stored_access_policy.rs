@@ -1,4 +1,18 @@
-use azure_core::{date, xml::read_xml};
+//! Stored access policies shared by containers, queues, tables and shares.
+//!
+//! A service SAS can be bound to one of these policies by its identifier, in
+//! which case revoking or changing the policy affects every token issued with
+//! it.
+
+use crate::shared_access_signature::service_sas::{
+    BlobSasPermissions, FileSasPermissions, QueueSasPermissions, TableSasPermissions,
+};
+use azure_core::{
+    date::{self, iso8601},
+    error::{Error, ErrorKind},
+    xml::read_xml,
+};
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -13,52 +27,153 @@ impl StoredAccessPolicyList {
         }
     }
 
+    /// Parses a list of complete policies. Lists holding policies that leave
+    /// out their start, expiry or permission must be parsed with
+    /// [`PartialStoredAccessPolicyList::from_xml`] instead.
     pub fn from_xml(bytes: &[u8]) -> azure_core::Result<Self> {
-        let sis: SignedIdentifiers = read_xml(bytes)?;
-        Ok(sis.into())
+        PartialStoredAccessPolicyList::from_xml(bytes)?.try_into()
     }
 
     pub fn to_xml(&self) -> String {
-        let mut s = String::new();
-        s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<SignedIdentifiers>\n");
-        for sa in &self.stored_access {
-            s.push_str("\t<SignedIdentifier>\n");
+        signed_identifiers_xml(self.stored_access.iter().map(|sa| {
+            (
+                sa.id.as_str(),
+                Some(&sa.start),
+                Some(&sa.expiry),
+                Some(sa.permission.as_str()),
+            )
+        }))
+    }
+}
 
-            s.push_str("\t\t<Id>");
-            s.push_str(&sa.id);
-            s.push_str("\n\t\t</Id>\n");
+impl TryFrom<PartialStoredAccessPolicyList> for StoredAccessPolicyList {
+    type Error = Error;
 
-            s.push_str("<AccessPolicy>\n");
+    fn try_from(list: PartialStoredAccessPolicyList) -> azure_core::Result<Self> {
+        let stored_access = list
+            .stored_access
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<azure_core::Result<_>>()?;
+        Ok(Self { stored_access })
+    }
+}
 
-            s.push_str("\t\t\t<Start>");
-            s.push_str(&date::to_rfc3339(&sa.start));
-            s.push_str("</Start>\n");
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredAccessPolicy {
+    pub id: String,
+    pub start: OffsetDateTime,
+    pub expiry: OffsetDateTime,
+    pub permission: String,
+}
 
-            s.push_str("\t\t\t<Expiry>");
-            s.push_str(&date::to_rfc3339(&sa.expiry));
-            s.push_str("</Expiry>\n");
+impl StoredAccessPolicy {
+    pub fn new<A, B>(id: A, start: OffsetDateTime, expiry: OffsetDateTime, permission: B) -> Self
+    where
+        A: Into<String>,
+        B: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            start,
+            expiry,
+            permission: permission.into(),
+        }
+    }
 
-            s.push_str("\t\t\t<Permission>");
-            s.push_str(&sa.permission);
-            s.push_str("</Permission>\n");
-            s.push_str("\t\t</AccessPolicy>\n\t</SignedIdentifier>\n");
+    /// Parses the permission string of the policy into the typed permissions
+    /// of a service.
+    pub fn permissions<T>(&self) -> azure_core::Result<T>
+    where
+        T: FromStr<Err = azure_core::Error>,
+    {
+        self.permission.parse()
+    }
+}
+
+impl TryFrom<PartialStoredAccessPolicy> for StoredAccessPolicy {
+    type Error = Error;
+
+    fn try_from(policy: PartialStoredAccessPolicy) -> azure_core::Result<Self> {
+        let missing = |field: &str| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "stored access policy {} does not set its {field}",
+                    policy.id
+                )
+            })
+        };
+        let start = policy.start.ok_or_else(|| missing("start"))?;
+        let expiry = policy.expiry.ok_or_else(|| missing("expiry"))?;
+        let permission = policy.permission.ok_or_else(|| missing("permission"))?;
+        Ok(Self {
+            id: policy.id,
+            start,
+            expiry,
+            permission,
+        })
+    }
+}
+
+/// A list of stored access policies, any of which may leave out its start,
+/// expiry or permission.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartialStoredAccessPolicyList {
+    pub stored_access: Vec<PartialStoredAccessPolicy>,
+}
+
+impl PartialStoredAccessPolicyList {
+    pub fn new(list: Vec<PartialStoredAccessPolicy>) -> Self {
+        Self {
+            stored_access: list,
         }
+    }
+
+    pub fn from_xml(bytes: &[u8]) -> azure_core::Result<Self> {
+        let sis: SignedIdentifiers = read_xml(bytes)?;
+        Ok(sis.into())
+    }
+
+    pub fn to_xml(&self) -> String {
+        signed_identifiers_xml(self.stored_access.iter().map(|sa| {
+            (
+                sa.id.as_str(),
+                sa.start.as_ref(),
+                sa.expiry.as_ref(),
+                sa.permission.as_deref(),
+            )
+        }))
+    }
+
+    /// The policies setting a start, an expiry and a permission.
+    pub fn complete(&self) -> Vec<StoredAccessPolicy> {
+        self.stored_access
+            .iter()
+            .cloned()
+            .filter_map(|policy| policy.try_into().ok())
+            .collect()
+    }
+}
 
-        s.push_str("</SignedIdentifiers>");
-        s
+impl From<StoredAccessPolicyList> for PartialStoredAccessPolicyList {
+    fn from(list: StoredAccessPolicyList) -> Self {
+        Self::new(list.stored_access.into_iter().map(Into::into).collect())
     }
 }
 
-impl From<SignedIdentifiers> for StoredAccessPolicyList {
+impl From<SignedIdentifiers> for PartialStoredAccessPolicyList {
     fn from(si: SignedIdentifiers) -> Self {
         let list = si
             .signed_identifiers
             .into_iter()
-            .map(|si| StoredAccessPolicy {
-                id: si.id,
-                start: si.access_policy.start,
-                expiry: si.access_policy.expiry,
-                permission: si.access_policy.permission,
+            .map(|si| {
+                let access_policy = si.access_policy.unwrap_or_default();
+                PartialStoredAccessPolicy {
+                    id: si.id,
+                    start: access_policy.start,
+                    expiry: access_policy.expiry,
+                    permission: access_policy.permission,
+                }
             })
             .collect();
         Self {
@@ -67,25 +182,152 @@ impl From<SignedIdentifiers> for StoredAccessPolicyList {
     }
 }
 
+/// A stored access policy that may leave out its start, expiry or
+/// permission, in which case they must be supplied by the SAS tokens bound to
+/// it instead.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct StoredAccessPolicy {
+pub struct PartialStoredAccessPolicy {
     pub id: String,
-    pub start: OffsetDateTime,
-    pub expiry: OffsetDateTime,
-    pub permission: String,
+    pub start: Option<OffsetDateTime>,
+    pub expiry: Option<OffsetDateTime>,
+    pub permission: Option<String>,
 }
 
-impl StoredAccessPolicy {
-    pub fn new<A, B>(id: A, start: OffsetDateTime, expiry: OffsetDateTime, permission: B) -> Self
+impl PartialStoredAccessPolicy {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            start: None,
+            expiry: None,
+            permission: None,
+        }
+    }
+
+    setters! {
+        start: OffsetDateTime => Some(start),
+        expiry: OffsetDateTime => Some(expiry),
+        permission: String => Some(permission),
+    }
+
+    /// Parses the permission string of the policy, if any, into the typed
+    /// permissions of a service.
+    pub fn permissions<T>(&self) -> azure_core::Result<Option<T>>
     where
-        A: Into<String>,
-        B: Into<String>,
+        T: FromStr<Err = azure_core::Error>,
     {
+        self.permission.as_deref().map(str::parse).transpose()
+    }
+}
+
+impl From<StoredAccessPolicy> for PartialStoredAccessPolicy {
+    fn from(policy: StoredAccessPolicy) -> Self {
+        Self {
+            id: policy.id,
+            start: Some(policy.start),
+            expiry: Some(policy.expiry),
+            permission: Some(policy.permission),
+        }
+    }
+}
+
+/// Builds a [`PartialStoredAccessPolicy`] from the typed permissions of a
+/// service.
+#[derive(Debug, Clone)]
+pub struct StoredAccessPolicyBuilder<T> {
+    id: String,
+    start: Option<OffsetDateTime>,
+    expiry: Option<OffsetDateTime>,
+    permissions: Option<T>,
+}
+
+impl<T> StoredAccessPolicyBuilder<T>
+where
+    T: fmt::Display,
+{
+    pub fn new(id: impl Into<String>) -> Self {
         Self {
             id: id.into(),
-            start,
-            expiry,
-            permission: permission.into(),
+            start: None,
+            expiry: None,
+            permissions: None,
+        }
+    }
+
+    setters! {
+        start: OffsetDateTime => Some(start),
+        expiry: OffsetDateTime => Some(expiry),
+        permissions: T => Some(permissions),
+    }
+
+    pub fn build(self) -> PartialStoredAccessPolicy {
+        PartialStoredAccessPolicy {
+            id: self.id,
+            start: self.start,
+            expiry: self.expiry,
+            permission: self.permissions.map(|p| p.to_string()),
+        }
+    }
+}
+
+pub type ContainerAccessPolicyBuilder = StoredAccessPolicyBuilder<BlobSasPermissions>;
+pub type QueueAccessPolicyBuilder = StoredAccessPolicyBuilder<QueueSasPermissions>;
+pub type TableAccessPolicyBuilder = StoredAccessPolicyBuilder<TableSasPermissions>;
+pub type ShareAccessPolicyBuilder = StoredAccessPolicyBuilder<FileSasPermissions>;
+
+fn signed_identifiers_xml<'a>(
+    policies: impl Iterator<
+        Item = (
+            &'a str,
+            Option<&'a OffsetDateTime>,
+            Option<&'a OffsetDateTime>,
+            Option<&'a str>,
+        ),
+    >,
+) -> String {
+    let mut s = String::new();
+    s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<SignedIdentifiers>\n");
+    for (id, start, expiry, permission) in policies {
+        s.push_str("\t<SignedIdentifier>\n");
+
+        s.push_str("\t\t<Id>");
+        push_escaped(&mut s, id);
+        s.push_str("</Id>\n");
+
+        s.push_str("\t\t<AccessPolicy>\n");
+
+        if let Some(start) = start {
+            s.push_str("\t\t\t<Start>");
+            s.push_str(&date::to_rfc3339(start));
+            s.push_str("</Start>\n");
+        }
+
+        if let Some(expiry) = expiry {
+            s.push_str("\t\t\t<Expiry>");
+            s.push_str(&date::to_rfc3339(expiry));
+            s.push_str("</Expiry>\n");
+        }
+
+        if let Some(permission) = permission {
+            s.push_str("\t\t\t<Permission>");
+            push_escaped(&mut s, permission);
+            s.push_str("</Permission>\n");
+        }
+        s.push_str("\t\t</AccessPolicy>\n\t</SignedIdentifier>\n");
+    }
+
+    s.push_str("</SignedIdentifiers>");
+    s
+}
+
+fn push_escaped(s: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => s.push_str("&amp;"),
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            '"' => s.push_str("&quot;"),
+            '\'' => s.push_str("&apos;"),
+            c => s.push(c),
         }
     }
 }
@@ -100,17 +342,17 @@ struct SignedIdentifiers {
 #[serde(rename_all = "PascalCase")]
 struct SignedIdentifier {
     id: String,
-    access_policy: AccessPolicy,
+    access_policy: Option<AccessPolicy>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct AccessPolicy {
-    #[serde(with = "azure_core::date::rfc3339")]
-    start: OffsetDateTime,
-    #[serde(with = "azure_core::date::rfc3339")]
-    expiry: OffsetDateTime,
-    permission: String,
+    #[serde(default, with = "iso8601::option")]
+    start: Option<OffsetDateTime>,
+    #[serde(default, with = "iso8601::option")]
+    expiry: Option<OffsetDateTime>,
+    permission: Option<String>,
 }
 
 #[cfg(test)]
@@ -141,7 +383,8 @@ mod test {
 
         let sis: SignedIdentifiers = read_xml(resp.as_bytes()).unwrap();
         assert_eq!(sis.signed_identifiers.len(), 2);
-        let sap: StoredAccessPolicyList = sis.into();
+        let sap: StoredAccessPolicyList =
+            PartialStoredAccessPolicyList::from(sis).try_into().unwrap();
         let sxml = sap.to_xml();
 
         fn remove_whitespace(mut s: String) -> String {
@@ -150,4 +393,66 @@ mod test {
         }
         assert_eq!(remove_whitespace(sxml), remove_whitespace(resp.to_owned()));
     }
+
+    #[test]
+    fn parse_partial_policy() -> azure_core::Result<()> {
+        let resp = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+    <SignedIdentifiers>
+      <SignedIdentifier>
+          <Id>readers</Id>
+          <AccessPolicy>
+            <Permission>rl</Permission>
+          </AccessPolicy>
+      </SignedIdentifier>
+    </SignedIdentifiers>";
+
+        assert!(StoredAccessPolicyList::from_xml(resp.as_bytes()).is_err());
+
+        let list = PartialStoredAccessPolicyList::from_xml(resp.as_bytes())?;
+        assert!(list.complete().is_empty());
+        let policy = &list.stored_access[0];
+        assert_eq!(policy.start, None);
+        assert_eq!(policy.expiry, None);
+        assert_eq!(
+            policy.permissions::<BlobSasPermissions>()?,
+            Some(BlobSasPermissions {
+                read: true,
+                list: true,
+                ..Default::default()
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn build_typed_policy() {
+        let policy = QueueAccessPolicyBuilder::new("processors")
+            .expiry(OffsetDateTime::UNIX_EPOCH)
+            .permissions(QueueSasPermissions {
+                read: true,
+                process: true,
+                ..Default::default()
+            })
+            .build();
+
+        assert_eq!(policy.permission.as_deref(), Some("rp"));
+        assert_eq!(policy.start, None);
+
+        let xml = PartialStoredAccessPolicyList::new(vec![policy]).to_xml();
+        assert!(xml.contains("<Id>processors</Id>"));
+        assert!(xml.contains("<Expiry>1970-01-01T00:00:00Z</Expiry>"));
+        assert!(!xml.contains("<Start>"));
+    }
+
+    #[test]
+    fn escape_identifiers() -> azure_core::Result<()> {
+        let policy = PartialStoredAccessPolicy::new("a<b>&\"c'").permission("r&w");
+        let xml = PartialStoredAccessPolicyList::new(vec![policy.clone()]).to_xml();
+        assert!(xml.contains("<Id>a&lt;b&gt;&amp;&quot;c&apos;</Id>"));
+        assert!(xml.contains("<Permission>r&amp;w</Permission>"));
+
+        let list = PartialStoredAccessPolicyList::from_xml(xml.as_bytes())?;
+        assert_eq!(list.stored_access, vec![policy]);
+        Ok(())
+    }
 }
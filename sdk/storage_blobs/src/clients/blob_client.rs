@@ -289,6 +289,34 @@ impl BlobClient {
         ))
     }
 
+    /// Create a shared access signature bound to a stored access policy of
+    /// the container.
+    pub async fn stored_policy_shared_access_signature(
+        &self,
+        identifier: impl Into<String>,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        let creds = self.container_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource = format!(
+            "/blob/{}/{}/{}",
+            account,
+            self.container_client.container_name(),
+            self.blob_name()
+        );
+        Ok(BlobSharedAccessSignature::with_stored_policy(
+            key.clone(),
+            canonicalized_resource,
+            identifier.into(),
+            BlobSignedResource::Blob,
+        ))
+    }
+
     /// Create a signed blob url
     pub fn generate_signed_blob_url<T>(&self, signature: &T) -> azure_core::Result<Url>
     where
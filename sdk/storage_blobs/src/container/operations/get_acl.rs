@@ -3,7 +3,7 @@ use crate::{
     prelude::*,
 };
 use azure_core::{date, headers::*, prelude::*, Method, RequestId, Response};
-use azure_storage::StoredAccessPolicyList;
+use azure_storage::{PartialStoredAccessPolicyList, StoredAccessPolicyList};
 use time::OffsetDateTime;
 
 operation! {
@@ -35,7 +35,11 @@ pub struct GetACLResponse {
     pub last_modified: OffsetDateTime,
     pub request_id: RequestId,
     pub date: OffsetDateTime,
+    /// The policies setting a start, an expiry and a permission.
     pub stored_access_policy_list: StoredAccessPolicyList,
+    /// Every policy of the container, including those leaving out their
+    /// start, expiry or permission.
+    pub partial_stored_access_policy_list: PartialStoredAccessPolicyList,
 }
 
 impl GetACLResponse {
@@ -57,7 +61,9 @@ impl GetACLResponse {
         let date = headers.get_str(&DATE)?;
         let date = date::parse_rfc1123(date)?;
 
-        let stored_access_policy_list = StoredAccessPolicyList::from_xml(&body)?;
+        let partial_stored_access_policy_list = PartialStoredAccessPolicyList::from_xml(&body)?;
+        let stored_access_policy_list =
+            StoredAccessPolicyList::new(partial_stored_access_policy_list.complete());
 
         Ok(GetACLResponse {
             public_access,
@@ -66,6 +72,70 @@ impl GetACLResponse {
             request_id,
             date,
             stored_access_policy_list,
+            partial_stored_access_policy_list,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{BytesStream, StatusCode};
+
+    #[tokio::test]
+    async fn parse_partial_policies() -> azure_core::Result<()> {
+        const BODY: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<SignedIdentifiers>
+    <SignedIdentifier>
+        <Id>readers</Id>
+        <AccessPolicy>
+            <Expiry>2030-01-01T00:00:00Z</Expiry>
+            <Permission>rl</Permission>
+        </AccessPolicy>
+    </SignedIdentifier>
+    <SignedIdentifier>
+        <Id>revoked</Id>
+    </SignedIdentifier>
+</SignedIdentifiers>";
+
+        let mut headers = Headers::new();
+        for (name, value) in [
+            ("etag", "\"0x8DA583A4F7B9C3E\""),
+            ("last-modified", "Mon, 27 Jun 2022 13:38:48 GMT"),
+            ("x-ms-request-id", "a3a0b1d4-f01a-0043-4f2c-8ba78e000000"),
+            ("date", "Mon, 27 Jun 2022 13:38:48 GMT"),
+            ("x-ms-blob-public-access", "blob"),
+        ] {
+            headers.insert(
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+        let response = Response::new(
+            StatusCode::Ok,
+            headers,
+            Box::pin(BytesStream::new(BODY.as_bytes())),
+        );
+
+        let response = GetACLResponse::from_response(response).await?;
+        assert_eq!(response.public_access, PublicAccess::Blob);
+
+        assert!(response.stored_access_policy_list.stored_access.is_empty());
+
+        let policies = response.partial_stored_access_policy_list.stored_access;
+        assert_eq!(policies.len(), 2);
+        assert_eq!(policies[0].id, "readers");
+        assert_eq!(policies[0].start, None);
+        assert_eq!(
+            policies[0].expiry,
+            Some(date::parse_rfc3339("2030-01-01T00:00:00Z")?)
+        );
+        assert_eq!(policies[0].permission.as_deref(), Some("rl"));
+
+        assert_eq!(policies[1].id, "revoked");
+        assert_eq!(policies[1].start, None);
+        assert_eq!(policies[1].expiry, None);
+        assert_eq!(policies[1].permission, None);
+        Ok(())
+    }
+}
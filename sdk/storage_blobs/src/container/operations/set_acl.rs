@@ -1,13 +1,13 @@
 use crate::{container::public_access_from_header, prelude::*};
 use azure_core::{headers::*, prelude::*, Body, Method};
-use azure_storage::StoredAccessPolicyList;
+use azure_storage::PartialStoredAccessPolicyList;
 
 operation! {
     SetACL,
     client: ContainerClient,
     public_access: PublicAccess,
     ?if_modified_since: IfModifiedSinceCondition,
-    ?stored_access_policy_list: StoredAccessPolicyList,
+    ?stored_access_policy_list: PartialStoredAccessPolicyList,
     ?lease_id: LeaseId
 }
 
@@ -1,10 +1,14 @@
-use crate::{clients::FileSystemClient, operations::ListFileSystemsBuilder};
+use crate::{
+    clients::FileSystemClient,
+    operations::{GetUserDelegationKeyBuilder, ListFileSystemsBuilder},
+};
 use azure_core::{ClientOptions, Pipeline, Url};
 use azure_storage::{
     clients::{new_pipeline_from_options, ServiceType},
     prelude::StorageCredentials,
     CloudLocation,
 };
+use time::OffsetDateTime;
 
 /// A builder for the blob service client.
 #[derive(Debug, Clone)]
@@ -52,8 +56,9 @@ impl DataLakeClientBuilder {
             options,
         } = self;
         DataLakeClient {
-            pipeline: new_pipeline_from_options(options, credentials),
+            pipeline: new_pipeline_from_options(options, credentials.clone()),
             cloud_location,
+            credentials,
         }
     }
 
@@ -92,6 +97,7 @@ impl DataLakeClientBuilder {
 pub struct DataLakeClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    credentials: StorageCredentials,
 }
 
 impl DataLakeClient {
@@ -112,6 +118,38 @@ impl DataLakeClient {
         self.cloud_location.url(ServiceType::DataLake)
     }
 
+    /// Some Data Lake operations (e.g. soft delete) are only exposed through the blob endpoint.
+    ///
+    /// The blob endpoint of a custom location is its `dfs` endpoint on the
+    /// `blob` subdomain, or the endpoint itself when it has no `dfs` subdomain.
+    pub(crate) fn blob_url(&self) -> azure_core::Result<Url> {
+        let mut url = self.cloud_location.url(ServiceType::Blob)?;
+        if let CloudLocation::Custom { .. } = self.cloud_location {
+            if let Some(host) = url.host_str() {
+                let host = host.replacen(".dfs.", ".blob.", 1);
+                url.set_host(Some(&host))?;
+            }
+        }
+        Ok(url)
+    }
+
+    pub fn account(&self) -> &str {
+        self.cloud_location.account()
+    }
+
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
+    /// Get a user delegation key, used to sign user delegation shared access signatures.
+    pub fn get_user_delegation_key(
+        &self,
+        start_time: OffsetDateTime,
+        expiry_time: OffsetDateTime,
+    ) -> GetUserDelegationKeyBuilder {
+        GetUserDelegationKeyBuilder::new(self.clone(), start_time, expiry_time)
+    }
+
     pub fn list_file_systems(&self) -> ListFileSystemsBuilder {
         ListFileSystemsBuilder::new(self.clone())
     }
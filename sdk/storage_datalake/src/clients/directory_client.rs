@@ -2,6 +2,14 @@ use crate::{
     clients::FileSystemClient, operations::*, prelude::PathClient, request_options::*, Properties,
 };
 use azure_core::{prelude::IfMatchCondition, Response, Url};
+use azure_storage::{
+    prelude::BlobSasPermissions,
+    shared_access_signature::{
+        service_sas::{BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey},
+        SasToken,
+    },
+};
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct DirectoryClient {
@@ -112,4 +120,58 @@ impl DirectoryClient {
 
         PatchPathBuilder::new(self.clone(), action).acl(acl)
     }
+
+    /// Create a shared access signature scoped to the directory and everything below it.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        Ok(self
+            .file_system_client
+            .path_shared_access_signature(
+                Some(&self.dir_path),
+                BlobSignedResource::Directory,
+                permissions,
+                expiry,
+            )
+            .await?
+            .signed_directory_depth(self.depth()))
+    }
+
+    /// Create a user delegation shared access signature scoped to the directory and everything below it.
+    pub async fn user_delegation_shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        user_delegation_key: &UserDeligationKey,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        Ok(self
+            .file_system_client
+            .path_user_delegation_shared_access_signature(
+                Some(&self.dir_path),
+                BlobSignedResource::Directory,
+                permissions,
+                user_delegation_key,
+            )
+            .await?
+            .signed_directory_depth(self.depth()))
+    }
+
+    /// Create a signed directory url
+    pub fn generate_signed_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
+    /// The number of path segments of the directory, required by directory scoped SAS (`sdd`).
+    fn depth(&self) -> usize {
+        self.dir_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .count()
+    }
 }
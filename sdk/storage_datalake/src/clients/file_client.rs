@@ -1,7 +1,15 @@
 use super::{FileSystemClient, PathClient};
 use crate::{operations::*, request_options::*, Properties};
 use azure_core::{prelude::IfMatchCondition, Url};
+use azure_storage::{
+    prelude::BlobSasPermissions,
+    shared_access_signature::{
+        service_sas::{BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey},
+        SasToken,
+    },
+};
 use bytes::Bytes;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct FileClient {
@@ -108,4 +116,46 @@ impl FileClient {
     ) -> PatchPathBuilder<Self> {
         PatchPathBuilder::new(self.clone(), PathUpdateAction::SetAccessControl).acl(acl)
     }
+
+    /// Create a shared access signature scoped to the file.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        self.file_system_client
+            .path_shared_access_signature(
+                Some(&self.file_path),
+                BlobSignedResource::Blob,
+                permissions,
+                expiry,
+            )
+            .await
+    }
+
+    /// Create a user delegation shared access signature scoped to the file.
+    pub async fn user_delegation_shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        user_delegation_key: &UserDeligationKey,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        self.file_system_client
+            .path_user_delegation_shared_access_signature(
+                Some(&self.file_path),
+                BlobSignedResource::Blob,
+                permissions,
+                user_delegation_key,
+            )
+            .await
+    }
+
+    /// Create a signed file url
+    pub fn generate_signed_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
 }
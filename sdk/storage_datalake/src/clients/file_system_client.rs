@@ -1,6 +1,19 @@
 use super::{DataLakeClient, DirectoryClient, FileClient};
 use crate::{operations::*, Properties};
-use azure_core::Url;
+use azure_core::{
+    error::{Error, ErrorKind},
+    Url,
+};
+use azure_storage::{
+    prelude::BlobSasPermissions,
+    shared_access_signature::{
+        service_sas::{BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey},
+        SasToken,
+    },
+    StorageCredentialsInner,
+};
+use std::ops::Deref;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct FileSystemClient {
@@ -20,6 +33,17 @@ impl FileSystemClient {
         Ok(self.data_lake_client.url()?.join(&self.name)?)
     }
 
+    /// The url of the file system on the blob endpoint, keeping the path of
+    /// the endpoint.
+    pub(crate) fn blob_url(&self) -> azure_core::Result<Url> {
+        let mut url = self.data_lake_client.blob_url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::message(ErrorKind::DataConversion, "invalid blob url"))?
+            .pop_if_empty()
+            .push(&self.name);
+        Ok(url)
+    }
+
     pub fn get_directory_client<P>(&self, path: P) -> DirectoryClient
     where
         P: Into<String>,
@@ -52,6 +76,18 @@ impl FileSystemClient {
         ListPathsBuilder::new(self.clone()).recursive(true)
     }
 
+    pub fn list_deleted_paths(&self) -> ListDeletedPathsBuilder {
+        ListDeletedPathsBuilder::new(self.clone())
+    }
+
+    pub fn undelete_path<P, D>(&self, path: P, deletion_id: D) -> UndeletePathBuilder
+    where
+        P: Into<String>,
+        D: Into<String>,
+    {
+        UndeletePathBuilder::new(self.clone(), path.into(), deletion_id.into())
+    }
+
     pub fn create(&self) -> CreateFileSystemBuilder {
         CreateFileSystemBuilder::new(self.clone())
     }
@@ -68,6 +104,98 @@ impl FileSystemClient {
         SetFileSystemPropertiesBuilder::new(self.clone(), properties)
     }
 
+    /// Create a shared access signature scoped to the file system.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        self.path_shared_access_signature(None, BlobSignedResource::Container, permissions, expiry)
+            .await
+    }
+
+    /// Create a user delegation shared access signature scoped to the file system.
+    pub async fn user_delegation_shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        user_delegation_key: &UserDeligationKey,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        self.path_user_delegation_shared_access_signature(
+            None,
+            BlobSignedResource::Container,
+            permissions,
+            user_delegation_key,
+        )
+        .await
+    }
+
+    /// Create a signed file system url
+    pub fn generate_signed_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
+    pub(crate) async fn path_shared_access_signature(
+        &self,
+        path: Option<&str>,
+        resource: BlobSignedResource,
+        permissions: BlobSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        let creds = self.data_lake_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        Ok(BlobSharedAccessSignature::new(
+            key.clone(),
+            self.canonicalized_resource(account, path),
+            permissions,
+            expiry,
+            resource,
+        ))
+    }
+
+    pub(crate) async fn path_user_delegation_shared_access_signature(
+        &self,
+        path: Option<&str>,
+        resource: BlobSignedResource,
+        permissions: BlobSasPermissions,
+        user_delegation_key: &UserDeligationKey,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        let creds = self.data_lake_client.credentials().0.read().await;
+        if !matches!(creds.deref(), StorageCredentialsInner::TokenCredential(_)) {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "User delegation access signature generation requires Token authentication",
+            ));
+        };
+
+        let account = self.data_lake_client.account();
+        Ok(BlobSharedAccessSignature::new(
+            user_delegation_key.clone(),
+            self.canonicalized_resource(account, path),
+            permissions,
+            user_delegation_key.signed_expiry,
+            resource,
+        ))
+    }
+
+    /// Data Lake paths are signed the same way as blobs, using the `blob` service prefix.
+    fn canonicalized_resource(&self, account: &str, path: Option<&str>) -> String {
+        match path {
+            Some(path) => format!("/blob/{}/{}/{}", account, self.name, path),
+            None => format!("/blob/{}/{}", account, self.name),
+        }
+    }
+
     pub(crate) async fn send(
         &self,
         ctx: &mut azure_core::Context,
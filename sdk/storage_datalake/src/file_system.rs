@@ -56,6 +56,21 @@ impl TryFrom<Bytes> for PathList {
     }
 }
 
+/// A path that has been soft deleted and can still be restored with
+/// [`FileSystemClient::undelete_path`](crate::clients::FileSystemClient::undelete_path).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedPath {
+    pub name: String,
+    pub deletion_id: String,
+    pub deleted_time: Option<OffsetDateTime>,
+    pub remaining_retention_days: Option<u32>,
+    pub creation_time: Option<OffsetDateTime>,
+    pub last_modified: Option<OffsetDateTime>,
+    pub etag: Option<Etag>,
+    pub content_length: Option<u64>,
+    pub resource_type: Option<String>,
+}
+
 fn deserialize_i64<'de, D>(deserializer: D) -> Result<i64, D::Error>
 where
     D: Deserializer<'de>,
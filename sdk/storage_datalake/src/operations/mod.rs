@@ -8,12 +8,15 @@ mod file_system_get_properties;
 mod file_system_set_properties;
 mod file_systems_list;
 mod path_delete;
+mod path_deleted_list;
 mod path_get;
 mod path_head;
 mod path_list;
 mod path_patch;
 mod path_put;
 mod path_rename;
+mod path_undelete;
+mod user_delegation_key_get;
 
 pub use file_system_create::*;
 pub use file_system_delete::*;
@@ -21,9 +24,12 @@ pub use file_system_get_properties::*;
 pub use file_system_set_properties::*;
 pub use file_systems_list::*;
 pub use path_delete::*;
+pub use path_deleted_list::*;
 pub use path_get::*;
 pub use path_head::*;
 pub use path_list::*;
 pub use path_patch::*;
 pub use path_put::*;
 pub use path_rename::*;
+pub use path_undelete::*;
+pub use user_delegation_key_get::*;
@@ -0,0 +1,248 @@
+use crate::{clients::FileSystemClient, file_system::DeletedPath};
+use azure_core::{error::Error, prelude::*, Etag, Pageable, Request, Response};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    #[stream]
+    ListDeletedPaths,
+    client: FileSystemClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+    ?continuation: NextMarker
+}
+
+impl ListDeletedPathsBuilder {
+    pub fn into_stream(self) -> Pageable<ListDeletedPathsResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let this = self.clone();
+            let mut ctx = self.context.clone();
+
+            async move {
+                // listing soft deleted paths is only supported by the blob endpoint
+                let mut url = this.client.blob_url()?;
+                url.query_pairs_mut().append_pair("restype", "container");
+                url.query_pairs_mut().append_pair("comp", "list");
+                url.query_pairs_mut().append_pair("showonly", "deleted");
+
+                this.prefix.append_to_url_query(&mut url);
+                this.max_results.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation.or(this.continuation) {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                let mut request = Request::new(url, azure_core::Method::Get);
+
+                let response = this.client.send(&mut ctx, &mut request).await?;
+
+                ListDeletedPathsResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ListDeletedPathsResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub prefix: Option<String>,
+    pub max_results: Option<u32>,
+    pub paths: Vec<DeletedPath>,
+    pub continuation: Option<NextMarker>,
+}
+
+impl ListDeletedPathsResponse {
+    pub(crate) async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        let list: ListDeletedPathsResponseInternal = body.xml().await?;
+
+        let continuation = match list.next_marker {
+            Some(ref nm) if nm.is_empty() => None,
+            Some(nm) => Some(nm.into()),
+            None => None,
+        };
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            prefix: list.prefix.filter(|prefix| !prefix.is_empty()),
+            max_results: list.max_results,
+            paths: list.blobs.items.into_iter().map(Into::into).collect(),
+            continuation,
+        })
+    }
+}
+
+impl Continuable for ListDeletedPathsResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.continuation.clone()
+    }
+}
+
+impl IntoIterator for ListDeletedPathsResponse {
+    type Item = DeletedPath;
+
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.paths.into_iter()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListDeletedPathsResponseInternal {
+    prefix: Option<String>,
+    max_results: Option<u32>,
+    next_marker: Option<String>,
+    #[serde(default)]
+    blobs: DeletedBlobs,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DeletedBlobs {
+    #[serde(rename = "Blob", default)]
+    items: Vec<DeletedBlob>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeletedBlob {
+    name: String,
+    deletion_id: String,
+    #[serde(default)]
+    properties: DeletedBlobProperties,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeletedBlobProperties {
+    #[serde(
+        default,
+        with = "azure_core::date::rfc1123::option",
+        rename = "Creation-Time"
+    )]
+    creation_time: Option<OffsetDateTime>,
+    #[serde(
+        default,
+        with = "azure_core::date::rfc1123::option",
+        rename = "Last-Modified"
+    )]
+    last_modified: Option<OffsetDateTime>,
+    etag: Option<Etag>,
+    #[serde(rename = "Content-Length")]
+    content_length: Option<u64>,
+    resource_type: Option<String>,
+    #[serde(default, with = "azure_core::date::rfc1123::option")]
+    deleted_time: Option<OffsetDateTime>,
+    remaining_retention_days: Option<u32>,
+}
+
+impl From<DeletedBlob> for DeletedPath {
+    fn from(blob: DeletedBlob) -> Self {
+        let DeletedBlob {
+            name,
+            deletion_id,
+            properties,
+        } = blob;
+        Self {
+            name,
+            deletion_id,
+            deleted_time: properties.deleted_time,
+            remaining_retention_days: properties.remaining_retention_days,
+            creation_time: properties.creation_time,
+            last_modified: properties.last_modified,
+            etag: properties.etag,
+            content_length: properties.content_length,
+            resource_type: properties.resource_type,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn deserialize_deleted_paths() -> azure_core::Result<()> {
+        const XML: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<EnumerationResults ServiceEndpoint=\"https://myaccount.blob.core.windows.net/\" ContainerName=\"myfilesystem\">
+    <Prefix />
+    <MaxResults>2</MaxResults>
+    <Blobs>
+        <Blob>
+            <Name>dir1/file1.txt</Name>
+            <Deleted>true</Deleted>
+            <DeletionId>132999999999999999</DeletionId>
+            <Properties>
+                <Creation-Time>Thu, 01 Jul 2021 10:44:59 GMT</Creation-Time>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>0x8D93C7D4629C227</Etag>
+                <Content-Length>8</Content-Length>
+                <ResourceType>file</ResourceType>
+                <DeletedTime>Fri, 02 Jul 2021 08:00:00 GMT</DeletedTime>
+                <RemainingRetentionDays>6</RemainingRetentionDays>
+            </Properties>
+        </Blob>
+        <Blob>
+            <Name>dir2</Name>
+            <Deleted>true</Deleted>
+            <DeletionId>133000000000000000</DeletionId>
+            <Properties>
+                <Creation-Time>Thu, 01 Jul 2021 10:44:59 GMT</Creation-Time>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>0x8D93C7D463004D6</Etag>
+                <Content-Length>0</Content-Length>
+                <ResourceType>directory</ResourceType>
+                <DeletedTime>Fri, 02 Jul 2021 09:00:00 GMT</DeletedTime>
+                <RemainingRetentionDays>6</RemainingRetentionDays>
+            </Properties>
+        </Blob>
+    </Blobs>
+    <NextMarker>2!84!MDAwMDE4IWRpcjIvZmlsZTIudHh0</NextMarker>
+</EnumerationResults>";
+
+        let list: ListDeletedPathsResponseInternal = read_xml(XML.as_bytes())?;
+        assert_eq!(list.max_results, Some(2));
+        assert_eq!(
+            list.next_marker.as_deref(),
+            Some("2!84!MDAwMDE4IWRpcjIvZmlsZTIudHh0")
+        );
+
+        let paths: Vec<DeletedPath> = list.blobs.items.into_iter().map(Into::into).collect();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].name, "dir1/file1.txt");
+        assert_eq!(paths[0].deletion_id, "132999999999999999");
+        assert_eq!(paths[0].content_length, Some(8));
+        assert_eq!(paths[0].resource_type.as_deref(), Some("file"));
+        assert_eq!(paths[0].remaining_retention_days, Some(6));
+        assert_eq!(
+            paths[0].deleted_time,
+            Some(azure_core::date::parse_rfc1123(
+                "Fri, 02 Jul 2021 08:00:00 GMT"
+            )?)
+        );
+        assert_eq!(paths[1].resource_type.as_deref(), Some("directory"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_no_deleted_paths() -> azure_core::Result<()> {
+        const XML: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<EnumerationResults ServiceEndpoint=\"https://myaccount.blob.core.windows.net/\" ContainerName=\"myfilesystem\">
+    <Blobs />
+    <NextMarker />
+</EnumerationResults>";
+
+        let list: ListDeletedPathsResponseInternal = read_xml(XML.as_bytes())?;
+        assert!(list.blobs.items.is_empty());
+        assert_eq!(list.next_marker.as_deref(), Some(""));
+
+        Ok(())
+    }
+}
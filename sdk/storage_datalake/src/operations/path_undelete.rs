@@ -0,0 +1,160 @@
+use crate::clients::FileSystemClient;
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    Request, Response as HttpResponse,
+};
+use azure_storage::headers::{self, CommonStorageResponseHeaders};
+use std::convert::TryInto;
+
+operation! {
+    UndeletePath,
+    client: FileSystemClient,
+    path: String,
+    deletion_id: String,
+}
+
+impl UndeletePathBuilder {
+    pub fn into_future(self) -> UndeletePath {
+        let mut ctx = self.context.clone();
+
+        Box::pin(async move {
+            // restoring soft deleted paths is only supported by the blob endpoint
+            let mut url = self.client.blob_url()?;
+            url.path_segments_mut()
+                .map_err(|()| Error::message(ErrorKind::DataConversion, "invalid blob url"))?
+                .extend(self.path.split('/'));
+            let undelete_source = format!("{}?deletionid={}", url.path(), self.deletion_id);
+            url.query_pairs_mut().append_pair("comp", "undelete");
+
+            let mut request = Request::new(url, azure_core::Method::Put);
+
+            request.insert_header(headers::UNDELETE_SOURCE, undelete_source);
+            request.insert_headers(&ContentLength::new(0));
+
+            let response = self.client.send(&mut ctx, &mut request).await?;
+
+            UndeletePathResponse::try_from(response)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UndeletePathResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// Either `file` or `directory`, depending on the kind of the restored path.
+    pub resource_type: Option<String>,
+}
+
+impl UndeletePathResponse {
+    pub fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+
+        Ok(Self {
+            common_storage_response_headers: headers.try_into()?,
+            resource_type: headers.get_optional_string(&headers::RESOURCE_TYPE),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use azure_core::{
+        headers::{HeaderName, Headers},
+        BytesStream, Context, Policy, PolicyResult, Request, Response, StatusCode,
+        TransportOptions,
+    };
+    use azure_storage::{CloudLocation, StorageCredentials};
+    use std::sync::{Arc, Mutex};
+
+    /// A transport recording the requests it receives.
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<Request>>);
+
+    #[async_trait::async_trait]
+    impl Policy for Recorder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            assert_eq!(0, next.len());
+            self.0.lock().unwrap().push(request.clone());
+
+            let mut headers = Headers::new();
+            for (name, value) in [
+                ("x-ms-request-id", "a3a0b1d4-f01a-0043-4f2c-8ba78e000000"),
+                ("x-ms-version", "2020-06-12"),
+                ("date", "Mon, 27 Jun 2022 13:38:48 GMT"),
+                ("server", "Windows-Azure-Blob/1.0 Microsoft-HTTPAPI/2.0"),
+                ("x-ms-resource-type", "file"),
+            ] {
+                headers.insert(name, value);
+            }
+            Ok(Response::new(
+                StatusCode::Ok,
+                headers,
+                Box::pin(BytesStream::new_empty()),
+            ))
+        }
+    }
+
+    async fn undelete(cloud_location: CloudLocation) -> azure_core::Result<Request> {
+        let recorder = Arc::new(Recorder::default());
+        let response =
+            DataLakeClientBuilder::with_location(cloud_location, StorageCredentials::anonymous())
+                .transport(TransportOptions::new_custom_policy(recorder.clone()))
+                .build()
+                .file_system_client("myfilesystem")
+                .undelete_path("dir1/file 1.txt", "132999999999999999")
+                .await?;
+        assert_eq!(response.resource_type.as_deref(), Some("file"));
+
+        let requests = recorder.0.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        Ok(requests[0].clone())
+    }
+
+    #[tokio::test]
+    async fn undelete_source_is_the_restored_path() -> azure_core::Result<()> {
+        let request = undelete(CloudLocation::Public {
+            account: "myaccount".to_owned(),
+        })
+        .await?;
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://myaccount.blob.core.windows.net/myfilesystem/dir1/file%201.txt?comp=undelete"
+        );
+        assert_eq!(
+            request
+                .headers()
+                .get_optional_str(&HeaderName::from_static("x-ms-undelete-source")),
+            Some("/myfilesystem/dir1/file%201.txt?deletionid=132999999999999999")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn custom_locations_use_their_blob_endpoint() -> azure_core::Result<()> {
+        let request = undelete(CloudLocation::Custom {
+            account: "myaccount".to_owned(),
+            uri: "https://myaccount.dfs.example.com/prefix".to_owned(),
+        })
+        .await?;
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://myaccount.blob.example.com/prefix/myfilesystem/dir1/file%201.txt?comp=undelete"
+        );
+        assert_eq!(
+            request
+                .headers()
+                .get_optional_str(&HeaderName::from_static("x-ms-undelete-source")),
+            Some("/prefix/myfilesystem/dir1/file%201.txt?deletionid=132999999999999999")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use crate::clients::DataLakeClient;
+use azure_core::{
+    date::iso8601,
+    headers::Headers,
+    xml::{read_xml_str, to_xml},
+    Method, Request,
+};
+use azure_storage::{
+    headers::CommonStorageResponseHeaders, shared_access_signature::service_sas::UserDeligationKey,
+};
+use bytes::{Bytes, BytesMut};
+use time::OffsetDateTime;
+
+operation! {
+    GetUserDelegationKey,
+    client: DataLakeClient,
+    start_time: OffsetDateTime,
+    expiry_time: OffsetDateTime,
+}
+
+impl GetUserDelegationKeyBuilder {
+    pub fn into_future(mut self) -> GetUserDelegationKey {
+        Box::pin(async move {
+            // user delegation keys are only issued by the blob endpoint
+            let mut url = self.client.blob_url()?;
+
+            url.query_pairs_mut()
+                .extend_pairs([("restype", "service"), ("comp", "userdelegationkey")]);
+
+            let body = GetUserDelegationKeyRequest {
+                start: self.start_time,
+                expiry: self.expiry_time,
+            }
+            .encode()?;
+
+            let mut request = Request::new(url, Method::Post);
+            request.set_body(body);
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            let (_, headers, body) = response.deconstruct();
+            let body = body.collect_string().await?;
+            GetUserDelegationKeyResponse::try_from(&headers, &body)
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "KeyInfo")]
+struct GetUserDelegationKeyRequest {
+    #[serde(rename = "Start", with = "iso8601")]
+    start: OffsetDateTime,
+    #[serde(rename = "Expiry", with = "iso8601")]
+    expiry: OffsetDateTime,
+}
+
+impl GetUserDelegationKeyRequest {
+    pub fn encode(&self) -> azure_core::Result<Bytes> {
+        let mut body = BytesMut::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>");
+        body.extend(to_xml(self)?);
+        Ok(body.freeze())
+    }
+}
+
+#[derive(Debug)]
+pub struct GetUserDelegationKeyResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub user_delegation_key: UserDeligationKey,
+}
+
+impl GetUserDelegationKeyResponse {
+    pub(crate) fn try_from(headers: &Headers, body: &str) -> azure_core::Result<Self> {
+        Ok(Self {
+            common_storage_response_headers: headers.try_into()?,
+            user_delegation_key: read_xml_str(body)?,
+        })
+    }
+}
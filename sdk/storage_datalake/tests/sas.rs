@@ -0,0 +1,88 @@
+use azure_storage::prelude::{BlobSasPermissions, StorageCredentials};
+use azure_storage_datalake::prelude::*;
+use time::{Duration, OffsetDateTime};
+
+const MOCK_ACCOUNT: &str = "account";
+const MOCK_SECRET_KEY: &str = "RZfi3m1W7eyQ5zD4ymSmGANVdJ2SDQmg4sE89SW104s=";
+
+fn file_system_client() -> FileSystemClient {
+    let credentials = StorageCredentials::access_key(MOCK_ACCOUNT, MOCK_SECRET_KEY);
+    DataLakeClient::new(MOCK_ACCOUNT, credentials).file_system_client("filesystem")
+}
+
+fn query_value(url: &url::Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+}
+
+#[tokio::test]
+async fn directory_sas_sets_depth() -> azure_core::Result<()> {
+    let directory_client = file_system_client().get_directory_client("dir1/dir2");
+    let permissions = BlobSasPermissions {
+        read: true,
+        list: true,
+        execute: true,
+        ..Default::default()
+    };
+    let sas = directory_client
+        .shared_access_signature(permissions, OffsetDateTime::now_utc() + Duration::hours(1))
+        .await?;
+    let url = directory_client.generate_signed_url(&sas)?;
+
+    assert_eq!(url.host_str(), Some("account.dfs.core.windows.net"));
+    assert_eq!(url.path(), "/filesystem/dir1/dir2");
+    assert_eq!(query_value(&url, "sr").as_deref(), Some("d"));
+    assert_eq!(query_value(&url, "sdd").as_deref(), Some("2"));
+    assert_eq!(query_value(&url, "sp").as_deref(), Some("rle"));
+    assert!(query_value(&url, "sig").is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn file_and_file_system_sas() -> azure_core::Result<()> {
+    let file_system_client = file_system_client();
+    let expiry = OffsetDateTime::now_utc() + Duration::hours(1);
+    let permissions = || BlobSasPermissions {
+        read: true,
+        move_: true,
+        ..Default::default()
+    };
+
+    let file_client = file_system_client.get_file_client("dir1/file.txt");
+    let sas = file_client
+        .shared_access_signature(permissions(), expiry)
+        .await?;
+    let url = file_client.generate_signed_url(&sas)?;
+    assert_eq!(url.path(), "/filesystem/dir1/file.txt");
+    assert_eq!(query_value(&url, "sr").as_deref(), Some("b"));
+    assert_eq!(query_value(&url, "sp").as_deref(), Some("rm"));
+    assert!(query_value(&url, "sdd").is_none());
+
+    let sas = file_system_client
+        .shared_access_signature(permissions(), expiry)
+        .await?;
+    let url = file_system_client.generate_signed_url(&sas)?;
+    assert_eq!(url.path(), "/filesystem");
+    assert_eq!(query_value(&url, "sr").as_deref(), Some("c"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn user_delegation_sas_requires_token_credentials() {
+    let file_client = file_system_client().get_file_client("file.txt");
+    let key = azure_storage::shared_access_signature::service_sas::UserDeligationKey {
+        signed_oid: uuid::Uuid::nil(),
+        signed_tid: uuid::Uuid::nil(),
+        signed_start: OffsetDateTime::now_utc(),
+        signed_expiry: OffsetDateTime::now_utc() + Duration::hours(1),
+        signed_service: "b".to_string(),
+        signed_version: "2022-11-02".to_string(),
+        value: azure_core::auth::Secret::new(MOCK_SECRET_KEY),
+    };
+
+    let result = file_client
+        .user_delegation_shared_access_signature(BlobSasPermissions::default(), &key)
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,43 @@
+use azure_storage::prelude::*;
+use azure_storage_files::prelude::*;
+use futures::stream::StreamExt;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+    let share_name = std::env::args()
+        .nth(1)
+        .expect("please specify share name as command line parameter");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let file_service = FileServiceClient::new(account, storage_credentials);
+    let share = file_service.share_client(share_name);
+
+    let properties = share.get_properties().await?;
+    println!("share quota is {} GiB", properties.quota);
+
+    let mut directories = vec![share.root_directory_client()];
+    while let Some(directory) = directories.pop() {
+        let mut stream = directory.list().into_stream();
+        while let Some(page) = stream.next().await {
+            let page = page?;
+            for file in page.files() {
+                println!(
+                    "{}/{} ({:?} bytes)",
+                    directory.directory_path(),
+                    file.name,
+                    file.properties.content_length
+                );
+            }
+            for subdirectory in page.directories() {
+                directories.push(directory.subdirectory_client(&subdirectory.name));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::headers::ACCESS_TIER;
+use azure_core::headers::{self, Header};
+
+create_enum!(
+    AccessTier,
+    (TransactionOptimized, "TransactionOptimized"),
+    (Hot, "Hot"),
+    (Cool, "Cool"),
+    (Premium, "Premium")
+);
+
+impl Header for AccessTier {
+    fn name(&self) -> headers::HeaderName {
+        ACCESS_TIER
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        self.as_ref().to_owned().into()
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{clients::PathClient, operations::*, FileClient, HandleId, ShareClient};
+use azure_core::{Context, Request, Response, Url};
+
+#[derive(Debug, Clone)]
+pub struct DirectoryClient {
+    share_client: ShareClient,
+    directory_path: String,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl PathClient for DirectoryClient {
+    fn url(&self) -> azure_core::Result<Url> {
+        DirectoryClient::url(self)
+    }
+
+    async fn send(&self, ctx: &mut Context, request: &mut Request) -> azure_core::Result<Response> {
+        self.share_client.send(ctx, request).await
+    }
+}
+
+impl DirectoryClient {
+    pub(crate) fn new(share_client: ShareClient, directory_path: String) -> Self {
+        Self {
+            share_client,
+            directory_path,
+        }
+    }
+
+    /// Creates the directory.
+    pub fn create(&self) -> CreateDirectoryBuilder {
+        CreateDirectoryBuilder::new(self.clone())
+    }
+
+    /// Deletes the directory, which must be empty.
+    pub fn delete(&self) -> DeleteDirectoryBuilder {
+        DeleteDirectoryBuilder::new(self.clone())
+    }
+
+    /// Gets the properties, SMB properties and metadata of the directory.
+    pub fn get_properties(&self) -> GetDirectoryPropertiesBuilder {
+        GetDirectoryPropertiesBuilder::new(self.clone())
+    }
+
+    /// Lists the files and subdirectories directly contained in the directory.
+    pub fn list(&self) -> ListDirectoryBuilder {
+        ListDirectoryBuilder::new(self.clone())
+    }
+
+    /// Lists the handles open on the directory, and optionally on its children.
+    pub fn list_handles(&self) -> ListHandlesBuilder<Self> {
+        ListHandlesBuilder::new(self.clone())
+    }
+
+    /// Closes the handles open on the directory, and optionally on its children.
+    pub fn force_close_handles(&self, handle_id: HandleId) -> ForceCloseHandlesBuilder<Self> {
+        ForceCloseHandlesBuilder::new(self.clone(), handle_id)
+    }
+
+    pub fn subdirectory_client<S: AsRef<str>>(&self, directory_name: S) -> DirectoryClient {
+        DirectoryClient::new(
+            self.share_client.clone(),
+            self.child_path(directory_name.as_ref()),
+        )
+    }
+
+    pub fn file_client<S: AsRef<str>>(&self, file_name: S) -> FileClient {
+        FileClient::new(
+            self.share_client.clone(),
+            self.child_path(file_name.as_ref()),
+        )
+    }
+
+    pub fn share_client(&self) -> &ShareClient {
+        &self.share_client
+    }
+
+    pub fn directory_path(&self) -> &str {
+        &self.directory_path
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        let mut url = self.share_client.url()?;
+        url.path_segments_mut()
+            .expect("invalid base url")
+            .extend(self.directory_path.split('/').filter(|s| !s.is_empty()));
+        Ok(url)
+    }
+
+    fn child_path(&self, name: &str) -> String {
+        if self.directory_path.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{}/{}", self.directory_path.trim_end_matches('/'), name)
+        }
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        ShareClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.share_client.send(context, request).await
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    clients::{share_client::signed_url, PathClient},
+    operations::*,
+    FileLeaseClient, HandleId, ShareClient,
+};
+use azure_core::{prelude::*, Body, Context, Request, Response, Url};
+use azure_storage::{
+    prelude::{FileSasPermissions, FileSignedResource},
+    shared_access_signature::{service_sas::FileSharedAccessSignature, SasToken},
+};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone)]
+pub struct FileClient {
+    share_client: ShareClient,
+    file_path: String,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl PathClient for FileClient {
+    fn url(&self) -> azure_core::Result<Url> {
+        FileClient::url(self)
+    }
+
+    async fn send(&self, ctx: &mut Context, request: &mut Request) -> azure_core::Result<Response> {
+        self.share_client.send(ctx, request).await
+    }
+}
+
+impl FileClient {
+    pub(crate) fn new(share_client: ShareClient, file_path: String) -> Self {
+        Self {
+            share_client,
+            file_path,
+        }
+    }
+
+    /// Creates the file, or replaces an existing one, with the given size.
+    ///
+    /// The content of the file is uploaded afterwards with `put_range`.
+    pub fn create(&self, content_length: u64) -> CreateFileBuilder {
+        CreateFileBuilder::new(self.clone(), content_length)
+    }
+
+    pub fn delete(&self) -> DeleteFileBuilder {
+        DeleteFileBuilder::new(self.clone())
+    }
+
+    /// Downloads the file, or a range of it.
+    pub fn get(&self) -> GetFileBuilder {
+        GetFileBuilder::new(self.clone())
+    }
+
+    /// Gets the properties, SMB properties and metadata of the file.
+    pub fn get_properties(&self) -> GetFilePropertiesBuilder {
+        GetFilePropertiesBuilder::new(self.clone())
+    }
+
+    /// Sets the HTTP properties and SMB properties of the file.
+    pub fn set_properties(&self) -> SetFilePropertiesBuilder {
+        SetFilePropertiesBuilder::new(self.clone())
+    }
+
+    /// Writes `content` at the given range of the file.
+    ///
+    /// The range must match the length of `content` and be at most 4 MiB.
+    pub fn put_range(&self, range: impl Into<Range>, content: impl Into<Body>) -> PutRangeBuilder {
+        PutRangeBuilder::new(self.clone(), range.into(), Some(content.into()))
+    }
+
+    /// Clears the given range of the file, releasing its storage.
+    pub fn clear_range(&self, range: impl Into<Range>) -> PutRangeBuilder {
+        PutRangeBuilder::new(self.clone(), range.into(), None)
+    }
+
+    /// Lists the ranges of the file that contain data.
+    pub fn list_ranges(&self) -> ListRangesBuilder {
+        ListRangesBuilder::new(self.clone())
+    }
+
+    /// Copies a blob or a file to this file.
+    pub fn copy(&self, copy_source: Url) -> CopyFileBuilder {
+        CopyFileBuilder::new(self.clone(), copy_source)
+    }
+
+    /// Aborts a pending copy, leaving the file with zero length.
+    pub fn abort_copy<S: Into<String>>(&self, copy_id: S) -> AbortCopyFileBuilder {
+        AbortCopyFileBuilder::new(self.clone(), copy_id.into())
+    }
+
+    /// Acquires an infinite lease on the file.
+    pub fn acquire_lease(&self) -> AcquireFileLeaseBuilder {
+        AcquireFileLeaseBuilder::new(self.clone())
+    }
+
+    pub fn break_lease(&self) -> BreakFileLeaseBuilder {
+        BreakFileLeaseBuilder::new(self.clone())
+    }
+
+    pub fn lease_client(&self, lease_id: LeaseId) -> FileLeaseClient {
+        FileLeaseClient::new(self.clone(), lease_id)
+    }
+
+    /// Lists the handles open on the file.
+    pub fn list_handles(&self) -> ListHandlesBuilder<Self> {
+        ListHandlesBuilder::new(self.clone())
+    }
+
+    /// Closes the handles open on the file.
+    pub fn force_close_handles(&self, handle_id: HandleId) -> ForceCloseHandlesBuilder<Self> {
+        ForceCloseHandlesBuilder::new(self.clone(), handle_id)
+    }
+
+    /// Create a shared access signature scoped to the file.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        self.share_client
+            .path_shared_access_signature(
+                Some(self.file_path()),
+                FileSignedResource::File,
+                permissions,
+                expiry,
+            )
+            .await
+    }
+
+    /// Create a signed file url
+    pub fn generate_signed_file_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        signed_url(self.url()?, signature)
+    }
+
+    pub fn share_client(&self) -> &ShareClient {
+        &self.share_client
+    }
+
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        let mut url = self.share_client.url()?;
+        url.path_segments_mut()
+            .expect("invalid base url")
+            .extend(self.file_path.split('/').filter(|s| !s.is_empty()));
+        Ok(url)
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        ShareClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.share_client.send(context, request).await
+    }
+}
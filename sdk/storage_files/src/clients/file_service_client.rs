@@ -0,0 +1,143 @@
+use crate::{operations::*, ShareClient};
+use azure_core::{ClientOptions, Context, Pipeline, Request, Response, Url};
+use azure_storage::{
+    clients::{new_pipeline_from_options, ServiceType},
+    prelude::StorageCredentials,
+    CloudLocation,
+};
+use std::fmt::Debug;
+
+/// A builder for the file service client.
+#[derive(Debug, Clone)]
+pub struct FileServiceClientBuilder {
+    cloud_location: CloudLocation,
+    options: ClientOptions,
+    credentials: StorageCredentials,
+}
+
+impl FileServiceClientBuilder {
+    /// Create a new instance of `FileServiceClientBuilder`.
+    #[must_use]
+    pub fn new<A, C>(account: A, credentials: C) -> Self
+    where
+        A: Into<String>,
+        C: Into<StorageCredentials>,
+    {
+        Self::with_location(
+            CloudLocation::Public {
+                account: account.into(),
+            },
+            credentials,
+        )
+    }
+
+    /// Create a new instance of `FileServiceClientBuilder` with a cloud location.
+    #[must_use]
+    pub fn with_location<C>(cloud_location: CloudLocation, credentials: C) -> Self
+    where
+        C: Into<StorageCredentials>,
+    {
+        Self {
+            options: ClientOptions::default(),
+            cloud_location,
+            credentials: credentials.into(),
+        }
+    }
+
+    /// Convert the builder into a `FileServiceClient` instance.
+    #[must_use]
+    pub fn build(self) -> FileServiceClient {
+        let Self {
+            cloud_location,
+            options,
+            credentials,
+        } = self;
+
+        FileServiceClient {
+            pipeline: new_pipeline_from_options(options, credentials.clone()),
+            cloud_location,
+            credentials,
+        }
+    }
+
+    /// Set the cloud location.
+    #[must_use]
+    pub fn cloud_location(mut self, cloud_location: CloudLocation) -> Self {
+        self.cloud_location = cloud_location;
+        self
+    }
+
+    /// Set the retry options.
+    #[must_use]
+    pub fn retry(mut self, retry: impl Into<azure_core::RetryOptions>) -> Self {
+        self.options = self.options.retry(retry);
+        self
+    }
+
+    /// Set the transport options.
+    #[must_use]
+    pub fn transport(mut self, transport: impl Into<azure_core::TransportOptions>) -> Self {
+        self.options = self.options.transport(transport);
+        self
+    }
+
+    /// Override all of the client options.
+    ///
+    /// *Warning!*: This overrides all client options that have been previously set on this builder.
+    #[must_use]
+    pub fn client_options(mut self, options: impl Into<azure_core::ClientOptions>) -> Self {
+        self.options = options.into();
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileServiceClient {
+    pipeline: Pipeline,
+    cloud_location: CloudLocation,
+    credentials: StorageCredentials,
+}
+
+impl FileServiceClient {
+    /// Create a new `FileServiceClient` which connects to the account's instance in the public Azure cloud.
+    #[must_use]
+    pub fn new(account: impl Into<String>, credentials: impl Into<StorageCredentials>) -> Self {
+        FileServiceClientBuilder::new(account, credentials).build()
+    }
+
+    /// List the shares of the account.
+    pub fn list_shares(&self) -> ListSharesBuilder {
+        ListSharesBuilder::new(self.clone())
+    }
+
+    pub fn share_client<S: Into<String>>(&self, share_name: S) -> ShareClient {
+        ShareClient::new(self.clone(), share_name.into())
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        self.cloud_location.url(ServiceType::File)
+    }
+
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        azure_storage::clients::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.pipeline
+            .send(context.insert(ServiceType::File), request)
+            .await
+    }
+}
@@ -0,0 +1,24 @@
+mod directory_client;
+mod file_client;
+mod file_lease_client;
+mod file_service_client;
+mod share_client;
+mod share_lease_client;
+
+pub use directory_client::DirectoryClient;
+pub use file_client::FileClient;
+pub use file_lease_client::FileLeaseClient;
+pub use file_service_client::{FileServiceClient, FileServiceClientBuilder};
+pub use share_client::ShareClient;
+pub use share_lease_client::ShareLeaseClient;
+
+use azure_core::{Context, Request, Response, Url};
+use std::fmt::Debug;
+
+/// A client pointing at a file or a directory of a share.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait PathClient: Debug + Clone + Send + Sync {
+    fn url(&self) -> azure_core::Result<Url>;
+    async fn send(&self, ctx: &mut Context, request: &mut Request) -> azure_core::Result<Response>;
+}
@@ -0,0 +1,289 @@
+use crate::{operations::*, DirectoryClient, FileClient, FileServiceClient, ShareLeaseClient};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    Context, Request, Response, Url,
+};
+use azure_storage::{
+    prelude::{FileSasPermissions, FileSignedResource},
+    shared_access_signature::{service_sas::FileSharedAccessSignature, SasToken},
+    StorageCredentialsInner,
+};
+use std::{fmt::Debug, ops::Deref};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone)]
+pub struct ShareClient {
+    service_client: FileServiceClient,
+    share_name: String,
+    snapshot: Option<String>,
+}
+
+impl ShareClient {
+    pub(crate) fn new(service_client: FileServiceClient, share_name: String) -> Self {
+        Self {
+            service_client,
+            share_name,
+            snapshot: None,
+        }
+    }
+
+    /// Creates the share.
+    pub fn create(&self) -> CreateShareBuilder {
+        CreateShareBuilder::new(self.clone())
+    }
+
+    /// Deletes the share, or the snapshot this client points to.
+    pub fn delete(&self) -> DeleteShareBuilder {
+        DeleteShareBuilder::new(self.clone())
+    }
+
+    /// Creates a read-only snapshot of the share.
+    pub fn create_snapshot(&self) -> CreateShareSnapshotBuilder {
+        CreateShareSnapshotBuilder::new(self.clone())
+    }
+
+    /// Gets the properties and metadata of the share.
+    pub fn get_properties(&self) -> GetSharePropertiesBuilder {
+        GetSharePropertiesBuilder::new(self.clone())
+    }
+
+    /// Sets the quota and the access tier of the share.
+    pub fn set_properties(&self) -> SetSharePropertiesBuilder {
+        SetSharePropertiesBuilder::new(self.clone())
+    }
+
+    /// Stores a security descriptor at the share level.
+    ///
+    /// The returned key can be used as `FilePermission::Key` when creating
+    /// files and directories, which is required for descriptors over 8 KiB.
+    pub fn create_permission<S: Into<String>>(
+        &self,
+        permission: S,
+    ) -> CreateSharePermissionBuilder {
+        CreateSharePermissionBuilder::new(self.clone(), permission.into())
+    }
+
+    /// Gets a security descriptor stored at the share level.
+    pub fn get_permission<S: Into<String>>(&self, permission_key: S) -> GetSharePermissionBuilder {
+        GetSharePermissionBuilder::new(self.clone(), permission_key.into())
+    }
+
+    pub fn acquire_lease<LD: Into<LeaseDuration>>(
+        &self,
+        lease_duration: LD,
+    ) -> AcquireShareLeaseBuilder {
+        AcquireShareLeaseBuilder::new(self.clone(), lease_duration.into())
+    }
+
+    pub fn break_lease(&self) -> BreakShareLeaseBuilder {
+        BreakShareLeaseBuilder::new(self.clone())
+    }
+
+    pub fn lease_client(&self, lease_id: LeaseId) -> ShareLeaseClient {
+        ShareLeaseClient::new(self.clone(), lease_id)
+    }
+
+    /// Create a client pointing at the given snapshot of the share.
+    pub fn snapshot_client<S: Into<String>>(&self, snapshot: S) -> ShareClient {
+        Self {
+            snapshot: Some(snapshot.into()),
+            ..self.clone()
+        }
+    }
+
+    /// Create a client for the root directory of the share.
+    pub fn root_directory_client(&self) -> DirectoryClient {
+        DirectoryClient::new(self.clone(), String::new())
+    }
+
+    pub fn directory_client<S: Into<String>>(&self, directory_path: S) -> DirectoryClient {
+        DirectoryClient::new(self.clone(), directory_path.into())
+    }
+
+    pub fn file_client<S: Into<String>>(&self, file_path: S) -> FileClient {
+        FileClient::new(self.clone(), file_path.into())
+    }
+
+    /// Create a shared access signature scoped to the share.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        self.path_shared_access_signature(None, FileSignedResource::Share, permissions, expiry)
+            .await
+    }
+
+    /// Create a shared access signature bound to a stored access policy of
+    /// the share.
+    ///
+    /// The permissions and expiry come from the policy, so revoking the policy
+    /// revokes the signature.
+    pub async fn stored_policy_shared_access_signature(
+        &self,
+        identifier: impl Into<String>,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        let creds = self.service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource = format!("/file/{}/{}", account, self.share_name());
+        Ok(FileSharedAccessSignature::with_stored_policy(
+            key.clone(),
+            canonicalized_resource,
+            identifier.into(),
+            FileSignedResource::Share,
+        ))
+    }
+
+    /// Create a signed share url
+    pub fn generate_signed_share_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        signed_url(self.url()?, signature)
+    }
+
+    pub(crate) async fn path_shared_access_signature(
+        &self,
+        path: Option<&str>,
+        resource: FileSignedResource,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        let creds = self.service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource = match path {
+            Some(path) => format!("/file/{}/{}/{}", account, self.share_name(), path),
+            None => format!("/file/{}/{}", account, self.share_name()),
+        };
+        Ok(FileSharedAccessSignature::new(
+            key.clone(),
+            canonicalized_resource,
+            permissions,
+            expiry,
+            resource,
+        ))
+    }
+
+    pub fn share_name(&self) -> &str {
+        &self.share_name
+    }
+
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        let mut url = self.service_client.url()?;
+        url.path_segments_mut()
+            .expect("invalid base url")
+            .push(self.share_name());
+        if let Some(snapshot) = self.snapshot() {
+            url.query_pairs_mut().append_pair("sharesnapshot", snapshot);
+        }
+        Ok(url)
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        FileServiceClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.service_client.send(context, request).await
+    }
+}
+
+/// Appends the signature to the query of the url, keeping the snapshot the
+/// url may point to.
+pub(crate) fn signed_url<T: SasToken>(mut url: Url, signature: &T) -> azure_core::Result<Url> {
+    let token = signature.token()?;
+    let query = match url.query() {
+        Some(query) if !query.is_empty() => format!("{query}&{token}"),
+        _ => token,
+    };
+    url.set_query(Some(&query));
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::auth::Secret;
+    use azure_storage::StorageCredentials;
+    use time::Duration;
+
+    #[test]
+    fn snapshot_url() -> azure_core::Result<()> {
+        let share_client = FileServiceClient::new("account", StorageCredentials::anonymous())
+            .share_client("myshare")
+            .snapshot_client("2023-01-01T00:00:00.0000000Z");
+        let file_client = share_client.file_client("dir one/file.txt");
+
+        assert_eq!(
+            file_client.url()?.as_str(),
+            "https://account.file.core.windows.net/myshare/dir%20one/file.txt?sharesnapshot=2023-01-01T00%3A00%3A00.0000000Z"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn signed_snapshot_url() -> azure_core::Result<()> {
+        let share_client = FileServiceClient::new("account", StorageCredentials::anonymous())
+            .share_client("myshare")
+            .snapshot_client("2023-01-01T00:00:00.0000000Z");
+        let signature = || {
+            FileSharedAccessSignature::new(
+                Secret::new("RZfi3m1W7eyQ5zD4ymSmGANVdJ2SDQmg4sE89SW104s="),
+                String::from("/file/account/myshare"),
+                FileSasPermissions {
+                    read: true,
+                    ..Default::default()
+                },
+                OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+                FileSignedResource::Share,
+            )
+        };
+        let token = signature().token()?;
+
+        let url = share_client.generate_signed_share_url(&signature())?;
+        assert_eq!(
+            url.query(),
+            Some(format!("sharesnapshot=2023-01-01T00%3A00%3A00.0000000Z&{token}").as_str())
+        );
+
+        let url = share_client
+            .file_client("file.txt")
+            .generate_signed_file_url(&signature())?;
+        assert!(url.query_pairs().any(
+            |(name, value)| name == "sharesnapshot" && value == "2023-01-01T00:00:00.0000000Z"
+        ));
+        assert!(url.query_pairs().any(|(name, _)| name == "sig"));
+
+        let url = FileServiceClient::new("account", StorageCredentials::anonymous())
+            .share_client("myshare")
+            .generate_signed_share_url(&signature())?;
+        assert_eq!(url.query(), Some(token.as_str()));
+        Ok(())
+    }
+}
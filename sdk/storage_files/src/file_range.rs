@@ -0,0 +1,15 @@
+use azure_core::prelude::Range;
+
+/// A range of bytes of a file, both ends inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FileRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl From<FileRange> for Range {
+    fn from(range: FileRange) -> Self {
+        Range::new(range.start, range.end + 1)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::headers::HANDLE_ID;
+use azure_core::headers::{Header, HeaderName, HeaderValue};
+use time::OffsetDateTime;
+
+/// An open SMB handle on a file or directory.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Handle {
+    pub handle_id: String,
+    pub path: String,
+    pub file_id: String,
+    pub parent_id: Option<String>,
+    pub session_id: String,
+    pub client_ip: String,
+    #[serde(with = "azure_core::date::rfc1123")]
+    pub open_time: OffsetDateTime,
+    #[serde(default, with = "azure_core::date::rfc1123::option")]
+    pub last_reconnect_time: Option<OffsetDateTime>,
+}
+
+/// Identifies the handles to close.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandleId {
+    /// All the handles open on the path.
+    All,
+    /// A single handle, as returned by the list handles operation.
+    Id(String),
+}
+
+impl From<&Handle> for HandleId {
+    fn from(handle: &Handle) -> Self {
+        Self::Id(handle.handle_id.clone())
+    }
+}
+
+impl Header for HandleId {
+    fn name(&self) -> HeaderName {
+        HANDLE_ID
+    }
+
+    fn value(&self) -> HeaderValue {
+        match self {
+            Self::All => HeaderValue::from_static("*"),
+            Self::Id(id) => id.clone().into(),
+        }
+    }
+}
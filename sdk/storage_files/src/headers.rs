@@ -0,0 +1,25 @@
+use azure_core::headers::HeaderName;
+
+pub const ACCESS_TIER: HeaderName = HeaderName::from_static("x-ms-access-tier");
+pub const CONTENT_LENGTH: HeaderName = HeaderName::from_static("x-ms-content-length");
+pub const CONTENT_TYPE: HeaderName = HeaderName::from_static("x-ms-content-type");
+pub const COPY_ACTION: HeaderName = HeaderName::from_static("x-ms-copy-action");
+pub const FILE_ATTRIBUTES: HeaderName = HeaderName::from_static("x-ms-file-attributes");
+pub const FILE_CHANGE_TIME: HeaderName = HeaderName::from_static("x-ms-file-change-time");
+pub const FILE_CREATION_TIME: HeaderName = HeaderName::from_static("x-ms-file-creation-time");
+pub const FILE_ID: HeaderName = HeaderName::from_static("x-ms-file-id");
+pub const FILE_LAST_WRITE_TIME: HeaderName = HeaderName::from_static("x-ms-file-last-write-time");
+pub const FILE_PARENT_ID: HeaderName = HeaderName::from_static("x-ms-file-parent-id");
+pub const FILE_PERMISSION: HeaderName = HeaderName::from_static("x-ms-file-permission");
+pub const FILE_PERMISSION_KEY: HeaderName = HeaderName::from_static("x-ms-file-permission-key");
+pub const HANDLE_ID: HeaderName = HeaderName::from_static("x-ms-handle-id");
+pub const MARKER: HeaderName = HeaderName::from_static("x-ms-marker");
+pub const NUMBER_OF_HANDLES_CLOSED: HeaderName =
+    HeaderName::from_static("x-ms-number-of-handles-closed");
+pub const NUMBER_OF_HANDLES_FAILED: HeaderName =
+    HeaderName::from_static("x-ms-number-of-handles-failed");
+pub const RECURSIVE: HeaderName = HeaderName::from_static("x-ms-recursive");
+pub const SHARE_QUOTA: HeaderName = HeaderName::from_static("x-ms-share-quota");
+pub const SNAPSHOT: HeaderName = HeaderName::from_static("x-ms-snapshot");
+pub const TYPE: HeaderName = HeaderName::from_static("x-ms-type");
+pub const WRITE: HeaderName = HeaderName::from_static("x-ms-write");
@@ -0,0 +1,61 @@
+/*!
+# The Azure Files crate
+
+This crate is from the [Azure SDK for Rust](https://github.com/azure/azure-sdk-for-rust).
+It supports [Azure Files](https://docs.microsoft.com/azure/storage/files/storage-files-introduction).
+
+# Example
+```no_run
+use azure_storage::prelude::*;
+use azure_storage_files::prelude::*;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    let account = std::env::var("STORAGE_ACCOUNT").expect("missing STORAGE_ACCOUNT");
+    let access_key = std::env::var("STORAGE_ACCESS_KEY").expect("missing STORAGE_ACCESS_KEY");
+    let share_name = std::env::var("STORAGE_SHARE_NAME").expect("missing STORAGE_SHARE_NAME");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let file_service = FileServiceClient::new(account, storage_credentials);
+    let share = file_service.share_client(share_name);
+
+    share.create().quota(1u64).await?;
+    share.directory_client("reports").create().await?;
+
+    let content = "hello world";
+    let file = share.file_client("reports/hello.txt");
+    file.create(content.len() as u64).await?;
+    file.put_range(0..content.len(), content).await?;
+
+    let response = file.get().await?;
+    println!("{:?}", response.data);
+
+    Ok(())
+}
+```
+
+*/
+
+#[macro_use]
+extern crate azure_core;
+#[macro_use]
+extern crate serde;
+
+mod access_tier;
+mod clients;
+mod file_range;
+mod handle;
+mod headers;
+pub mod operations;
+pub mod prelude;
+mod share;
+mod smb_properties;
+#[cfg(test)]
+mod test_transport;
+
+pub use access_tier::AccessTier;
+pub use clients::*;
+pub use file_range::FileRange;
+pub use handle::{Handle, HandleId};
+pub use share::{Share, ShareProperties};
+pub use smb_properties::{FileAttributes, FilePermission, FileTime, SmbProperties};
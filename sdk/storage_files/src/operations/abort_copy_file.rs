@@ -0,0 +1,73 @@
+use crate::{headers::COPY_ACTION, FileClient};
+use azure_core::{error::Error, headers::Headers, prelude::*, Method, Response as AzureResponse};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    AbortCopyFile,
+    client: FileClient,
+    copy_id: String,
+    ?lease_id: LeaseId
+}
+
+impl AbortCopyFileBuilder {
+    pub fn into_future(mut self) -> AbortCopyFile {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("comp", "copy")
+                .append_pair("copyid", &self.copy_id);
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_ACTION, "abort");
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AbortCopyFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl std::convert::TryFrom<AzureResponse> for AbortCopyFileResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        Ok(AbortCopyFileResponse {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{Method, StatusCode};
+
+    #[tokio::test]
+    async fn abort_copy() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::NoContent).client();
+
+        client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .abort_copy("d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt?comp=copy&copyid=d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c"
+        );
+        assert_eq!(header(&request, "x-ms-copy-action"), Some("abort"));
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::FileClient;
+use azure_core::{headers::*, prelude::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    AcquireFileLease,
+    client: FileClient,
+    ?proposed_lease_id: ProposedLeaseId
+}
+
+impl AcquireFileLeaseBuilder {
+    pub fn into_future(mut self) -> AcquireFileLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "acquire");
+            // file leases never expire
+            headers.add(LeaseDuration::Infinite);
+            headers.add(self.proposed_lease_id);
+
+            let mut request =
+                FileClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            AcquireFileLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(AcquireFileLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_id_from_headers => lease_id: LeaseId,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{prelude::LeaseId, Method, StatusCode};
+
+    #[tokio::test]
+    async fn acquire_file_lease() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .header("x-ms-lease-id", "d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+            .client();
+
+        let proposed_lease_id: LeaseId = "d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c".parse().unwrap();
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .acquire_lease()
+            .proposed_lease_id(proposed_lease_id)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt?comp=lease"
+        );
+        assert_eq!(header(&request, "x-ms-lease-action"), Some("acquire"));
+        assert_eq!(header(&request, "x-ms-lease-duration"), Some("-1"));
+        assert_eq!(
+            header(&request, "x-ms-proposed-lease-id"),
+            Some("d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+        );
+        assert_eq!(response.lease_id, proposed_lease_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::ShareClient;
+use azure_core::{headers::*, prelude::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    AcquireShareLease,
+    client: ShareClient,
+    lease_duration: LeaseDuration,
+    ?proposed_lease_id: ProposedLeaseId
+}
+
+impl AcquireShareLeaseBuilder {
+    pub fn into_future(mut self) -> AcquireShareLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("restype", "share")
+                .append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "acquire");
+            headers.add(self.lease_duration);
+            headers.add(self.proposed_lease_id);
+
+            let mut request =
+                ShareClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            AcquireShareLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(AcquireShareLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_id_from_headers => lease_id: LeaseId,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{prelude::LeaseId, Method, StatusCode};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn acquire_share_lease() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .header("x-ms-lease-id", "d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+            .client();
+
+        let proposed_lease_id: LeaseId = "d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c".parse().unwrap();
+        let response = client
+            .share_client("share")
+            .acquire_lease(Duration::from_secs(30))
+            .proposed_lease_id(proposed_lease_id)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share?restype=share&comp=lease"
+        );
+        assert_eq!(header(&request, "x-ms-lease-action"), Some("acquire"));
+        assert_eq!(header(&request, "x-ms-lease-duration"), Some("30"));
+        assert_eq!(
+            header(&request, "x-ms-proposed-lease-id"),
+            Some("d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+        );
+        assert_eq!(response.lease_id, proposed_lease_id);
+        Ok(())
+    }
+}
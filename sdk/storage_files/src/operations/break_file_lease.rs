@@ -0,0 +1,72 @@
+use crate::FileClient;
+use azure_core::{headers::*, prelude::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    BreakFileLease,
+    client: FileClient,
+    ?lease_id: LeaseId
+}
+
+impl BreakFileLeaseBuilder {
+    pub fn into_future(mut self) -> BreakFileLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "break");
+            headers.add(self.lease_id);
+
+            let mut request =
+                FileClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            BreakFileLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(BreakFileLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{prelude::LeaseId, Method, StatusCode};
+
+    #[tokio::test]
+    async fn break_file_lease() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .client();
+
+        let lease_id: LeaseId = "b6f4ff6c-4ac5-4c1f-bf0a-2d4e3ba5e1c4".parse().unwrap();
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .break_lease()
+            .lease_id(lease_id)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt?comp=lease"
+        );
+        assert_eq!(header(&request, "x-ms-lease-action"), Some("break"));
+        assert_eq!(
+            header(&request, "x-ms-lease-id"),
+            Some("b6f4ff6c-4ac5-4c1f-bf0a-2d4e3ba5e1c4")
+        );
+        assert_eq!(response.etag, "\"0x8DA583A4F7B9C3E\"");
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use crate::ShareClient;
+use azure_core::{headers::*, prelude::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    BreakShareLease,
+    client: ShareClient,
+    ?lease_break_period: LeaseBreakPeriod,
+    ?lease_id: LeaseId
+}
+
+impl BreakShareLeaseBuilder {
+    pub fn into_future(mut self) -> BreakShareLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut()
+                .append_pair("restype", "share")
+                .append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "break");
+            headers.add(self.lease_break_period);
+            headers.add(self.lease_id);
+
+            let mut request =
+                ShareClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            BreakShareLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(BreakShareLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_time_from_headers => lease_time: u8,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{Method, StatusCode};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn break_share_lease() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .header("x-ms-lease-time", "10")
+            .client();
+
+        let response = client
+            .share_client("share")
+            .break_lease()
+            .lease_break_period(Duration::from_secs(10))
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share?restype=share&comp=lease"
+        );
+        assert_eq!(header(&request, "x-ms-lease-action"), Some("break"));
+        assert_eq!(header(&request, "x-ms-lease-break-period"), Some("10"));
+        assert_eq!(header(&request, "x-ms-lease-id"), None);
+        assert_eq!(response.lease_time, 10);
+        Ok(())
+    }
+}
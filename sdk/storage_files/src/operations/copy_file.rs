@@ -0,0 +1,106 @@
+use crate::FileClient;
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers, COPY_SOURCE, COPY_STATUS},
+    prelude::*,
+    Method, Response as AzureResponse, Url,
+};
+use azure_storage::{copy_id_from_headers, headers::CommonStorageResponseHeaders, CopyId};
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    CopyFile,
+    client: FileClient,
+    copy_source: Url,
+    ?metadata: Metadata,
+    ?lease_id: LeaseId
+}
+
+impl CopyFileBuilder {
+    pub fn into_future(mut self) -> CopyFile {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_SOURCE, self.copy_source.to_string());
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CopyFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub copy_id: CopyId,
+    /// Either `success` or `pending`.
+    pub copy_status: String,
+}
+
+impl std::convert::TryFrom<AzureResponse> for CopyFileResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CopyFileResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            copy_id: copy_id_from_headers(headers)?,
+            copy_status: headers.get_as(&COPY_STATUS)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{Method, StatusCode, Url};
+
+    #[tokio::test]
+    async fn start_copy() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Accepted)
+            .resource_headers()
+            .header("x-ms-copy-id", "d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+            .header("x-ms-copy-status", "pending")
+            .client();
+
+        let source = Url::parse("https://other.file.core.windows.net/share/source.txt")?;
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .copy(source)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt"
+        );
+        assert_eq!(
+            header(&request, "x-ms-copy-source"),
+            Some("https://other.file.core.windows.net/share/source.txt")
+        );
+
+        assert_eq!(
+            response.copy_id.to_string(),
+            "d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c"
+        );
+        assert_eq!(response.copy_status, "pending");
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    headers::{CONTENT_LENGTH, CONTENT_TYPE, TYPE},
+    smb_properties::add_smb_headers,
+    FileAttributes, FileClient, FilePermission, FileTime, SmbProperties,
+};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    CreateFile,
+    client: FileClient,
+    content_length: u64,
+    ?content_type: String,
+    ?metadata: Metadata,
+    ?file_attributes: FileAttributes,
+    ?file_creation_time: FileTime,
+    ?file_last_write_time: FileTime,
+    ?file_change_time: FileTime,
+    ?file_permission: FilePermission,
+    ?lease_id: LeaseId
+}
+
+impl CreateFileBuilder {
+    pub fn into_future(mut self) -> CreateFile {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.insert(TYPE, "file");
+            headers.insert(CONTENT_LENGTH, self.content_length.to_string());
+            if let Some(content_type) = &self.content_type {
+                headers.insert(CONTENT_TYPE, content_type.clone());
+            }
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+            add_smb_headers(
+                &mut headers,
+                self.file_attributes,
+                self.file_creation_time,
+                self.file_last_write_time,
+                self.file_change_time,
+                self.file_permission.clone(),
+            );
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_properties: SmbProperties,
+}
+
+impl std::convert::TryFrom<AzureResponse> for CreateFileResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CreateFileResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_properties: headers.try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_transport::{header, TestTransport},
+        FileAttributes, FilePermission, FileTime,
+    };
+    use azure_core::{Method, StatusCode};
+
+    #[tokio::test]
+    async fn create_file() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Created)
+            .resource_headers()
+            .smb_headers()
+            .client();
+
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .create(1024)
+            .content_type("text/plain")
+            .file_attributes("ReadOnly".parse::<FileAttributes>()?)
+            .file_creation_time(FileTime::Now)
+            .file_permission(FilePermission::Key("4066528134148476695*1".to_owned()))
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt"
+        );
+        assert_eq!(header(&request, "x-ms-type"), Some("file"));
+        assert_eq!(header(&request, "x-ms-content-length"), Some("1024"));
+        assert_eq!(header(&request, "x-ms-content-type"), Some("text/plain"));
+        assert_eq!(header(&request, "x-ms-file-attributes"), Some("ReadOnly"));
+        assert_eq!(header(&request, "x-ms-file-creation-time"), Some("now"));
+        assert_eq!(
+            header(&request, "x-ms-file-permission-key"),
+            Some("4066528134148476695*1")
+        );
+        assert_eq!(header(&request, "x-ms-file-permission"), None);
+
+        assert_eq!(response.etag, "\"0x8DA583A4F7B9C3E\"");
+        assert!(response.smb_properties.file_attributes.archive);
+        assert_eq!(response.smb_properties.file_id, "13835128424026341376");
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{headers::SHARE_QUOTA, AccessTier, ShareClient};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    CreateShare,
+    client: ShareClient,
+    ?quota: u64,
+    ?access_tier: AccessTier,
+    ?metadata: Metadata
+}
+
+impl CreateShareBuilder {
+    pub fn into_future(mut self) -> CreateShare {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("restype", "share");
+
+            let mut headers = Headers::new();
+            if let Some(quota) = self.quota {
+                headers.insert(SHARE_QUOTA, quota.to_string());
+            }
+            headers.add(self.access_tier);
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateShareResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
+
+impl std::convert::TryFrom<AzureResponse> for CreateShareResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CreateShareResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+        })
+    }
+}
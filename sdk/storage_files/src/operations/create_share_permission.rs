@@ -0,0 +1,62 @@
+use crate::{headers::FILE_PERMISSION_KEY, ShareClient};
+use azure_core::{
+    error::Error,
+    headers::{Headers, CONTENT_TYPE},
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    CreateSharePermission,
+    client: ShareClient,
+    permission: String,
+}
+
+impl CreateSharePermissionBuilder {
+    pub fn into_future(mut self) -> CreateSharePermission {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("restype", "share")
+                .append_pair("comp", "filepermission");
+
+            let body = serde_json::to_vec(&SharePermission {
+                permission: self.permission.clone(),
+            })?;
+
+            let mut headers = Headers::new();
+            headers.insert(CONTENT_TYPE, "application/json");
+
+            let mut request =
+                ShareClient::finalize_request(url, Method::Put, headers, Some(body.into()))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSharePermissionResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub file_permission_key: String,
+}
+
+impl std::convert::TryFrom<AzureResponse> for CreateSharePermissionResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CreateSharePermissionResponse {
+            common_storage_response_headers: headers.try_into()?,
+            file_permission_key: headers.get_as(&FILE_PERMISSION_KEY)?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SharePermission {
+    pub permission: String,
+}
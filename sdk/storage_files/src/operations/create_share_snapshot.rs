@@ -0,0 +1,97 @@
+use crate::{headers::SNAPSHOT, ShareClient};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    CreateShareSnapshot,
+    client: ShareClient,
+    ?metadata: Metadata
+}
+
+impl CreateShareSnapshotBuilder {
+    pub fn into_future(mut self) -> CreateShareSnapshot {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("restype", "share")
+                .append_pair("comp", "snapshot");
+
+            let mut headers = Headers::new();
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateShareSnapshotResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// The snapshot identifier, to be passed to `ShareClient::snapshot_client`.
+    pub snapshot: String,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
+
+impl std::convert::TryFrom<AzureResponse> for CreateShareSnapshotResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(CreateShareSnapshotResponse {
+            common_storage_response_headers: headers.try_into()?,
+            snapshot: headers.get_as(&SNAPSHOT)?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{prelude::Metadata, Method, StatusCode};
+
+    #[tokio::test]
+    async fn create_share_snapshot() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Created)
+            .resource_headers()
+            .header("x-ms-snapshot", "2022-06-27T13:38:48.0000000Z")
+            .client();
+
+        let mut metadata = Metadata::new();
+        metadata.insert("reason", "backup");
+        let response = client
+            .share_client("share")
+            .create_snapshot()
+            .metadata(metadata)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share?restype=share&comp=snapshot"
+        );
+        assert_eq!(header(&request, "x-ms-meta-reason"), Some("backup"));
+
+        assert_eq!(response.snapshot, "2022-06-27T13:38:48.0000000Z");
+        assert_eq!(response.etag, "\"0x8DA583A4F7B9C3E\"");
+        Ok(())
+    }
+}
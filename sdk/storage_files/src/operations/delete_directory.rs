@@ -0,0 +1,40 @@
+use crate::DirectoryClient;
+use azure_core::{error::Error, headers::Headers, Method, Response as AzureResponse};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    DeleteDirectory,
+    client: DirectoryClient,
+}
+
+impl DeleteDirectoryBuilder {
+    pub fn into_future(mut self) -> DeleteDirectory {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("restype", "directory");
+
+            let mut request =
+                DirectoryClient::finalize_request(url, Method::Delete, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteDirectoryResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl std::convert::TryFrom<AzureResponse> for DeleteDirectoryResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        Ok(DeleteDirectoryResponse {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
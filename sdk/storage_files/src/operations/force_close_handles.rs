@@ -0,0 +1,130 @@
+use crate::{
+    clients::PathClient,
+    headers::{MARKER, NUMBER_OF_HANDLES_CLOSED, NUMBER_OF_HANDLES_FAILED, RECURSIVE},
+    HandleId,
+};
+use azure_core::{
+    error::Error, headers::Headers, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    #[stream]
+    ForceCloseHandles<C: PathClient>,
+    client: C,
+    handle_id: HandleId,
+    ?recursive: bool
+}
+
+impl<C: PathClient + 'static> ForceCloseHandlesBuilder<C> {
+    /// Closes the handles, one page at a time.
+    ///
+    /// The service stops closing handles when the request runs for too long,
+    /// so the stream must be drained to close all of them.
+    pub fn into_stream(self) -> Pageable<ForceCloseHandlesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut()
+                    .append_pair("comp", "forceclosehandles");
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                let mut headers = Headers::new();
+                headers.add(this.handle_id.clone());
+                if let Some(recursive) = this.recursive {
+                    headers.insert(RECURSIVE, recursive.to_string());
+                }
+
+                let mut request =
+                    azure_storage::clients::finalize_request(url, Method::Put, headers, None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                response.try_into()
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ForceCloseHandlesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub number_of_handles_closed: u32,
+    pub number_of_handles_failed: u32,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl std::convert::TryFrom<AzureResponse> for ForceCloseHandlesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(ForceCloseHandlesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            number_of_handles_closed: headers.get_as(&NUMBER_OF_HANDLES_CLOSED)?,
+            number_of_handles_failed: headers
+                .get_optional_as(&NUMBER_OF_HANDLES_FAILED)?
+                .unwrap_or_default(),
+            next_marker: NextMarker::from_possibly_empty_string(
+                headers.get_optional_string(&MARKER),
+            ),
+        })
+    }
+}
+
+impl Continuable for ForceCloseHandlesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_transport::{header, TestTransport},
+        HandleId,
+    };
+    use azure_core::{Method, StatusCode};
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn force_close_handles() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .header("x-ms-number-of-handles-closed", "3")
+            .header("x-ms-number-of-handles-failed", "1")
+            .header("x-ms-marker", "")
+            .client();
+
+        let mut pages = client
+            .share_client("share")
+            .directory_client("dir")
+            .force_close_handles(HandleId::All)
+            .recursive(true)
+            .into_stream();
+        let page = pages.next().await.unwrap()?;
+        assert!(pages.next().await.is_none());
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir?comp=forceclosehandles"
+        );
+        assert_eq!(header(&request, "x-ms-handle-id"), Some("*"));
+        assert_eq!(header(&request, "x-ms-recursive"), Some("true"));
+
+        assert_eq!(page.number_of_handles_closed, 3);
+        assert_eq!(page.number_of_handles_failed, 1);
+        assert!(page.next_marker.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{DirectoryClient, SmbProperties};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    GetDirectoryProperties,
+    client: DirectoryClient,
+}
+
+impl GetDirectoryPropertiesBuilder {
+    pub fn into_future(mut self) -> GetDirectoryProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("restype", "directory");
+
+            let mut request =
+                DirectoryClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetDirectoryPropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_properties: SmbProperties,
+    pub metadata: Metadata,
+}
+
+impl std::convert::TryFrom<AzureResponse> for GetDirectoryPropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(GetDirectoryPropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_properties: headers.try_into()?,
+            metadata: headers.into(),
+        })
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{FileClient, SmbProperties};
+use azure_core::{
+    headers::{
+        content_type_from_headers, etag_from_headers, last_modified_from_headers, Headers,
+        CONTENT_LENGTH, CONTENT_RANGE, MS_RANGE,
+    },
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use bytes::Bytes;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    GetFile,
+    client: FileClient,
+    ?range: Range,
+    ?lease_id: LeaseId
+}
+
+impl GetFileBuilder {
+    pub fn into_future(mut self) -> GetFile {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            if let Some(range) = &self.range {
+                headers.insert(MS_RANGE, range.to_string());
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetFileResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_length: u64,
+    pub content_type: String,
+    /// The range returned, when a range was requested.
+    pub content_range: Option<String>,
+    pub smb_properties: SmbProperties,
+    pub metadata: Metadata,
+    pub data: Bytes,
+}
+
+impl GetFileResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            etag: etag_from_headers(&headers)?,
+            last_modified: last_modified_from_headers(&headers)?,
+            content_length: headers.get_as(&CONTENT_LENGTH)?,
+            content_type: content_type_from_headers(&headers)?,
+            content_range: headers.get_optional_string(&CONTENT_RANGE),
+            smb_properties: (&headers).try_into()?,
+            metadata: (&headers).into(),
+            data: body.collect().await?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{Method, StatusCode};
+
+    #[tokio::test]
+    async fn get_file_range() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::PartialContent)
+            .resource_headers()
+            .smb_headers()
+            .header("content-length", "5")
+            .header("content-type", "text/plain")
+            .header("content-range", "bytes 0-4/11")
+            .header("x-ms-meta-origin", "tests")
+            .body("hello")
+            .client();
+
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .get()
+            .range(0u64..5)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Get);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt"
+        );
+        assert_eq!(header(&request, "x-ms-range"), Some("bytes=0-4"));
+
+        assert_eq!(response.content_length, 5);
+        assert_eq!(response.content_type, "text/plain");
+        assert_eq!(response.content_range.as_deref(), Some("bytes 0-4/11"));
+        assert_eq!(
+            response.metadata.get("origin"),
+            Some(bytes::Bytes::from_static(b"tests"))
+        );
+        assert_eq!(response.data.as_ref(), b"hello");
+        Ok(())
+    }
+}
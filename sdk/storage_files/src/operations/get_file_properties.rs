@@ -0,0 +1,117 @@
+use crate::{FileClient, SmbProperties};
+use azure_core::{
+    error::Error,
+    headers::{
+        content_type_from_headers, etag_from_headers, last_modified_from_headers, Headers,
+        CONTENT_LENGTH, COPY_STATUS,
+    },
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::{CommonStorageResponseHeaders, COPY_ID};
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    GetFileProperties,
+    client: FileClient,
+    ?lease_id: LeaseId
+}
+
+impl GetFilePropertiesBuilder {
+    pub fn into_future(mut self) -> GetFileProperties {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Head, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetFilePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_length: u64,
+    pub content_type: String,
+    pub copy_id: Option<String>,
+    pub copy_status: Option<String>,
+    pub smb_properties: SmbProperties,
+    pub metadata: Metadata,
+}
+
+impl std::convert::TryFrom<AzureResponse> for GetFilePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(GetFilePropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            content_length: headers.get_as(&CONTENT_LENGTH)?,
+            content_type: content_type_from_headers(headers)?,
+            copy_id: headers.get_optional_string(&COPY_ID),
+            copy_status: headers.get_optional_string(&COPY_STATUS),
+            smb_properties: headers.try_into()?,
+            metadata: headers.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{prelude::LeaseId, Method, StatusCode};
+
+    #[tokio::test]
+    async fn get_file_properties() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .smb_headers()
+            .header("content-length", "11")
+            .header("content-type", "application/octet-stream")
+            .header("x-ms-copy-id", "d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+            .header("x-ms-copy-status", "pending")
+            .client();
+
+        let lease_id: LeaseId = "b6f4ff6c-4ac5-4c1f-bf0a-2d4e3ba5e1c4".parse().unwrap();
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .get_properties()
+            .lease_id(lease_id)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Head);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt"
+        );
+        assert_eq!(
+            header(&request, "x-ms-lease-id"),
+            Some("b6f4ff6c-4ac5-4c1f-bf0a-2d4e3ba5e1c4")
+        );
+
+        assert_eq!(response.content_length, 11);
+        assert_eq!(
+            response.copy_id.as_deref(),
+            Some("d3d5a1f0-6f4a-4b8e-9c51-5f8a7e9a1b2c")
+        );
+        assert_eq!(response.copy_status.as_deref(), Some("pending"));
+        assert_eq!(
+            response.smb_properties.file_permission_key,
+            "4066528134148476695*1"
+        );
+        Ok(())
+    }
+}
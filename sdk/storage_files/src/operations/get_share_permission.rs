@@ -0,0 +1,51 @@
+use crate::{
+    headers::FILE_PERMISSION_KEY, operations::create_share_permission::SharePermission, ShareClient,
+};
+use azure_core::{headers::Headers, Method, Response as AzureResponse};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    GetSharePermission,
+    client: ShareClient,
+    permission_key: String,
+}
+
+impl GetSharePermissionBuilder {
+    pub fn into_future(mut self) -> GetSharePermission {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("restype", "share")
+                .append_pair("comp", "filepermission");
+
+            let mut headers = Headers::new();
+            headers.insert(FILE_PERMISSION_KEY, self.permission_key.clone());
+
+            let mut request = ShareClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetSharePermissionResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetSharePermissionResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// The security descriptor, in SDDL format.
+    pub permission: String,
+}
+
+impl GetSharePermissionResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let permission: SharePermission = body.json().await?;
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            permission: permission.permission,
+        })
+    }
+}
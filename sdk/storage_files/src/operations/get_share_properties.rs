@@ -0,0 +1,110 @@
+use crate::{
+    headers::{ACCESS_TIER, SHARE_QUOTA},
+    AccessTier, ShareClient,
+};
+use azure_core::{
+    error::Error,
+    headers::{
+        etag_from_headers, last_modified_from_headers, Headers, LEASE_DURATION, LEASE_STATE,
+        LEASE_STATUS,
+    },
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    GetShareProperties,
+    client: ShareClient,
+    ?lease_id: LeaseId
+}
+
+impl GetSharePropertiesBuilder {
+    pub fn into_future(mut self) -> GetShareProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("restype", "share");
+
+            let mut headers = Headers::new();
+            headers.add(self.lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetSharePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    /// The quota of the share, in GiB.
+    pub quota: u64,
+    pub access_tier: Option<AccessTier>,
+    pub lease_status: Option<String>,
+    pub lease_state: Option<String>,
+    pub lease_duration: Option<String>,
+    pub metadata: Metadata,
+}
+
+impl std::convert::TryFrom<AzureResponse> for GetSharePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(GetSharePropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            quota: headers.get_as(&SHARE_QUOTA)?,
+            access_tier: headers.get_optional_as(&ACCESS_TIER)?,
+            lease_status: headers.get_optional_string(&LEASE_STATUS),
+            lease_state: headers.get_optional_string(&LEASE_STATE),
+            lease_duration: headers.get_optional_string(&LEASE_DURATION),
+            metadata: headers.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_transport::TestTransport, AccessTier};
+    use azure_core::{Method, StatusCode};
+
+    #[tokio::test]
+    async fn get_share_properties() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .header("x-ms-share-quota", "5120")
+            .header("x-ms-access-tier", "Cool")
+            .header("x-ms-lease-status", "locked")
+            .header("x-ms-lease-state", "leased")
+            .header("x-ms-lease-duration", "infinite")
+            .client();
+
+        let response = client
+            .share_client("share")
+            .snapshot_client("2022-06-27T13:38:48.0000000Z")
+            .get_properties()
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Get);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share?sharesnapshot=2022-06-27T13%3A38%3A48.0000000Z&restype=share"
+        );
+
+        assert_eq!(response.quota, 5120);
+        assert_eq!(response.access_tier, Some(AccessTier::Cool));
+        assert_eq!(response.lease_state.as_deref(), Some("leased"));
+        assert_eq!(response.lease_duration.as_deref(), Some("infinite"));
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{DirectoryClient, FileAttributes};
+use azure_core::{
+    error::Error, headers::Headers, prelude::*, Etag, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    #[stream]
+    ListDirectory,
+    client: DirectoryClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+    ?include_extended_info: bool
+}
+
+impl ListDirectoryBuilder {
+    pub fn into_stream(self) -> Pageable<ListDirectoryResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut()
+                    .append_pair("restype", "directory")
+                    .append_pair("comp", "list");
+
+                this.prefix.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut headers = Headers::new();
+                if this.include_extended_info.unwrap_or(false) {
+                    url.query_pairs_mut()
+                        .append_pair("include", "Timestamps,ETag,Attributes,PermissionKey");
+                    headers.insert("x-ms-file-extended-info", "true");
+                }
+
+                let mut request =
+                    DirectoryClient::finalize_request(url, Method::Get, headers, None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListDirectoryResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListDirectoryResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub prefix: Option<String>,
+    pub max_results: Option<u32>,
+    pub directory_id: Option<String>,
+    pub entries: Vec<DirectoryEntry>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl ListDirectoryResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let list: ListDirectoryResponseInternal = body.xml().await?;
+
+        Ok(ListDirectoryResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            prefix: list.prefix.filter(|prefix| !prefix.is_empty()),
+            max_results: list.max_results,
+            directory_id: list.directory_id,
+            entries: list.entries.items,
+            next_marker: NextMarker::from_possibly_empty_string(list.next_marker),
+        })
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &FileEntry> {
+        self.entries.iter().filter_map(|entry| match entry {
+            DirectoryEntry::File(file) => Some(file),
+            DirectoryEntry::Directory(_) => None,
+        })
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = &SubdirectoryEntry> {
+        self.entries.iter().filter_map(|entry| match entry {
+            DirectoryEntry::File(_) => None,
+            DirectoryEntry::Directory(directory) => Some(directory),
+        })
+    }
+}
+
+impl Continuable for ListDirectoryResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum DirectoryEntry {
+    File(FileEntry),
+    Directory(SubdirectoryEntry),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FileEntry {
+    pub name: String,
+    pub file_id: Option<String>,
+    #[serde(default)]
+    pub properties: EntryProperties,
+    pub attributes: Option<FileAttributes>,
+    pub permission_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SubdirectoryEntry {
+    pub name: String,
+    pub file_id: Option<String>,
+    #[serde(default)]
+    pub properties: EntryProperties,
+    pub attributes: Option<FileAttributes>,
+    pub permission_key: Option<String>,
+}
+
+/// The properties of a directory entry.
+///
+/// Only the content length of files is returned unless the listing was
+/// requested with `include_extended_info`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EntryProperties {
+    #[serde(rename = "Content-Length")]
+    pub content_length: Option<u64>,
+    #[serde(default, with = "azure_core::date::iso8601::option")]
+    pub creation_time: Option<OffsetDateTime>,
+    #[serde(default, with = "azure_core::date::iso8601::option")]
+    pub last_access_time: Option<OffsetDateTime>,
+    #[serde(default, with = "azure_core::date::iso8601::option")]
+    pub last_write_time: Option<OffsetDateTime>,
+    #[serde(default, with = "azure_core::date::iso8601::option")]
+    pub change_time: Option<OffsetDateTime>,
+    #[serde(
+        default,
+        with = "azure_core::date::rfc1123::option",
+        rename = "Last-Modified"
+    )]
+    pub last_modified: Option<OffsetDateTime>,
+    pub etag: Option<Etag>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListDirectoryResponseInternal {
+    prefix: Option<String>,
+    max_results: Option<u32>,
+    directory_id: Option<String>,
+    #[serde(default)]
+    entries: Entries,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Entries {
+    #[serde(rename = "$value", default)]
+    items: Vec<DirectoryEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn deserialize_entries() -> azure_core::Result<()> {
+        const XML: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<EnumerationResults ServiceEndpoint=\"https://myaccount.file.core.windows.net/\" ShareName=\"myshare\" DirectoryPath=\"dir1\">
+    <MaxResults>100</MaxResults>
+    <DirectoryId>13835093239654252544</DirectoryId>
+    <Entries>
+        <File>
+            <FileId>13835128424026341376</FileId>
+            <Name>file.txt</Name>
+            <Properties>
+                <Content-Length>11</Content-Length>
+                <CreationTime>2021-07-01T10:44:59.1234567Z</CreationTime>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>\"0x8D93C7D4629C227\"</Etag>
+            </Properties>
+            <Attributes>Archive</Attributes>
+            <PermissionKey>4066528134148476695*1</PermissionKey>
+        </File>
+        <Directory>
+            <FileId>13835163608398430208</FileId>
+            <Name>subdir</Name>
+            <Properties />
+        </Directory>
+    </Entries>
+    <NextMarker />
+</EnumerationResults>";
+
+        let list: ListDirectoryResponseInternal = read_xml(XML.as_bytes())?;
+        assert_eq!(list.directory_id.as_deref(), Some("13835093239654252544"));
+        assert_eq!(list.next_marker.as_deref(), Some(""));
+        assert_eq!(list.entries.items.len(), 2);
+
+        match &list.entries.items[0] {
+            DirectoryEntry::File(file) => {
+                assert_eq!(file.name, "file.txt");
+                assert_eq!(file.properties.content_length, Some(11));
+                assert!(file.properties.creation_time.is_some());
+                assert!(file.attributes.expect("attributes").archive);
+            }
+            entry => panic!("unexpected entry {entry:?}"),
+        }
+        match &list.entries.items[1] {
+            DirectoryEntry::Directory(directory) => assert_eq!(directory.name, "subdir"),
+            entry => panic!("unexpected entry {entry:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{clients::PathClient, headers::RECURSIVE, Handle};
+use azure_core::{
+    error::Error, headers::Headers, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    #[stream]
+    ListHandles<C: PathClient>,
+    client: C,
+    ?max_results: MaxResults,
+    ?recursive: bool
+}
+
+impl<C: PathClient + 'static> ListHandlesBuilder<C> {
+    pub fn into_stream(self) -> Pageable<ListHandlesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut().append_pair("comp", "listhandles");
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut headers = Headers::new();
+                if let Some(recursive) = this.recursive {
+                    headers.insert(RECURSIVE, recursive.to_string());
+                }
+
+                let mut request =
+                    azure_storage::clients::finalize_request(url, Method::Get, headers, None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListHandlesResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListHandlesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub handles: Vec<Handle>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl ListHandlesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let list: ListHandlesResponseInternal = body.xml().await?;
+
+        Ok(ListHandlesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            handles: list.entries.items,
+            next_marker: NextMarker::from_possibly_empty_string(list.next_marker),
+        })
+    }
+}
+
+impl Continuable for ListHandlesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListHandlesResponseInternal {
+    #[serde(default)]
+    entries: Handles,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Handles {
+    #[serde(rename = "Handle", default)]
+    items: Vec<Handle>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn deserialize_handles() -> azure_core::Result<()> {
+        const XML: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<EnumerationResults>
+    <Entries>
+        <Handle>
+            <HandleId>155490108964</HandleId>
+            <Path>dir1/file.txt</Path>
+            <FileId>13835128424026341376</FileId>
+            <ParentId>13835093239654252544</ParentId>
+            <SessionId>9385737614310506553</SessionId>
+            <ClientIp>10.0.0.4:49154</ClientIp>
+            <OpenTime>Thu, 01 Jul 2021 10:44:59 GMT</OpenTime>
+        </Handle>
+    </Entries>
+    <NextMarker />
+</EnumerationResults>";
+
+        let list: ListHandlesResponseInternal = read_xml(XML.as_bytes())?;
+        assert_eq!(list.entries.items.len(), 1);
+        let handle = &list.entries.items[0];
+        assert_eq!(handle.handle_id, "155490108964");
+        assert_eq!(handle.path, "dir1/file.txt");
+        assert_eq!(handle.client_ip, "10.0.0.4:49154");
+        assert_eq!(handle.last_reconnect_time, None);
+
+        Ok(())
+    }
+}
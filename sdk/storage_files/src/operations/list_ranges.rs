@@ -0,0 +1,135 @@
+use crate::{FileClient, FileRange};
+use azure_core::{
+    headers::{etag_from_headers, last_modified_from_headers, Headers, MS_RANGE},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    ListRanges,
+    client: FileClient,
+    ?range: Range,
+    ?previous_snapshot: String,
+    ?lease_id: LeaseId
+}
+
+impl ListRangesBuilder {
+    pub fn into_future(mut self) -> ListRanges {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "rangelist");
+            // when diffing against a snapshot, cleared ranges are returned too
+            if let Some(previous_snapshot) = &self.previous_snapshot {
+                url.query_pairs_mut()
+                    .append_pair("prevsharesnapshot", previous_snapshot);
+            }
+
+            let mut headers = Headers::new();
+            if let Some(range) = &self.range {
+                headers.insert(MS_RANGE, range.to_string());
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            ListRangesResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListRangesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub ranges: Vec<FileRange>,
+    pub clear_ranges: Vec<FileRange>,
+}
+
+impl ListRangesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let list: RangesInternal = body.xml().await?;
+        let (ranges, clear_ranges) = list.split();
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            etag: etag_from_headers(&headers)?,
+            last_modified: last_modified_from_headers(&headers)?,
+            ranges,
+            clear_ranges,
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RangesInternal {
+    #[serde(rename = "$value", default)]
+    items: Vec<RangeItem>,
+}
+
+impl RangesInternal {
+    fn split(self) -> (Vec<FileRange>, Vec<FileRange>) {
+        let mut ranges = Vec::new();
+        let mut clear_ranges = Vec::new();
+        for item in self.items {
+            match item {
+                RangeItem::Range(range) => ranges.push(range),
+                RangeItem::ClearRange(range) => clear_ranges.push(range),
+            }
+        }
+        (ranges, clear_ranges)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+enum RangeItem {
+    Range(FileRange),
+    ClearRange(FileRange),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn deserialize_ranges() -> azure_core::Result<()> {
+        const XML: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<Ranges>
+    <Range><Start>0</Start><End>511</End></Range>
+    <ClearRange><Start>512</Start><End>1023</End></ClearRange>
+    <Range><Start>1024</Start><End>2047</End></Range>
+</Ranges>";
+
+        let list: RangesInternal = read_xml(XML.as_bytes())?;
+        let (ranges, clear_ranges) = list.split();
+        assert_eq!(
+            ranges,
+            vec![
+                FileRange { start: 0, end: 511 },
+                FileRange {
+                    start: 1024,
+                    end: 2047
+                }
+            ]
+        );
+        assert_eq!(
+            clear_ranges,
+            vec![FileRange {
+                start: 512,
+                end: 1023
+            }]
+        );
+
+        let list: RangesInternal = read_xml("<Ranges />".as_bytes())?;
+        assert!(list.items.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{FileServiceClient, Share};
+use azure_core::{
+    error::Error, headers::Headers, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+operation! {
+    #[stream]
+    ListShares,
+    client: FileServiceClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+    ?include_metadata: bool,
+    ?include_snapshots: bool,
+    ?include_deleted: bool
+}
+
+impl ListSharesBuilder {
+    pub fn into_stream(self) -> Pageable<ListSharesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut().append_pair("comp", "list");
+
+                this.prefix.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let include = [
+                    (this.include_metadata, "metadata"),
+                    (this.include_snapshots, "snapshots"),
+                    (this.include_deleted, "deleted"),
+                ]
+                .iter()
+                .filter(|(include, _)| include.unwrap_or(false))
+                .map(|(_, name)| *name)
+                .collect::<Vec<_>>();
+                if !include.is_empty() {
+                    url.query_pairs_mut()
+                        .append_pair("include", &include.join(","));
+                }
+
+                let mut request =
+                    FileServiceClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListSharesResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListSharesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub prefix: Option<String>,
+    pub max_results: Option<u32>,
+    pub shares: Vec<Share>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl Continuable for ListSharesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+impl ListSharesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let list: ListSharesResponseInternal = body.xml().await?;
+
+        Ok(ListSharesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            prefix: list.prefix.filter(|prefix| !prefix.is_empty()),
+            max_results: list.max_results,
+            shares: list.shares.items,
+            next_marker: NextMarker::from_possibly_empty_string(list.next_marker),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListSharesResponseInternal {
+    prefix: Option<String>,
+    max_results: Option<u32>,
+    #[serde(default)]
+    shares: Shares,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Shares {
+    #[serde(rename = "Share", default)]
+    items: Vec<Share>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccessTier;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn deserialize_shares() -> azure_core::Result<()> {
+        const XML: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<EnumerationResults ServiceEndpoint=\"https://myaccount.file.core.windows.net/\">
+    <MaxResults>2</MaxResults>
+    <Shares>
+        <Share>
+            <Name>share1</Name>
+            <Properties>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>\"0x8D93C7D4629C227\"</Etag>
+                <Quota>5120</Quota>
+                <AccessTier>TransactionOptimized</AccessTier>
+                <LeaseStatus>unlocked</LeaseStatus>
+                <LeaseState>available</LeaseState>
+            </Properties>
+            <Metadata><owner>rust</owner></Metadata>
+        </Share>
+        <Share>
+            <Name>share1</Name>
+            <Snapshot>2021-07-02T08:00:00.0000000Z</Snapshot>
+            <Properties>
+                <Last-Modified>Fri, 02 Jul 2021 08:00:00 GMT</Last-Modified>
+                <Etag>\"0x8D93C7D463004D6\"</Etag>
+                <Quota>5120</Quota>
+            </Properties>
+        </Share>
+    </Shares>
+    <NextMarker>/myaccount/share2</NextMarker>
+</EnumerationResults>";
+
+        let list: ListSharesResponseInternal = read_xml(XML.as_bytes())?;
+        assert_eq!(list.max_results, Some(2));
+        assert_eq!(list.next_marker.as_deref(), Some("/myaccount/share2"));
+
+        let shares = list.shares.items;
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[0].name, "share1");
+        assert_eq!(shares[0].properties.quota, 5120);
+        assert_eq!(
+            shares[0].properties.access_tier,
+            Some(AccessTier::TransactionOptimized)
+        );
+        assert_eq!(
+            shares[0].metadata.get("owner").map(String::as_str),
+            Some("rust")
+        );
+        assert_eq!(
+            shares[1].snapshot.as_deref(),
+            Some("2021-07-02T08:00:00.0000000Z")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+mod abort_copy_file;
+mod acquire_file_lease;
+mod acquire_share_lease;
+mod break_file_lease;
+mod break_share_lease;
+mod change_file_lease;
+mod change_share_lease;
+mod copy_file;
+mod create_directory;
+mod create_file;
+mod create_share;
+mod create_share_permission;
+mod create_share_snapshot;
+mod delete_directory;
+mod delete_file;
+mod delete_share;
+mod force_close_handles;
+mod get_directory_properties;
+mod get_file;
+mod get_file_properties;
+mod get_share_permission;
+mod get_share_properties;
+mod list_directory;
+mod list_handles;
+mod list_ranges;
+mod list_shares;
+mod put_range;
+mod release_file_lease;
+mod release_share_lease;
+mod renew_share_lease;
+mod set_file_properties;
+mod set_share_properties;
+
+pub use abort_copy_file::{AbortCopyFileBuilder, AbortCopyFileResponse};
+pub use acquire_file_lease::{AcquireFileLeaseBuilder, AcquireFileLeaseResponse};
+pub use acquire_share_lease::{AcquireShareLeaseBuilder, AcquireShareLeaseResponse};
+pub use break_file_lease::{BreakFileLeaseBuilder, BreakFileLeaseResponse};
+pub use break_share_lease::{BreakShareLeaseBuilder, BreakShareLeaseResponse};
+pub use change_file_lease::{ChangeFileLeaseBuilder, ChangeFileLeaseResponse};
+pub use change_share_lease::{ChangeShareLeaseBuilder, ChangeShareLeaseResponse};
+pub use copy_file::{CopyFileBuilder, CopyFileResponse};
+pub use create_directory::{CreateDirectoryBuilder, CreateDirectoryResponse};
+pub use create_file::{CreateFileBuilder, CreateFileResponse};
+pub use create_share::{CreateShareBuilder, CreateShareResponse};
+pub use create_share_permission::{CreateSharePermissionBuilder, CreateSharePermissionResponse};
+pub use create_share_snapshot::{CreateShareSnapshotBuilder, CreateShareSnapshotResponse};
+pub use delete_directory::{DeleteDirectoryBuilder, DeleteDirectoryResponse};
+pub use delete_file::{DeleteFileBuilder, DeleteFileResponse};
+pub use delete_share::{DeleteShareBuilder, DeleteShareResponse};
+pub use force_close_handles::{ForceCloseHandlesBuilder, ForceCloseHandlesResponse};
+pub use get_directory_properties::{GetDirectoryPropertiesBuilder, GetDirectoryPropertiesResponse};
+pub use get_file::{GetFileBuilder, GetFileResponse};
+pub use get_file_properties::{GetFilePropertiesBuilder, GetFilePropertiesResponse};
+pub use get_share_permission::{GetSharePermissionBuilder, GetSharePermissionResponse};
+pub use get_share_properties::{GetSharePropertiesBuilder, GetSharePropertiesResponse};
+pub use list_directory::{
+    DirectoryEntry, EntryProperties, FileEntry, ListDirectoryBuilder, ListDirectoryResponse,
+    SubdirectoryEntry,
+};
+pub use list_handles::{ListHandlesBuilder, ListHandlesResponse};
+pub use list_ranges::{ListRangesBuilder, ListRangesResponse};
+pub use list_shares::{ListSharesBuilder, ListSharesResponse};
+pub use put_range::{PutRangeBuilder, PutRangeResponse};
+pub use release_file_lease::{ReleaseFileLeaseBuilder, ReleaseFileLeaseResponse};
+pub use release_share_lease::{ReleaseShareLeaseBuilder, ReleaseShareLeaseResponse};
+pub use renew_share_lease::{RenewShareLeaseBuilder, RenewShareLeaseResponse};
+pub use set_file_properties::{SetFilePropertiesBuilder, SetFilePropertiesResponse};
+pub use set_share_properties::{SetSharePropertiesBuilder, SetSharePropertiesResponse};
@@ -0,0 +1,125 @@
+use crate::{headers::WRITE, FileClient};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers, MS_RANGE},
+    prelude::*,
+    Body, Method, Response as AzureResponse,
+};
+use azure_storage::{
+    headers::{content_md5_from_headers_optional, CommonStorageResponseHeaders},
+    ConsistencyMD5,
+};
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    PutRange,
+    client: FileClient,
+    range: Range,
+    content: Option<Body>,
+    ?lease_id: LeaseId
+}
+
+impl PutRangeBuilder {
+    pub fn into_future(mut self) -> PutRange {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "range");
+
+            let mut headers = Headers::new();
+            headers.insert(MS_RANGE, self.range.to_string());
+            // a range without content is cleared
+            match &self.content {
+                Some(_) => headers.insert(WRITE, "update"),
+                None => headers.insert(WRITE, "clear"),
+            }
+            headers.add(self.lease_id);
+
+            let mut request =
+                FileClient::finalize_request(url, Method::Put, headers, self.content.clone())?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PutRangeResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_md5: Option<ConsistencyMD5>,
+}
+
+impl std::convert::TryFrom<AzureResponse> for PutRangeResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(PutRangeResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            content_md5: content_md5_from_headers_optional(headers)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_transport::{header, TestTransport};
+    use azure_core::{Body, Method, StatusCode};
+
+    #[tokio::test]
+    async fn put_range() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Created)
+            .resource_headers()
+            .header("content-md5", "XrY7u+Ae7tCTyyK7j1rNww==")
+            .client();
+
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .put_range(512u64..523, "hello world")
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt?comp=range"
+        );
+        assert_eq!(header(&request, "x-ms-range"), Some("bytes=512-522"));
+        assert_eq!(header(&request, "x-ms-write"), Some("update"));
+        match request.body() {
+            Body::Bytes(bytes) => assert_eq!(bytes.as_ref(), b"hello world"),
+            body => panic!("unexpected body {body:?}"),
+        }
+
+        assert_eq!(response.etag, "\"0x8DA583A4F7B9C3E\"");
+        assert!(response.content_md5.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn clear_range() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Created)
+            .resource_headers()
+            .client();
+
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .clear_range(0u64..512)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(header(&request, "x-ms-range"), Some("bytes=0-511"));
+        assert_eq!(header(&request, "x-ms-write"), Some("clear"));
+        assert!(matches!(request.body(), Body::Bytes(bytes) if bytes.is_empty()));
+        assert_eq!(response.content_md5, None);
+        Ok(())
+    }
+}
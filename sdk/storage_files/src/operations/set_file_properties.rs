@@ -0,0 +1,127 @@
+use crate::{
+    headers::{CONTENT_LENGTH, CONTENT_TYPE},
+    smb_properties::add_smb_headers,
+    FileAttributes, FileClient, FilePermission, FileTime, SmbProperties,
+};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    SetFileProperties,
+    client: FileClient,
+    ?content_length: u64,
+    ?content_type: String,
+    ?file_attributes: FileAttributes,
+    ?file_creation_time: FileTime,
+    ?file_last_write_time: FileTime,
+    ?file_change_time: FileTime,
+    ?file_permission: FilePermission,
+    ?lease_id: LeaseId
+}
+
+impl SetFilePropertiesBuilder {
+    pub fn into_future(mut self) -> SetFileProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let mut headers = Headers::new();
+            // setting the content length resizes the file
+            if let Some(content_length) = self.content_length {
+                headers.insert(CONTENT_LENGTH, content_length.to_string());
+            }
+            if let Some(content_type) = &self.content_type {
+                headers.insert(CONTENT_TYPE, content_type.clone());
+            }
+            add_smb_headers(
+                &mut headers,
+                self.file_attributes,
+                self.file_creation_time,
+                self.file_last_write_time,
+                self.file_change_time,
+                self.file_permission.clone(),
+            );
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetFilePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_properties: SmbProperties,
+}
+
+impl std::convert::TryFrom<AzureResponse> for SetFilePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(SetFilePropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_properties: headers.try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_transport::{header, TestTransport},
+        FileTime,
+    };
+    use azure_core::{Method, StatusCode};
+    use time::macros::datetime;
+
+    #[tokio::test]
+    async fn set_file_properties() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .smb_headers()
+            .client();
+
+        let response = client
+            .share_client("share")
+            .file_client("dir/file.txt")
+            .set_properties()
+            .content_length(2048u64)
+            .file_last_write_time(FileTime::Time(datetime!(2022-06-27 13:38:48 UTC)))
+            .file_change_time(FileTime::Preserve)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share/dir/file.txt?comp=properties"
+        );
+        assert_eq!(header(&request, "x-ms-content-length"), Some("2048"));
+        assert_eq!(
+            header(&request, "x-ms-file-last-write-time"),
+            Some("2022-06-27T13:38:48.0000000Z")
+        );
+        assert_eq!(header(&request, "x-ms-file-change-time"), Some("preserve"));
+        assert_eq!(header(&request, "x-ms-content-type"), None);
+
+        assert_eq!(response.etag, "\"0x8DA583A4F7B9C3E\"");
+        assert_eq!(response.smb_properties.file_parent_id, "0");
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{headers::SHARE_QUOTA, AccessTier, ShareClient};
+use azure_core::{
+    error::Error,
+    headers::{etag_from_headers, last_modified_from_headers, Headers},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+use time::OffsetDateTime;
+
+operation! {
+    SetShareProperties,
+    client: ShareClient,
+    ?quota: u64,
+    ?access_tier: AccessTier,
+    ?lease_id: LeaseId
+}
+
+impl SetSharePropertiesBuilder {
+    pub fn into_future(mut self) -> SetShareProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("restype", "share")
+                .append_pair("comp", "properties");
+
+            let mut headers = Headers::new();
+            if let Some(quota) = self.quota {
+                headers.insert(SHARE_QUOTA, quota.to_string());
+            }
+            headers.add(self.access_tier);
+            headers.add(self.lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetSharePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
+
+impl std::convert::TryFrom<AzureResponse> for SetSharePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+        Ok(SetSharePropertiesResponse {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_transport::{header, TestTransport},
+        AccessTier,
+    };
+    use azure_core::{Method, StatusCode};
+
+    #[tokio::test]
+    async fn set_share_quota() -> azure_core::Result<()> {
+        let (transport, client) = TestTransport::new(StatusCode::Ok)
+            .resource_headers()
+            .client();
+
+        let response = client
+            .share_client("share")
+            .set_properties()
+            .quota(100u64)
+            .access_tier(AccessTier::TransactionOptimized)
+            .await?;
+
+        let request = transport.request();
+        assert_eq!(request.method(), &Method::Put);
+        assert_eq!(
+            request.url().as_str(),
+            "https://account.file.core.windows.net/share?restype=share&comp=properties"
+        );
+        assert_eq!(header(&request, "x-ms-share-quota"), Some("100"));
+        assert_eq!(
+            header(&request, "x-ms-access-tier"),
+            Some("TransactionOptimized")
+        );
+
+        assert_eq!(response.etag, "\"0x8DA583A4F7B9C3E\"");
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub use crate::{
+    AccessTier, DirectoryClient, FileAttributes, FileClient, FileLeaseClient, FilePermission,
+    FileServiceClient, FileServiceClientBuilder, FileTime, HandleId, PathClient, ShareClient,
+    ShareLeaseClient,
+};
@@ -0,0 +1,36 @@
+use crate::AccessTier;
+use azure_core::Etag;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+
+/// A share as returned by the list shares operation.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Share {
+    pub name: String,
+    pub snapshot: Option<String>,
+    #[serde(default)]
+    pub deleted: bool,
+    pub version: Option<String>,
+    pub properties: ShareProperties,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShareProperties {
+    #[serde(with = "azure_core::date::rfc1123", rename = "Last-Modified")]
+    pub last_modified: OffsetDateTime,
+    pub etag: Etag,
+    pub quota: u64,
+    pub access_tier: Option<AccessTier>,
+    #[serde(default, with = "azure_core::date::rfc1123::option")]
+    pub access_tier_change_time: Option<OffsetDateTime>,
+    pub lease_status: Option<String>,
+    pub lease_state: Option<String>,
+    pub lease_duration: Option<String>,
+    #[serde(default, with = "azure_core::date::rfc1123::option")]
+    pub deleted_time: Option<OffsetDateTime>,
+    pub remaining_retention_days: Option<u32>,
+}
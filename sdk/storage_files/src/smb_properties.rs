@@ -0,0 +1,259 @@
+use crate::headers::*;
+use azure_core::{
+    error::{Error, ErrorKind, ResultExt},
+    headers::{Header, HeaderName, HeaderValue, Headers},
+};
+use serde::{de, Deserialize, Deserializer};
+use std::{fmt, str::FromStr};
+use time::{format_description::FormatItem, macros::format_description, OffsetDateTime, UtcOffset};
+
+const FILE_TIME_FORMAT: &[FormatItem] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:7]Z");
+
+/// A value for one of the SMB timestamps of a file or directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileTime {
+    /// Use the time of the request.
+    Now,
+    /// Keep the existing value.
+    Preserve,
+    /// Use the given time.
+    Time(OffsetDateTime),
+}
+
+impl From<OffsetDateTime> for FileTime {
+    fn from(time: OffsetDateTime) -> Self {
+        Self::Time(time)
+    }
+}
+
+impl fmt::Display for FileTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Now => write!(f, "now"),
+            Self::Preserve => write!(f, "preserve"),
+            Self::Time(time) => {
+                let time = time
+                    .to_offset(UtcOffset::UTC)
+                    .format(FILE_TIME_FORMAT)
+                    .map_err(|_| fmt::Error)?;
+                write!(f, "{time}")
+            }
+        }
+    }
+}
+
+/// The security descriptor to apply to a file or directory.
+///
+/// Descriptors larger than 8 KiB must be uploaded with
+/// `ShareClient::create_permission` first and referenced by their key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilePermission {
+    /// Inherit the permission of the parent directory.
+    Inherit,
+    /// Keep the existing permission.
+    Preserve,
+    /// A security descriptor in SDDL format.
+    Sddl(String),
+    /// The key of a permission created at the share level.
+    Key(String),
+}
+
+impl Header for FilePermission {
+    fn name(&self) -> HeaderName {
+        match self {
+            Self::Key(_) => FILE_PERMISSION_KEY,
+            _ => FILE_PERMISSION,
+        }
+    }
+
+    fn value(&self) -> HeaderValue {
+        match self {
+            Self::Inherit => HeaderValue::from_static("inherit"),
+            Self::Preserve => HeaderValue::from_static("preserve"),
+            Self::Sddl(sddl) => sddl.clone().into(),
+            Self::Key(key) => key.clone().into(),
+        }
+    }
+}
+
+/// The SMB attributes of a file or directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileAttributes {
+    pub read_only: bool,
+    pub hidden: bool,
+    pub system: bool,
+    pub directory: bool,
+    pub archive: bool,
+    pub temporary: bool,
+    pub offline: bool,
+    pub not_content_indexed: bool,
+    pub no_scrub_data: bool,
+}
+
+impl FileAttributes {
+    fn flags(&self) -> [(bool, &'static str); 9] {
+        [
+            (self.read_only, "ReadOnly"),
+            (self.hidden, "Hidden"),
+            (self.system, "System"),
+            (self.directory, "Directory"),
+            (self.archive, "Archive"),
+            (self.temporary, "Temporary"),
+            (self.offline, "Offline"),
+            (self.not_content_indexed, "NotContentIndexed"),
+            (self.no_scrub_data, "NoScrubData"),
+        ]
+    }
+}
+
+impl fmt::Display for FileAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let attributes = self
+            .flags()
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>();
+
+        if attributes.is_empty() {
+            write!(f, "None")
+        } else {
+            write!(f, "{}", attributes.join(" | "))
+        }
+    }
+}
+
+impl FromStr for FileAttributes {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut attributes = Self::default();
+        for attribute in s.split('|').map(str::trim).filter(|a| !a.is_empty()) {
+            match attribute {
+                "None" | "Normal" => {}
+                "ReadOnly" => attributes.read_only = true,
+                "Hidden" => attributes.hidden = true,
+                "System" => attributes.system = true,
+                "Directory" => attributes.directory = true,
+                "Archive" => attributes.archive = true,
+                "Temporary" => attributes.temporary = true,
+                "Offline" => attributes.offline = true,
+                "NotContentIndexed" => attributes.not_content_indexed = true,
+                "NoScrubData" => attributes.no_scrub_data = true,
+                _ => {
+                    return Err(Error::with_message(ErrorKind::DataConversion, || {
+                        format!("unknown file attribute {attribute}")
+                    }))
+                }
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+impl<'de> Deserialize<'de> for FileAttributes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Header for FileAttributes {
+    fn name(&self) -> HeaderName {
+        FILE_ATTRIBUTES
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.to_string().into()
+    }
+}
+
+/// The SMB properties returned by the service for a file or directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmbProperties {
+    pub file_attributes: FileAttributes,
+    pub file_creation_time: OffsetDateTime,
+    pub file_last_write_time: OffsetDateTime,
+    pub file_change_time: OffsetDateTime,
+    pub file_permission_key: String,
+    pub file_id: String,
+    pub file_parent_id: String,
+}
+
+impl TryFrom<&Headers> for SmbProperties {
+    type Error = Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            file_attributes: headers.get_as(&FILE_ATTRIBUTES)?,
+            file_creation_time: file_time_from_headers(headers, &FILE_CREATION_TIME)?,
+            file_last_write_time: file_time_from_headers(headers, &FILE_LAST_WRITE_TIME)?,
+            file_change_time: file_time_from_headers(headers, &FILE_CHANGE_TIME)?,
+            file_permission_key: headers.get_as(&FILE_PERMISSION_KEY)?,
+            file_id: headers.get_as(&FILE_ID)?,
+            file_parent_id: headers.get_as(&FILE_PARENT_ID)?,
+        })
+    }
+}
+
+fn file_time_from_headers(
+    headers: &Headers,
+    name: &HeaderName,
+) -> azure_core::Result<OffsetDateTime> {
+    let value = headers.get_str(name)?;
+    azure_core::date::parse_rfc3339(value).with_context(ErrorKind::DataConversion, || {
+        format!("unable to parse {} value {value}", name.as_str())
+    })
+}
+
+/// Adds the optional SMB properties of a create or set properties request.
+pub(crate) fn add_smb_headers(
+    headers: &mut Headers,
+    file_attributes: Option<FileAttributes>,
+    file_creation_time: Option<FileTime>,
+    file_last_write_time: Option<FileTime>,
+    file_change_time: Option<FileTime>,
+    file_permission: Option<FilePermission>,
+) {
+    headers.add(file_attributes);
+    if let Some(time) = file_creation_time {
+        headers.insert(FILE_CREATION_TIME, time.to_string());
+    }
+    if let Some(time) = file_last_write_time {
+        headers.insert(FILE_LAST_WRITE_TIME, time.to_string());
+    }
+    if let Some(time) = file_change_time {
+        headers.insert(FILE_CHANGE_TIME, time.to_string());
+    }
+    headers.add(file_permission);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn file_time_format() {
+        let time = FileTime::Time(datetime!(2017-05-10 17:52:33.955186 UTC));
+        assert_eq!(time.to_string(), "2017-05-10T17:52:33.9551860Z");
+        assert_eq!(FileTime::Now.to_string(), "now");
+    }
+
+    #[test]
+    fn file_attributes_round_trip() -> azure_core::Result<()> {
+        let attributes: FileAttributes = "ReadOnly | Archive".parse()?;
+        assert!(attributes.read_only);
+        assert!(attributes.archive);
+        assert!(!attributes.hidden);
+        assert_eq!(attributes.to_string(), "ReadOnly | Archive");
+
+        assert_eq!(FileAttributes::default().to_string(), "None");
+        assert!("Bogus".parse::<FileAttributes>().is_err());
+        Ok(())
+    }
+}
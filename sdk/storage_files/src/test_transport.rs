@@ -0,0 +1,121 @@
+use crate::{FileServiceClient, FileServiceClientBuilder};
+use azure_core::{
+    headers::{HeaderName, HeaderValue, Headers},
+    BytesStream, Context, Policy, PolicyResult, Request, Response, StatusCode, TransportOptions,
+};
+use azure_storage::StorageCredentials;
+use bytes::Bytes;
+use std::sync::{Arc, Mutex};
+
+/// A transport recording the requests it receives and answering them with a
+/// canned response.
+#[derive(Debug)]
+pub(crate) struct TestTransport {
+    status: StatusCode,
+    headers: Vec<(&'static str, String)>,
+    body: Bytes,
+    requests: Mutex<Vec<Request>>,
+}
+
+impl TestTransport {
+    /// A transport answering with `status` and the headers common to every
+    /// storage response.
+    pub(crate) fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: vec![
+                (
+                    "x-ms-request-id",
+                    "a3a0b1d4-f01a-0043-4f2c-8ba78e000000".to_owned(),
+                ),
+                ("x-ms-version", "2022-11-02".to_owned()),
+                ("date", "Mon, 27 Jun 2022 13:38:48 GMT".to_owned()),
+                (
+                    "server",
+                    "Windows-Azure-File/1.0 Microsoft-HTTPAPI/2.0".to_owned(),
+                ),
+            ],
+            body: Bytes::new(),
+            requests: Mutex::default(),
+        }
+    }
+
+    /// Adds the ETag and last modified time of a resource to the response.
+    pub(crate) fn resource_headers(self) -> Self {
+        self.header("etag", "\"0x8DA583A4F7B9C3E\"")
+            .header("last-modified", "Mon, 27 Jun 2022 13:38:48 GMT")
+    }
+
+    /// Adds the SMB properties of a file to the response.
+    pub(crate) fn smb_headers(self) -> Self {
+        self.header("x-ms-file-attributes", "Archive")
+            .header("x-ms-file-creation-time", "2022-06-27T13:38:48.1234567Z")
+            .header("x-ms-file-last-write-time", "2022-06-27T13:38:48.1234567Z")
+            .header("x-ms-file-change-time", "2022-06-27T13:38:48.1234567Z")
+            .header("x-ms-file-permission-key", "4066528134148476695*1")
+            .header("x-ms-file-id", "13835128424026341376")
+            .header("x-ms-file-parent-id", "0")
+    }
+
+    pub(crate) fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    pub(crate) fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// A file service client sending its requests to the transport.
+    pub(crate) fn client(self) -> (Arc<Self>, FileServiceClient) {
+        let transport = Arc::new(self);
+        let client = FileServiceClientBuilder::new("account", StorageCredentials::anonymous())
+            .transport(TransportOptions::new_custom_policy(transport.clone()))
+            .build();
+        (transport, client)
+    }
+
+    /// The only request received.
+    pub(crate) fn request(&self) -> Request {
+        let requests = self.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1, "expected a single request");
+        requests[0].clone()
+    }
+}
+
+/// The value of a header of a request, if set.
+pub(crate) fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .get_optional_str(&HeaderName::from_static(name))
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for TestTransport {
+    async fn send(
+        &self,
+        _ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        // there must be no more policies
+        assert_eq!(0, next.len());
+
+        self.requests.lock().unwrap().push(request.clone());
+
+        let mut headers = Headers::new();
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from_static(name),
+                HeaderValue::from(value.clone()),
+            );
+        }
+        Ok(Response::new(
+            self.status,
+            headers,
+            Box::pin(BytesStream::new(self.body.clone())),
+        ))
+    }
+}
@@ -0,0 +1,40 @@
+use azure_storage::prelude::*;
+use azure_storage_blobs::prelude::*;
+use azure_storage_queues::{prelude::*, ClaimCheck};
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    let queue_name = std::env::args()
+        .nth(1)
+        .expect("Please pass the queue name as first parameter");
+    let container_name = std::env::args()
+        .nth(2)
+        .expect("Please pass the container name as second parameter");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let container_client = BlobServiceClient::new(account.clone(), storage_credentials.clone())
+        .container_client(container_name);
+    let queue = QueueServiceClient::new(account, storage_credentials)
+        .queue_client(queue_name)
+        .with_message_encoding(MessageEncoding::Base64)
+        .with_claim_check(ClaimCheck::new(container_client));
+
+    // too large for a queue message, so the payload goes to a blob
+    let payload = vec![42u8; 1024 * 1024];
+    queue.put_binary_message(payload.clone()).await?;
+
+    let response = queue.get_messages().await?;
+    for decoded in response.decoded_messages() {
+        assert_eq!(decoded.payload?, payload);
+        // deleting the message deletes the blob too
+        queue.pop_receipt_client(decoded.message).delete().await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use azure_storage::prelude::*;
+use azure_storage_queues::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Order {
+    id: u64,
+    item: String,
+}
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    let queue_name = std::env::args()
+        .nth(1)
+        .expect("Please pass the queue name as first parameter");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let queue_service = QueueServiceClient::new(account, storage_credentials);
+
+    // base64 is what the other Azure Storage SDKs use by default
+    let queue = queue_service
+        .queue_client(queue_name)
+        .with_message_encoding(MessageEncoding::Base64);
+
+    queue
+        .put_json(&Order {
+            id: 1,
+            item: "coffee".to_owned(),
+        })?
+        .await?;
+
+    let response = queue.get_messages_as::<Order>().await?;
+    for decoded in response.messages {
+        match decoded.payload {
+            Ok(order) => {
+                println!("received order {order:?}");
+                queue.pop_receipt_client(decoded.message).delete().await?;
+            }
+            Err(err) => println!(
+                "message {} is not an order: {err}",
+                decoded.message.message_id
+            ),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use azure_core::sleep::sleep;
+use azure_storage::prelude::*;
+use azure_storage_queues::prelude::*;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    let queue_name = std::env::args()
+        .nth(1)
+        .expect("Please pass the queue name as first parameter");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let queue_service = QueueServiceClient::new(account, storage_credentials);
+
+    let queue = queue_service.queue_client(&queue_name);
+    let poison_queue = queue_service.queue_client(format!("{queue_name}-poison"));
+    poison_queue.create().await?;
+
+    let processor = QueueProcessor::new(queue)
+        .poison_queue_client(poison_queue)
+        .max_concurrent_messages(4_usize)
+        .max_dequeue_count(3_u64);
+
+    // process messages for a minute, then finish the ones in progress
+    processor
+        .run(
+            |message| async move {
+                println!("processing message {}", message.message_text);
+                sleep(Duration::from_secs(1)).await;
+                Ok(())
+            },
+            sleep(Duration::from_secs(60)),
+        )
+        .await;
+
+    Ok(())
+}
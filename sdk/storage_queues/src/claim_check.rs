@@ -0,0 +1,401 @@
+use crate::{
+    operations::{Message, PeekMessage},
+    MessageEncoding,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    from_json, to_json,
+};
+use azure_storage_blobs::prelude::{BlobClient, ContainerClient};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// The default size, in bytes, above which message text is checked in to a
+/// blob. The queue service accepts messages of up to 64 KiB.
+pub const DEFAULT_CLAIM_CHECK_THRESHOLD: usize = 48 * 1024;
+
+/// Stores payloads too large for a queue message in blobs.
+///
+/// When set on a `QueueClient`, messages whose encoded text is larger than the
+/// threshold are uploaded to a blob of the container and a reference to the
+/// blob is enqueued instead. Messages received with `get_messages` or
+/// `peek_messages` have their references resolved back to the payload, and the
+/// blob is deleted once the message is deleted. A reference which cannot be
+/// resolved leaves the text of its message as enqueued, with the error
+/// available from `claim_check_error`, and the other messages are returned
+/// as usual.
+///
+/// Only references to blobs of the container are followed: the reference
+/// comes from the message, so anyone able to enqueue could otherwise have the
+/// consumers read and delete any blob of the account.
+#[derive(Debug, Clone)]
+pub struct ClaimCheck {
+    container_client: ContainerClient,
+    threshold: usize,
+}
+
+impl ClaimCheck {
+    pub fn new(container_client: ContainerClient) -> Self {
+        Self {
+            container_client,
+            threshold: DEFAULT_CLAIM_CHECK_THRESHOLD,
+        }
+    }
+
+    setters! {
+        threshold: usize => threshold,
+    }
+
+    pub fn container_client(&self) -> &ContainerClient {
+        &self.container_client
+    }
+
+    pub(crate) fn exceeds_threshold(&self, message_text: &str) -> bool {
+        message_text.len() > self.threshold
+    }
+
+    /// Uploads the payload to a new blob and returns the message text
+    /// referencing it, along with the name of the blob.
+    pub(crate) async fn check_in(
+        &self,
+        payload: Vec<u8>,
+        message_encoding: &MessageEncoding,
+    ) -> azure_core::Result<(String, String)> {
+        let blob_name = uuid::Uuid::new_v4().to_string();
+        self.container_client
+            .blob_client(&blob_name)
+            .put_block_blob(payload)
+            .await?;
+
+        let envelope = Envelope {
+            claim_check: Reference {
+                container: self.container_client.container_name().to_owned(),
+                blob: blob_name.clone(),
+            },
+        };
+        Ok((message_encoding.encode(&to_json(&envelope)?)?, blob_name))
+    }
+
+    /// Deletes a blob checked in for a message that could not be enqueued.
+    pub(crate) async fn discard(&self, blob: &str) {
+        if let Err(err) = self.container_client.blob_client(blob).delete().await {
+            log::warn!("unable to delete claim check blob {blob}: {err}");
+        }
+    }
+
+    /// Replaces the text of a message referencing a blob with the payload of
+    /// the blob, or records why it could not.
+    pub(crate) async fn resolve(&self, message: &mut Message, message_encoding: &MessageEncoding) {
+        match self
+            .resolve_text(&mut message.message_text, message_encoding)
+            .await
+        {
+            Ok(reference) => message.claim_check = reference,
+            Err(err) => message.claim_check_error = Some(Arc::new(err)),
+        }
+    }
+
+    /// Replaces the text of a peeked message referencing a blob with the
+    /// payload of the blob, or records why it could not.
+    pub(crate) async fn resolve_peeked(
+        &self,
+        message: &mut PeekMessage,
+        message_encoding: &MessageEncoding,
+    ) {
+        if let Err(err) = self
+            .resolve_text(&mut message.message_text, message_encoding)
+            .await
+        {
+            message.claim_check_error = Some(Arc::new(err));
+        }
+    }
+
+    /// Replaces message text referencing a blob with the payload of the blob,
+    /// returning the reference. Text not referencing a blob is left as is.
+    pub(crate) async fn resolve_text(
+        &self,
+        message_text: &mut String,
+        message_encoding: &MessageEncoding,
+    ) -> azure_core::Result<Option<ClaimCheckReference>> {
+        let Some(reference) = message_encoding
+            .decode(message_text)
+            .ok()
+            .and_then(|payload| from_json::<_, Envelope>(payload).ok())
+            .map(|envelope| envelope.claim_check)
+        else {
+            return Ok(None);
+        };
+
+        let payload = self
+            .blob_client(&reference.container, &reference.blob)?
+            .get_content()
+            .await?;
+        let original = std::mem::replace(message_text, message_encoding.encode(&payload)?);
+        Ok(Some(ClaimCheckReference {
+            container: reference.container,
+            blob: reference.blob,
+            message_text: original,
+        }))
+    }
+
+    /// Deletes the blob of a message that has been deleted.
+    pub(crate) async fn check_out(&self, reference: &ClaimCheckReference) {
+        let result = match self.blob_client(&reference.container, &reference.blob) {
+            Ok(blob_client) => blob_client.delete().await.map(|_| ()),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            log::warn!(
+                "unable to delete claim check blob {}: {err}",
+                reference.blob
+            );
+        }
+    }
+
+    fn blob_client(&self, container: &str, blob: &str) -> azure_core::Result<BlobClient> {
+        let expected = self.container_client.container_name();
+        if container != expected {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "claim check references blob {blob} of container {container} instead of {expected}"
+                )
+            }));
+        }
+        Ok(self.container_client.blob_client(blob))
+    }
+}
+
+/// The error of a message whose payload could not be resolved, for its
+/// decoded payload.
+pub(crate) fn unresolved(err: &Arc<Error>) -> Error {
+    Error::new(err.kind().clone(), err.clone())
+}
+
+/// The blob holding the payload of a received message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ClaimCheckReference {
+    pub(crate) container: String,
+    pub(crate) blob: String,
+    /// The text of the message as enqueued.
+    pub(crate) message_text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    #[serde(rename = "$claimCheck")]
+    claim_check: Reference,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Reference {
+    container: String,
+    blob: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_storage::StorageCredentials;
+    use azure_storage_blobs::prelude::BlobServiceClient;
+
+    fn message(message_text: &str) -> azure_core::Result<Message> {
+        let mut message: Message = azure_core::xml::read_xml(
+            b"<QueueMessage>\
+            <MessageId>00000000-0000-0000-0000-000000000000</MessageId>\
+            <InsertionTime>Mon, 27 Jun 2022 13:38:48 GMT</InsertionTime>\
+            <ExpirationTime>Mon, 04 Jul 2022 13:38:48 GMT</ExpirationTime>\
+            <PopReceipt>REDACTED</PopReceipt>\
+            <TimeNextVisible>Mon, 27 Jun 2022 13:38:53 GMT</TimeNextVisible>\
+            <DequeueCount>1</DequeueCount>\
+            <MessageText></MessageText>\
+            </QueueMessage>",
+        )?;
+        message.message_text = message_text.to_owned();
+        Ok(message)
+    }
+
+    #[tokio::test]
+    async fn plain_messages_are_not_resolved() -> azure_core::Result<()> {
+        let container_client = BlobServiceClient::new("account", StorageCredentials::anonymous())
+            .container_client("payloads");
+        let claim_check = ClaimCheck::new(container_client).threshold(4_usize);
+
+        assert!(!claim_check.exceeds_threshold("tiny"));
+        assert!(claim_check.exceeds_threshold("larger"));
+
+        let mut plain = message("aGVsbG8=")?;
+        claim_check
+            .resolve(&mut plain, &MessageEncoding::Base64)
+            .await;
+        assert_eq!(plain.message_text, "aGVsbG8=");
+        assert_eq!(plain.claim_check, None);
+        assert!(plain.claim_check_error().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn references_to_other_containers_are_rejected() -> azure_core::Result<()> {
+        let container_client = BlobServiceClient::new("account", StorageCredentials::anonymous())
+            .container_client("payloads");
+        let claim_check = ClaimCheck::new(container_client);
+
+        let mut foreign = message(r#"{"$claimCheck":{"container":"secrets","blob":"key"}}"#)?;
+        claim_check
+            .resolve(&mut foreign, &MessageEncoding::None)
+            .await;
+        assert_eq!(
+            foreign.claim_check_error().map(Error::kind),
+            Some(&ErrorKind::DataConversion)
+        );
+        assert_eq!(
+            foreign.message_text,
+            r#"{"$claimCheck":{"container":"secrets","blob":"key"}}"#
+        );
+        assert_eq!(foreign.claim_check, None);
+        Ok(())
+    }
+
+    /// A storage account serving a queue of messages, some referencing blobs
+    /// of the `payloads` container, of which only `present` exists.
+    #[derive(Debug)]
+    struct FakeStorage;
+
+    #[async_trait::async_trait]
+    impl azure_core::Policy for FakeStorage {
+        async fn send(
+            &self,
+            _ctx: &azure_core::Context,
+            request: &mut azure_core::Request,
+            next: &[std::sync::Arc<dyn azure_core::Policy>],
+        ) -> azure_core::PolicyResult {
+            assert_eq!(0, next.len());
+
+            const DATE: &str = "Mon, 27 Jun 2022 13:38:48 GMT";
+            let mut headers = azure_core::headers::Headers::new();
+            headers.insert("x-ms-request-id", "a3a0b1d4-f01a-0043-4f2c-8ba78e000000");
+            headers.insert("x-ms-version", "2019-12-12");
+            headers.insert("date", DATE);
+            headers.insert("server", "Windows-Azure/1.0 Microsoft-HTTPAPI/2.0");
+
+            let (status, body) = match request.url().path() {
+                "/queue/messages" => {
+                    let messages: String = [
+                        "hello",
+                        r#"{"$claimCheck":{"container":"payloads","blob":"missing"}}"#,
+                        r#"{"$claimCheck":{"container":"secrets","blob":"key"}}"#,
+                        r#"{"$claimCheck":{"container":"payloads","blob":"present"}}"#,
+                    ]
+                    .iter()
+                    .enumerate()
+                    .map(|(id, text)| {
+                        format!(
+                            "<QueueMessage><MessageId>message-{id}</MessageId>\
+                            <InsertionTime>{DATE}</InsertionTime>\
+                            <ExpirationTime>{DATE}</ExpirationTime>\
+                            <PopReceipt>receipt-{id}</PopReceipt>\
+                            <TimeNextVisible>{DATE}</TimeNextVisible>\
+                            <DequeueCount>1</DequeueCount>\
+                            <MessageText>{text}</MessageText></QueueMessage>"
+                        )
+                    })
+                    .collect();
+                    (
+                        azure_core::StatusCode::Ok,
+                        format!("<QueueMessagesList>{messages}</QueueMessagesList>"),
+                    )
+                }
+                "/payloads/present" => {
+                    let payload = "a payload too large for a message";
+                    for (name, value) in [
+                        ("content-length", payload.len().to_string()),
+                        (
+                            "content-range",
+                            format!("bytes 0-{}/{}", payload.len() - 1, payload.len()),
+                        ),
+                        ("last-modified", DATE.to_owned()),
+                        ("x-ms-creation-time", DATE.to_owned()),
+                        ("etag", "\"0x8DA583A4F7B9C3E\"".to_owned()),
+                        ("x-ms-blob-type", "BlockBlob".to_owned()),
+                        ("x-ms-server-encrypted", "true".to_owned()),
+                    ] {
+                        headers.insert(name, value);
+                    }
+                    (azure_core::StatusCode::PartialContent, payload.to_owned())
+                }
+                _ => (azure_core::StatusCode::NotFound, String::new()),
+            };
+            Ok(azure_core::Response::new(
+                status,
+                headers,
+                Box::pin(azure_core::BytesStream::new(body)),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn unresolved_messages_do_not_fail_the_batch() -> azure_core::Result<()> {
+        let transport =
+            azure_core::TransportOptions::new_custom_policy(std::sync::Arc::new(FakeStorage));
+        let container_client = azure_storage_blobs::prelude::ClientBuilder::new(
+            "account",
+            StorageCredentials::anonymous(),
+        )
+        .transport(transport.clone())
+        .retry(azure_core::RetryOptions::none())
+        .container_client("payloads");
+        let queue_client =
+            crate::QueueServiceClientBuilder::new("account", StorageCredentials::anonymous())
+                .transport(transport)
+                .build()
+                .queue_client("queue")
+                .with_message_encoding(MessageEncoding::None)
+                .with_claim_check(ClaimCheck::new(container_client));
+
+        let response = queue_client.get_messages().await?;
+
+        let messages = &response.messages;
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].message_text, "hello");
+        assert!(messages[0].claim_check_error().is_none());
+        // a missing blob and a foreign container only fail their own message
+        assert!(messages[1].claim_check_error().is_some());
+        assert_eq!(
+            messages[1].message_text,
+            r#"{"$claimCheck":{"container":"payloads","blob":"missing"}}"#
+        );
+        assert_eq!(
+            messages[2].claim_check_error().map(Error::kind),
+            Some(&ErrorKind::DataConversion)
+        );
+        assert_eq!(
+            messages[3].message_text,
+            "a payload too large for a message"
+        );
+        assert!(messages[3].claim_check_error().is_none());
+
+        let decoded = response.decoded_messages();
+        assert!(decoded[0].payload.is_ok());
+        assert!(decoded[1].payload.is_err());
+        assert!(decoded[2].payload.is_err());
+        assert_eq!(
+            decoded[3].payload.as_deref().ok(),
+            Some(&b"a payload too large for a message"[..])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn envelope_format() -> azure_core::Result<()> {
+        let envelope = Envelope {
+            claim_check: Reference {
+                container: "payloads".to_owned(),
+                blob: "blob".to_owned(),
+            },
+        };
+        assert_eq!(
+            to_json(&envelope)?,
+            r#"{"$claimCheck":{"container":"payloads","blob":"blob"}}"#
+        );
+        Ok(())
+    }
+}
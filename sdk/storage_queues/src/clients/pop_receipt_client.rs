@@ -24,7 +24,18 @@ impl PopReceiptClient {
         body: impl Into<String>,
         visibility_timeout: impl Into<VisibilityTimeout>,
     ) -> UpdateMessageBuilder {
-        UpdateMessageBuilder::new(self.clone(), body.into(), visibility_timeout.into())
+        UpdateMessageBuilder::new(self.clone(), Some(body.into()), visibility_timeout.into())
+    }
+
+    /// Extends or shortens the time the message stays invisible, leaving its
+    /// content untouched.
+    ///
+    /// The response carries the pop receipt to use for further calls.
+    pub fn update_visibility_timeout(
+        &self,
+        visibility_timeout: impl Into<VisibilityTimeout>,
+    ) -> UpdateMessageBuilder {
+        UpdateMessageBuilder::new(self.clone(), None, visibility_timeout.into())
     }
 
     /// Deletes the message.
@@ -35,6 +46,15 @@ impl PopReceiptClient {
         DeleteMessageBuilder::new(self.clone())
     }
 
+    pub(crate) fn queue_client(&self) -> &QueueClient {
+        &self.client
+    }
+
+    #[cfg(feature = "claim_check")]
+    pub(crate) fn pop_receipt(&self) -> &PopReceipt {
+        &self.pop_receipt
+    }
+
     pub(crate) async fn send(
         &self,
         context: &mut Context,
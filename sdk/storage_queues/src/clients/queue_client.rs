@@ -1,13 +1,25 @@
-use crate::{
-    operations::*, PopReceipt, PopReceiptClient, QueueServiceClient, QueueStoredAccessPolicy,
+use crate::{operations::*, MessageEncoding, PopReceipt, PopReceiptClient, QueueServiceClient};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    to_json, Context, Request, Response, Url,
 };
-use azure_core::{prelude::*, Context, Request, Response, Url};
-use std::fmt::Debug;
+use azure_storage::{
+    prelude::QueueSasPermissions,
+    shared_access_signature::{service_sas::QueueSharedAccessSignature, SasToken},
+    PartialStoredAccessPolicy, StorageCredentialsInner,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Debug, ops::Deref};
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct QueueClient {
     service_client: QueueServiceClient,
     queue_name: String,
+    message_encoding: MessageEncoding,
+    #[cfg(feature = "claim_check")]
+    claim_check: Option<crate::ClaimCheck>,
 }
 
 impl QueueClient {
@@ -15,9 +27,41 @@ impl QueueClient {
         Self {
             service_client,
             queue_name,
+            message_encoding: MessageEncoding::None,
+            #[cfg(feature = "claim_check")]
+            claim_check: None,
         }
     }
 
+    /// Sets how message payloads are encoded when sent and decoded when
+    /// received.
+    ///
+    /// Messages are stored verbatim by default. Use `MessageEncoding::Base64`
+    /// to interoperate with the other Azure Storage SDKs or to send binary
+    /// payloads.
+    #[must_use]
+    pub fn with_message_encoding(mut self, message_encoding: MessageEncoding) -> Self {
+        self.message_encoding = message_encoding;
+        self
+    }
+
+    pub fn message_encoding(&self) -> &MessageEncoding {
+        &self.message_encoding
+    }
+
+    /// Stores the payloads too large for a queue message in blobs.
+    #[cfg(feature = "claim_check")]
+    #[must_use]
+    pub fn with_claim_check(mut self, claim_check: crate::ClaimCheck) -> Self {
+        self.claim_check = Some(claim_check);
+        self
+    }
+
+    #[cfg(feature = "claim_check")]
+    pub fn claim_check(&self) -> Option<&crate::ClaimCheck> {
+        self.claim_check.as_ref()
+    }
+
     /// Creates the queue.
     pub fn create(&self) -> CreateQueueBuilder {
         CreateQueueBuilder::new(self.clone())
@@ -61,15 +105,38 @@ impl QueueClient {
     /// While this SDK does not enforce any limit, keep in mind Azure supports a
     /// limited number of stored access policies for each queue.  More info here
     /// [https://docs.microsoft.com/rest/api/storageservices/set-queue-acl#remarks](https://docs.microsoft.com/rest/api/storageservices/set-queue-acl#remarks).
-    pub fn set_acl(&self, policies: Vec<QueueStoredAccessPolicy>) -> SetQueueACLBuilder {
-        SetQueueACLBuilder::new(self.clone(), policies)
+    ///
+    /// Policies leaving out their start, expiry or permission can be built
+    /// with `QueueAccessPolicyBuilder`.
+    pub fn set_acl<I, P>(&self, policies: I) -> SetQueueACLBuilder
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PartialStoredAccessPolicy>,
+    {
+        SetQueueACLBuilder::new(self.clone(), policies.into_iter().map(Into::into).collect())
     }
 
     /// Puts a message in the queue.
     pub fn put_message<S: Into<String>>(&self, message: S) -> PutMessageBuilder {
+        PutMessageBuilder::new(self.clone(), message.into().into_bytes())
+    }
+
+    /// Puts a binary message in the queue.
+    ///
+    /// Unless the payload is valid UTF-8, the client needs a message encoding
+    /// such as `MessageEncoding::Base64`.
+    pub fn put_binary_message<B: Into<Vec<u8>>>(&self, message: B) -> PutMessageBuilder {
         PutMessageBuilder::new(self.clone(), message.into())
     }
 
+    /// Puts a message in the queue, serialized as JSON.
+    pub fn put_json<T: Serialize>(&self, message: &T) -> azure_core::Result<PutMessageBuilder> {
+        Ok(PutMessageBuilder::new(
+            self.clone(),
+            to_json(message)?.to_vec(),
+        ))
+    }
+
     /// Peeks, without removing, one or more messages.
     pub fn peek_messages(&self) -> PeekMessagesBuilder {
         PeekMessagesBuilder::new(self.clone())
@@ -80,6 +147,14 @@ impl QueueClient {
         GetMessagesBuilder::new(self.clone())
     }
 
+    /// Gets, shadowing them, one or more messages with JSON payloads.
+    ///
+    /// Each payload is decoded separately, so messages that are not the
+    /// expected JSON are returned with their decoding error.
+    pub fn get_messages_as<T: DeserializeOwned + Send + 'static>(&self) -> GetMessagesAsBuilder<T> {
+        GetMessagesAsBuilder::new(self.clone())
+    }
+
     /// Removes all messages from the queue.
     pub fn clear_messages(&self) -> ClearMessagesBuilder {
         ClearMessagesBuilder::new(self.clone())
@@ -90,6 +165,68 @@ impl QueueClient {
         PopReceiptClient::new(self.clone(), pop_receipt.into())
     }
 
+    /// Create a shared access signature scoped to the queue.
+    ///
+    /// The permissions and expiry are part of the signature. To take them from
+    /// a stored access policy instead, use
+    /// `stored_policy_shared_access_signature`.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: QueueSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<QueueSharedAccessSignature> {
+        let creds = self.service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource = format!("/queue/{}/{}", account, self.queue_name());
+        Ok(QueueSharedAccessSignature::new(
+            key.clone(),
+            canonicalized_resource,
+            permissions,
+            expiry,
+        ))
+    }
+
+    /// Create a shared access signature bound to a stored access policy of
+    /// the queue.
+    ///
+    /// The permissions and expiry come from the policy, so revoking the policy
+    /// revokes the signature.
+    pub async fn stored_policy_shared_access_signature(
+        &self,
+        identifier: impl Into<String>,
+    ) -> azure_core::Result<QueueSharedAccessSignature> {
+        let creds = self.service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource = format!("/queue/{}/{}", account, self.queue_name());
+        Ok(QueueSharedAccessSignature::with_stored_policy(
+            key.clone(),
+            canonicalized_resource,
+            identifier.into(),
+        ))
+    }
+
+    /// Create a signed queue url
+    pub fn generate_signed_queue_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
     pub fn queue_name(&self) -> &str {
         &self.queue_name
     }
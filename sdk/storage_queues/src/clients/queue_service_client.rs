@@ -66,8 +66,9 @@ impl QueueServiceClientBuilder {
         } = self;
 
         QueueServiceClient {
-            pipeline: new_pipeline_from_options(options, credentials),
+            pipeline: new_pipeline_from_options(options, credentials.clone()),
             cloud_location,
+            credentials,
         }
     }
 
@@ -106,6 +107,7 @@ impl QueueServiceClientBuilder {
 pub struct QueueServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    credentials: StorageCredentials,
 }
 
 impl QueueServiceClient {
@@ -146,6 +148,10 @@ impl QueueServiceClient {
         self.cloud_location.url(ServiceType::Queue)
     }
 
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: azure_core::Method,
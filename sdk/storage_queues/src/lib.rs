@@ -41,21 +41,29 @@ async fn main() -> azure_core::Result<()> {
 #[macro_use]
 extern crate azure_core;
 
+#[cfg(feature = "claim_check")]
+mod claim_check;
 mod clients;
+mod message_encoding;
 mod message_ttl;
 mod number_of_messages;
 pub mod operations;
 mod pop_receipt;
 pub mod prelude;
 mod queue_message;
+mod queue_processor;
 mod queue_service_properties;
 mod queue_stored_access_policy;
 mod visibility_timeout;
 
+#[cfg(feature = "claim_check")]
+pub use claim_check::{ClaimCheck, DEFAULT_CLAIM_CHECK_THRESHOLD};
 pub use clients::*;
+pub use message_encoding::{DecodedMessage, MessageEncoder, MessageEncoding};
 pub use message_ttl::MessageTTL;
 pub use number_of_messages::NumberOfMessages;
 pub use pop_receipt::PopReceipt;
+pub use queue_processor::QueueProcessor;
 pub use queue_service_properties::{CorsRule, QueueServiceProperties};
 pub use queue_stored_access_policy::QueueStoredAccessPolicy;
 pub use visibility_timeout::VisibilityTimeout;
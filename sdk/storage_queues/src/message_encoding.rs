@@ -0,0 +1,119 @@
+use azure_core::{
+    base64,
+    error::{ErrorKind, ResultExt},
+};
+use serde::de::DeserializeOwned;
+use std::{fmt::Debug, sync::Arc};
+
+/// Converts message payloads to the text stored by the queue service, and back.
+pub trait MessageEncoder: Debug + Send + Sync {
+    fn encode(&self, payload: &[u8]) -> azure_core::Result<String>;
+
+    fn decode(&self, message_text: &str) -> azure_core::Result<Vec<u8>>;
+}
+
+/// How a `QueueClient` encodes the messages it sends and decodes the messages
+/// it receives.
+#[derive(Debug, Clone, Default)]
+pub enum MessageEncoding {
+    /// The payload is stored verbatim and must be valid UTF-8.
+    #[default]
+    None,
+    /// The payload is base64 encoded, as done by default by the other Azure
+    /// Storage SDKs.
+    Base64,
+    /// The payload is encoded by a user supplied encoder.
+    Custom(Arc<dyn MessageEncoder>),
+}
+
+impl MessageEncoding {
+    pub fn custom(encoder: impl MessageEncoder + 'static) -> Self {
+        Self::Custom(Arc::new(encoder))
+    }
+
+    pub fn encode(&self, payload: &[u8]) -> azure_core::Result<String> {
+        match self {
+            Self::None => String::from_utf8(payload.to_vec()).context(
+                ErrorKind::DataConversion,
+                "message payload is not valid UTF-8",
+            ),
+            Self::Base64 => Ok(base64::encode(payload)),
+            Self::Custom(encoder) => encoder.encode(payload),
+        }
+    }
+
+    pub fn decode(&self, message_text: &str) -> azure_core::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(message_text.as_bytes().to_vec()),
+            Self::Base64 => base64::decode(message_text).context(
+                ErrorKind::DataConversion,
+                "message text is not valid base64",
+            ),
+            Self::Custom(encoder) => encoder.decode(message_text),
+        }
+    }
+
+    pub(crate) fn decode_json<T>(&self, message_text: &str) -> azure_core::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let payload = self.decode(message_text)?;
+        azure_core::from_json(payload).context(
+            ErrorKind::DataConversion,
+            "message payload is not the expected JSON",
+        )
+    }
+}
+
+/// A received message along with its decoded payload.
+///
+/// Payloads are decoded one message at a time, so a message that fails to
+/// decode does not prevent the others in the same batch from being processed.
+#[derive(Debug)]
+pub struct DecodedMessage<M, T> {
+    pub message: M,
+    pub payload: azure_core::Result<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Reversed;
+
+    impl MessageEncoder for Reversed {
+        fn encode(&self, payload: &[u8]) -> azure_core::Result<String> {
+            Ok(String::from_utf8_lossy(payload).chars().rev().collect())
+        }
+
+        fn decode(&self, message_text: &str) -> azure_core::Result<Vec<u8>> {
+            Ok(message_text.chars().rev().collect::<String>().into_bytes())
+        }
+    }
+
+    #[test]
+    fn encodings_round_trip() -> azure_core::Result<()> {
+        for (encoding, expected) in [
+            (MessageEncoding::None, "hello"),
+            (MessageEncoding::Base64, "aGVsbG8="),
+            (MessageEncoding::custom(Reversed), "olleh"),
+        ] {
+            let text = encoding.encode(b"hello")?;
+            assert_eq!(text, expected);
+            assert_eq!(encoding.decode(&text)?, b"hello");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn binary_payloads_require_an_encoding() -> azure_core::Result<()> {
+        let payload = [0xff, 0x00, 0xfe];
+        assert!(MessageEncoding::None.encode(&payload).is_err());
+
+        let text = MessageEncoding::Base64.encode(&payload)?;
+        assert_eq!(MessageEncoding::Base64.decode(&text)?, payload);
+        assert!(MessageEncoding::Base64.decode("not base64!").is_err());
+        Ok(())
+    }
+}
@@ -18,6 +18,14 @@ impl DeleteMessageBuilder {
 
             let response = self.client.send(&mut self.context, &mut request).await?;
 
+            #[cfg(feature = "claim_check")]
+            if let (Some(claim_check), Some(reference)) = (
+                self.client.queue_client().claim_check(),
+                &self.client.pop_receipt().claim_check,
+            ) {
+                claim_check.check_out(reference).await;
+            }
+
             response.try_into()
         })
     }
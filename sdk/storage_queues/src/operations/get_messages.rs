@@ -1,8 +1,10 @@
-use crate::{prelude::*, PopReceipt};
+use crate::{prelude::*, DecodedMessage, MessageEncoding, PopReceipt};
 use azure_core::{headers::Headers, prelude::*, xml::read_xml, Method, Response as AzureResponse};
 use azure_storage::headers::CommonStorageResponseHeaders;
-use serde::Deserialize;
-use std::convert::TryInto;
+use serde::{de::DeserializeOwned, Deserialize};
+#[cfg(feature = "claim_check")]
+use std::sync::Arc;
+use std::{convert::TryInto, marker::PhantomData};
 use time::OffsetDateTime;
 
 operation! {
@@ -25,7 +27,21 @@ impl GetMessagesBuilder {
 
             let response = self.client.send(&mut self.context, &mut request).await?;
 
-            GetMessagesResponse::try_from(response).await
+            #[allow(unused_mut)]
+            let mut response =
+                GetMessagesResponse::try_from(response, self.client.message_encoding().clone())
+                    .await?;
+
+            #[cfg(feature = "claim_check")]
+            if let Some(claim_check) = self.client.claim_check() {
+                for message in &mut response.messages {
+                    claim_check
+                        .resolve(message, self.client.message_encoding())
+                        .await;
+                }
+            }
+
+            Ok(response)
         })
     }
 }
@@ -34,6 +50,7 @@ impl GetMessagesBuilder {
 pub struct GetMessagesResponse {
     pub common_storage_response_headers: CommonStorageResponseHeaders,
     pub messages: Vec<Message>,
+    message_encoding: MessageEncoding,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -49,17 +66,53 @@ pub struct Message {
     pub time_next_visible: OffsetDateTime,
     pub dequeue_count: u64,
     pub message_text: String,
+    #[cfg(feature = "claim_check")]
+    #[serde(skip)]
+    pub(crate) claim_check: Option<crate::claim_check::ClaimCheckReference>,
+    #[cfg(feature = "claim_check")]
+    #[serde(skip)]
+    pub(crate) claim_check_error: Option<Arc<azure_core::Error>>,
 }
 
 impl Message {
+    /// Why the payload checked in to a blob could not be read, in which case
+    /// the text of the message is left as enqueued.
+    #[cfg(feature = "claim_check")]
+    pub fn claim_check_error(&self) -> Option<&azure_core::Error> {
+        self.claim_check_error.as_deref()
+    }
+
+    fn decode<T>(
+        &self,
+        decode: impl FnOnce(&str) -> azure_core::Result<T>,
+    ) -> azure_core::Result<T> {
+        #[cfg(feature = "claim_check")]
+        if let Some(err) = &self.claim_check_error {
+            return Err(crate::claim_check::unresolved(err));
+        }
+        decode(&self.message_text)
+    }
+
     pub fn pop_receipt(&self) -> PopReceipt {
-        PopReceipt::new(self.message_id.clone(), self.pop_receipt.clone())
+        #[allow(unused_mut)]
+        let mut pop_receipt = PopReceipt::new(self.message_id.clone(), self.pop_receipt.clone());
+        #[cfg(feature = "claim_check")]
+        {
+            pop_receipt.claim_check = self.claim_check.clone();
+        }
+        pop_receipt
     }
 }
 
 impl From<Message> for PopReceipt {
     fn from(message: Message) -> Self {
-        PopReceipt::new(message.message_id, message.pop_receipt)
+        #[allow(unused_mut)]
+        let mut pop_receipt = PopReceipt::new(message.message_id, message.pop_receipt);
+        #[cfg(feature = "claim_check")]
+        {
+            pop_receipt.claim_check = message.claim_check;
+        }
+        pop_receipt
     }
 }
 
@@ -75,7 +128,10 @@ impl GetMessagesResponse {
         Ok(response.messages)
     }
 
-    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+    async fn try_from(
+        response: AzureResponse,
+        message_encoding: MessageEncoding,
+    ) -> azure_core::Result<Self> {
         let (_, headers, body) = response.deconstruct();
         let body = body.collect().await?;
 
@@ -84,8 +140,102 @@ impl GetMessagesResponse {
         Ok(GetMessagesResponse {
             common_storage_response_headers: (&headers).try_into()?,
             messages,
+            message_encoding,
         })
     }
+
+    /// Decodes the payload of each message with the message encoding of the
+    /// queue client.
+    pub fn decoded_messages(&self) -> Vec<DecodedMessage<Message, Vec<u8>>> {
+        self.messages
+            .iter()
+            .map(|message| DecodedMessage {
+                payload: message.decode(|text| self.message_encoding.decode(text)),
+                message: message.clone(),
+            })
+            .collect()
+    }
+
+    /// Decodes the JSON payload of each message with the message encoding of
+    /// the queue client.
+    pub fn decoded_messages_as<T: DeserializeOwned>(&self) -> Vec<DecodedMessage<Message, T>> {
+        self.messages
+            .iter()
+            .map(|message| DecodedMessage {
+                payload: message.decode(|text| self.message_encoding.decode_json(text)),
+                message: message.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Gets messages and decodes their JSON payloads as `T`.
+#[derive(Debug, Clone)]
+pub struct GetMessagesAsBuilder<T> {
+    builder: GetMessagesBuilder,
+    payload: PhantomData<fn() -> T>,
+}
+
+impl<T> GetMessagesAsBuilder<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    pub(crate) fn new(client: QueueClient) -> Self {
+        Self {
+            builder: GetMessagesBuilder::new(client),
+            payload: PhantomData,
+        }
+    }
+
+    pub fn number_of_messages(self, number_of_messages: impl Into<NumberOfMessages>) -> Self {
+        Self {
+            builder: self.builder.number_of_messages(number_of_messages),
+            payload: PhantomData,
+        }
+    }
+
+    pub fn visibility_timeout(self, visibility_timeout: impl Into<VisibilityTimeout>) -> Self {
+        Self {
+            builder: self.builder.visibility_timeout(visibility_timeout),
+            payload: PhantomData,
+        }
+    }
+
+    pub fn context(self, context: impl Into<Context>) -> Self {
+        Self {
+            builder: self.builder.context(context),
+            payload: PhantomData,
+        }
+    }
+
+    pub fn into_future(self) -> GetMessagesAs<T> {
+        Box::pin(async move {
+            let response = self.builder.await?;
+            Ok(GetMessagesAsResponse {
+                messages: response.decoded_messages_as(),
+                common_storage_response_headers: response.common_storage_response_headers,
+            })
+        })
+    }
+}
+
+azure_core::future!(GetMessagesAs<T>);
+
+impl<T> std::future::IntoFuture for GetMessagesAsBuilder<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    type IntoFuture = GetMessagesAs<T>;
+    type Output = <GetMessagesAs<T> as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct GetMessagesAsResponse<T> {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub messages: Vec<DecodedMessage<Message, T>>,
 }
 
 #[cfg(test)]
@@ -125,4 +275,54 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_decode_messages_individually() -> azure_core::Result<()> {
+        let body = b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+            <QueueMessagesList><QueueMessage>\
+            <MessageId>00000000-0000-0000-0000-000000000000</MessageId>\
+            <InsertionTime>Mon, 27 Jun 2022 13:38:48 GMT</InsertionTime>\
+            <ExpirationTime>Mon, 04 Jul 2022 13:38:48 GMT</ExpirationTime>\
+            <PopReceipt>REDACTED1</PopReceipt>\
+            <TimeNextVisible>Mon, 27 Jun 2022 13:38:53 GMT</TimeNextVisible>\
+            <DequeueCount>1</DequeueCount>\
+            <MessageText>eyJpZCI6NDJ9</MessageText>\
+            </QueueMessage>\
+            <QueueMessage>\
+            <MessageId>11111111-1111-1111-1111-111111111111</MessageId>\
+            <InsertionTime>Mon, 27 Jun 2022 13:38:48 GMT</InsertionTime>\
+            <ExpirationTime>Mon, 04 Jul 2022 13:38:48 GMT</ExpirationTime>\
+            <PopReceipt>REDACTED2</PopReceipt>\
+            <TimeNextVisible>Mon, 27 Jun 2022 13:38:53 GMT</TimeNextVisible>\
+            <DequeueCount>1</DequeueCount>\
+            <MessageText>not base64!</MessageText>\
+            </QueueMessage>\
+            </QueueMessagesList>\
+            ";
+
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Payload {
+            id: u32,
+        }
+
+        let mut headers = Headers::new();
+        headers.insert("x-ms-request-id", "00000000-0000-0000-0000-000000000000");
+        headers.insert("x-ms-version", "2019-12-12");
+        headers.insert("date", "Mon, 27 Jun 2022 13:38:48 GMT");
+        headers.insert("server", "Windows-Azure-Queue/1.0");
+
+        let response = GetMessagesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            messages: GetMessagesResponse::parse_messages(body)?,
+            message_encoding: MessageEncoding::Base64,
+        };
+
+        let decoded = response.decoded_messages_as::<Payload>();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].payload.as_ref().ok(), Some(&Payload { id: 42 }));
+        assert!(decoded[1].payload.is_err());
+        assert_eq!(decoded[1].message.pop_receipt, "REDACTED2");
+
+        Ok(())
+    }
 }
@@ -1,6 +1,8 @@
 use crate::{clients::QueueClient, QueueStoredAccessPolicy};
 use azure_core::{headers::Headers, Method, Response as AzureResponse};
-use azure_storage::{headers::CommonStorageResponseHeaders, StoredAccessPolicyList};
+use azure_storage::{
+    headers::CommonStorageResponseHeaders, PartialStoredAccessPolicy, PartialStoredAccessPolicyList,
+};
 use std::convert::TryInto;
 
 operation! {
@@ -28,7 +30,11 @@ impl GetQueueACLBuilder {
 #[derive(Debug, Clone)]
 pub struct GetQueueACLResponse {
     pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// The policies setting a start, an expiry and a permission.
     pub stored_access_policies: Vec<QueueStoredAccessPolicy>,
+    /// Every policy of the queue, including those leaving out their start,
+    /// expiry or permission.
+    pub partial_stored_access_policies: Vec<PartialStoredAccessPolicy>,
 }
 
 impl GetQueueACLResponse {
@@ -36,16 +42,83 @@ impl GetQueueACLResponse {
         let (_, headers, body) = response.deconstruct();
         let body = body.collect().await?;
 
+        let list = PartialStoredAccessPolicyList::from_xml(&body)?;
         let a: azure_core::Result<Vec<QueueStoredAccessPolicy>> =
-            StoredAccessPolicyList::from_xml(&body)?
-                .stored_access
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect();
+            list.complete().into_iter().map(TryInto::try_into).collect();
 
         Ok(GetQueueACLResponse {
             common_storage_response_headers: (&headers).try_into()?,
             stored_access_policies: a?,
+            partial_stored_access_policies: list.stored_access,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{
+        headers::{HeaderName, HeaderValue},
+        BytesStream, StatusCode,
+    };
+
+    #[tokio::test]
+    async fn parse_partial_policies() -> azure_core::Result<()> {
+        const BODY: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<SignedIdentifiers>
+    <SignedIdentifier>
+        <Id>processors</Id>
+        <AccessPolicy>
+            <Permission>rp</Permission>
+        </AccessPolicy>
+    </SignedIdentifier>
+    <SignedIdentifier>
+        <Id>readers</Id>
+        <AccessPolicy>
+            <Start>2030-01-01T00:00:00Z</Start>
+            <Expiry>2030-02-01T00:00:00Z</Expiry>
+            <Permission>r</Permission>
+        </AccessPolicy>
+    </SignedIdentifier>
+    <SignedIdentifier>
+        <Id>revoked</Id>
+    </SignedIdentifier>
+</SignedIdentifiers>";
+
+        let mut headers = Headers::new();
+        for (name, value) in [
+            ("x-ms-request-id", "a3a0b1d4-f01a-0043-4f2c-8ba78e000000"),
+            ("x-ms-version", "2019-12-12"),
+            ("date", "Mon, 27 Jun 2022 13:38:48 GMT"),
+            ("server", "Windows-Azure-Queue/1.0 Microsoft-HTTPAPI/2.0"),
+        ] {
+            headers.insert(
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+        let response = AzureResponse::new(
+            StatusCode::Ok,
+            headers,
+            Box::pin(BytesStream::new(BODY.as_bytes())),
+        );
+
+        let response = GetQueueACLResponse::try_from(response).await?;
+        let policies = response.stored_access_policies;
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].id, "readers");
+        assert_eq!(policies[0].to_permission_string(), "r");
+
+        let policies = response.partial_stored_access_policies;
+        assert_eq!(policies.len(), 3);
+
+        assert_eq!(policies[0].id, "processors");
+        assert_eq!(policies[0].start, None);
+        assert_eq!(policies[0].expiry, None);
+        assert_eq!(policies[0].permission.as_deref(), Some("rp"));
+
+        assert_eq!(policies[2].id, "revoked");
+        assert_eq!(policies[2].permission, None);
+        Ok(())
+    }
+}
@@ -19,13 +19,13 @@ pub use clear_messages::ClearMessagesBuilder;
 pub use create_queue::CreateQueueBuilder;
 pub use delete_message::DeleteMessageBuilder;
 pub use delete_queue::DeleteQueueBuilder;
-pub use get_messages::{GetMessagesBuilder, Message};
+pub use get_messages::{GetMessagesAsBuilder, GetMessagesAsResponse, GetMessagesBuilder, Message};
 pub use get_queue_acl::GetQueueACLBuilder;
 pub use get_queue_metadata::GetQueueMetadataBuilder;
 pub use get_queue_service_properties::GetQueueServicePropertiesBuilder;
 pub use get_queue_service_stats::GetQueueServiceStatsBuilder;
 pub use list_queues::ListQueuesBuilder;
-pub use peek_messages::PeekMessagesBuilder;
+pub use peek_messages::{PeekMessage, PeekMessagesBuilder};
 pub use put_message::PutMessageBuilder;
 pub use set_queue_acl::SetQueueACLBuilder;
 pub use set_queue_metadata::SetQueueMetadataBuilder;
@@ -1,8 +1,10 @@
-use crate::prelude::*;
+use crate::{prelude::*, DecodedMessage, MessageEncoding};
 use azure_core::{headers::Headers, prelude::*, Method, Response as AzureResponse};
 use azure_storage::headers::CommonStorageResponseHeaders;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::convert::TryInto;
+#[cfg(feature = "claim_check")]
+use std::sync::Arc;
 use time::OffsetDateTime;
 
 operation! {
@@ -24,7 +26,21 @@ impl PeekMessagesBuilder {
 
             let response = self.client.send(&mut self.context, &mut request).await?;
 
-            PeekMessagesResponse::try_from(response).await
+            #[allow(unused_mut)]
+            let mut response =
+                PeekMessagesResponse::try_from(response, self.client.message_encoding().clone())
+                    .await?;
+
+            #[cfg(feature = "claim_check")]
+            if let Some(claim_check) = self.client.claim_check() {
+                for message in &mut response.messages {
+                    claim_check
+                        .resolve_peeked(message, self.client.message_encoding())
+                        .await;
+                }
+            }
+
+            Ok(response)
         })
     }
 }
@@ -33,6 +49,7 @@ impl PeekMessagesBuilder {
 pub struct PeekMessagesResponse {
     pub common_storage_response_headers: CommonStorageResponseHeaders,
     pub messages: Vec<PeekMessage>,
+    message_encoding: MessageEncoding,
 }
 
 #[derive(Debug, Deserialize)]
@@ -51,10 +68,36 @@ pub struct PeekMessage {
     pub expiration_time: OffsetDateTime,
     pub dequeue_count: u64,
     pub message_text: String,
+    #[cfg(feature = "claim_check")]
+    #[serde(skip)]
+    pub(crate) claim_check_error: Option<Arc<azure_core::Error>>,
+}
+
+impl PeekMessage {
+    /// Why the payload checked in to a blob could not be read, in which case
+    /// the text of the message is left as enqueued.
+    #[cfg(feature = "claim_check")]
+    pub fn claim_check_error(&self) -> Option<&azure_core::Error> {
+        self.claim_check_error.as_deref()
+    }
+
+    fn decode<T>(
+        &self,
+        decode: impl FnOnce(&str) -> azure_core::Result<T>,
+    ) -> azure_core::Result<T> {
+        #[cfg(feature = "claim_check")]
+        if let Some(err) = &self.claim_check_error {
+            return Err(crate::claim_check::unresolved(err));
+        }
+        decode(&self.message_text)
+    }
 }
 
 impl PeekMessagesResponse {
-    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+    async fn try_from(
+        response: AzureResponse,
+        message_encoding: MessageEncoding,
+    ) -> azure_core::Result<Self> {
         let (_, headers, body) = response.deconstruct();
         let body: PeekMessagesBody = body.xml().await?;
         let messages = body.messages;
@@ -62,6 +105,31 @@ impl PeekMessagesResponse {
         Ok(PeekMessagesResponse {
             common_storage_response_headers: (&headers).try_into()?,
             messages,
+            message_encoding,
         })
     }
+
+    /// Decodes the payload of each message with the message encoding of the
+    /// queue client.
+    pub fn decoded_messages(&self) -> Vec<DecodedMessage<PeekMessage, Vec<u8>>> {
+        self.messages
+            .iter()
+            .map(|message| DecodedMessage {
+                payload: message.decode(|text| self.message_encoding.decode(text)),
+                message: message.clone(),
+            })
+            .collect()
+    }
+
+    /// Decodes the JSON payload of each message with the message encoding of
+    /// the queue client.
+    pub fn decoded_messages_as<T: DeserializeOwned>(&self) -> Vec<DecodedMessage<PeekMessage, T>> {
+        self.messages
+            .iter()
+            .map(|message| DecodedMessage {
+                payload: message.decode(|text| self.message_encoding.decode_json(text)),
+                message: message.clone(),
+            })
+            .collect()
+    }
 }
@@ -10,7 +10,7 @@ use time::OffsetDateTime;
 operation! {
     PutMessage,
     client: QueueClient,
-    body: String,
+    body: Vec<u8>,
     ?visibility_timeout: VisibilityTimeout,
     ?ttl: MessageTTL
 }
@@ -23,9 +23,23 @@ impl PutMessageBuilder {
             self.visibility_timeout.append_to_url_query(&mut url);
             self.ttl.append_to_url_query(&mut url);
 
-            let message = to_xml(&QueueMessageSubmit {
-                message_text: self.body,
-            })?;
+            #[allow(unused_mut)]
+            let mut message_text = self.client.message_encoding().encode(&self.body)?;
+
+            #[cfg(feature = "claim_check")]
+            let mut checked_in_blob = None;
+            #[cfg(feature = "claim_check")]
+            if let Some(claim_check) = self.client.claim_check() {
+                if claim_check.exceeds_threshold(&message_text) {
+                    let (text, blob) = claim_check
+                        .check_in(self.body, self.client.message_encoding())
+                        .await?;
+                    message_text = text;
+                    checked_in_blob = Some(blob);
+                }
+            }
+
+            let message = to_xml(&QueueMessageSubmit { message_text })?;
 
             let mut request = QueueClient::finalize_request(
                 url,
@@ -34,9 +48,17 @@ impl PutMessageBuilder {
                 Some(message.into()),
             )?;
 
-            let response = self.client.send(&mut self.context, &mut request).await?;
+            let response = self.client.send(&mut self.context, &mut request).await;
+
+            // the payload of a message that was not enqueued is not referenced
+            #[cfg(feature = "claim_check")]
+            if let (Err(_), Some(claim_check), Some(blob)) =
+                (&response, self.client.claim_check(), &checked_in_blob)
+            {
+                claim_check.discard(blob).await;
+            }
 
-            PutMessageResponse::try_from(response).await
+            PutMessageResponse::try_from(response?).await
         })
     }
 }
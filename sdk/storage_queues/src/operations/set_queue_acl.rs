@@ -1,12 +1,14 @@
-use crate::{clients::QueueClient, QueueStoredAccessPolicy};
+use crate::clients::QueueClient;
 use azure_core::{error::Error, headers::Headers, Method, Response as AzureResponse};
-use azure_storage::{headers::CommonStorageResponseHeaders, StoredAccessPolicyList};
+use azure_storage::{
+    headers::CommonStorageResponseHeaders, PartialStoredAccessPolicy, PartialStoredAccessPolicyList,
+};
 use std::convert::TryInto;
 
 operation! {
     SetQueueACL,
     client: QueueClient,
-    policies: Vec<QueueStoredAccessPolicy>,
+    policies: Vec<PartialStoredAccessPolicy>,
 }
 
 impl SetQueueACLBuilder {
@@ -16,13 +18,7 @@ impl SetQueueACLBuilder {
 
             url.query_pairs_mut().append_pair("comp", "acl");
 
-            // convert the queue_stored_access_policies slice
-            // in a StoredAccessPolicyList to get its XML
-            // representation.
-            let xml_body = {
-                let stored_access = self.policies.into_iter().map(Into::into).collect();
-                StoredAccessPolicyList::new(stored_access).to_xml()
-            };
+            let xml_body = PartialStoredAccessPolicyList::new(self.policies).to_xml();
 
             let mut request = QueueClient::finalize_request(
                 url,
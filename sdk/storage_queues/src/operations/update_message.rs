@@ -14,7 +14,7 @@ use time::OffsetDateTime;
 operation! {
     UpdateMessage,
     client: PopReceiptClient,
-    body: String,
+    body: Option<String>,
     visibility_timeout: VisibilityTimeout,
 }
 
@@ -25,16 +25,21 @@ impl UpdateMessageBuilder {
 
             self.visibility_timeout.append_to_url_query(&mut url);
 
-            let message = to_xml(&QueueMessageSubmit {
-                message_text: self.body,
-            })?;
+            // without a body only the visibility timeout of the message changes
+            let message = match self.body {
+                Some(body) => {
+                    let message_text = self
+                        .client
+                        .queue_client()
+                        .message_encoding()
+                        .encode(body.as_bytes())?;
+                    Some(to_xml(&QueueMessageSubmit { message_text })?.into())
+                }
+                None => None,
+            };
 
-            let mut request = PopReceiptClient::finalize_request(
-                url,
-                Method::Put,
-                Headers::new(),
-                Some(message.into()),
-            )?;
+            let mut request =
+                PopReceiptClient::finalize_request(url, Method::Put, Headers::new(), message)?;
 
             let response = self.client.send(&mut self.context, &mut request).await?;
 
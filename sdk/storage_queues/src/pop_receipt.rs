@@ -3,6 +3,8 @@
 pub struct PopReceipt {
     message_id: String,
     pop_receipt: String,
+    #[cfg(feature = "claim_check")]
+    pub(crate) claim_check: Option<crate::claim_check::ClaimCheckReference>,
 }
 
 impl PopReceipt {
@@ -10,6 +12,16 @@ impl PopReceipt {
         Self {
             message_id: message_id.into(),
             pop_receipt: pop_receipt.into(),
+            #[cfg(feature = "claim_check")]
+            claim_check: None,
+        }
+    }
+
+    /// The same message with the pop receipt returned by an update.
+    pub(crate) fn renewed(self, pop_receipt: impl Into<String>) -> Self {
+        Self {
+            pop_receipt: pop_receipt.into(),
+            ..self
         }
     }
 
@@ -1,4 +1,4 @@
 pub use crate::{
-    MessageTTL, NumberOfMessages, PopReceipt, QueueClient, QueueServiceClient,
-    QueueStoredAccessPolicy, VisibilityTimeout,
+    MessageEncoding, MessageTTL, NumberOfMessages, PopReceipt, QueueClient, QueueProcessor,
+    QueueServiceClient, QueueStoredAccessPolicy, VisibilityTimeout,
 };
@@ -0,0 +1,553 @@
+use crate::{operations::Message, MessageEncoding, PopReceipt, QueueClient};
+#[cfg(not(test))]
+use azure_core::sleep::sleep;
+use futures::{
+    future::{select, Either, Fuse, FusedFuture},
+    stream::FuturesUnordered,
+    FutureExt, StreamExt,
+};
+use std::{future::Future, time::Duration};
+// the tests run on the paused clock of tokio rather than the wall clock
+#[cfg(test)]
+use tokio::time::sleep;
+
+/// Processes the messages of a queue with a user supplied handler.
+///
+/// The processor polls the queue, backing off while it is empty, and runs the
+/// handler on up to `max_concurrent_messages` messages at a time. While a
+/// handler runs, the visibility timeout of its message is renewed so that no
+/// other consumer picks it up. Messages are deleted once their handler
+/// succeeds; when a handler fails the message becomes visible again after the
+/// visibility timeout and is retried.
+///
+/// Messages dequeued more than `max_dequeue_count` times are moved, with their
+/// text untouched, to the poison queue if one is configured, or otherwise
+/// deleted. Messages whose claim-checked payload cannot be read are not
+/// handled, so that they end up there too.
+#[derive(Debug, Clone)]
+pub struct QueueProcessor {
+    queue_client: QueueClient,
+    poison_queue_client: Option<QueueClient>,
+    max_concurrent_messages: usize,
+    batch_size: u8,
+    visibility_timeout: Duration,
+    min_polling_interval: Duration,
+    max_polling_interval: Duration,
+    max_dequeue_count: u64,
+}
+
+impl QueueProcessor {
+    pub fn new(queue_client: QueueClient) -> Self {
+        Self {
+            queue_client,
+            poison_queue_client: None,
+            max_concurrent_messages: 16,
+            batch_size: 16,
+            visibility_timeout: Duration::from_secs(30),
+            min_polling_interval: Duration::from_millis(100),
+            max_polling_interval: Duration::from_secs(30),
+            max_dequeue_count: 5,
+        }
+    }
+
+    setters! {
+        poison_queue_client: QueueClient => Some(poison_queue_client),
+        max_concurrent_messages: usize => max_concurrent_messages.max(1),
+        batch_size: u8 => batch_size.clamp(1, 32),
+        visibility_timeout: Duration => visibility_timeout,
+        min_polling_interval: Duration => min_polling_interval,
+        max_polling_interval: Duration => max_polling_interval,
+        max_dequeue_count: u64 => max_dequeue_count,
+    }
+
+    /// Processes messages until `shutdown` completes.
+    ///
+    /// On shutdown no new messages are received, and the call returns once the
+    /// messages being handled are done. Errors from the service are logged
+    /// and retried, so this only returns on shutdown.
+    pub async fn run<F, Fut, S>(&self, handler: F, shutdown: S)
+    where
+        F: Fn(Message) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+        S: Future<Output = ()>,
+    {
+        let shutdown = shutdown.fuse();
+        futures::pin_mut!(shutdown);
+
+        let poll = Fuse::terminated();
+        futures::pin_mut!(poll);
+
+        let mut in_flight = FuturesUnordered::new();
+        let mut polling_interval = Duration::ZERO;
+
+        loop {
+            let capacity = self.max_concurrent_messages - in_flight.len();
+            if poll.is_terminated() && !shutdown.is_terminated() && capacity > 0 {
+                poll.set(self.receive(polling_interval, capacity).fuse());
+            }
+
+            futures::select! {
+                () = shutdown => {
+                    // messages received by an abandoned poll become visible
+                    // again after the visibility timeout
+                    poll.set(Fuse::terminated());
+                }
+                received = poll => match received {
+                    Ok(messages) => {
+                        polling_interval = self.next_polling_interval(
+                            polling_interval,
+                            !messages.is_empty(),
+                        );
+                        for message in messages {
+                            in_flight.push(self.process(&handler, message));
+                        }
+                    }
+                    Err(err) => {
+                        log::warn!("unable to receive messages: {err}");
+                        polling_interval = self.next_polling_interval(polling_interval, false);
+                    }
+                },
+                () = in_flight.select_next_some() => {}
+                complete => break,
+            }
+        }
+    }
+
+    /// Receives up to `capacity` messages once `delay` has elapsed.
+    async fn receive(&self, delay: Duration, capacity: usize) -> azure_core::Result<Vec<Message>> {
+        if !delay.is_zero() {
+            sleep(delay).await;
+        }
+
+        let number_of_messages = capacity.min(self.batch_size as usize) as u8;
+        let response = self
+            .queue_client
+            .get_messages()
+            .number_of_messages(number_of_messages)
+            .visibility_timeout(self.visibility_timeout)
+            .await?;
+        Ok(response.messages)
+    }
+
+    /// Polls again right away while messages keep coming, doubling the
+    /// interval up to the maximum while the queue is empty.
+    fn next_polling_interval(&self, current: Duration, received: bool) -> Duration {
+        if received {
+            Duration::ZERO
+        } else {
+            (current * 2).clamp(self.min_polling_interval, self.max_polling_interval)
+        }
+    }
+
+    async fn process<F, Fut>(&self, handler: &F, message: Message)
+    where
+        F: Fn(Message) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+    {
+        let message_id = message.message_id.clone();
+
+        if message.dequeue_count > self.max_dequeue_count {
+            if let Err(err) = self.move_to_poison_queue(message).await {
+                log::warn!("unable to move poison message {message_id}: {err}");
+            }
+            return;
+        }
+
+        #[cfg(feature = "claim_check")]
+        if let Some(err) = message.claim_check_error() {
+            log::warn!("unable to read the payload of message {message_id}: {err}");
+            return;
+        }
+
+        let mut pop_receipt = message.pop_receipt();
+        let mut handled = Box::pin(handler(message));
+
+        // renew the visibility halfway through the timeout, until the
+        // handler is done
+        let outcome = loop {
+            let renewal = Box::pin(sleep(self.visibility_timeout / 2));
+            match select(handled, renewal).await {
+                Either::Left((outcome, _)) => break outcome,
+                Either::Right(((), pending)) => {
+                    handled = pending;
+                    match self
+                        .queue_client
+                        .pop_receipt_client(pop_receipt.clone())
+                        .update_visibility_timeout(self.visibility_timeout)
+                        .await
+                    {
+                        Ok(response) => pop_receipt = pop_receipt.renewed(response.pop_receipt),
+                        Err(err) => {
+                            log::warn!("unable to renew visibility of message {message_id}: {err}");
+                        }
+                    }
+                }
+            }
+        };
+
+        match outcome {
+            Ok(()) => {
+                if let Err(err) = self
+                    .queue_client
+                    .pop_receipt_client(pop_receipt)
+                    .delete()
+                    .await
+                {
+                    log::warn!("unable to delete processed message {message_id}: {err}");
+                }
+            }
+            Err(err) => log::warn!("handler failed for message {message_id}: {err}"),
+        }
+    }
+
+    async fn move_to_poison_queue(&self, message: Message) -> azure_core::Result<()> {
+        // the text is already encoded, so it is forwarded as is
+        let message_text = message.message_text.clone();
+
+        // a payload checked in to a blob stays there for the poison queue
+        #[cfg(feature = "claim_check")]
+        let message_text = match &message.claim_check {
+            Some(reference) => reference.message_text.clone(),
+            None => message_text,
+        };
+        let pop_receipt = PopReceipt::new(message.message_id, message.pop_receipt);
+
+        if let Some(poison_queue_client) = &self.poison_queue_client {
+            poison_queue_client
+                .clone()
+                .with_message_encoding(MessageEncoding::None)
+                .put_message(message_text)
+                .await?;
+        }
+
+        self.queue_client
+            .pop_receipt_client(pop_receipt)
+            .delete()
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{QueueServiceClient, QueueServiceClientBuilder};
+    use azure_core::{
+        headers::{HeaderName, HeaderValue, Headers},
+        Body, BytesStream, Context, Method, Policy, PolicyResult, Request, Response, StatusCode,
+        TransportOptions,
+    };
+    use azure_storage::StorageCredentials;
+    use std::sync::{Arc, Mutex};
+
+    const DATE: &str = "Mon, 27 Jun 2022 13:38:48 GMT";
+
+    /// A request received by the [`FakeQueueService`].
+    #[derive(Debug, Clone)]
+    struct Received {
+        method: Method,
+        path: String,
+        pop_receipt: Option<String>,
+        body: String,
+    }
+
+    /// A queue service answering the requests of the processor, handing out
+    /// each of its messages once.
+    #[derive(Debug, Default)]
+    struct FakeQueueService {
+        messages: Mutex<Vec<(String, u64)>>,
+        requests: Mutex<Vec<Received>>,
+    }
+
+    impl FakeQueueService {
+        fn new(messages: &[(&str, u64)]) -> Arc<Self> {
+            Arc::new(Self {
+                messages: Mutex::new(
+                    messages
+                        .iter()
+                        .map(|(text, dequeue_count)| ((*text).to_owned(), *dequeue_count))
+                        .collect(),
+                ),
+                requests: Mutex::default(),
+            })
+        }
+
+        fn processor(self: &Arc<Self>) -> QueueProcessor {
+            let service_client =
+                QueueServiceClientBuilder::new("account", StorageCredentials::anonymous())
+                    .transport(TransportOptions::new_custom_policy(self.clone()))
+                    .build();
+            QueueProcessor::new(service_client.queue_client("queue"))
+        }
+
+        fn poison_queue_client(self: &Arc<Self>) -> QueueClient {
+            QueueServiceClientBuilder::new("account", StorageCredentials::anonymous())
+                .transport(TransportOptions::new_custom_policy(self.clone()))
+                .build()
+                .queue_client("poison")
+        }
+
+        /// The requests received for `method`, in order.
+        fn requests(&self, method: Method) -> Vec<Received> {
+            self.requests
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|received| received.method == method)
+                .cloned()
+                .collect()
+        }
+
+        /// Completes once a request for `method` has been received `count`
+        /// times.
+        async fn received(&self, method: Method, count: usize) {
+            while self.requests(method).len() < count {
+                sleep(Duration::from_millis(5)).await;
+            }
+        }
+
+        fn respond(&self, received: &Received) -> (StatusCode, Headers, String) {
+            let mut headers = Headers::new();
+            for (name, value) in [
+                ("x-ms-request-id", "a3a0b1d4-f01a-0043-4f2c-8ba78e000000"),
+                ("x-ms-version", "2019-12-12"),
+                ("date", DATE),
+                ("server", "Windows-Azure-Queue/1.0 Microsoft-HTTPAPI/2.0"),
+            ] {
+                headers.insert(
+                    HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                );
+            }
+
+            match received.method {
+                Method::Get => {
+                    let messages: String = std::mem::take(&mut *self.messages.lock().unwrap())
+                        .into_iter()
+                        .enumerate()
+                        .map(|(id, (text, dequeue_count))| {
+                            format!(
+                                "<QueueMessage><MessageId>message-{id}</MessageId>\
+                                <InsertionTime>{DATE}</InsertionTime>\
+                                <ExpirationTime>{DATE}</ExpirationTime>\
+                                <PopReceipt>receipt-0</PopReceipt>\
+                                <TimeNextVisible>{DATE}</TimeNextVisible>\
+                                <DequeueCount>{dequeue_count}</DequeueCount>\
+                                <MessageText>{text}</MessageText></QueueMessage>"
+                            )
+                        })
+                        .collect();
+                    (
+                        StatusCode::Ok,
+                        headers,
+                        format!("<QueueMessagesList>{messages}</QueueMessagesList>"),
+                    )
+                }
+                Method::Put => {
+                    let renewals = self.requests(Method::Put).len() + 1;
+                    headers.insert(
+                        HeaderName::from_static("x-ms-popreceipt"),
+                        format!("receipt-{renewals}"),
+                    );
+                    headers.insert(
+                        HeaderName::from_static("x-ms-time-next-visible"),
+                        HeaderValue::from_static(DATE),
+                    );
+                    (StatusCode::NoContent, headers, String::new())
+                }
+                Method::Post => (
+                    StatusCode::Created,
+                    headers,
+                    format!(
+                        "<QueueMessagesList><QueueMessage><MessageId>poisoned</MessageId>\
+                        <InsertionTime>{DATE}</InsertionTime>\
+                        <ExpirationTime>{DATE}</ExpirationTime>\
+                        <PopReceipt>receipt-0</PopReceipt>\
+                        <TimeNextVisible>{DATE}</TimeNextVisible>\
+                        </QueueMessage></QueueMessagesList>"
+                    ),
+                ),
+                _ => (StatusCode::NoContent, headers, String::new()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for FakeQueueService {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            assert_eq!(0, next.len());
+
+            let received = Received {
+                method: *request.method(),
+                path: request.url().path().to_owned(),
+                pop_receipt: request
+                    .url()
+                    .query_pairs()
+                    .find(|(name, _)| name == "popreceipt")
+                    .map(|(_, value)| value.into_owned()),
+                body: match request.body() {
+                    Body::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+                    _ => String::new(),
+                },
+            };
+            let (status, headers, body) = self.respond(&received);
+            self.requests.lock().unwrap().push(received);
+            Ok(Response::new(
+                status,
+                headers,
+                Box::pin(BytesStream::new(body)),
+            ))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn messages_are_renewed_and_deleted_with_the_latest_pop_receipt() {
+        let service = FakeQueueService::new(&[("hello", 1)]);
+        let handled = Arc::new(Mutex::new(Vec::new()));
+
+        let handler = |message: Message| {
+            let service = service.clone();
+            let handled = handled.clone();
+            async move {
+                let started = tokio::time::Instant::now();
+                handled.lock().unwrap().push(message.message_text);
+                // outlive the visibility timeout so that it gets renewed
+                service.received(Method::Put, 2).await;
+                // the visibility is renewed halfway through the timeout
+                let elapsed = started.elapsed();
+                assert!(elapsed >= Duration::from_secs(30));
+                assert!(elapsed < Duration::from_secs(31));
+                Ok(())
+            }
+        };
+        service
+            .processor()
+            .run(handler, service.received(Method::Delete, 1))
+            .await;
+
+        assert_eq!(*handled.lock().unwrap(), ["hello"]);
+        let renewals = service.requests(Method::Put);
+        assert!(renewals.len() >= 2);
+        for (renewal, received) in renewals.iter().enumerate() {
+            assert_eq!(received.path, "/queue/messages/message-0");
+            assert_eq!(
+                received.pop_receipt.as_deref(),
+                Some(format!("receipt-{renewal}").as_str())
+            );
+        }
+        let deletes = service.requests(Method::Delete);
+        assert_eq!(deletes.len(), 1);
+        assert_eq!(deletes[0].path, "/queue/messages/message-0");
+        assert_eq!(
+            deletes[0].pop_receipt.as_deref(),
+            Some(format!("receipt-{}", renewals.len()).as_str())
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_messages_are_not_deleted() {
+        let service = FakeQueueService::new(&[("hello", 1)]);
+        let handled = Arc::new(Mutex::new(Vec::new()));
+
+        let handler = |message: Message| {
+            let handled = handled.clone();
+            async move {
+                handled.lock().unwrap().push(message.message_text);
+                Err(azure_core::Error::message(
+                    azure_core::error::ErrorKind::Other,
+                    "handler failed",
+                ))
+            }
+        };
+        let shutdown = {
+            let handled = handled.clone();
+            async move {
+                while handled.lock().unwrap().is_empty() {
+                    sleep(Duration::from_millis(5)).await;
+                }
+            }
+        };
+        service.processor().run(handler, shutdown).await;
+
+        assert_eq!(*handled.lock().unwrap(), ["hello"]);
+        assert!(service.requests(Method::Delete).is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn poison_messages_are_moved_to_the_poison_queue() {
+        let service = FakeQueueService::new(&[("poison", 6)]);
+        let processor = service
+            .processor()
+            .max_dequeue_count(5_u64)
+            .poison_queue_client(service.poison_queue_client());
+
+        processor
+            .run(
+                |_: Message| async { panic!("poison messages are not handled") },
+                service.received(Method::Delete, 1),
+            )
+            .await;
+
+        let moved = service.requests(Method::Post);
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].path, "/poison/messages");
+        assert!(moved[0].body.contains("<MessageText>poison</MessageText>"));
+        let deletes = service.requests(Method::Delete);
+        assert_eq!(deletes[0].path, "/queue/messages/message-0");
+        assert_eq!(deletes[0].pop_receipt.as_deref(), Some("receipt-0"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_waits_for_the_messages_being_handled() {
+        let service = FakeQueueService::new(&[("hello", 1)]);
+        let finished = Arc::new(Mutex::new(false));
+
+        let handler = |_: Message| {
+            let service = service.clone();
+            let finished = finished.clone();
+            async move {
+                // shut down while the message is being handled
+                service.received(Method::Put, 2).await;
+                *finished.lock().unwrap() = true;
+                Ok(())
+            }
+        };
+        service
+            .processor()
+            .run(handler, service.received(Method::Put, 1))
+            .await;
+
+        assert!(*finished.lock().unwrap());
+        assert_eq!(service.requests(Method::Delete).len(), 1);
+        let polls = service.requests(Method::Get).len();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(service.requests(Method::Get).len(), polls);
+    }
+
+    #[test]
+    fn polling_interval_backs_off_while_empty() {
+        let queue_client = QueueServiceClient::new("account", StorageCredentials::anonymous())
+            .queue_client("queue");
+        let processor = QueueProcessor::new(queue_client)
+            .min_polling_interval(Duration::from_secs(1))
+            .max_polling_interval(Duration::from_secs(5));
+
+        let mut interval = Duration::ZERO;
+        let mut intervals = Vec::new();
+        for _ in 0..5 {
+            interval = processor.next_polling_interval(interval, false);
+            intervals.push(interval.as_secs());
+        }
+        assert_eq!(intervals, [1, 2, 4, 5, 5]);
+
+        assert_eq!(
+            processor.next_polling_interval(interval, true),
+            Duration::ZERO
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use azure_core::error::{Error, ErrorKind};
-use azure_storage::StoredAccessPolicy;
+use azure_storage::{PartialStoredAccessPolicy, StoredAccessPolicy};
 use std::convert::TryFrom;
 use time::OffsetDateTime;
 
@@ -143,3 +143,9 @@ impl From<QueueStoredAccessPolicy> for StoredAccessPolicy {
         }
     }
 }
+
+impl From<QueueStoredAccessPolicy> for PartialStoredAccessPolicy {
+    fn from(queue_stored_access_policy: QueueStoredAccessPolicy) -> Self {
+        StoredAccessPolicy::from(queue_stored_access_policy).into()
+    }
+}